@@ -3,23 +3,34 @@ import { PublicKey } from "@solana/web3.js";
 import * as anchor from "@coral-xyz/anchor";
 import { buildAddBlacklistMessage, buildRemoveBlacklistMessage } from "./helpers";
 import { initializeMultisigContext, buildAndSendMultisigTransaction, handleError } from "./shared-utils";
+import { BlacklistReason, blacklistDetails, blacklistEntry } from "../../../utils/helpers";
 
-export async function addBlacklist(target: PublicKey): Promise<string> {
+export async function addBlacklist(
+  target: PublicKey,
+  reason: BlacklistReason,
+  caseReference: string,
+  reviewAt = 0,
+  expiresAt = 0
+): Promise<string> {
   console.log("\n=== Add Blacklist Operation ===");
   console.log("Target:", target.toString());
+  console.log("Reason:", reason, "Case:", caseReference);
 
   try {
     const context = await initializeMultisigContext();
+    const { details, bytes } = blacklistDetails(reason, caseReference, reviewAt, expiresAt);
 
     const message = buildAddBlacklistMessage(
       context.pdas.blacklist,
       target,
+      bytes,
       context.multisigAccount.nonce.toNumber()
     );
 
     const instruction = await context.program.methods
-      .addBlacklist(target)
+      .addBlacklist(target, details)
       .accounts({
+        payer: context.payer.publicKey,
         mint: context.cngnMint,
         tokenConfig: context.pdas.tokenConfig,
         canMint: context.pdas.canMint,
@@ -30,7 +41,9 @@ export async function addBlacklist(target: PublicKey): Promise<string> {
         blacklist: context.pdas.blacklist,
         multisig: context.pdas.multisig,
         instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+        systemProgram: anchor.web3.SystemProgram.programId,
       })
+      .remainingAccounts([blacklistEntry(context.cngnMint, context.program.programId, target)])
       .instruction();
 
     return await buildAndSendMultisigTransaction(context, message, instruction);
@@ -60,8 +73,10 @@ export async function removeBlacklist(target: PublicKey): Promise<string> {
         tokenConfig: context.pdas.tokenConfig,
         blacklist: context.pdas.blacklist,
         multisig: context.pdas.multisig,
+        rentRecipient: context.payer.publicKey,
         instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
       })
+      .remainingAccounts([blacklistEntry(context.cngnMint, context.program.programId, target)])
       .instruction();
 
     return await buildAndSendMultisigTransaction(context, message, instruction);
@@ -104,17 +104,20 @@ export function buildRemoveCanForwardMessage(
 }
 
 /**
- * Build SHA256 hash message for adding to blacklist
+ * Build SHA256 hash message for adding to blacklist; `details` are the
+ * encoded blacklist details (see `blacklistDetails` in utils/helpers.ts)
  */
 export function buildAddBlacklistMessage(
     blacklistAccount: PublicKey,
     user: PublicKey,
+    details: Buffer,
     nonce: number
 ): Buffer {
     const hash = createHash("sha256");
     hash.update("ADD_BLACKLIST");
     hash.update(blacklistAccount.toBuffer());
     hash.update(user.toBuffer());
+    hash.update(details);
 
     const nonceBuffer = Buffer.alloc(8);
     nonceBuffer.writeBigUInt64LE(BigInt(nonce));
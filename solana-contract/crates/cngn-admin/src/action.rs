@@ -0,0 +1,1317 @@
+// crates/cngn-admin/src/action.rs
+use crate::pdas::Pdas;
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::{system_program, sysvar};
+use anchor_lang::{InstructionData, ToAccountMetas};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use clap::{ArgAction, Subcommand, ValueEnum};
+use cngn::state as ms;
+pub use cngn::state::BlacklistReason;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Which of the two messages the program accepts the owners sign.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageFormat {
+    /// SHA-256 digest of the action
+    #[default]
+    Digest,
+    /// Solana off-chain message with a human-readable description, for
+    /// hardware wallets and `solana sign-offchain-message`
+    Readable,
+}
+
+impl MessageFormat {
+    /// How an owner sees `message`: the hex digest, or the text of a readable
+    /// message as passed to `solana sign-offchain-message`.
+    pub fn display(self, message: &[u8]) -> String {
+        match self {
+            Self::Digest => hex::encode(message),
+            Self::Readable => {
+                let header = ms::OFFCHAIN_SIGNING_DOMAIN.len() + 4;
+                String::from_utf8_lossy(&message[header..]).into_owned()
+            }
+        }
+    }
+}
+
+/// A multisig-gated admin instruction understood by the cNGN program.
+#[derive(Debug, Clone, Subcommand, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AdminAction {
+    /// Authorise a new minter
+    AddCanMint {
+        #[serde(with = "crate::serde_str")]
+        user: Pubkey,
+    },
+    /// Revoke a minter
+    RemoveCanMint {
+        #[serde(with = "crate::serde_str")]
+        user: Pubkey,
+    },
+    /// Set the exact amount a minter may mint next
+    SetMintAmount {
+        #[serde(with = "crate::serde_str")]
+        user: Pubkey,
+        amount: u64,
+    },
+    /// Reset a minter's allowance to zero
+    RemoveMintAmount {
+        #[serde(with = "crate::serde_str")]
+        user: Pubkey,
+    },
+    /// Register a trusted forwarder
+    AddCanForward {
+        #[serde(with = "crate::serde_str")]
+        forwarder: Pubkey,
+    },
+    /// Remove a trusted forwarder
+    RemoveCanForward {
+        #[serde(with = "crate::serde_str")]
+        forwarder: Pubkey,
+    },
+    /// Blacklist an address (also strips it from every other list), recording
+    /// why
+    AddBlacklist {
+        #[serde(with = "crate::serde_str")]
+        user: Pubkey,
+        #[arg(long)]
+        #[serde(with = "crate::serde_str")]
+        reason: BlacklistReason,
+        #[arg(long)]
+        case_reference: String,
+        /// Unix time the listing is up for review; 0 for none
+        #[arg(long, default_value_t = 0)]
+        #[serde(default)]
+        review_at: i64,
+        /// Unix time the listing lapses and can be pruned by anyone; 0 for
+        /// never
+        #[arg(long, default_value_t = 0)]
+        #[serde(default)]
+        expires_at: i64,
+    },
+    /// Lift a blacklisting
+    RemoveBlacklist {
+        #[serde(with = "crate::serde_str")]
+        user: Pubkey,
+    },
+    /// Add an address to the internal whitelist
+    WhitelistInternal {
+        #[serde(with = "crate::serde_str")]
+        user: Pubkey,
+    },
+    /// Remove an address from the internal whitelist
+    BlacklistInternal {
+        #[serde(with = "crate::serde_str")]
+        user: Pubkey,
+    },
+    /// Add an address to the external whitelist
+    WhitelistExternal {
+        #[serde(with = "crate::serde_str")]
+        user: Pubkey,
+    },
+    /// Remove an address from the external whitelist
+    BlacklistExternal {
+        #[serde(with = "crate::serde_str")]
+        user: Pubkey,
+    },
+    /// Register a trusted contract
+    AddTrustedContract {
+        #[serde(with = "crate::serde_str")]
+        contract: Pubkey,
+    },
+    /// Remove a trusted contract
+    RemoveTrustedContract {
+        #[serde(with = "crate::serde_str")]
+        contract: Pubkey,
+    },
+    /// Pause (`true`) or resume (`false`) minting
+    PauseMinting {
+        #[arg(action = ArgAction::Set)]
+        paused: bool,
+    },
+    /// Rotate the multisig owner set and threshold
+    UpdateMultisig {
+        #[arg(long = "owner", required = true)]
+        #[serde(with = "crate::serde_str::vec")]
+        owners: Vec<Pubkey>,
+        #[arg(long)]
+        threshold: u8,
+    },
+    /// Replace the bridge attesters and threshold, and pause or resume the bridge
+    UpdateBridge {
+        #[arg(long = "attester")]
+        #[serde(with = "crate::serde_str::vec")]
+        attesters: Vec<Pubkey>,
+        #[arg(long)]
+        threshold: u8,
+        #[arg(long, action = ArgAction::Set)]
+        paused: bool,
+    },
+    /// Write the Token-2022 metadata onto the mint, using the token config's
+    /// name and symbol
+    InitializeMetadata { uri: String },
+    /// Set a metadata field: `name`, `symbol`, `uri`, or any other key as an
+    /// additional field (e.g. `issuer`)
+    UpdateMetadataField { field: String, value: String },
+    /// Allow a key to KYC-approve and thaw token accounts
+    AddKycOperator {
+        #[serde(with = "crate::serde_str")]
+        operator: Pubkey,
+    },
+    /// Revoke a KYC operator
+    RemoveKycOperator {
+        #[serde(with = "crate::serde_str")]
+        operator: Pubkey,
+    },
+    /// Set the per-transaction and daily transfer caps of a tier
+    ConfigureTransferTier {
+        tier: u8,
+        #[arg(long)]
+        per_transaction_limit: u64,
+        #[arg(long)]
+        daily_limit: u64,
+    },
+    /// Assign a token owner to a transfer tier
+    SetTransferTier {
+        #[serde(with = "crate::serde_str")]
+        owner: Pubkey,
+        tier: u8,
+    },
+    /// Set the transfer fee; it takes effect two epochs later
+    SetTransferFee {
+        #[arg(long)]
+        basis_points: u16,
+        #[arg(long)]
+        maximum_fee: u64,
+    },
+    /// Set the fee treasury token account and whether internal-whitelisted
+    /// owners are refunded their fees
+    UpdateFeeConfig {
+        #[serde(with = "crate::serde_str")]
+        treasury: Pubkey,
+        #[arg(long, action = ArgAction::Set)]
+        exempt_internal_whitelist: bool,
+    },
+    /// Set the base64 ElGamal key confidential transfers are audited with;
+    /// omit `--auditor` to remove the auditor
+    UpdateConfidentialAuditor {
+        #[arg(long)]
+        #[serde(with = "crate::serde_str::option", default)]
+        auditor: Option<ElGamalPubkey>,
+    },
+    /// Set the annual savings yield rate, the accrual period and the supply
+    /// cap and reserves yield minting must stay within
+    UpdateYieldConfig {
+        #[arg(long)]
+        annual_rate_basis_points: u16,
+        #[arg(long)]
+        period_seconds: u32,
+        #[arg(long)]
+        supply_cap: u64,
+        #[arg(long)]
+        reserves: u64,
+    },
+    /// Publish the external allowlist's hex Merkle root; proofs against the
+    /// previous root stay valid for the grace period
+    UpdateExternalAllowlist {
+        #[serde(with = "crate::serde_str")]
+        merkle_root: MerkleRoot,
+        #[arg(long)]
+        grace_period_seconds: u32,
+    },
+    /// Apply one list update to many addresses under a single approval
+    Batch {
+        #[arg(value_enum)]
+        op: BatchOp,
+        #[arg(required = true)]
+        #[serde(with = "crate::serde_str::vec")]
+        addresses: Vec<Pubkey>,
+        /// Why the addresses are blacklisted; `add-blacklist` only
+        #[arg(long, required_if_eq("op", "add-blacklist"))]
+        #[serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "crate::serde_str::option"
+        )]
+        reason: Option<BlacklistReason>,
+        /// `add-blacklist` only
+        #[arg(long, required_if_eq("op", "add-blacklist"))]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        case_reference: Option<String>,
+        /// Unix time the listings are up for review; 0 for none.
+        /// `add-blacklist` only
+        #[arg(long, default_value_t = 0)]
+        #[serde(default)]
+        review_at: i64,
+        /// Unix time the listings lapse; 0 for never. `add-blacklist` only
+        #[arg(long, default_value_t = 0)]
+        #[serde(default)]
+        expires_at: i64,
+    },
+}
+
+fn blacklist_details(
+    reason: BlacklistReason,
+    case_reference: &str,
+    review_at: i64,
+    expires_at: i64,
+) -> ms::BlacklistDetails {
+    ms::BlacklistDetails {
+        reason,
+        case_reference: case_reference.to_string(),
+        review_at,
+        expires_at,
+    }
+}
+
+/// The list updates `batch` can apply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchOp {
+    AddBlacklist,
+    RemoveBlacklist,
+    WhitelistInternal,
+    WhitelistExternal,
+    AddCanForward,
+    RemoveCanForward,
+    AddTrustedContract,
+    RemoveTrustedContract,
+}
+
+impl BatchOp {
+    /// The tag the program's batch message starts with.
+    fn action(self) -> &'static str {
+        match self {
+            Self::AddBlacklist => "BATCH_ADD_BLACKLIST",
+            Self::RemoveBlacklist => "BATCH_REMOVE_BLACKLIST",
+            Self::WhitelistInternal => "BATCH_WHITELIST_INTERNAL",
+            Self::WhitelistExternal => "BATCH_WHITELIST_EXTERNAL",
+            Self::AddCanForward => "BATCH_ADD_CAN_FORWARD",
+            Self::RemoveCanForward => "BATCH_REMOVE_CAN_FORWARD",
+            Self::AddTrustedContract => "BATCH_ADD_TRUSTED_CONTRACT",
+            Self::RemoveTrustedContract => "BATCH_REMOVE_TRUSTED_CONTRACT",
+        }
+    }
+
+    /// The list account the batch message commits to.
+    fn list_account(self, pdas: &Pdas) -> &Pubkey {
+        match self {
+            Self::AddBlacklist | Self::RemoveBlacklist => &pdas.blacklist,
+            Self::WhitelistInternal => &pdas.internal_whitelist,
+            Self::WhitelistExternal => &pdas.external_whitelist,
+            Self::AddCanForward | Self::RemoveCanForward => &pdas.can_forward,
+            Self::AddTrustedContract | Self::RemoveTrustedContract => &pdas.trusted_contracts,
+        }
+    }
+
+    /// The single-address action; its instruction takes the same accounts.
+    fn single(self, address: Pubkey) -> AdminAction {
+        match self {
+            Self::AddBlacklist => AdminAction::AddBlacklist {
+                user: address,
+                reason: BlacklistReason::Other,
+                case_reference: String::new(),
+                review_at: 0,
+                expires_at: 0,
+            },
+            Self::RemoveBlacklist => AdminAction::RemoveBlacklist { user: address },
+            Self::WhitelistInternal => AdminAction::WhitelistInternal { user: address },
+            Self::WhitelistExternal => AdminAction::WhitelistExternal { user: address },
+            Self::AddCanForward => AdminAction::AddCanForward { forwarder: address },
+            Self::RemoveCanForward => AdminAction::RemoveCanForward { forwarder: address },
+            Self::AddTrustedContract => AdminAction::AddTrustedContract { contract: address },
+            Self::RemoveTrustedContract => AdminAction::RemoveTrustedContract { contract: address },
+        }
+    }
+
+    fn data(self, addresses: &[Pubkey], details: ms::BlacklistDetails) -> Vec<u8> {
+        use cngn::instruction;
+
+        let addresses = addresses.to_vec();
+        match self {
+            Self::AddBlacklist => instruction::BatchAddBlacklist {
+                users: addresses,
+                details,
+            }
+            .data(),
+            Self::RemoveBlacklist => instruction::BatchRemoveBlacklist { users: addresses }.data(),
+            Self::WhitelistInternal => {
+                instruction::BatchWhitelistInternal { users: addresses }.data()
+            }
+            Self::WhitelistExternal => {
+                instruction::BatchWhitelistExternal { users: addresses }.data()
+            }
+            Self::AddCanForward => instruction::BatchAddCanForward {
+                forwarders: addresses,
+            }
+            .data(),
+            Self::RemoveCanForward => instruction::BatchRemoveCanForward {
+                forwarders: addresses,
+            }
+            .data(),
+            Self::AddTrustedContract => instruction::BatchAddTrustedContract {
+                contracts: addresses,
+            }
+            .data(),
+            Self::RemoveTrustedContract => instruction::BatchRemoveTrustedContract {
+                contracts: addresses,
+            }
+            .data(),
+        }
+    }
+}
+
+fn tier_limits(per_transaction: u64, daily: u64) -> ms::TierLimits {
+    ms::TierLimits {
+        per_transaction,
+        daily,
+    }
+}
+
+/// An ElGamal public key in the base64 form printed by `spl-token`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ElGamalPubkey(pub [u8; 32]);
+
+impl FromStr for ElGamalPubkey {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> anyhow::Result<Self> {
+        let bytes = BASE64.decode(value)?;
+        let key = bytes
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("an ElGamal public key is 32 bytes"))?;
+        Ok(Self(key))
+    }
+}
+
+impl fmt::Display for ElGamalPubkey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&BASE64.encode(self.0))
+    }
+}
+
+/// A Merkle root in hex, as printed by `cngn-admin allowlist`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MerkleRoot(pub [u8; 32]);
+
+impl FromStr for MerkleRoot {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> anyhow::Result<Self> {
+        let bytes = hex::decode(value)?;
+        let root = bytes
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("a Merkle root is 32 bytes"))?;
+        Ok(Self(root))
+    }
+}
+
+impl fmt::Display for MerkleRoot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&hex::encode(self.0))
+    }
+}
+
+/// The program's metadata field for a CLI field name.
+fn metadata_field(field: &str) -> ms::MetadataField {
+    match field {
+        "name" => ms::MetadataField::Name,
+        "symbol" => ms::MetadataField::Symbol,
+        "uri" => ms::MetadataField::Uri,
+        key => ms::MetadataField::Key(key.to_string()),
+    }
+}
+
+impl AdminAction {
+    /// The digest the multisig owners must sign, exactly as the program rebuilds it.
+    pub fn message(&self, pdas: &Pdas, nonce: u64) -> Vec<u8> {
+        match self {
+            Self::AddCanMint { user } => {
+                ms::build_add_can_mint_message(&pdas.can_mint, user, nonce)
+            }
+            Self::RemoveCanMint { user } => {
+                ms::build_remove_can_mint_message(&pdas.can_mint, user, nonce)
+            }
+            Self::SetMintAmount { user, .. } => {
+                ms::build_set_mint_amount_message(&pdas.can_mint, user, nonce)
+            }
+            Self::RemoveMintAmount { user } => {
+                ms::build_remove_mint_amount_message(&pdas.can_mint, user, nonce)
+            }
+            Self::AddCanForward { forwarder } => {
+                ms::build_add_can_forward_message(&pdas.can_forward, forwarder, nonce)
+            }
+            Self::RemoveCanForward { forwarder } => {
+                ms::build_remove_can_forward_message(&pdas.can_forward, forwarder, nonce)
+            }
+            Self::AddBlacklist {
+                user,
+                reason,
+                case_reference,
+                review_at,
+                expires_at,
+            } => ms::build_add_blacklist_message(
+                &pdas.blacklist,
+                user,
+                &blacklist_details(*reason, case_reference, *review_at, *expires_at),
+                nonce,
+            ),
+            Self::RemoveBlacklist { user } => {
+                ms::build_remove_blacklist_message(&pdas.blacklist, user, nonce)
+            }
+            Self::WhitelistInternal { user } => {
+                ms::build_whitelist_internal_message(&pdas.internal_whitelist, user, nonce)
+            }
+            Self::BlacklistInternal { user } => {
+                ms::build_blacklist_internal_message(&pdas.internal_whitelist, user, nonce)
+            }
+            Self::WhitelistExternal { user } => {
+                ms::build_whitelist_external_message(&pdas.external_whitelist, user, nonce)
+            }
+            Self::BlacklistExternal { user } => {
+                ms::build_blacklist_external_message(&pdas.external_whitelist, user, nonce)
+            }
+            Self::AddTrustedContract { contract } => {
+                ms::build_add_trusted_contract_message(&pdas.trusted_contracts, contract, nonce)
+            }
+            Self::RemoveTrustedContract { contract } => {
+                ms::build_remove_trusted_contract_message(&pdas.trusted_contracts, contract, nonce)
+            }
+            Self::PauseMinting { .. } => ms::build_pause_mint_message(&pdas.token_config, nonce),
+            Self::UpdateMultisig { owners, threshold } => {
+                ms::build_update_multisig_message(&pdas.multisig, owners, *threshold, nonce)
+            }
+            Self::UpdateBridge {
+                attesters,
+                threshold,
+                paused,
+            } => ms::build_update_bridge_message(
+                &pdas.bridge_config,
+                attesters,
+                *threshold,
+                *paused,
+                nonce,
+            ),
+            Self::InitializeMetadata { uri } => {
+                ms::build_initialize_metadata_message(&pdas.mint, uri, nonce)
+            }
+            Self::UpdateMetadataField { field, value } => ms::build_update_metadata_field_message(
+                &pdas.mint,
+                &metadata_field(field),
+                value,
+                nonce,
+            ),
+            Self::AddKycOperator { operator } => {
+                ms::build_add_kyc_operator_message(&pdas.kyc_operators, operator, nonce)
+            }
+            Self::RemoveKycOperator { operator } => {
+                ms::build_remove_kyc_operator_message(&pdas.kyc_operators, operator, nonce)
+            }
+            Self::ConfigureTransferTier {
+                tier,
+                per_transaction_limit,
+                daily_limit,
+            } => ms::build_configure_transfer_tier_message(
+                &pdas.transfer_tiers,
+                *tier,
+                &tier_limits(*per_transaction_limit, *daily_limit),
+                nonce,
+            ),
+            Self::SetTransferTier { owner, tier } => ms::build_set_transfer_tier_message(
+                &pdas.transfer_limits(owner),
+                owner,
+                *tier,
+                nonce,
+            ),
+            Self::SetTransferFee {
+                basis_points,
+                maximum_fee,
+            } => ms::build_set_transfer_fee_message(&pdas.mint, *basis_points, *maximum_fee, nonce),
+            Self::UpdateFeeConfig {
+                treasury,
+                exempt_internal_whitelist,
+            } => ms::build_update_fee_config_message(
+                &pdas.fee_config,
+                treasury,
+                *exempt_internal_whitelist,
+                nonce,
+            ),
+            Self::UpdateConfidentialAuditor { auditor } => {
+                ms::build_update_confidential_auditor_message(
+                    &pdas.mint,
+                    auditor.map(|auditor| auditor.0),
+                    nonce,
+                )
+            }
+            Self::UpdateYieldConfig {
+                annual_rate_basis_points,
+                period_seconds,
+                supply_cap,
+                reserves,
+            } => ms::build_update_yield_config_message(
+                &pdas.yield_config,
+                *annual_rate_basis_points,
+                *period_seconds,
+                *supply_cap,
+                *reserves,
+                nonce,
+            ),
+            Self::UpdateExternalAllowlist {
+                merkle_root,
+                grace_period_seconds,
+            } => ms::build_update_external_allowlist_message(
+                &pdas.external_allowlist,
+                &merkle_root.0,
+                *grace_period_seconds,
+                nonce,
+            ),
+            Self::Batch {
+                op: BatchOp::AddBlacklist,
+                addresses,
+                ..
+            } => ms::build_batch_add_blacklist_message(
+                &pdas.blacklist,
+                addresses,
+                &self.batch_details(),
+                nonce,
+            ),
+            Self::Batch { op, addresses, .. } => {
+                ms::build_batch_admin_message(op.action(), op.list_account(pdas), addresses, nonce)
+            }
+        }
+    }
+
+    /// The off-chain message wrapping a human-readable description of the
+    /// action, exactly as the program rebuilds it.
+    pub fn readable_message(&self, pdas: &Pdas, nonce: u64) -> Vec<u8> {
+        let target = |action, account, target| {
+            ms::build_readable_target_message(action, account, target, nonce)
+        };
+
+        match self {
+            Self::AddCanMint { user } => target("ADD_CAN_MINT", &pdas.can_mint, user),
+            Self::RemoveCanMint { user } => target("REMOVE_CAN_MINT", &pdas.can_mint, user),
+            Self::SetMintAmount { user, amount } => {
+                ms::build_readable_set_mint_amount_message(&pdas.can_mint, user, *amount, nonce)
+            }
+            Self::RemoveMintAmount { user } => target("REMOVE_MINT_AMOUNT", &pdas.can_mint, user),
+            Self::AddCanForward { forwarder } => {
+                target("ADD_CAN_FORWARD", &pdas.can_forward, forwarder)
+            }
+            Self::RemoveCanForward { forwarder } => {
+                target("REMOVE_CAN_FORWARD", &pdas.can_forward, forwarder)
+            }
+            Self::AddBlacklist {
+                user,
+                reason,
+                case_reference,
+                review_at,
+                expires_at,
+            } => ms::build_readable_add_blacklist_message(
+                &pdas.blacklist,
+                user,
+                &blacklist_details(*reason, case_reference, *review_at, *expires_at),
+                nonce,
+            ),
+            Self::RemoveBlacklist { user } => target("REMOVE_BLACKLIST", &pdas.blacklist, user),
+            Self::WhitelistInternal { user } => {
+                target("WHITELIST_INTERNAL", &pdas.internal_whitelist, user)
+            }
+            Self::BlacklistInternal { user } => {
+                target("BLACKLIST_INTERNAL", &pdas.internal_whitelist, user)
+            }
+            Self::WhitelistExternal { user } => {
+                target("WHITELIST_EXTERNAL", &pdas.external_whitelist, user)
+            }
+            Self::BlacklistExternal { user } => {
+                target("BLACKLIST_EXTERNAL", &pdas.external_whitelist, user)
+            }
+            Self::AddTrustedContract { contract } => {
+                target("ADD_TRUSTED_CONTRACT", &pdas.trusted_contracts, contract)
+            }
+            Self::RemoveTrustedContract { contract } => {
+                target("REMOVE_TRUSTED_CONTRACT", &pdas.trusted_contracts, contract)
+            }
+            Self::PauseMinting { paused } => {
+                ms::build_readable_pause_mint_message(&pdas.token_config, *paused, nonce)
+            }
+            Self::UpdateMultisig { owners, threshold } => {
+                ms::build_readable_update_multisig_message(
+                    &pdas.multisig,
+                    owners,
+                    *threshold,
+                    nonce,
+                )
+            }
+            Self::UpdateBridge {
+                attesters,
+                threshold,
+                paused,
+            } => ms::build_readable_update_bridge_message(
+                &pdas.bridge_config,
+                attesters,
+                *threshold,
+                *paused,
+                nonce,
+            ),
+            Self::InitializeMetadata { uri } => {
+                ms::build_readable_initialize_metadata_message(&pdas.mint, uri, nonce)
+            }
+            Self::UpdateMetadataField { field, value } => {
+                ms::build_readable_update_metadata_field_message(
+                    &pdas.mint,
+                    &metadata_field(field),
+                    value,
+                    nonce,
+                )
+            }
+            Self::AddKycOperator { operator } => {
+                target("ADD_KYC_OPERATOR", &pdas.kyc_operators, operator)
+            }
+            Self::RemoveKycOperator { operator } => {
+                target("REMOVE_KYC_OPERATOR", &pdas.kyc_operators, operator)
+            }
+            Self::ConfigureTransferTier {
+                tier,
+                per_transaction_limit,
+                daily_limit,
+            } => ms::build_readable_configure_transfer_tier_message(
+                &pdas.transfer_tiers,
+                *tier,
+                &tier_limits(*per_transaction_limit, *daily_limit),
+                nonce,
+            ),
+            Self::SetTransferTier { owner, tier } => {
+                ms::build_readable_set_transfer_tier_message(
+                    &pdas.transfer_limits(owner),
+                    owner,
+                    *tier,
+                    nonce,
+                )
+            }
+            Self::SetTransferFee {
+                basis_points,
+                maximum_fee,
+            } => ms::build_readable_set_transfer_fee_message(
+                &pdas.mint,
+                *basis_points,
+                *maximum_fee,
+                nonce,
+            ),
+            Self::UpdateFeeConfig {
+                treasury,
+                exempt_internal_whitelist,
+            } => ms::build_readable_update_fee_config_message(
+                &pdas.fee_config,
+                treasury,
+                *exempt_internal_whitelist,
+                nonce,
+            ),
+            Self::UpdateConfidentialAuditor { auditor } => {
+                ms::build_readable_update_confidential_auditor_message(
+                    &pdas.mint,
+                    auditor.map(|auditor| auditor.0),
+                    nonce,
+                )
+            }
+            Self::UpdateYieldConfig {
+                annual_rate_basis_points,
+                period_seconds,
+                supply_cap,
+                reserves,
+            } => ms::build_readable_update_yield_config_message(
+                &pdas.yield_config,
+                *annual_rate_basis_points,
+                *period_seconds,
+                *supply_cap,
+                *reserves,
+                nonce,
+            ),
+            Self::UpdateExternalAllowlist {
+                merkle_root,
+                grace_period_seconds,
+            } => ms::build_readable_update_external_allowlist_message(
+                &pdas.external_allowlist,
+                &merkle_root.0,
+                *grace_period_seconds,
+                nonce,
+            ),
+            Self::Batch {
+                op: BatchOp::AddBlacklist,
+                addresses,
+                ..
+            } => ms::build_readable_batch_add_blacklist_message(
+                &pdas.blacklist,
+                addresses,
+                &self.batch_details(),
+                nonce,
+            ),
+            Self::Batch { op, addresses, .. } => ms::build_readable_batch_admin_message(
+                op.action(),
+                op.list_account(pdas),
+                addresses,
+                nonce,
+            ),
+        }
+    }
+
+    /// The message to sign in the given format.
+    pub fn encode(&self, format: MessageFormat, pdas: &Pdas, nonce: u64) -> Vec<u8> {
+        match format {
+            MessageFormat::Digest => self.message(pdas, nonce),
+            MessageFormat::Readable => self.readable_message(pdas, nonce),
+        }
+    }
+
+    /// The blacklist details `batch add-blacklist` records. Missing details,
+    /// possible only in a hand-written request, become an empty case reference
+    /// the program rejects.
+    fn batch_details(&self) -> ms::BlacklistDetails {
+        match self {
+            Self::Batch {
+                reason,
+                case_reference,
+                review_at,
+                expires_at,
+                ..
+            } => blacklist_details(
+                reason.unwrap_or(BlacklistReason::Other),
+                case_reference.as_deref().unwrap_or_default(),
+                *review_at,
+                *expires_at,
+            ),
+            _ => unreachable!("only batches carry batch details"),
+        }
+    }
+
+    /// The users whose blacklist entries the action closes, refunding each
+    /// entry's rent to the payer recorded in it.
+    pub fn closed_blacklist_entries(&self) -> &[Pubkey] {
+        match self {
+            Self::RemoveBlacklist { user } => std::slice::from_ref(user),
+            Self::Batch {
+                op: BatchOp::RemoveBlacklist,
+                addresses,
+                ..
+            } => addresses,
+            _ => &[],
+        }
+    }
+
+    /// The blacklist entries the blacklist instructions take as remaining
+    /// accounts. Batch removals follow each entry with its rent recipient.
+    fn blacklist_entries(&self, pdas: &Pdas, rent_recipients: &[Pubkey]) -> Vec<AccountMeta> {
+        let entry = |user: &Pubkey| AccountMeta::new(pdas.blacklist_entry(user), false);
+        match self {
+            Self::AddBlacklist { user, .. } | Self::RemoveBlacklist { user } => vec![entry(user)],
+            Self::Batch {
+                op: BatchOp::AddBlacklist,
+                addresses,
+                ..
+            } => addresses.iter().map(entry).collect(),
+            Self::Batch {
+                op: BatchOp::RemoveBlacklist,
+                addresses,
+                ..
+            } => addresses
+                .iter()
+                .zip(rent_recipients)
+                .flat_map(|(user, recipient)| [entry(user), AccountMeta::new(*recipient, false)])
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// The program instruction that executes this action once the Ed25519
+    /// approvals precede it in the same transaction. `payer` funds any rent
+    /// the action needs. `entry_payers` holds the payer recorded in each of
+    /// the `closed_blacklist_entries`, or none where the entry does not
+    /// exist and `payer` stands in.
+    pub fn instruction(
+        &self,
+        program_id: &Pubkey,
+        pdas: &Pdas,
+        payer: &Pubkey,
+        entry_payers: &[Option<Pubkey>],
+    ) -> Instruction {
+        let rent_recipients: Vec<Pubkey> = entry_payers
+            .iter()
+            .map(|entry_payer| entry_payer.unwrap_or(*payer))
+            .collect();
+        let (mut accounts, data) = self.accounts_and_data(pdas, payer, &rent_recipients);
+        accounts.extend(self.blacklist_entries(pdas, &rent_recipients));
+
+        Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        }
+    }
+
+    fn accounts_and_data(
+        &self,
+        pdas: &Pdas,
+        payer: &Pubkey,
+        rent_recipients: &[Pubkey],
+    ) -> (Vec<AccountMeta>, Vec<u8>) {
+        use cngn::{accounts, instruction};
+
+        let mint = pdas.mint;
+        let token_config = pdas.token_config;
+        let multisig = pdas.multisig;
+        let instructions = sysvar::instructions::ID;
+
+        match self {
+            Self::AddCanMint { user } => (
+                accounts::AddCanMint {
+                    mint,
+                    token_config,
+                    blacklist: pdas.blacklist,
+                    can_mint: pdas.can_mint,
+                    trusted_contracts: pdas.trusted_contracts,
+                    multisig,
+                    instructions,
+                }
+                .to_account_metas(None),
+                instruction::AddCanMint { user: *user }.data(),
+            ),
+            Self::RemoveCanMint { user } => (
+                accounts::RemoveCanMint {
+                    mint,
+                    token_config,
+                    can_mint: pdas.can_mint,
+                    trusted_contracts: pdas.trusted_contracts,
+                    multisig,
+                    instructions,
+                }
+                .to_account_metas(None),
+                instruction::RemoveCanMint { user: *user }.data(),
+            ),
+            Self::SetMintAmount { user, amount } => (
+                accounts::SetMintAmount {
+                    multisig,
+                    mint,
+                    token_config,
+                    can_mint: pdas.can_mint,
+                    instructions,
+                }
+                .to_account_metas(None),
+                instruction::SetMintAmount {
+                    user: *user,
+                    amount: *amount,
+                }
+                .data(),
+            ),
+            Self::RemoveMintAmount { user } => (
+                accounts::RemoveMintAmount {
+                    multisig,
+                    mint,
+                    token_config,
+                    can_mint: pdas.can_mint,
+                    instructions,
+                }
+                .to_account_metas(None),
+                instruction::RemoveMintAmount { user: *user }.data(),
+            ),
+            Self::AddCanForward { forwarder } => (
+                accounts::AddCanForward {
+                    mint,
+                    token_config,
+                    blacklist: pdas.blacklist,
+                    can_forward: pdas.can_forward,
+                    multisig,
+                    instructions,
+                }
+                .to_account_metas(None),
+                instruction::AddCanForward {
+                    forwarder: *forwarder,
+                }
+                .data(),
+            ),
+            Self::RemoveCanForward { forwarder } => (
+                accounts::RemoveCanForward {
+                    mint,
+                    token_config,
+                    can_forward: pdas.can_forward,
+                    multisig,
+                    instructions,
+                }
+                .to_account_metas(None),
+                instruction::RemoveCanForward {
+                    forwarder: *forwarder,
+                }
+                .data(),
+            ),
+            Self::AddBlacklist {
+                user,
+                reason,
+                case_reference,
+                review_at,
+                expires_at,
+            } => (
+                accounts::AddBlackList {
+                    payer: *payer,
+                    mint,
+                    token_config,
+                    can_mint: pdas.can_mint,
+                    internal_whitelist: pdas.internal_whitelist,
+                    external_whitelist: pdas.external_whitelist,
+                    trusted_contracts: pdas.trusted_contracts,
+                    can_forward: pdas.can_forward,
+                    blacklist: pdas.blacklist,
+                    multisig,
+                    instructions,
+                    system_program: system_program::ID,
+                }
+                .to_account_metas(None),
+                instruction::AddBlacklist {
+                    evil_user: *user,
+                    details: blacklist_details(*reason, case_reference, *review_at, *expires_at),
+                }
+                .data(),
+            ),
+            Self::RemoveBlacklist { user } => (
+                accounts::RemoveBlackList {
+                    mint,
+                    token_config,
+                    blacklist: pdas.blacklist,
+                    multisig,
+                    // Batches pair a recipient with each entry instead
+                    rent_recipient: rent_recipients.first().copied().unwrap_or(*payer),
+                    instructions,
+                }
+                .to_account_metas(None),
+                instruction::RemoveBlacklist { clear_user: *user }.data(),
+            ),
+            Self::WhitelistInternal { user } => (
+                accounts::WhitelistInternalUser {
+                    mint,
+                    token_config,
+                    internal_whitelist: pdas.internal_whitelist,
+                    blacklist: pdas.blacklist,
+                    multisig,
+                    instructions,
+                }
+                .to_account_metas(None),
+                instruction::WhitelistInternalUser { user: *user }.data(),
+            ),
+            Self::BlacklistInternal { user } => (
+                accounts::BlacklistInternalUser {
+                    mint,
+                    token_config,
+                    internal_whitelist: pdas.internal_whitelist,
+                    trusted_contracts: pdas.trusted_contracts,
+                    multisig,
+                    instructions,
+                }
+                .to_account_metas(None),
+                instruction::BlacklistInternalUser { user: *user }.data(),
+            ),
+            Self::WhitelistExternal { user } => (
+                accounts::WhitelistExternalUser {
+                    mint,
+                    token_config,
+                    external_whitelist: pdas.external_whitelist,
+                    blacklist: pdas.blacklist,
+                    multisig,
+                    instructions,
+                }
+                .to_account_metas(None),
+                instruction::WhitelistExternalUser { user: *user }.data(),
+            ),
+            Self::BlacklistExternal { user } => (
+                accounts::BlacklistExternalUser {
+                    mint,
+                    token_config,
+                    external_whitelist: pdas.external_whitelist,
+                    multisig,
+                    instructions,
+                }
+                .to_account_metas(None),
+                instruction::BlacklistExternalUser { user: *user }.data(),
+            ),
+            Self::AddTrustedContract { contract } => (
+                accounts::AddTrustedContract {
+                    mint,
+                    token_config,
+                    trusted_contracts: pdas.trusted_contracts,
+                    multisig,
+                    instructions,
+                }
+                .to_account_metas(None),
+                instruction::AddTrustedContract {
+                    contract: *contract,
+                }
+                .data(),
+            ),
+            Self::RemoveTrustedContract { contract } => (
+                accounts::RemoveTrustedContract {
+                    mint,
+                    token_config,
+                    trusted_contracts: pdas.trusted_contracts,
+                    multisig,
+                    instructions,
+                }
+                .to_account_metas(None),
+                instruction::RemoveTrustedContract {
+                    contract: *contract,
+                }
+                .data(),
+            ),
+            Self::PauseMinting { paused } => (
+                accounts::PauseMint {
+                    multisig,
+                    token_config,
+                    instructions,
+                }
+                .to_account_metas(None),
+                instruction::PauseMinting {
+                    pause_mint: *paused,
+                }
+                .data(),
+            ),
+            Self::UpdateMultisig { owners, threshold } => (
+                accounts::UpdateMultisig {
+                    multisig,
+                    mint,
+                    instructions,
+                }
+                .to_account_metas(None),
+                instruction::UpdateMultisig {
+                    owners: owners.clone(),
+                    threshold: *threshold,
+                }
+                .data(),
+            ),
+            Self::UpdateBridge {
+                attesters,
+                threshold,
+                paused,
+            } => (
+                accounts::UpdateBridge {
+                    mint,
+                    token_config,
+                    bridge_config: pdas.bridge_config,
+                    multisig,
+                    instructions,
+                }
+                .to_account_metas(None),
+                instruction::UpdateBridge {
+                    attesters: attesters.clone(),
+                    threshold: *threshold,
+                    paused: *paused,
+                }
+                .data(),
+            ),
+            Self::InitializeMetadata { uri } => (
+                accounts::InitializeMetadata {
+                    payer: *payer,
+                    mint,
+                    token_config,
+                    mint_authority: pdas.mint_authority,
+                    multisig,
+                    instructions,
+                    token_program: anchor_spl::token_2022::ID,
+                    system_program: system_program::ID,
+                }
+                .to_account_metas(None),
+                instruction::InitializeMetadata { uri: uri.clone() }.data(),
+            ),
+            Self::UpdateMetadataField { field, value } => (
+                accounts::UpdateMetadataField {
+                    payer: *payer,
+                    mint,
+                    token_config,
+                    mint_authority: pdas.mint_authority,
+                    multisig,
+                    instructions,
+                    token_program: anchor_spl::token_2022::ID,
+                    system_program: system_program::ID,
+                }
+                .to_account_metas(None),
+                instruction::UpdateMetadataField {
+                    field: metadata_field(field),
+                    value: value.clone(),
+                }
+                .data(),
+            ),
+            Self::AddKycOperator { operator } => (
+                accounts::AddKycOperator {
+                    mint,
+                    token_config,
+                    blacklist: pdas.blacklist,
+                    kyc_operators: pdas.kyc_operators,
+                    multisig,
+                    instructions,
+                }
+                .to_account_metas(None),
+                instruction::AddKycOperator {
+                    operator: *operator,
+                }
+                .data(),
+            ),
+            Self::RemoveKycOperator { operator } => (
+                accounts::RemoveKycOperator {
+                    mint,
+                    token_config,
+                    kyc_operators: pdas.kyc_operators,
+                    multisig,
+                    instructions,
+                }
+                .to_account_metas(None),
+                instruction::RemoveKycOperator {
+                    operator: *operator,
+                }
+                .data(),
+            ),
+            Self::ConfigureTransferTier {
+                tier,
+                per_transaction_limit,
+                daily_limit,
+            } => (
+                accounts::ConfigureTransferTier {
+                    mint,
+                    token_config,
+                    transfer_tiers: pdas.transfer_tiers,
+                    multisig,
+                    instructions,
+                }
+                .to_account_metas(None),
+                instruction::ConfigureTransferTier {
+                    tier: *tier,
+                    per_transaction_limit: *per_transaction_limit,
+                    daily_limit: *daily_limit,
+                }
+                .data(),
+            ),
+            Self::SetTransferTier { owner, tier } => (
+                accounts::SetTransferTier {
+                    payer: *payer,
+                    mint,
+                    token_config,
+                    transfer_limits: pdas.transfer_limits(owner),
+                    kyc_record: pdas.kyc_record(owner),
+                    internal_whitelist: pdas.internal_whitelist,
+                    external_whitelist: pdas.external_whitelist,
+                    multisig,
+                    instructions,
+                    system_program: system_program::ID,
+                }
+                .to_account_metas(None),
+                instruction::SetTransferTier {
+                    owner: *owner,
+                    tier: *tier,
+                }
+                .data(),
+            ),
+            Self::SetTransferFee {
+                basis_points,
+                maximum_fee,
+            } => (
+                accounts::SetTransferFee {
+                    mint,
+                    token_config,
+                    mint_authority: pdas.mint_authority,
+                    multisig,
+                    instructions,
+                    token_program: anchor_spl::token_2022::ID,
+                }
+                .to_account_metas(None),
+                instruction::SetTransferFee {
+                    basis_points: *basis_points,
+                    maximum_fee: *maximum_fee,
+                }
+                .data(),
+            ),
+            Self::UpdateFeeConfig {
+                treasury,
+                exempt_internal_whitelist,
+            } => (
+                accounts::UpdateFeeConfig {
+                    payer: *payer,
+                    mint,
+                    token_config,
+                    fee_config: pdas.fee_config,
+                    treasury: *treasury,
+                    multisig,
+                    instructions,
+                    system_program: system_program::ID,
+                }
+                .to_account_metas(None),
+                instruction::UpdateFeeConfig {
+                    exempt_internal_whitelist: *exempt_internal_whitelist,
+                }
+                .data(),
+            ),
+            Self::UpdateConfidentialAuditor { auditor } => (
+                accounts::UpdateConfidentialAuditor {
+                    mint,
+                    token_config,
+                    mint_authority: pdas.mint_authority,
+                    multisig,
+                    instructions,
+                    token_program: anchor_spl::token_2022::ID,
+                }
+                .to_account_metas(None),
+                instruction::UpdateConfidentialAuditor {
+                    auditor_elgamal_pubkey: auditor.map(|auditor| auditor.0),
+                }
+                .data(),
+            ),
+            Self::UpdateYieldConfig {
+                annual_rate_basis_points,
+                period_seconds,
+                supply_cap,
+                reserves,
+            } => (
+                accounts::UpdateYieldConfig {
+                    payer: *payer,
+                    mint,
+                    token_config,
+                    yield_config: pdas.yield_config,
+                    multisig,
+                    instructions,
+                    system_program: system_program::ID,
+                }
+                .to_account_metas(None),
+                instruction::UpdateYieldConfig {
+                    annual_rate_basis_points: *annual_rate_basis_points,
+                    period_seconds: *period_seconds,
+                    supply_cap: *supply_cap,
+                    reserves: *reserves,
+                }
+                .data(),
+            ),
+            Self::UpdateExternalAllowlist {
+                merkle_root,
+                grace_period_seconds,
+            } => (
+                accounts::UpdateExternalAllowlist {
+                    payer: *payer,
+                    mint,
+                    token_config,
+                    external_allowlist: pdas.external_allowlist,
+                    multisig,
+                    instructions,
+                    system_program: system_program::ID,
+                }
+                .to_account_metas(None),
+                instruction::UpdateExternalAllowlist {
+                    merkle_root: merkle_root.0,
+                    grace_period_seconds: *grace_period_seconds,
+                }
+                .data(),
+            ),
+            Self::Batch { op, addresses, .. } => {
+                let (accounts, _) =
+                    op.single(Pubkey::default())
+                        .accounts_and_data(pdas, payer, &[]);
+                (accounts, op.data(addresses, self.batch_details()))
+            }
+        }
+    }
+}
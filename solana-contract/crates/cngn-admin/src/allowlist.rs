@@ -0,0 +1,80 @@
+// crates/cngn-admin/src/allowlist.rs
+//! Builds the external allowlist tree the program verifies proofs against
+//! (`allowlist_proof_root` in `programs/cngn/src/state/external_allowlist.rs`).
+use anchor_lang::prelude::Pubkey;
+use anyhow::{anyhow, Context, Result};
+use cngn::state::{allowlist_leaf, allowlist_parent};
+use std::path::Path;
+
+/// Every level of the tree over `owners`, leaves first. Leaves are sorted
+/// and deduplicated; a level's odd last node moves up unchanged.
+pub struct AllowlistTree {
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl AllowlistTree {
+    pub fn new(owners: &[Pubkey]) -> Result<Self> {
+        let mut leaves: Vec<[u8; 32]> = owners.iter().map(allowlist_leaf).collect();
+        leaves.sort_unstable();
+        leaves.dedup();
+        if leaves.is_empty() {
+            return Err(anyhow!("the allowlist is empty"));
+        }
+
+        let mut levels = vec![leaves];
+        while levels.last().map_or(0, Vec::len) > 1 {
+            let level = levels.last().expect("levels start with the leaves");
+            let parents = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => allowlist_parent(left, right),
+                    [node] => *node,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(parents);
+        }
+
+        Ok(Self { levels })
+    }
+
+    /// Read one base58 address per line; blank lines and `#` comments are
+    /// skipped.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let text =
+            std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        let owners = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| {
+                line.parse()
+                    .with_context(|| format!("invalid address {line}"))
+            })
+            .collect::<Result<Vec<Pubkey>>>()?;
+
+        Self::new(&owners)
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.levels.last().expect("levels start with the leaves")[0]
+    }
+
+    /// Sibling hashes from `owner`'s leaf to the root.
+    pub fn proof(&self, owner: &Pubkey) -> Result<Vec<[u8; 32]>> {
+        let leaf = allowlist_leaf(owner);
+        let mut index = self.levels[0]
+            .binary_search(&leaf)
+            .map_err(|_| anyhow!("{owner} is not on the allowlist"))?;
+
+        let mut proof = Vec::new();
+        for level in &self.levels[..self.levels.len() - 1] {
+            if let Some(sibling) = level.get(index ^ 1) {
+                proof.push(*sibling);
+            }
+            index /= 2;
+        }
+
+        Ok(proof)
+    }
+}
@@ -0,0 +1,160 @@
+// crates/cngn-admin/src/approval.rs
+//! Portable approval files for owners who sign on air-gapped machines.
+//!
+//! An [`ApprovalRequest`] describes one admin action at a fixed multisig nonce
+//! together with the digest the program will rebuild. Each owner verifies the
+//! request offline and produces a [`SignatureFile`]; the assembler merges the
+//! signature files into a transaction.
+use crate::action::{AdminAction, MessageFormat};
+use crate::pdas::Pdas;
+use crate::signing::Approval;
+use anchor_lang::prelude::Pubkey;
+use anyhow::{bail, ensure, Context, Result};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use solana_keypair::Keypair;
+use solana_signature::Signature;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+pub const APPROVAL_FORMAT_VERSION: u8 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApprovalRequest {
+    pub version: u8,
+    #[serde(with = "crate::serde_str")]
+    pub program_id: Pubkey,
+    #[serde(with = "crate::serde_str")]
+    pub mint: Pubkey,
+    #[serde(with = "crate::serde_str")]
+    pub multisig: Pubkey,
+    pub nonce: u64,
+    pub action: AdminAction,
+    /// Whether owners sign the digest or the human-readable message.
+    #[serde(default)]
+    pub format: MessageFormat,
+    /// Hex encoded message the owners sign.
+    pub digest: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignatureFile {
+    pub version: u8,
+    /// Hex encoded message that was signed; must equal the request digest.
+    pub digest: String,
+    #[serde(with = "crate::serde_str")]
+    pub owner: Pubkey,
+    #[serde(with = "crate::serde_str")]
+    pub signature: Signature,
+}
+
+impl ApprovalRequest {
+    pub fn new(
+        program_id: Pubkey,
+        mint: Pubkey,
+        nonce: u64,
+        action: AdminAction,
+        format: MessageFormat,
+    ) -> Self {
+        let pdas = Pdas::derive(&program_id, &mint);
+        let digest = hex::encode(action.encode(format, &pdas, nonce));
+
+        Self {
+            version: APPROVAL_FORMAT_VERSION,
+            program_id,
+            mint,
+            multisig: pdas.multisig,
+            nonce,
+            action,
+            format,
+            digest,
+        }
+    }
+
+    pub fn pdas(&self) -> Pdas {
+        Pdas::derive(&self.program_id, &self.mint)
+    }
+
+    /// Rebuild the digest from the action and check it against the one in the
+    /// file, so a tampered or hand-edited request is never signed or sent.
+    pub fn verified_message(&self) -> Result<Vec<u8>> {
+        ensure!(
+            self.version == APPROVAL_FORMAT_VERSION,
+            "unsupported approval request version {}",
+            self.version
+        );
+
+        let pdas = self.pdas();
+        ensure!(
+            self.multisig == pdas.multisig,
+            "request multisig {} is not the multisig PDA {} of mint {}",
+            self.multisig,
+            pdas.multisig,
+            self.mint
+        );
+
+        let message = self.action.encode(self.format, &pdas, self.nonce);
+        ensure!(
+            hex::encode(&message) == self.digest.to_lowercase(),
+            "request digest {} does not match the action (expected {})",
+            self.digest,
+            hex::encode(&message)
+        );
+
+        Ok(message)
+    }
+
+    pub fn sign(&self, keypair: &Keypair) -> Result<SignatureFile> {
+        let message = self.verified_message()?;
+        let approval = Approval::sign(keypair, &message);
+
+        Ok(SignatureFile {
+            version: APPROVAL_FORMAT_VERSION,
+            digest: hex::encode(&message),
+            owner: approval.owner,
+            signature: approval.signature,
+        })
+    }
+
+    /// Verify every signature file against this request and return one
+    /// approval per distinct owner.
+    pub fn collect(&self, files: &[SignatureFile]) -> Result<Vec<Approval>> {
+        let message = self.verified_message()?;
+        let mut owners = BTreeSet::new();
+        let mut approvals = Vec::with_capacity(files.len());
+
+        for file in files {
+            ensure!(
+                file.digest.to_lowercase() == hex::encode(&message),
+                "signature from {} is for digest {}, not {}",
+                file.owner,
+                file.digest,
+                self.digest
+            );
+
+            let approval = Approval {
+                owner: file.owner,
+                signature: file.signature,
+            };
+            if !approval.verify(&message) {
+                bail!("signature from {} is invalid", file.owner);
+            }
+            if owners.insert(file.owner) {
+                approvals.push(approval);
+            }
+        }
+
+        Ok(approvals)
+    }
+}
+
+pub fn read_json<T: DeserializeOwned>(path: &Path) -> Result<T> {
+    let contents =
+        fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+    serde_json::from_str(&contents).with_context(|| format!("{} is malformed", path.display()))
+}
+
+pub fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    let contents = serde_json::to_string_pretty(value)?;
+    fs::write(path, contents + "\n").with_context(|| format!("failed to write {}", path.display()))
+}
@@ -0,0 +1,13 @@
+// crates/cngn-admin/src/lib.rs
+//! Build, approve and submit multisig-gated cNGN admin instructions.
+pub mod action;
+pub mod allowlist;
+pub mod approval;
+pub mod pdas;
+pub mod rpc;
+pub mod serde_str;
+pub mod signing;
+pub mod status;
+
+pub use action::{AdminAction, MessageFormat};
+pub use pdas::Pdas;
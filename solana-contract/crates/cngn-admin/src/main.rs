@@ -0,0 +1,441 @@
+// crates/cngn-admin/src/main.rs
+//! `cngn-admin`: build, approve and submit multisig-gated cNGN admin instructions.
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::AccountDeserialize;
+use anyhow::{anyhow, bail, Context, Result};
+use clap::{Parser, Subcommand, ValueEnum};
+use cngn::state::{BlacklistEntry, Multisig};
+use cngn_admin::action::{AdminAction, MessageFormat};
+use cngn_admin::allowlist::AllowlistTree;
+use cngn_admin::approval::{read_json, write_json, ApprovalRequest, SignatureFile};
+use cngn_admin::pdas::Pdas;
+use cngn_admin::rpc::{self, RpcClient};
+use cngn_admin::signing::{
+    collect_approvals, ed25519_instruction, expand_tilde, load_keypair, Approval,
+};
+use cngn_admin::status::StatusReport;
+use solana_hash::Hash;
+use solana_signer::Signer;
+use solana_transaction::Transaction;
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[command(name = "cngn-admin", version, about)]
+struct Cli {
+    /// JSON-RPC endpoint of the cluster
+    #[arg(
+        long,
+        short = 'u',
+        env = "RPC_URL",
+        default_value = "http://127.0.0.1:8899"
+    )]
+    url: String,
+
+    /// cNGN program id
+    #[arg(long, default_value_t = cngn::ID)]
+    program_id: Pubkey,
+
+    /// cNGN token mint
+    #[arg(long, env = "CNGN_MINT")]
+    mint: Option<Pubkey>,
+
+    /// Fee payer keypair file
+    #[arg(
+        long,
+        env = "ANCHOR_WALLET",
+        default_value = "~/.config/solana/id.json"
+    )]
+    payer: String,
+
+    /// Owner keypair file to approve with (repeatable)
+    #[arg(long = "signer", value_name = "KEYPAIR")]
+    signers: Vec<String>,
+
+    /// Pre-signed owner approval as PUBKEY=SIGNATURE (repeatable)
+    #[arg(long = "signature", value_name = "PUBKEY=SIGNATURE")]
+    signatures: Vec<String>,
+
+    /// Multisig nonce to sign for; fetched from the multisig account when omitted
+    #[arg(long)]
+    nonce: Option<u64>,
+
+    /// Recent blockhash; fetched from the cluster when omitted
+    #[arg(long)]
+    blockhash: Option<Hash>,
+
+    /// Message the owners sign: the SHA-256 digest, or a human-readable
+    /// off-chain message that hardware wallets can display
+    #[arg(long, value_enum, default_value_t = MessageFormat::Digest)]
+    message_format: MessageFormat,
+
+    /// What to do with the assembled transaction
+    #[arg(long, value_enum, default_value_t = Output::Send)]
+    output: Output,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    #[command(flatten)]
+    Action(AdminAction),
+
+    /// Offline approval workflow for owners on air-gapped machines
+    #[command(subcommand)]
+    Approval(ApprovalCommand),
+
+    /// Report list membership, minter allowance and token accounts of a wallet
+    Status {
+        /// Wallet address to report on
+        owner: Pubkey,
+
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Print the external allowlist root of a file of addresses, one per
+    /// line, or an owner's proof against it
+    Allowlist {
+        /// Address file
+        file: PathBuf,
+
+        /// Print this owner's proof, one hex hash per line, instead of the root
+        #[arg(long)]
+        proof: Option<Pubkey>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ApprovalCommand {
+    /// Write an approval request file for an admin action
+    Export {
+        /// Where to write the request
+        #[arg(long, short)]
+        out: PathBuf,
+
+        #[command(subcommand)]
+        action: AdminAction,
+    },
+    /// Verify a request file and sign its digest; needs no network access
+    Sign {
+        /// Approval request file
+        request: PathBuf,
+
+        /// Owner keypair file
+        #[arg(long)]
+        keypair: String,
+
+        /// Where to write the signature file
+        #[arg(long, short)]
+        out: PathBuf,
+    },
+    /// Verify signature files against a request and build its transaction
+    Assemble {
+        /// Approval request file
+        request: PathBuf,
+
+        /// Signature files produced by `approval sign`
+        #[arg(required = true)]
+        signatures: Vec<PathBuf>,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Output {
+    /// Print the message the owners must sign: the hex digest, or the text of
+    /// a readable message
+    Digest,
+    /// Print PUBKEY=SIGNATURE approvals from the --signer keypairs
+    Signatures,
+    /// Print the signed transaction as base64
+    Print,
+    /// Simulate the transaction and print the program logs
+    Simulate,
+    /// Submit the transaction and wait for confirmation
+    Send,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let rpc = RpcClient::new(&cli.url);
+
+    match &cli.command {
+        Command::Action(action) => run_action(&cli, &rpc, action),
+        Command::Approval(command) => run_approval(&cli, &rpc, command),
+        Command::Status { owner, json } => {
+            let pdas = Pdas::derive(&cli.program_id, &required_mint(&cli)?);
+            let report = StatusReport::fetch(&rpc, &pdas, *owner)?;
+            if *json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                print!("{report}");
+            }
+            Ok(())
+        }
+        Command::Allowlist { file, proof } => {
+            let tree = AllowlistTree::from_file(file)?;
+            match proof {
+                Some(owner) => {
+                    for sibling in tree.proof(owner)? {
+                        println!("{}", hex::encode(sibling));
+                    }
+                }
+                None => println!("{}", hex::encode(tree.root())),
+            }
+            Ok(())
+        }
+    }
+}
+
+fn run_action(cli: &Cli, rpc: &RpcClient, action: &AdminAction) -> Result<()> {
+    let pdas = Pdas::derive(&cli.program_id, &required_mint(cli)?);
+
+    let multisig = match cli.nonce {
+        Some(_) => None,
+        None => Some(fetch_multisig(rpc, &pdas.multisig)?),
+    };
+    let nonce = cli
+        .nonce
+        .or(multisig.as_ref().map(|multisig| multisig.nonce))
+        .expect("nonce is either given or fetched");
+
+    let message = action.encode(cli.message_format, &pdas, nonce);
+    eprintln!("Multisig nonce: {nonce}");
+    eprintln!("Message:        {}", cli.message_format.display(&message));
+
+    if cli.output == Output::Digest {
+        println!("{}", cli.message_format.display(&message));
+        return Ok(());
+    }
+
+    let keypairs = cli
+        .signers
+        .iter()
+        .map(|path| load_keypair(&expand_tilde(path)))
+        .collect::<Result<Vec<_>>>()?;
+    let approvals = collect_approvals(&keypairs, &cli.signatures, &message)?;
+
+    if cli.output == Output::Signatures {
+        for approval in &approvals {
+            println!("{approval}");
+        }
+        return Ok(());
+    }
+
+    if let Some(multisig) = &multisig {
+        check_approvals(multisig, &approvals)?;
+    }
+
+    let entry_payers = fetch_entry_payers(rpc, &pdas, action)?;
+    submit(cli, rpc, &approvals, &message, |payer| {
+        action.instruction(&cli.program_id, &pdas, payer, &entry_payers)
+    })
+}
+
+fn run_approval(cli: &Cli, rpc: &RpcClient, command: &ApprovalCommand) -> Result<()> {
+    match command {
+        ApprovalCommand::Export { out, action } => {
+            let mint = required_mint(cli)?;
+            let nonce = match cli.nonce {
+                Some(nonce) => nonce,
+                None => {
+                    let pdas = Pdas::derive(&cli.program_id, &mint);
+                    fetch_multisig(rpc, &pdas.multisig)?.nonce
+                }
+            };
+
+            let request = ApprovalRequest::new(
+                cli.program_id,
+                mint,
+                nonce,
+                action.clone(),
+                cli.message_format,
+            );
+            let message = request.verified_message()?;
+            write_json(out, &request)?;
+            eprintln!("Multisig nonce: {nonce}");
+            eprintln!("Message:        {}", request.format.display(&message));
+            eprintln!("Approval request written to {}", out.display());
+        }
+        ApprovalCommand::Sign {
+            request,
+            keypair,
+            out,
+        } => {
+            let request: ApprovalRequest = read_json(request)?;
+            let keypair = load_keypair(&expand_tilde(keypair))?;
+            let signature = request.sign(&keypair)?;
+            let message = request.verified_message()?;
+
+            eprintln!("Program:        {}", request.program_id);
+            eprintln!("Mint:           {}", request.mint);
+            eprintln!("Multisig nonce: {}", request.nonce);
+            eprintln!(
+                "Action:         {}",
+                serde_json::to_string(&request.action)?
+            );
+            eprintln!("Message:        {}", request.format.display(&message));
+            write_json(out, &signature)?;
+            eprintln!(
+                "Signature by {} written to {}",
+                signature.owner,
+                out.display()
+            );
+        }
+        ApprovalCommand::Assemble {
+            request,
+            signatures,
+        } => {
+            let request: ApprovalRequest = read_json(request)?;
+            let files = signatures
+                .iter()
+                .map(|path| read_json::<SignatureFile>(path))
+                .collect::<Result<Vec<_>>>()?;
+            let approvals = request.collect(&files)?;
+            let message = request.verified_message()?;
+            eprintln!("Multisig nonce: {}", request.nonce);
+            eprintln!("Message:        {}", request.format.display(&message));
+
+            match cli.output {
+                Output::Digest => {
+                    println!("{}", request.format.display(&message));
+                    return Ok(());
+                }
+                Output::Signatures => {
+                    for approval in &approvals {
+                        println!("{approval}");
+                    }
+                    return Ok(());
+                }
+                Output::Print if cli.blockhash.is_some() => {}
+                _ => {
+                    let multisig = fetch_multisig(rpc, &request.multisig)?;
+                    if multisig.nonce != request.nonce {
+                        bail!(
+                            "request was made for nonce {} but the multisig is at nonce {}",
+                            request.nonce,
+                            multisig.nonce
+                        );
+                    }
+                    check_approvals(&multisig, &approvals)?;
+                }
+            }
+
+            let pdas = request.pdas();
+            let entry_payers = fetch_entry_payers(rpc, &pdas, &request.action)?;
+            submit(cli, rpc, &approvals, &message, |payer| {
+                request
+                    .action
+                    .instruction(&request.program_id, &pdas, payer, &entry_payers)
+            })?;
+        }
+    }
+
+    Ok(())
+}
+
+fn required_mint(cli: &Cli) -> Result<Pubkey> {
+    cli.mint
+        .ok_or_else(|| anyhow!("--mint (or CNGN_MINT) is required for this command"))
+}
+
+fn check_approvals(multisig: &Multisig, approvals: &[Approval]) -> Result<()> {
+    for approval in approvals {
+        if !multisig.is_owner(&approval.owner) {
+            bail!("{} is not a multisig owner", approval.owner);
+        }
+    }
+    if approvals.len() < multisig.threshold as usize {
+        bail!(
+            "{} approval(s) collected but the multisig threshold is {}",
+            approvals.len(),
+            multisig.threshold
+        );
+    }
+    Ok(())
+}
+
+/// Prepend an Ed25519 instruction verifying every approval to the instruction
+/// built for the fee payer, sign with the fee payer and print, simulate or
+/// send the result.
+fn submit(
+    cli: &Cli,
+    rpc: &RpcClient,
+    approvals: &[Approval],
+    message: &[u8],
+    instruction: impl FnOnce(&Pubkey) -> Instruction,
+) -> Result<()> {
+    if approvals.is_empty() {
+        bail!("no approvals given; pass --signer or --signature");
+    }
+    let payer = load_keypair(&expand_tilde(&cli.payer))?;
+    let instructions = [
+        ed25519_instruction(approvals, message),
+        instruction(&payer.pubkey()),
+    ];
+
+    let blockhash = match cli.blockhash {
+        Some(blockhash) => blockhash,
+        None => rpc.get_latest_blockhash()?,
+    };
+    let transaction = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&payer.pubkey()),
+        &[&payer],
+        blockhash,
+    );
+
+    match cli.output {
+        Output::Print => println!("{}", rpc::encode_transaction(&transaction)?),
+        Output::Simulate => {
+            let result = rpc.simulate_transaction(&transaction)?;
+            for line in result.logs.unwrap_or_default() {
+                println!("{line}");
+            }
+            if let Some(err) = result.err {
+                bail!("simulation failed: {err}");
+            }
+        }
+        Output::Send => {
+            let signature = rpc.send_and_confirm_transaction(&transaction)?;
+            println!("{signature}");
+        }
+        Output::Digest | Output::Signatures => unreachable!("handled by the caller"),
+    }
+
+    Ok(())
+}
+
+/// The payer recorded in each blacklist entry `action` closes, who gets its
+/// rent back; none for addresses blacklisted before entries existed.
+fn fetch_entry_payers(
+    rpc: &RpcClient,
+    pdas: &Pdas,
+    action: &AdminAction,
+) -> Result<Vec<Option<Pubkey>>> {
+    action
+        .closed_blacklist_entries()
+        .iter()
+        .map(|user| {
+            let address = pdas.blacklist_entry(user);
+            let Some(data) = rpc.get_account_data(&address)? else {
+                return Ok(None);
+            };
+            let entry = BlacklistEntry::try_deserialize(&mut data.as_slice())
+                .with_context(|| format!("{address} is not a cNGN blacklist entry account"))?;
+            Ok(Some(entry.payer))
+        })
+        .collect()
+}
+
+fn fetch_multisig(rpc: &RpcClient, address: &Pubkey) -> Result<Multisig> {
+    let data = rpc
+        .get_account_data(address)?
+        .ok_or_else(|| anyhow!("multisig account {address} not found on {}", rpc.url()))?;
+
+    Multisig::try_deserialize(&mut data.as_slice())
+        .with_context(|| format!("{address} is not a cNGN multisig account"))
+}
@@ -0,0 +1,78 @@
+// crates/cngn-admin/src/pdas.rs
+use anchor_lang::prelude::Pubkey;
+use cngn::state::*;
+
+/// Every program-derived account the cNGN program keeps for a single mint.
+#[derive(Debug, Clone)]
+pub struct Pdas {
+    pub program_id: Pubkey,
+    pub mint: Pubkey,
+    pub token_config: Pubkey,
+    pub can_mint: Pubkey,
+    pub can_forward: Pubkey,
+    pub blacklist: Pubkey,
+    pub internal_whitelist: Pubkey,
+    pub external_whitelist: Pubkey,
+    pub trusted_contracts: Pubkey,
+    pub mint_authority: Pubkey,
+    pub multisig: Pubkey,
+    pub bridge_config: Pubkey,
+    pub kyc_operators: Pubkey,
+    pub transfer_tiers: Pubkey,
+    pub fee_config: Pubkey,
+    pub yield_config: Pubkey,
+    pub external_allowlist: Pubkey,
+}
+
+impl Pdas {
+    pub fn derive(program_id: &Pubkey, mint: &Pubkey) -> Self {
+        let pda = |seed: &[u8]| Pubkey::find_program_address(&[seed, mint.as_ref()], program_id).0;
+
+        Self {
+            program_id: *program_id,
+            mint: *mint,
+            token_config: pda(TOKEN_CONFIG_SEED),
+            can_mint: pda(CAN_MINT_SEED),
+            can_forward: pda(CAN_FORWARD_SEED),
+            blacklist: pda(BLACK_LIST_SEED),
+            internal_whitelist: pda(INTERNAL_WHITELIST_SEED),
+            external_whitelist: pda(EXTERNAL_WHITELIST_SEED),
+            trusted_contracts: pda(TRUSTED_CONTRACTS_SEED),
+            mint_authority: pda(MINT_AUTHORITY_SEED),
+            multisig: pda(Multisig::MULTISIG_SEED),
+            bridge_config: pda(BRIDGE_CONFIG_SEED),
+            kyc_operators: pda(KYC_OPERATORS_SEED),
+            transfer_tiers: pda(TRANSFER_TIERS_SEED),
+            fee_config: pda(FEE_CONFIG_SEED),
+            yield_config: pda(YIELD_CONFIG_SEED),
+            external_allowlist: pda(EXTERNAL_ALLOWLIST_SEED),
+        }
+    }
+
+    /// The tier and usage account of a token owner.
+    pub fn transfer_limits(&self, owner: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[TRANSFER_LIMITS_SEED, self.mint.as_ref(), owner.as_ref()],
+            &self.program_id,
+        )
+        .0
+    }
+
+    /// The KYC record of a token owner.
+    pub fn kyc_record(&self, owner: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[KYC_RECORD_SEED, self.mint.as_ref(), owner.as_ref()],
+            &self.program_id,
+        )
+        .0
+    }
+
+    /// Where a blacklisted user's reason and case reference are kept.
+    pub fn blacklist_entry(&self, user: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[BLACKLIST_ENTRY_SEED, self.mint.as_ref(), user.as_ref()],
+            &self.program_id,
+        )
+        .0
+    }
+}
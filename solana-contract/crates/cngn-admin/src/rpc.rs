@@ -0,0 +1,220 @@
+// crates/cngn-admin/src/rpc.rs
+use anchor_lang::prelude::Pubkey;
+use anyhow::{anyhow, bail, Context, Result};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use solana_hash::Hash;
+use solana_signature::Signature;
+use solana_transaction::Transaction;
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+
+const CONFIRM_TIMEOUT: Duration = Duration::from_secs(60);
+const CONFIRM_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Minimal JSON-RPC client covering the calls the admin tooling needs.
+pub struct RpcClient {
+    url: String,
+    agent: ureq::Agent,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SimulationResult {
+    pub err: Option<Value>,
+    #[serde(default)]
+    pub logs: Option<Vec<String>>,
+}
+
+#[derive(Deserialize)]
+struct RpcResponse<T> {
+    result: Option<T>,
+    error: Option<Value>,
+}
+
+#[derive(Deserialize)]
+struct WithContext<T> {
+    value: T,
+}
+
+#[derive(Deserialize)]
+struct UiAccount {
+    data: (String, String),
+}
+
+#[derive(Deserialize)]
+struct UiKeyedAccount {
+    pubkey: String,
+    account: UiAccount,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UiBlockhash {
+    blockhash: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UiSignatureStatus {
+    err: Option<Value>,
+    confirmation_status: Option<String>,
+}
+
+impl RpcClient {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .build(),
+        }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn request<T: DeserializeOwned>(&self, method: &str, params: Value) -> Result<T> {
+        let body = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        });
+
+        let response: RpcResponse<T> = self
+            .agent
+            .post(&self.url)
+            .send_json(body)
+            .with_context(|| format!("{method} request to {} failed", self.url))?
+            .into_json()
+            .with_context(|| format!("invalid {method} response"))?;
+
+        if let Some(error) = response.error {
+            bail!("{method} returned an error: {error}");
+        }
+        response
+            .result
+            .ok_or_else(|| anyhow!("{method} returned no result"))
+    }
+
+    /// Raw account data, or `None` when the account does not exist.
+    pub fn get_account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>> {
+        let response: WithContext<Option<UiAccount>> = self.request(
+            "getAccountInfo",
+            json!([address.to_string(), { "encoding": "base64", "commitment": "confirmed" }]),
+        )?;
+
+        response
+            .value
+            .map(|account| {
+                BASE64
+                    .decode(account.data.0)
+                    .context("account data is not valid base64")
+            })
+            .transpose()
+    }
+
+    /// Address and raw data of every token account of `mint` held by `owner`.
+    pub fn get_token_accounts_by_owner(
+        &self,
+        owner: &Pubkey,
+        mint: &Pubkey,
+    ) -> Result<Vec<(Pubkey, Vec<u8>)>> {
+        let response: WithContext<Vec<UiKeyedAccount>> = self.request(
+            "getTokenAccountsByOwner",
+            json!([
+                owner.to_string(),
+                { "mint": mint.to_string() },
+                { "encoding": "base64", "commitment": "confirmed" }
+            ]),
+        )?;
+
+        response
+            .value
+            .into_iter()
+            .map(|keyed| {
+                let address = keyed
+                    .pubkey
+                    .parse()
+                    .map_err(|_| anyhow!("invalid token account address {}", keyed.pubkey))?;
+                let data = BASE64
+                    .decode(keyed.account.data.0)
+                    .context("account data is not valid base64")?;
+                Ok((address, data))
+            })
+            .collect()
+    }
+
+    pub fn get_latest_blockhash(&self) -> Result<Hash> {
+        let response: WithContext<UiBlockhash> =
+            self.request("getLatestBlockhash", json!([{ "commitment": "confirmed" }]))?;
+
+        response
+            .value
+            .blockhash
+            .parse()
+            .map_err(|_| anyhow!("invalid blockhash returned by {}", self.url))
+    }
+
+    pub fn simulate_transaction(&self, transaction: &Transaction) -> Result<SimulationResult> {
+        let response: WithContext<SimulationResult> = self.request(
+            "simulateTransaction",
+            json!([
+                encode_transaction(transaction)?,
+                { "encoding": "base64", "commitment": "confirmed", "sigVerify": true }
+            ]),
+        )?;
+
+        Ok(response.value)
+    }
+
+    pub fn send_transaction(&self, transaction: &Transaction) -> Result<Signature> {
+        let signature: String = self.request(
+            "sendTransaction",
+            json!([
+                encode_transaction(transaction)?,
+                { "encoding": "base64", "preflightCommitment": "confirmed" }
+            ]),
+        )?;
+
+        signature
+            .parse()
+            .map_err(|_| anyhow!("invalid signature returned by {}", self.url))
+    }
+
+    /// Submit a transaction and block until it is confirmed or fails.
+    pub fn send_and_confirm_transaction(&self, transaction: &Transaction) -> Result<Signature> {
+        let signature = self.send_transaction(transaction)?;
+        let started = Instant::now();
+
+        while started.elapsed() < CONFIRM_TIMEOUT {
+            let statuses: WithContext<Vec<Option<UiSignatureStatus>>> =
+                self.request("getSignatureStatuses", json!([[signature.to_string()]]))?;
+
+            if let Some(Some(status)) = statuses.value.into_iter().next() {
+                if let Some(err) = status.err {
+                    bail!("transaction {signature} failed: {err}");
+                }
+                if matches!(
+                    status.confirmation_status.as_deref(),
+                    Some("confirmed") | Some("finalized")
+                ) {
+                    return Ok(signature);
+                }
+            }
+
+            sleep(CONFIRM_POLL_INTERVAL);
+        }
+
+        bail!("transaction {signature} was not confirmed within {CONFIRM_TIMEOUT:?}")
+    }
+}
+
+/// Base64 wire encoding of a transaction, as accepted by the RPC `send`/`simulate` calls.
+pub fn encode_transaction(transaction: &Transaction) -> Result<String> {
+    let bytes = bincode::serialize(transaction).context("failed to serialize transaction")?;
+    Ok(BASE64.encode(bytes))
+}
@@ -0,0 +1,144 @@
+// crates/cngn-admin/src/signing.rs
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anyhow::{anyhow, bail, Context, Result};
+use solana_keypair::{read_keypair_file, Keypair};
+use solana_program::ed25519_program;
+use solana_signature::Signature;
+use solana_signer::Signer;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+// Layout of an Ed25519 program instruction, matching what `parse_ed25519_ix`
+// in the program accepts: a 2 byte header, one 14 byte offsets struct per
+// signature, then each signature and public key, then the shared message.
+const HEADER_SIZE: usize = 2;
+const OFFSETS_SIZE: usize = 14;
+const SIGNATURE_SIZE: usize = 64;
+const PUBLIC_KEY_SIZE: usize = 32;
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// One owner's signature over a multisig message.
+#[derive(Debug, Clone)]
+pub struct Approval {
+    pub owner: Pubkey,
+    pub signature: Signature,
+}
+
+impl Approval {
+    pub fn sign(keypair: &Keypair, message: &[u8]) -> Self {
+        Self {
+            owner: keypair.pubkey(),
+            signature: keypair.sign_message(message),
+        }
+    }
+
+    pub fn verify(&self, message: &[u8]) -> bool {
+        self.signature.verify(self.owner.as_ref(), message)
+    }
+
+    /// Parse a pre-signed approval given as `PUBKEY=SIGNATURE` (both base58).
+    pub fn parse(input: &str) -> Result<Self> {
+        let (owner, signature) = input
+            .split_once('=')
+            .ok_or_else(|| anyhow!("expected PUBKEY=SIGNATURE, got `{input}`"))?;
+
+        Ok(Self {
+            owner: owner
+                .trim()
+                .parse()
+                .map_err(|_| anyhow!("invalid owner pubkey `{owner}`"))?,
+            signature: signature
+                .trim()
+                .parse()
+                .map_err(|_| anyhow!("invalid signature `{signature}`"))?,
+        })
+    }
+}
+
+impl std::fmt::Display for Approval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}={}", self.owner, self.signature)
+    }
+}
+
+/// Build one Ed25519 signature-verification instruction covering every
+/// approval, with a single copy of the message they all signed.
+pub fn ed25519_instruction(approvals: &[Approval], message: &[u8]) -> Instruction {
+    let count = u8::try_from(approvals.len()).expect("at most 255 approvals");
+    let offset = |position: usize| u16::try_from(position).expect("instruction fits in u16");
+
+    let keys_start = HEADER_SIZE + approvals.len() * OFFSETS_SIZE;
+    let message_offset = keys_start + approvals.len() * (SIGNATURE_SIZE + PUBLIC_KEY_SIZE);
+
+    let mut data = Vec::with_capacity(message_offset + message.len());
+    data.extend_from_slice(&[count, 0]); // signature count, padding
+    for i in 0..approvals.len() {
+        let signature_offset = keys_start + i * (SIGNATURE_SIZE + PUBLIC_KEY_SIZE);
+        for field in [
+            offset(signature_offset),
+            CURRENT_INSTRUCTION,
+            offset(signature_offset + SIGNATURE_SIZE),
+            CURRENT_INSTRUCTION,
+            offset(message_offset),
+            offset(message.len()),
+            CURRENT_INSTRUCTION,
+        ] {
+            data.extend_from_slice(&field.to_le_bytes());
+        }
+    }
+    for approval in approvals {
+        data.extend_from_slice(approval.signature.as_ref());
+        data.extend_from_slice(approval.owner.as_ref());
+    }
+    data.extend_from_slice(message);
+
+    Instruction {
+        program_id: ed25519_program::ID,
+        accounts: vec![],
+        data,
+    }
+}
+
+pub fn load_keypair(path: &Path) -> Result<Keypair> {
+    read_keypair_file(path)
+        .map_err(|err| anyhow!("failed to read keypair {}: {err}", path.display()))
+}
+
+/// Expand a leading `~/` so default keypair paths work without a shell.
+pub fn expand_tilde(path: &str) -> std::path::PathBuf {
+    match (path.strip_prefix("~/"), std::env::var_os("HOME")) {
+        (Some(rest), Some(home)) => Path::new(&home).join(rest),
+        _ => path.into(),
+    }
+}
+
+/// Sign with every keypair, merge in the pre-signed approvals and check each
+/// one against `message`. Duplicates collapse to a single approval per owner.
+pub fn collect_approvals(
+    keypairs: &[Keypair],
+    presigned: &[String],
+    message: &[u8],
+) -> Result<Vec<Approval>> {
+    let mut approvals = BTreeMap::new();
+
+    for keypair in keypairs {
+        let approval = Approval::sign(keypair, message);
+        approvals.insert(approval.owner, approval);
+    }
+
+    for input in presigned {
+        let approval =
+            Approval::parse(input).with_context(|| format!("bad --signature {input}"))?;
+        if !approval.verify(message) {
+            bail!(
+                "signature from {} does not match the message digest {}",
+                approval.owner,
+                hex::encode(message)
+            );
+        }
+        approvals.insert(approval.owner, approval);
+    }
+
+    Ok(approvals.into_values().collect())
+}
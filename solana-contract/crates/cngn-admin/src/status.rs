@@ -0,0 +1,196 @@
+// crates/cngn-admin/src/status.rs
+//! Compliance view of a single wallet: list membership, minter allowance and
+//! cNGN token accounts, read straight from the program accounts.
+use crate::pdas::Pdas;
+use crate::rpc::RpcClient;
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::AccountDeserialize;
+use anchor_spl::token_interface::TokenAccount;
+use anyhow::{anyhow, Context, Result};
+use cngn::state::*;
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Debug, Serialize)]
+pub struct StatusReport {
+    #[serde(with = "crate::serde_str")]
+    pub owner: Pubkey,
+    #[serde(with = "crate::serde_str")]
+    pub mint: Pubkey,
+    pub decimals: u8,
+    pub blacklisted: bool,
+    /// Unix time the blacklisting lapses; `None` when not blacklisted or
+    /// the listing does not expire. A lapsed listing no longer blocks
+    /// transfers but stays listed until it is pruned.
+    pub blacklist_expires_at: Option<i64>,
+    pub internal_whitelisted: bool,
+    pub external_whitelisted: bool,
+    pub forwarder: bool,
+    pub trusted_contract: bool,
+    pub minter: bool,
+    /// Remaining allowance in base units; `None` when not a minter.
+    pub mint_allowance: Option<u64>,
+    /// Sum of all token account balances in base units.
+    pub balance: u64,
+    pub token_accounts: Vec<TokenAccountStatus>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TokenAccountStatus {
+    #[serde(with = "crate::serde_str")]
+    pub address: Pubkey,
+    pub amount: u64,
+    pub frozen: bool,
+    #[serde(serialize_with = "serialize_optional_key")]
+    pub delegate: Option<Pubkey>,
+}
+
+impl StatusReport {
+    pub fn fetch(rpc: &RpcClient, pdas: &Pdas, owner: Pubkey) -> Result<Self> {
+        let token_config: TokenConfig = fetch_account(rpc, &pdas.token_config, "token config")?;
+        let blacklist: BlackList = fetch_account(rpc, &pdas.blacklist, "blacklist")?;
+        let internal_whitelist: InternalWhiteList =
+            fetch_account(rpc, &pdas.internal_whitelist, "internal whitelist")?;
+        let external_whitelist: ExternalWhiteList =
+            fetch_account(rpc, &pdas.external_whitelist, "external whitelist")?;
+        let can_forward: CanForward = fetch_account(rpc, &pdas.can_forward, "forwarder list")?;
+        let trusted_contracts: TrustedContracts =
+            fetch_account(rpc, &pdas.trusted_contracts, "trusted contracts")?;
+        let can_mint: CanMint = fetch_account(rpc, &pdas.can_mint, "minter list")?;
+
+        let token_accounts = rpc
+            .get_token_accounts_by_owner(&owner, &pdas.mint)?
+            .into_iter()
+            .map(|(address, data)| {
+                let account = TokenAccount::try_deserialize(&mut data.as_slice())
+                    .with_context(|| format!("{address} is not a token account"))?;
+                Ok(TokenAccountStatus {
+                    address,
+                    amount: account.amount,
+                    frozen: account.is_frozen(),
+                    delegate: account.delegate.into(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // The expiry lives in the owner's entry, which addresses listed
+        // before entries existed do not have
+        let blacklisted = blacklist.is_blacklisted(&owner);
+        let entry = match blacklisted {
+            true => rpc
+                .get_account_data(&pdas.blacklist_entry(&owner))?
+                .map(|data| BlacklistEntry::try_deserialize(&mut data.as_slice()))
+                .transpose()
+                .context("blacklist entry is not a cNGN blacklist entry account")?,
+            false => None,
+        };
+
+        Ok(Self {
+            owner,
+            mint: pdas.mint,
+            decimals: token_config.decimals,
+            blacklisted,
+            blacklist_expires_at: entry
+                .map(|entry| entry.details.expires_at)
+                .filter(|&expires_at| expires_at != 0),
+            internal_whitelisted: internal_whitelist.is_whitelisted(&owner),
+            external_whitelisted: external_whitelist.is_whitelisted(&owner),
+            forwarder: can_forward.is_trusted_forwarder(&owner),
+            trusted_contract: trusted_contracts.is_trusted_contract(&owner),
+            minter: can_mint.can_mint(&owner),
+            mint_allowance: can_mint.get_mint_amount(&owner).ok(),
+            balance: token_accounts.iter().map(|account| account.amount).sum(),
+            token_accounts,
+        })
+    }
+
+    fn ui_amount(&self, amount: u64) -> String {
+        let scale = 10u128.pow(self.decimals.into());
+        let whole = amount as u128 / scale;
+        let fraction = amount as u128 % scale;
+
+        if self.decimals == 0 {
+            whole.to_string()
+        } else {
+            format!("{whole}.{fraction:0width$}", width = self.decimals as usize)
+        }
+    }
+}
+
+impl fmt::Display for StatusReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let yes_no = |flag: bool| if flag { "yes" } else { "no" };
+
+        writeln!(f, "Wallet:             {}", self.owner)?;
+        writeln!(f, "Mint:               {}", self.mint)?;
+        match self.blacklist_expires_at {
+            Some(expires_at) => writeln!(f, "Blacklisted:        yes, until {expires_at}")?,
+            None => writeln!(f, "Blacklisted:        {}", yes_no(self.blacklisted))?,
+        }
+        writeln!(
+            f,
+            "Internal whitelist: {}",
+            yes_no(self.internal_whitelisted)
+        )?;
+        writeln!(
+            f,
+            "External whitelist: {}",
+            yes_no(self.external_whitelisted)
+        )?;
+        writeln!(f, "Forwarder:          {}", yes_no(self.forwarder))?;
+        writeln!(f, "Trusted contract:   {}", yes_no(self.trusted_contract))?;
+        match self.mint_allowance {
+            Some(allowance) => writeln!(
+                f,
+                "Minter:             yes, allowance {}",
+                self.ui_amount(allowance)
+            )?,
+            None => writeln!(f, "Minter:             no")?,
+        }
+        writeln!(f, "Balance:            {}", self.ui_amount(self.balance))?;
+
+        if self.token_accounts.is_empty() {
+            writeln!(f, "Token accounts:     none")?;
+        } else {
+            writeln!(f, "Token accounts:")?;
+            for account in &self.token_accounts {
+                write!(
+                    f,
+                    "  {}  {}{}",
+                    account.address,
+                    self.ui_amount(account.amount),
+                    if account.frozen { "  FROZEN" } else { "" }
+                )?;
+                if let Some(delegate) = account.delegate {
+                    write!(f, "  delegate {delegate}")?;
+                }
+                writeln!(f)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn fetch_account<T: AccountDeserialize>(
+    rpc: &RpcClient,
+    address: &Pubkey,
+    name: &str,
+) -> Result<T> {
+    let data = rpc
+        .get_account_data(address)?
+        .ok_or_else(|| anyhow!("{name} account {address} not found on {}", rpc.url()))?;
+
+    T::try_deserialize(&mut data.as_slice())
+        .with_context(|| format!("{address} is not a cNGN {name} account"))
+}
+
+fn serialize_optional_key<S: serde::Serializer>(
+    key: &Option<Pubkey>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match key {
+        Some(key) => serializer.collect_str(key),
+        None => serializer.serialize_none(),
+    }
+}
@@ -0,0 +1,203 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use cngn::state::{self as ms, TOKEN_CONFIG_SEED};
+use cngn_admin::action::{BatchOp, BlacklistReason};
+use cngn_admin::signing::expand_tilde;
+use cngn_admin::{AdminAction, MessageFormat, Pdas};
+use std::path::Path;
+
+const NONCE: u64 = 7;
+
+fn pdas() -> Pdas {
+    Pdas::derive(&cngn::ID, &Pubkey::new_unique())
+}
+
+fn instruction(pdas: &Pdas, action: &AdminAction, entry_payers: &[Option<Pubkey>]) -> Instruction {
+    action.instruction(&cngn::ID, pdas, &Pubkey::new_unique(), entry_payers)
+}
+
+fn batch(op: BatchOp, addresses: Vec<Pubkey>) -> AdminAction {
+    AdminAction::Batch {
+        op,
+        addresses,
+        reason: Some(BlacklistReason::Sanctions),
+        case_reference: Some("OFAC-2024-17".to_string()),
+        review_at: 0,
+        expires_at: 0,
+    }
+}
+
+#[test]
+fn derives_the_program_accounts_of_the_mint() {
+    let pdas = pdas();
+    let (token_config, _) =
+        Pubkey::find_program_address(&[TOKEN_CONFIG_SEED, pdas.mint.as_ref()], &cngn::ID);
+
+    assert_eq!(pdas.token_config, token_config);
+}
+
+#[test]
+fn builds_the_program_instruction_and_digest() {
+    let pdas = pdas();
+    let user = Pubkey::new_unique();
+    let action = AdminAction::AddCanMint { user };
+    let ix = instruction(&pdas, &action, &[]);
+
+    assert_eq!(ix.program_id, cngn::ID);
+    let (discriminator, args) = ix.data.split_at(8);
+    assert_eq!(discriminator, cngn::instruction::AddCanMint::DISCRIMINATOR);
+    assert_eq!(Pubkey::try_from_slice(args).unwrap(), user);
+
+    assert!(ix
+        .accounts
+        .contains(&AccountMeta::new(pdas.multisig, false)));
+    assert!(ix
+        .accounts
+        .contains(&AccountMeta::new_readonly(sysvar::instructions::ID, false)));
+
+    assert_eq!(
+        action.encode(MessageFormat::Digest, &pdas, NONCE),
+        ms::build_add_can_mint_message(&pdas.can_mint, &user, NONCE)
+    );
+}
+
+#[test]
+fn readable_message_names_the_action_and_nonce() {
+    let pdas = pdas();
+    let action = AdminAction::AddCanMint {
+        user: Pubkey::new_unique(),
+    };
+    let message = action.encode(MessageFormat::Readable, &pdas, NONCE);
+    let text = String::from_utf8_lossy(&message);
+
+    assert!(text.contains("cNGN ADD_CAN_MINT"));
+    assert!(text.contains(&format!("nonce:{NONCE}")));
+}
+
+#[test]
+fn add_blacklist_takes_the_user_entry() {
+    let pdas = pdas();
+    let user = Pubkey::new_unique();
+    let action = AdminAction::AddBlacklist {
+        user,
+        reason: BlacklistReason::CourtOrder,
+        case_reference: "FHC/L/CS/77/2024".to_string(),
+        review_at: 0,
+        expires_at: 0,
+    };
+    let ix = instruction(&pdas, &action, &[]);
+
+    assert_eq!(
+        ix.accounts.last(),
+        Some(&AccountMeta::new(pdas.blacklist_entry(&user), false))
+    );
+}
+
+#[test]
+fn remove_blacklist_refunds_the_recorded_payer() {
+    let pdas = pdas();
+    let user = Pubkey::new_unique();
+    let recorded = Pubkey::new_unique();
+    let payer = Pubkey::new_unique();
+    let action = AdminAction::RemoveBlacklist { user };
+    assert_eq!(action.closed_blacklist_entries(), &[user]);
+
+    let ix = action.instruction(&cngn::ID, &pdas, &payer, &[Some(recorded)]);
+    assert!(ix.accounts.contains(&AccountMeta::new(recorded, false)));
+    assert!(!ix.accounts.iter().any(|meta| meta.pubkey == payer));
+    assert_eq!(
+        ix.accounts.last(),
+        Some(&AccountMeta::new(pdas.blacklist_entry(&user), false))
+    );
+
+    // Without an entry the submitting payer stands in
+    let ix = action.instruction(&cngn::ID, &pdas, &payer, &[None]);
+    assert!(ix.accounts.contains(&AccountMeta::new(payer, false)));
+}
+
+#[test]
+fn batch_remove_blacklist_pairs_each_entry_with_its_payer() {
+    let pdas = pdas();
+    let users = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+    let recorded = Pubkey::new_unique();
+    let payer = Pubkey::new_unique();
+    let action = batch(BatchOp::RemoveBlacklist, users.clone());
+    assert_eq!(action.closed_blacklist_entries(), users.as_slice());
+
+    let ix = action.instruction(&cngn::ID, &pdas, &payer, &[Some(recorded), None]);
+    let remaining = &ix.accounts[ix.accounts.len() - 4..];
+    assert_eq!(
+        remaining,
+        &[
+            AccountMeta::new(pdas.blacklist_entry(&users[0]), false),
+            AccountMeta::new(recorded, false),
+            AccountMeta::new(pdas.blacklist_entry(&users[1]), false),
+            AccountMeta::new(payer, false),
+        ]
+    );
+}
+
+#[test]
+fn batch_add_blacklist_takes_an_entry_per_user() {
+    let pdas = pdas();
+    let users = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+    let action = batch(BatchOp::AddBlacklist, users.clone());
+    assert!(action.closed_blacklist_entries().is_empty());
+
+    let ix = instruction(&pdas, &action, &[]);
+    let (discriminator, _) = ix.data.split_at(8);
+    assert_eq!(
+        discriminator,
+        cngn::instruction::BatchAddBlacklist::DISCRIMINATOR
+    );
+
+    let entries: Vec<AccountMeta> = users
+        .iter()
+        .map(|user| AccountMeta::new(pdas.blacklist_entry(user), false))
+        .collect();
+    assert!(ix.accounts.ends_with(&entries));
+}
+
+#[test]
+fn other_batches_take_the_single_address_accounts() {
+    let pdas = pdas();
+    let user = Pubkey::new_unique();
+    let batch_ix = instruction(&pdas, &batch(BatchOp::WhitelistInternal, vec![user]), &[]);
+    let single_ix = instruction(&pdas, &AdminAction::WhitelistInternal { user }, &[]);
+
+    assert_eq!(batch_ix.accounts, single_ix.accounts);
+    assert_eq!(
+        &batch_ix.data[..8],
+        cngn::instruction::BatchWhitelistInternal::DISCRIMINATOR
+    );
+}
+
+#[test]
+fn actions_round_trip_through_request_json() {
+    let pdas = pdas();
+    let action = batch(BatchOp::AddBlacklist, vec![Pubkey::new_unique()]);
+    let json = serde_json::to_string(&action).unwrap();
+    let decoded: AdminAction = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(
+        decoded.encode(MessageFormat::Digest, &pdas, NONCE),
+        action.encode(MessageFormat::Digest, &pdas, NONCE)
+    );
+}
+
+#[test]
+fn expands_a_leading_tilde_to_home() {
+    let home = std::env::var_os("HOME").expect("HOME is set");
+
+    assert_eq!(
+        expand_tilde("~/.config/solana/owner.json"),
+        Path::new(&home).join(".config/solana/owner.json")
+    );
+    assert_eq!(
+        expand_tilde("/keys/owner.json"),
+        Path::new("/keys/owner.json")
+    );
+    assert_eq!(expand_tilde("owner~/x.json"), Path::new("owner~/x.json"));
+}
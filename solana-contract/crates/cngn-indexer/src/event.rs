@@ -0,0 +1,181 @@
+// crates/cngn-indexer/src/event.rs
+//! Typed cNGN events decoded from `Program data:` log payloads.
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use anyhow::{Context, Result};
+use cngn::events;
+
+macro_rules! cngn_events {
+    ($($name:ident),* $(,)?) => {
+        /// Every event the cNGN program emits, wrapping the program's own types.
+        pub enum CngnEvent {
+            $($name(events::$name),)*
+        }
+
+        impl CngnEvent {
+            /// Decode an Anchor event payload (discriminator followed by the
+            /// borsh encoded event). Returns `None` for unknown discriminators.
+            pub fn decode(data: &[u8]) -> Result<Option<Self>> {
+                if data.len() < 8 {
+                    return Ok(None);
+                }
+                let (discriminator, body) = data.split_at(8);
+
+                $(
+                    if discriminator == events::$name::DISCRIMINATOR {
+                        let event = events::$name::try_from_slice(body)
+                            .with_context(|| format!("malformed {} event", stringify!($name)))?;
+                        return Ok(Some(Self::$name(event)));
+                    }
+                )*
+
+                Ok(None)
+            }
+
+            /// Name of the event a payload's discriminator belongs to, also
+            /// when its body does not decode with the current layout.
+            pub fn name_of(data: &[u8]) -> Option<&'static str> {
+                let discriminator = data.get(..8)?;
+
+                $(
+                    if discriminator == events::$name::DISCRIMINATOR {
+                        return Some(stringify!($name));
+                    }
+                )*
+
+                None
+            }
+
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Self::$name(_) => stringify!($name),)*
+                }
+            }
+        }
+    };
+}
+
+cngn_events! {
+    TokenInitializedEvent,
+    SecondaryInitializedEvent,
+    ThirdInitializedEvent,
+    TokenConfigMigratedEvent,
+    TransferHookUpgradedEvent,
+    TokensMintedEvent,
+    MinterConsumed,
+    TokensBurnedEvent,
+    DestroyedBlackFundsEvent,
+    TokensTransferredEvent,
+    TokenMintingPauseEvent,
+    TokenTransferPauseEvent,
+    TokenUnpausedEvent,
+    RedemptionEvent,
+    WhitelistedMinter,
+    BlackListedMinter,
+    MintAmountAdded,
+    MintAmountRemoved,
+    WhitelistedContract,
+    BlackListedContract,
+    AddedBlackList,
+    RemovedBlackList,
+    WhitelistedInternalUser,
+    BlackListedInternalUser,
+    WhitelistedExternalSender,
+    BlackListedExternalSender,
+    MintAmountUpdatedEvent,
+    WhitelistedForwarder,
+    BlackListedForwarder,
+    ForwardedEvent,
+    AdminChangedEvent,
+    MultisigInitializedEvent,
+    MultisigUpdatedEvent,
+    BridgeUpdatedEvent,
+    BridgeInEvent,
+    BridgeOutEvent,
+    MetadataInitializedEvent,
+    MetadataUpdatedEvent,
+    KycOperatorAddedEvent,
+    KycOperatorRemovedEvent,
+    TransferTierConfiguredEvent,
+    TransferTierAssignedEvent,
+    KycApprovedEvent,
+    TransferFeeUpdatedEvent,
+    FeeConfigUpdatedEvent,
+    TransferFeesWithdrawnEvent,
+    TransferFeeRefundedEvent,
+    ConfidentialAuditorUpdatedEvent,
+    ConfidentialAccountApprovedEvent,
+    YieldConfigUpdatedEvent,
+    YieldPeriodAccruedEvent,
+    YieldOptedInEvent,
+    YieldOptedOutEvent,
+    YieldDistributedEvent,
+    EscrowCreatedEvent,
+    EscrowDisputedEvent,
+    EscrowReleasedEvent,
+    EscrowRefundedEvent,
+    SubscriptionCreatedEvent,
+    SubscriptionPulledEvent,
+    SubscriptionCancelledEvent,
+    BatchTransferEvent,
+    ExternalAllowlistUpdatedEvent,
+    AllowlistApprovalRevokedEvent,
+}
+
+/// The multisig approval recorded in an admin event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Approval {
+    pub mint: Pubkey,
+    pub nonce: u64,
+    /// Bitmap over the multisig owners at the time of approval
+    pub approvers: u16,
+}
+
+macro_rules! approved_events {
+    ($($name:ident),* $(,)?) => {
+        impl CngnEvent {
+            /// The multisig approval behind the event, for events emitted by
+            /// multisig-approved instructions.
+            pub fn approval(&self) -> Option<Approval> {
+                match self {
+                    $(Self::$name(event) => Some(Approval {
+                        mint: event.mint,
+                        nonce: event.nonce,
+                        approvers: event.approvers,
+                    }),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+approved_events! {
+    TokenMintingPauseEvent,
+    WhitelistedMinter,
+    BlackListedMinter,
+    WhitelistedContract,
+    BlackListedContract,
+    AddedBlackList,
+    RemovedBlackList,
+    WhitelistedInternalUser,
+    BlackListedInternalUser,
+    WhitelistedExternalSender,
+    BlackListedExternalSender,
+    MintAmountUpdatedEvent,
+    WhitelistedForwarder,
+    BlackListedForwarder,
+    MultisigUpdatedEvent,
+    BridgeUpdatedEvent,
+    MetadataInitializedEvent,
+    MetadataUpdatedEvent,
+    KycOperatorAddedEvent,
+    KycOperatorRemovedEvent,
+    TransferTierConfiguredEvent,
+    TransferTierAssignedEvent,
+    TransferFeeUpdatedEvent,
+    FeeConfigUpdatedEvent,
+    ConfidentialAuditorUpdatedEvent,
+    YieldConfigUpdatedEvent,
+    ExternalAllowlistUpdatedEvent,
+}
@@ -0,0 +1,12 @@
+// crates/cngn-indexer/src/lib.rs
+//! Decode cNGN program events from transaction logs, store them in SQLite and
+//! query the history they describe.
+pub mod event;
+pub mod logs;
+pub mod record;
+pub mod rpc;
+pub mod store;
+
+pub use event::{Approval, CngnEvent};
+pub use record::TransactionRecord;
+pub use store::Store;
@@ -0,0 +1,47 @@
+// crates/cngn-indexer/src/logs.rs
+//! Extract Anchor event payloads from a transaction's log messages.
+use anchor_lang::prelude::Pubkey;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+
+const PROGRAM_DATA_PREFIX: &str = "Program data: ";
+
+/// Decoded `Program data:` payloads logged while `program_id` was the
+/// executing program. The invoke stack is tracked so that data logged by other
+/// programs, including ones cNGN calls into, is ignored.
+pub fn program_data(logs: &[String], program_id: &Pubkey) -> Vec<Vec<u8>> {
+    let program_id = program_id.to_string();
+    let mut stack: Vec<&str> = Vec::new();
+    let mut payloads = Vec::new();
+
+    for line in logs {
+        // Runtime lines are `Program <ID> invoke [N]`, `Program <ID> success`
+        // and `Program <ID> failed: ...`; program output is `Program log:`,
+        // `Program data:` and so on.
+        if let Some((id, status)) = line
+            .strip_prefix("Program ")
+            .and_then(|rest| rest.split_once(' '))
+            .filter(|(id, _)| !id.ends_with(':'))
+        {
+            if status.starts_with("invoke [") {
+                stack.push(id);
+            } else if status == "success" || status.starts_with("failed") {
+                stack.pop();
+            }
+            continue;
+        }
+
+        if stack.last() != Some(&program_id.as_str()) {
+            continue;
+        }
+        if let Some(data) = line.strip_prefix(PROGRAM_DATA_PREFIX) {
+            // A single log line may carry several space separated fields.
+            for field in data.split_whitespace() {
+                if let Ok(bytes) = BASE64.decode(field) {
+                    payloads.push(bytes);
+                }
+            }
+        }
+    }
+
+    payloads
+}
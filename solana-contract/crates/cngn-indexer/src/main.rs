@@ -0,0 +1,188 @@
+// crates/cngn-indexer/src/main.rs
+//! `cngn-indexer`: index cNGN program events and query their history.
+use anchor_lang::prelude::Pubkey;
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+use cngn_indexer::rpc::RpcSource;
+use cngn_indexer::{Store, TransactionRecord};
+use serde::Serialize;
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[command(name = "cngn-indexer", version, about)]
+struct Cli {
+    /// SQLite database file
+    #[arg(long, env = "CNGN_INDEX_DB", default_value = "cngn-index.sqlite")]
+    db: PathBuf,
+
+    /// cNGN program id
+    #[arg(long, default_value_t = cngn::ID)]
+    program_id: Pubkey,
+
+    /// Print query results as JSON
+    #[arg(long, global = true)]
+    json: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Index transactions from files of `getTransaction` results, oldest first
+    Ingest {
+        /// JSON or JSON-lines files
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+
+        /// Clear the database first and rebuild it from these files
+        #[arg(long)]
+        replay: bool,
+    },
+    /// Index the program's transactions from a validator
+    Sync {
+        /// JSON-RPC endpoint of the cluster
+        #[arg(
+            long,
+            short = 'u',
+            env = "RPC_URL",
+            default_value = "http://127.0.0.1:8899"
+        )]
+        url: String,
+
+        /// Clear the database first and replay the program's history from genesis
+        #[arg(long)]
+        replay: bool,
+    },
+    /// List indexed events
+    Events {
+        /// Only events with this name, e.g. TokensMintedEvent
+        #[arg(long)]
+        name: Option<String>,
+    },
+    /// Mint history, optionally for one minter
+    Mints {
+        #[arg(long)]
+        minter: Option<Pubkey>,
+    },
+    /// Blacklist additions and removals, optionally for one user
+    Blacklist {
+        #[arg(long)]
+        user: Option<Pubkey>,
+
+        /// Show only the users currently blacklisted according to the history
+        #[arg(long, conflicts_with = "user")]
+        current: bool,
+    },
+    /// Admin changes
+    AdminChanges,
+    /// Multisig owner approvals, optionally for one owner
+    Approvals {
+        #[arg(long)]
+        owner: Option<Pubkey>,
+    },
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let mut store = Store::open(&cli.db, cli.program_id)?;
+
+    match &cli.command {
+        Command::Ingest { files, replay } => {
+            if *replay {
+                store.reset()?;
+            }
+            let (mut transactions, mut events) = (0, 0);
+            for file in files {
+                for record in TransactionRecord::read_file(file)? {
+                    events += store.ingest(&record)?;
+                    transactions += 1;
+                }
+            }
+            eprintln!("Read {transactions} transaction(s), indexed {events} event(s)");
+        }
+        Command::Sync { url, replay } => {
+            if *replay {
+                store.reset()?;
+            }
+            let rpc = RpcSource::new(url);
+            let until = store.last_signature()?;
+            let signatures = rpc.signatures(&cli.program_id, until.as_deref())?;
+            let mut events = 0;
+            for info in &signatures {
+                events += store.ingest(&rpc.transaction(info)?)?;
+            }
+            eprintln!(
+                "Fetched {} new transaction(s), indexed {events} event(s)",
+                signatures.len()
+            );
+        }
+        Command::Events { name } => print(&cli, &store.events(name.as_deref())?, |event| {
+            format!("{}  {}", location(&event.origin), event.name)
+        })?,
+        Command::Mints { minter } => print(&cli, &store.mint_history(minter.as_ref())?, |mint| {
+            format!(
+                "{}  {} to {} by {}",
+                location(&mint.origin),
+                mint.amount,
+                mint.token_account,
+                mint.minter
+            )
+        })?,
+        Command::Blacklist { user, current } => {
+            let changes = if *current {
+                store.current_blacklist()?
+            } else {
+                store.blacklist_timeline(user.as_ref())?
+            };
+            print(&cli, &changes, |change| {
+                format!(
+                    "{}  {} {}",
+                    location(&change.origin),
+                    if change.listed { "added  " } else { "removed" },
+                    change.user
+                )
+            })?
+        }
+        Command::AdminChanges => print(&cli, &store.admin_changes()?, |change| {
+            format!(
+                "{}  {} -> {} (signed by {})",
+                location(&change.origin),
+                change.old_admin,
+                change.new_admin,
+                change.authority
+            )
+        })?,
+        Command::Approvals { owner } => {
+            print(&cli, &store.approvals(owner.as_ref())?, |approval| {
+                format!(
+                    "{}  {} nonce {} approved by {}",
+                    location(&approval.origin),
+                    approval.name,
+                    approval.nonce,
+                    approval
+                        .owner
+                        .clone()
+                        .unwrap_or_else(|| format!("owner #{}", approval.owner_index))
+                )
+            })?
+        }
+    }
+
+    Ok(())
+}
+
+fn location(origin: &cngn_indexer::store::Origin) -> String {
+    format!("slot {:>10}  {}", origin.slot, origin.signature)
+}
+
+fn print<T: Serialize>(cli: &Cli, rows: &[T], line: impl Fn(&T) -> String) -> Result<()> {
+    if cli.json {
+        println!("{}", serde_json::to_string_pretty(rows)?);
+    } else {
+        for row in rows {
+            println!("{}", line(row));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,99 @@
+// crates/cngn-indexer/src/record.rs
+//! Transaction records the indexer consumes, read from files or fetched from
+//! a validator.
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+/// The parts of a confirmed transaction the indexer needs.
+#[derive(Debug, Clone)]
+pub struct TransactionRecord {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    /// Whether the transaction failed; events of failed transactions are rolled back.
+    pub failed: bool,
+    pub logs: Vec<String>,
+}
+
+/// `getTransaction` result as returned with `"encoding": "json"`.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcTransaction {
+    slot: u64,
+    block_time: Option<i64>,
+    transaction: RpcTransactionBody,
+    meta: Option<RpcMeta>,
+}
+
+#[derive(Deserialize)]
+struct RpcTransactionBody {
+    signatures: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcMeta {
+    err: Option<Value>,
+    log_messages: Option<Vec<String>>,
+}
+
+impl TransactionRecord {
+    /// Parse a `getTransaction` result (JSON encoding).
+    pub fn from_rpc(value: Value) -> Result<Self> {
+        let transaction: RpcTransaction =
+            serde_json::from_value(value).context("not a getTransaction result")?;
+        let meta = transaction
+            .meta
+            .ok_or_else(|| anyhow!("transaction has no status meta"))?;
+        let signature = transaction
+            .transaction
+            .signatures
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow!("transaction has no signatures"))?;
+
+        Ok(Self {
+            signature,
+            slot: transaction.slot,
+            block_time: transaction.block_time,
+            failed: meta.err.is_some_and(|err| !err.is_null()),
+            logs: meta.log_messages.unwrap_or_default(),
+        })
+    }
+
+    /// Read a file of `getTransaction` results, either as one JSON array or as
+    /// one JSON document per line. A JSON-RPC response wrapper (`{"result": ..}`)
+    /// around each result is accepted too.
+    pub fn read_file(path: &Path) -> Result<Vec<Self>> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+
+        let values = match serde_json::from_str::<Value>(&contents) {
+            Ok(Value::Array(values)) => values,
+            Ok(value) => vec![value],
+            Err(_) => contents
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(serde_json::from_str)
+                .collect::<serde_json::Result<Vec<Value>>>()
+                .with_context(|| format!("{} is not JSON or JSON lines", path.display()))?,
+        };
+
+        values
+            .into_iter()
+            .enumerate()
+            .map(|(index, value)| {
+                let value = match value {
+                    Value::Object(mut object) if object.contains_key("result") => {
+                        object.remove("result").unwrap_or_default()
+                    }
+                    value => value,
+                };
+                Self::from_rpc(value).with_context(|| format!("{}: record {index}", path.display()))
+            })
+            .collect()
+    }
+}
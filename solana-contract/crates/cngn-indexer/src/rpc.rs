@@ -0,0 +1,128 @@
+// crates/cngn-indexer/src/rpc.rs
+//! Fetch the cNGN program's transaction history from a validator.
+use crate::record::TransactionRecord;
+use anchor_lang::prelude::Pubkey;
+use anyhow::{anyhow, bail, Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::time::Duration;
+
+/// `getSignaturesForAddress` returns at most this many entries per call.
+const SIGNATURE_PAGE_SIZE: usize = 1000;
+
+pub struct RpcSource {
+    url: String,
+    agent: ureq::Agent,
+}
+
+#[derive(Deserialize)]
+struct RpcResponse<T> {
+    result: Option<T>,
+    error: Option<Value>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignatureInfo {
+    pub signature: String,
+    pub slot: u64,
+    pub err: Option<Value>,
+    pub block_time: Option<i64>,
+}
+
+impl RpcSource {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .build(),
+        }
+    }
+
+    fn request<T: DeserializeOwned>(&self, method: &str, params: Value) -> Result<T> {
+        let body = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        });
+
+        let response: RpcResponse<T> = self
+            .agent
+            .post(&self.url)
+            .send_json(body)
+            .with_context(|| format!("{method} request to {} failed", self.url))?
+            .into_json()
+            .with_context(|| format!("invalid {method} response"))?;
+
+        if let Some(error) = response.error {
+            bail!("{method} returned an error: {error}");
+        }
+        response
+            .result
+            .ok_or_else(|| anyhow!("{method} returned no result"))
+    }
+
+    /// Every transaction mentioning `address` after `until` (exclusive), or
+    /// its whole history when `until` is `None`, oldest first.
+    pub fn signatures(&self, address: &Pubkey, until: Option<&str>) -> Result<Vec<SignatureInfo>> {
+        let mut signatures = Vec::new();
+        let mut before: Option<String> = None;
+
+        loop {
+            let page: Vec<SignatureInfo> = self.request(
+                "getSignaturesForAddress",
+                json!([
+                    address.to_string(),
+                    {
+                        "limit": SIGNATURE_PAGE_SIZE,
+                        "before": before,
+                        "until": until,
+                        "commitment": "confirmed",
+                    }
+                ]),
+            )?;
+
+            let full_page = page.len() == SIGNATURE_PAGE_SIZE;
+            before = page.last().map(|info| info.signature.clone());
+            signatures.extend(page);
+
+            if !full_page {
+                break;
+            }
+        }
+
+        signatures.reverse();
+        Ok(signatures)
+    }
+
+    /// Fetch a transaction's logs. Failed transactions are not fetched since
+    /// their events never took effect.
+    pub fn transaction(&self, info: &SignatureInfo) -> Result<TransactionRecord> {
+        if info.err.as_ref().is_some_and(|err| !err.is_null()) {
+            return Ok(TransactionRecord {
+                signature: info.signature.clone(),
+                slot: info.slot,
+                block_time: info.block_time,
+                failed: true,
+                logs: Vec::new(),
+            });
+        }
+
+        let value: Value = self.request(
+            "getTransaction",
+            json!([
+                info.signature,
+                {
+                    "encoding": "json",
+                    "commitment": "confirmed",
+                    "maxSupportedTransactionVersion": 0,
+                }
+            ]),
+        )?;
+        TransactionRecord::from_rpc(value)
+            .with_context(|| format!("transaction {}", info.signature))
+    }
+}
@@ -0,0 +1,493 @@
+// crates/cngn-indexer/src/store.rs
+//! SQLite backed event store and the queries built on it.
+use crate::event::CngnEvent;
+use crate::logs::program_data;
+use crate::record::TransactionRecord;
+use anchor_lang::prelude::Pubkey;
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use serde::Serialize;
+use std::path::Path;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS transactions (
+    seq        INTEGER PRIMARY KEY AUTOINCREMENT,
+    signature  TEXT NOT NULL UNIQUE,
+    slot       INTEGER NOT NULL,
+    block_time INTEGER,
+    failed     INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS events (
+    seq         INTEGER NOT NULL REFERENCES transactions(seq),
+    event_index INTEGER NOT NULL,
+    name        TEXT NOT NULL,
+    data        BLOB NOT NULL,
+    PRIMARY KEY (seq, event_index)
+);
+CREATE TABLE IF NOT EXISTS mints (
+    seq           INTEGER NOT NULL REFERENCES transactions(seq),
+    event_index   INTEGER NOT NULL,
+    mint          TEXT NOT NULL,
+    minter        TEXT NOT NULL,
+    token_account TEXT NOT NULL,
+    amount        INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS blacklist (
+    seq         INTEGER NOT NULL REFERENCES transactions(seq),
+    event_index INTEGER NOT NULL,
+    mint        TEXT NOT NULL,
+    user        TEXT NOT NULL,
+    listed      INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS admin_changes (
+    seq         INTEGER NOT NULL REFERENCES transactions(seq),
+    event_index INTEGER NOT NULL,
+    mint        TEXT NOT NULL,
+    old_admin   TEXT NOT NULL,
+    new_admin   TEXT NOT NULL,
+    authority   TEXT NOT NULL,
+    timestamp   INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS multisig_owners (
+    mint   TEXT PRIMARY KEY,
+    owners TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS approvals (
+    seq         INTEGER NOT NULL REFERENCES transactions(seq),
+    event_index INTEGER NOT NULL,
+    mint        TEXT NOT NULL,
+    nonce       INTEGER NOT NULL,
+    owner_index INTEGER NOT NULL,
+    owner       TEXT
+);
+CREATE INDEX IF NOT EXISTS mints_minter ON mints(minter);
+CREATE INDEX IF NOT EXISTS approvals_owner ON approvals(owner);
+CREATE INDEX IF NOT EXISTS blacklist_user ON blacklist(user);
+";
+
+/// Where and when an event was emitted.
+#[derive(Debug, Clone, Serialize)]
+pub struct Origin {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    pub event_index: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StoredEvent {
+    #[serde(flatten)]
+    pub origin: Origin,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MintRecord {
+    #[serde(flatten)]
+    pub origin: Origin,
+    pub mint: String,
+    /// Minter whose allowance was consumed
+    pub minter: String,
+    pub token_account: String,
+    pub amount: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BlacklistChange {
+    #[serde(flatten)]
+    pub origin: Origin,
+    pub mint: String,
+    pub user: String,
+    pub listed: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AdminChange {
+    #[serde(flatten)]
+    pub origin: Origin,
+    pub mint: String,
+    pub old_admin: String,
+    pub new_admin: String,
+    pub authority: String,
+    pub timestamp: i64,
+}
+
+/// One owner's approval of a multisig-approved action.
+#[derive(Debug, Clone, Serialize)]
+pub struct ApprovalRecord {
+    #[serde(flatten)]
+    pub origin: Origin,
+    /// Name of the event the approved instruction emitted
+    pub name: String,
+    pub mint: String,
+    pub nonce: u64,
+    /// Position of the approver in the multisig owner list
+    pub owner_index: u8,
+    /// The approving owner; `None` if the owner list at the time is unknown
+    /// because the history does not reach back to the multisig's creation.
+    pub owner: Option<String>,
+}
+
+pub struct Store {
+    connection: Connection,
+    program_id: Pubkey,
+}
+
+impl Store {
+    pub fn open(path: &Path, program_id: Pubkey) -> Result<Self> {
+        let connection = Connection::open(path)
+            .with_context(|| format!("failed to open database {}", path.display()))?;
+        connection.execute_batch(SCHEMA)?;
+        Ok(Self {
+            connection,
+            program_id,
+        })
+    }
+
+    pub fn open_in_memory(program_id: Pubkey) -> Result<Self> {
+        let connection = Connection::open_in_memory()?;
+        connection.execute_batch(SCHEMA)?;
+        Ok(Self {
+            connection,
+            program_id,
+        })
+    }
+
+    /// Drop everything indexed so far, ahead of a replay from genesis.
+    pub fn reset(&mut self) -> Result<()> {
+        self.connection.execute_batch(
+            "DELETE FROM approvals; DELETE FROM multisig_owners;
+             DELETE FROM admin_changes; DELETE FROM blacklist; DELETE FROM mints;
+             DELETE FROM events; DELETE FROM transactions;
+             DELETE FROM sqlite_sequence WHERE name = 'transactions';",
+        )?;
+        Ok(())
+    }
+
+    /// Signature of the most recently indexed transaction.
+    pub fn last_signature(&self) -> Result<Option<String>> {
+        Ok(self
+            .connection
+            .query_row(
+                "SELECT signature FROM transactions ORDER BY seq DESC LIMIT 1",
+                [],
+                |row| row.get(0),
+            )
+            .optional()?)
+    }
+
+    /// Index one transaction and return the number of cNGN events stored.
+    /// Transactions are indexed once; seeing a signature again is a no-op.
+    /// Records must be ingested oldest first. An event that no longer decodes,
+    /// such as one emitted under an older layout, is stored by name only and
+    /// left out of the typed queries.
+    pub fn ingest(&mut self, record: &TransactionRecord) -> Result<usize> {
+        let tx = self.connection.transaction()?;
+
+        let inserted = tx.execute(
+            "INSERT OR IGNORE INTO transactions (signature, slot, block_time, failed)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                record.signature,
+                record.slot,
+                record.block_time,
+                record.failed
+            ],
+        )?;
+        if inserted == 0 || record.failed {
+            tx.commit()?;
+            return Ok(0);
+        }
+        let seq = tx.last_insert_rowid();
+
+        let mut count = 0;
+
+        for (index, data) in program_data(&record.logs, &self.program_id)
+            .into_iter()
+            .enumerate()
+        {
+            let event = match CngnEvent::decode(&data) {
+                Ok(Some(event)) => event,
+                Ok(None) => continue,
+                // Events emitted before a layout change no longer decode; keep
+                // their raw payload rather than stop indexing the history
+                Err(error) => {
+                    eprintln!(
+                        "warning: transaction {}: {error:#}; stored undecoded",
+                        record.signature
+                    );
+                    tx.execute(
+                        "INSERT INTO events (seq, event_index, name, data) VALUES (?1, ?2, ?3, ?4)",
+                        params![seq, index, CngnEvent::name_of(&data), data],
+                    )?;
+                    count += 1;
+                    continue;
+                }
+            };
+
+            tx.execute(
+                "INSERT INTO events (seq, event_index, name, data) VALUES (?1, ?2, ?3, ?4)",
+                params![seq, index, event.name(), data],
+            )?;
+            count += 1;
+
+            if let Some(approval) = event.approval() {
+                // A multisig update is approved by the owners it replaces
+                let owners = match &event {
+                    CngnEvent::MultisigUpdatedEvent(event) => Some(event.old_owners.clone()),
+                    _ => multisig_owners(&tx, &approval.mint)?,
+                };
+                for owner_index in (0..u16::BITS).filter(|bit| approval.approvers & (1 << bit) != 0)
+                {
+                    let owner = owners
+                        .as_ref()
+                        .and_then(|owners| owners.get(owner_index as usize))
+                        .map(Pubkey::to_string);
+                    tx.execute(
+                        "INSERT INTO approvals (seq, event_index, mint, nonce, owner_index, owner)
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                        params![
+                            seq,
+                            index,
+                            approval.mint.to_string(),
+                            approval.nonce,
+                            owner_index,
+                            owner
+                        ],
+                    )?;
+                }
+            }
+
+            match event {
+                CngnEvent::TokensMintedEvent(event) => {
+                    tx.execute(
+                        "INSERT INTO mints (seq, event_index, mint, minter, token_account, amount)
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                        params![
+                            seq,
+                            index,
+                            event.mint.to_string(),
+                            event.minter.to_string(),
+                            event.to.to_string(),
+                            event.amount,
+                        ],
+                    )?;
+                }
+                CngnEvent::AddedBlackList(event) => {
+                    tx.execute(
+                        "INSERT INTO blacklist (seq, event_index, mint, user, listed)
+                         VALUES (?1, ?2, ?3, ?4, 1)",
+                        params![seq, index, event.mint.to_string(), event.user.to_string()],
+                    )?;
+                }
+                CngnEvent::RemovedBlackList(event) => {
+                    tx.execute(
+                        "INSERT INTO blacklist (seq, event_index, mint, user, listed)
+                         VALUES (?1, ?2, ?3, ?4, 0)",
+                        params![seq, index, event.mint.to_string(), event.user.to_string()],
+                    )?;
+                }
+                CngnEvent::MultisigInitializedEvent(event) => {
+                    set_multisig_owners(&tx, &event.mint, &event.owners)?;
+                }
+                CngnEvent::MultisigUpdatedEvent(event) => {
+                    set_multisig_owners(&tx, &event.mint, &event.owners)?;
+                }
+                CngnEvent::AdminChangedEvent(event) => {
+                    tx.execute(
+                        "INSERT INTO admin_changes
+                         (seq, event_index, mint, old_admin, new_admin, authority, timestamp)
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                        params![
+                            seq,
+                            index,
+                            event.mint.to_string(),
+                            event.old_admin.to_string(),
+                            event.new_admin.to_string(),
+                            event.authority.to_string(),
+                            event.timestamp,
+                        ],
+                    )?;
+                }
+                _ => {}
+            }
+        }
+
+        tx.commit()?;
+        Ok(count)
+    }
+
+    /// All stored events in order, optionally only those with a given name.
+    pub fn events(&self, name: Option<&str>) -> Result<Vec<StoredEvent>> {
+        self.query(
+            "SELECT t.signature, t.slot, t.block_time, e.event_index, e.name
+             FROM events e JOIN transactions t ON t.seq = e.seq
+             WHERE ?1 IS NULL OR e.name = ?1
+             ORDER BY e.seq, e.event_index",
+            params![name],
+            |row| {
+                Ok(StoredEvent {
+                    origin: origin(row)?,
+                    name: row.get(4)?,
+                })
+            },
+        )
+    }
+
+    /// Mints in order, optionally only those consuming a given minter's allowance.
+    pub fn mint_history(&self, minter: Option<&Pubkey>) -> Result<Vec<MintRecord>> {
+        self.query(
+            "SELECT t.signature, t.slot, t.block_time, m.event_index,
+                    m.mint, m.minter, m.token_account, m.amount
+             FROM mints m JOIN transactions t ON t.seq = m.seq
+             WHERE ?1 IS NULL OR m.minter = ?1
+             ORDER BY m.seq, m.event_index",
+            params![minter.map(Pubkey::to_string)],
+            |row| {
+                Ok(MintRecord {
+                    origin: origin(row)?,
+                    mint: row.get(4)?,
+                    minter: row.get(5)?,
+                    token_account: row.get(6)?,
+                    amount: row.get(7)?,
+                })
+            },
+        )
+    }
+
+    /// Blacklist additions and removals in order, optionally for one user.
+    pub fn blacklist_timeline(&self, user: Option<&Pubkey>) -> Result<Vec<BlacklistChange>> {
+        self.query(
+            "SELECT t.signature, t.slot, t.block_time, b.event_index, b.mint, b.user, b.listed
+             FROM blacklist b JOIN transactions t ON t.seq = b.seq
+             WHERE ?1 IS NULL OR b.user = ?1
+             ORDER BY b.seq, b.event_index",
+            params![user.map(Pubkey::to_string)],
+            blacklist_change,
+        )
+    }
+
+    /// Users whose latest blacklist event added them, i.e. the blacklist as
+    /// reconstructed from the event history.
+    pub fn current_blacklist(&self) -> Result<Vec<BlacklistChange>> {
+        self.query(
+            "SELECT t.signature, t.slot, t.block_time, b.event_index, b.mint, b.user, b.listed
+             FROM blacklist b JOIN transactions t ON t.seq = b.seq
+             WHERE b.listed = 1 AND NOT EXISTS (
+                 SELECT 1 FROM blacklist later
+                 WHERE later.mint = b.mint AND later.user = b.user
+                   AND (later.seq, later.event_index) > (b.seq, b.event_index)
+             )
+             ORDER BY b.seq, b.event_index",
+            [],
+            blacklist_change,
+        )
+    }
+
+    pub fn admin_changes(&self) -> Result<Vec<AdminChange>> {
+        self.query(
+            "SELECT t.signature, t.slot, t.block_time, a.event_index,
+                    a.mint, a.old_admin, a.new_admin, a.authority, a.timestamp
+             FROM admin_changes a JOIN transactions t ON t.seq = a.seq
+             ORDER BY a.seq, a.event_index",
+            [],
+            |row| {
+                Ok(AdminChange {
+                    origin: origin(row)?,
+                    mint: row.get(4)?,
+                    old_admin: row.get(5)?,
+                    new_admin: row.get(6)?,
+                    authority: row.get(7)?,
+                    timestamp: row.get(8)?,
+                })
+            },
+        )
+    }
+
+    /// Owner approvals of multisig actions in order, optionally for one owner.
+    pub fn approvals(&self, owner: Option<&Pubkey>) -> Result<Vec<ApprovalRecord>> {
+        self.query(
+            "SELECT t.signature, t.slot, t.block_time, a.event_index,
+                    e.name, a.mint, a.nonce, a.owner_index, a.owner
+             FROM approvals a
+             JOIN transactions t ON t.seq = a.seq
+             JOIN events e ON e.seq = a.seq AND e.event_index = a.event_index
+             WHERE ?1 IS NULL OR a.owner = ?1
+             ORDER BY a.seq, a.event_index, a.owner_index",
+            params![owner.map(Pubkey::to_string)],
+            |row| {
+                Ok(ApprovalRecord {
+                    origin: origin(row)?,
+                    name: row.get(4)?,
+                    mint: row.get(5)?,
+                    nonce: row.get(6)?,
+                    owner_index: row.get(7)?,
+                    owner: row.get(8)?,
+                })
+            },
+        )
+    }
+
+    fn query<T, P: rusqlite::Params>(
+        &self,
+        sql: &str,
+        params: P,
+        map: impl FnMut(&Row<'_>) -> rusqlite::Result<T>,
+    ) -> Result<Vec<T>> {
+        let mut statement = self.connection.prepare(sql)?;
+        let rows = statement.query_map(params, map)?;
+        Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+    }
+}
+
+/// Read the first four columns every query starts with.
+fn origin(row: &Row<'_>) -> rusqlite::Result<Origin> {
+    Ok(Origin {
+        signature: row.get(0)?,
+        slot: row.get(1)?,
+        block_time: row.get(2)?,
+        event_index: row.get(3)?,
+    })
+}
+
+fn blacklist_change(row: &Row<'_>) -> rusqlite::Result<BlacklistChange> {
+    Ok(BlacklistChange {
+        origin: origin(row)?,
+        mint: row.get(4)?,
+        user: row.get(5)?,
+        listed: row.get(6)?,
+    })
+}
+
+/// The multisig owner list of a mint, as of the events indexed so far.
+fn multisig_owners(connection: &Connection, mint: &Pubkey) -> Result<Option<Vec<Pubkey>>> {
+    let owners: Option<String> = connection
+        .query_row(
+            "SELECT owners FROM multisig_owners WHERE mint = ?1",
+            params![mint.to_string()],
+            |row| row.get(0),
+        )
+        .optional()?;
+    owners
+        .map(|owners| {
+            owners
+                .split(',')
+                .map(|owner| owner.parse().context("malformed stored owner"))
+                .collect()
+        })
+        .transpose()
+}
+
+fn set_multisig_owners(connection: &Connection, mint: &Pubkey, owners: &[Pubkey]) -> Result<()> {
+    let owners = owners
+        .iter()
+        .map(Pubkey::to_string)
+        .collect::<Vec<_>>()
+        .join(",");
+    connection.execute(
+        "INSERT INTO multisig_owners (mint, owners) VALUES (?1, ?2)
+         ON CONFLICT (mint) DO UPDATE SET owners = excluded.owners",
+        params![mint.to_string(), owners],
+    )?;
+    Ok(())
+}
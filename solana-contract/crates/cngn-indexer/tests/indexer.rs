@@ -0,0 +1,263 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::Event;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use cngn::events::*;
+use cngn::state::{BlacklistDetails, BlacklistReason};
+use cngn_indexer::logs::program_data;
+use cngn_indexer::{CngnEvent, Store, TransactionRecord};
+
+const TOKEN_2022: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PazBYoFmBLhCkoQvdGh";
+
+fn record(signature: &str, slot: u64, events: &[Vec<u8>]) -> TransactionRecord {
+    let program = cngn::ID.to_string();
+    let mut logs = vec![
+        format!("Program {program} invoke [1]"),
+        "Program log: Instruction: Mint".to_string(),
+        format!("Program {TOKEN_2022} invoke [2]"),
+        // Data logged by a CPI target must not be attributed to cNGN.
+        format!("Program data: {}", BASE64.encode(&events[0])),
+        format!("Program {TOKEN_2022} success"),
+    ];
+    logs.extend(
+        events
+            .iter()
+            .map(|event| format!("Program data: {}", BASE64.encode(event))),
+    );
+    logs.push(format!(
+        "Program {program} consumed 5000 of 200000 compute units"
+    ));
+    logs.push(format!("Program {program} success"));
+
+    TransactionRecord {
+        signature: signature.to_string(),
+        slot,
+        block_time: Some(1_700_000_000 + slot as i64),
+        failed: false,
+        logs,
+    }
+}
+
+fn added(mint: Pubkey, user: Pubkey) -> Vec<u8> {
+    AddedBlackList {
+        mint,
+        user,
+        removed_minter: false,
+        removed_mint_allowance: 0,
+        removed_forwarder: false,
+        removed_internal_whitelist: false,
+        removed_external_whitelist: false,
+        removed_trusted_contract: false,
+        details: BlacklistDetails {
+            reason: BlacklistReason::Sanctions,
+            case_reference: "OFAC-2024-001".to_string(),
+            review_at: 0,
+            expires_at: 0,
+        },
+        nonce: 1,
+        approvers: 0b11,
+        timestamp: 1_700_000_000,
+    }
+    .data()
+}
+
+#[test]
+fn decodes_only_cngn_program_data() {
+    let mint = Pubkey::new_unique();
+    let user = Pubkey::new_unique();
+    let event = added(mint, user);
+
+    let payloads = program_data(
+        &record("sig", 1, std::slice::from_ref(&event)).logs,
+        &cngn::ID,
+    );
+    assert_eq!(payloads, vec![event.clone()]);
+
+    match CngnEvent::decode(&event).unwrap() {
+        Some(CngnEvent::AddedBlackList(decoded)) => {
+            assert_eq!(decoded.mint, mint);
+            assert_eq!(decoded.user, user);
+        }
+        _ => panic!("expected AddedBlackList"),
+    }
+    assert!(CngnEvent::decode(&[0u8; 16]).unwrap().is_none());
+}
+
+#[test]
+fn indexes_and_queries_history() {
+    let mut store = Store::open_in_memory(cngn::ID).unwrap();
+    let mint = Pubkey::new_unique();
+    let minter = Pubkey::new_unique();
+    let user = Pubkey::new_unique();
+    let token_account = Pubkey::new_unique();
+
+    let mint_tx = record(
+        "mint",
+        10,
+        &[
+            MinterConsumed {
+                mint,
+                authority: minter,
+                amount: 500,
+                timestamp: 1_700_000_010,
+            }
+            .data(),
+            TokensMintedEvent {
+                mint,
+                to: token_account,
+                amount: 500,
+                minter,
+                timestamp: 1_700_000_010,
+            }
+            .data(),
+        ],
+    );
+    let add_tx = record("add", 11, &[added(mint, user)]);
+    let remove_tx = record(
+        "remove",
+        12,
+        &[RemovedBlackList {
+            mint,
+            user,
+            details: None,
+            nonce: 2,
+            approvers: 0b11,
+            timestamp: 1_700_000_012,
+        }
+        .data()],
+    );
+    let mut failed_tx = record("failed", 13, &[added(mint, user)]);
+    failed_tx.failed = true;
+
+    assert_eq!(store.ingest(&mint_tx).unwrap(), 2);
+    assert_eq!(store.ingest(&add_tx).unwrap(), 1);
+    assert_eq!(
+        store.ingest(&add_tx).unwrap(),
+        0,
+        "replayed signature is ignored"
+    );
+
+    let mints = store.mint_history(Some(&minter)).unwrap();
+    assert_eq!(mints.len(), 1);
+    assert_eq!(mints[0].amount, 500);
+    assert_eq!(mints[0].token_account, token_account.to_string());
+    assert!(store.mint_history(Some(&user)).unwrap().is_empty());
+
+    assert_eq!(store.current_blacklist().unwrap().len(), 1);
+    store.ingest(&remove_tx).unwrap();
+    assert_eq!(store.ingest(&failed_tx).unwrap(), 0);
+
+    let timeline = store.blacklist_timeline(Some(&user)).unwrap();
+    assert_eq!(
+        timeline
+            .iter()
+            .map(|change| change.listed)
+            .collect::<Vec<_>>(),
+        vec![true, false]
+    );
+    assert!(store.current_blacklist().unwrap().is_empty());
+    assert_eq!(store.last_signature().unwrap().as_deref(), Some("failed"));
+
+    store.reset().unwrap();
+    assert!(store.events(None).unwrap().is_empty());
+    assert_eq!(store.last_signature().unwrap(), None);
+}
+
+#[test]
+fn keeps_events_that_no_longer_decode() {
+    let mut store = Store::open_in_memory(cngn::ID).unwrap();
+    let mint = Pubkey::new_unique();
+    let user = Pubkey::new_unique();
+
+    // An AddedBlackList in its original layout, just the mint and the user
+    let legacy = added(mint, user);
+    let legacy = legacy[..8 + 32 + 32].to_vec();
+    assert!(CngnEvent::decode(&legacy).is_err());
+    assert_eq!(CngnEvent::name_of(&legacy), Some("AddedBlackList"));
+
+    let tx = record("legacy", 20, &[legacy, added(mint, user)]);
+    assert_eq!(store.ingest(&tx).unwrap(), 2);
+
+    let events = store.events(Some("AddedBlackList")).unwrap();
+    assert_eq!(events.len(), 2);
+    assert_eq!(store.blacklist_timeline(Some(&user)).unwrap().len(), 1);
+}
+
+#[test]
+fn attributes_approvals_to_owners() {
+    let mut store = Store::open_in_memory(cngn::ID).unwrap();
+    let mint = Pubkey::new_unique();
+    let multisig = Pubkey::new_unique();
+    let owners: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+    let rotated = vec![owners[2], Pubkey::new_unique()];
+    let user = Pubkey::new_unique();
+
+    let init_tx = record(
+        "init",
+        1,
+        &[MultisigInitializedEvent {
+            mint,
+            multisig,
+            owners: owners.clone(),
+            threshold: 2,
+            initializer: Pubkey::new_unique(),
+            timestamp: 1_700_000_001,
+        }
+        .data()],
+    );
+    // `added` is approved by the first two owners
+    let add_tx = record("add", 2, &[added(mint, user)]);
+    let update_tx = record(
+        "update",
+        3,
+        &[MultisigUpdatedEvent {
+            mint,
+            multisig,
+            old_owners: owners.clone(),
+            old_threshold: 2,
+            owners: rotated.clone(),
+            threshold: 1,
+            nonce: 2,
+            approvers: 0b110,
+            timestamp: 1_700_000_003,
+        }
+        .data()],
+    );
+    let remove_tx = record(
+        "remove",
+        4,
+        &[RemovedBlackList {
+            mint,
+            user,
+            details: None,
+            nonce: 3,
+            approvers: 0b10,
+            timestamp: 1_700_000_004,
+        }
+        .data()],
+    );
+
+    for tx in [&init_tx, &add_tx, &update_tx, &remove_tx] {
+        store.ingest(tx).unwrap();
+    }
+
+    let approvers = |records: Vec<cngn_indexer::store::ApprovalRecord>| {
+        records
+            .into_iter()
+            .map(|record| (record.origin.signature, record.owner.unwrap()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        approvers(store.approvals(None).unwrap()),
+        vec![
+            ("add".to_string(), owners[0].to_string()),
+            ("add".to_string(), owners[1].to_string()),
+            ("update".to_string(), owners[1].to_string()),
+            ("update".to_string(), owners[2].to_string()),
+            ("remove".to_string(), rotated[1].to_string()),
+        ]
+    );
+    assert_eq!(
+        approvers(store.approvals(Some(&owners[2])).unwrap()),
+        vec![("update".to_string(), owners[2].to_string())]
+    );
+}
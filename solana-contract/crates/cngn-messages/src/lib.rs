@@ -0,0 +1,186 @@
+// crates/cngn-messages/src/lib.rs
+//! Canonical byte format of cNGN cross-chain transfer messages, shared by the
+//! Solana program, relayers and the EVM contracts.
+//!
+//! Version 1 is 97 bytes, all integers big-endian so EVM code can read them
+//! with `abi.encodePacked` semantics:
+//!
+//! | offset | size | field               |
+//! |-------:|-----:|---------------------|
+//! |      0 |    1 | version (`1`)       |
+//! |      1 |    4 | source chain id     |
+//! |      5 |    4 | destination chain id|
+//! |      9 |    8 | nonce               |
+//! |     17 |   32 | sender              |
+//! |     49 |   32 | recipient           |
+//! |     81 |   16 | amount              |
+//!
+//! Addresses are 32 bytes; 20-byte EVM addresses are left-padded with zeros.
+//! Amounts are expressed in [`CANONICAL_DECIMALS`] regardless of the decimals
+//! of the token on either chain; see [`to_canonical`] and [`from_canonical`].
+use std::fmt;
+
+/// Version written by [`TransferMessage::encode`].
+pub const VERSION: u8 = 1;
+
+/// Decimals of `TransferMessage::amount` on the wire.
+pub const CANONICAL_DECIMALS: u8 = 18;
+
+/// Chain ids of the networks cNGN is deployed on. They are cNGN's own
+/// registry, not EVM chain ids, and never change once assigned.
+pub mod chain {
+    pub const SOLANA: u32 = 1;
+    pub const ETHEREUM: u32 = 2;
+    pub const BNB: u32 = 3;
+    pub const POLYGON: u32 = 4;
+    pub const BASE: u32 = 5;
+    pub const LISK: u32 = 6;
+    pub const ASSETCHAIN: u32 = 7;
+    pub const BANTU: u32 = 8;
+
+    /// Name of a known chain id.
+    pub fn name(id: u32) -> Option<&'static str> {
+        Some(match id {
+            SOLANA => "solana",
+            ETHEREUM => "ethereum",
+            BNB => "bnb",
+            POLYGON => "polygon",
+            BASE => "base",
+            LISK => "lisk",
+            ASSETCHAIN => "assetchain",
+            BANTU => "bantu",
+            _ => return None,
+        })
+    }
+}
+
+/// A transfer of `amount` from `sender` on the source chain to `recipient` on
+/// the destination chain. `nonce` numbers the source chain's outbound
+/// transfers, so (source chain, nonce) identifies a message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransferMessage {
+    pub source_chain: u32,
+    pub destination_chain: u32,
+    pub nonce: u64,
+    pub sender: [u8; 32],
+    pub recipient: [u8; 32],
+    /// Amount in `CANONICAL_DECIMALS`
+    pub amount: u128,
+}
+
+impl TransferMessage {
+    /// Encoded size of a version 1 message.
+    pub const LEN: usize = 1 + 4 + 4 + 8 + 32 + 32 + 16;
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(Self::LEN);
+        bytes.push(VERSION);
+        bytes.extend_from_slice(&self.source_chain.to_be_bytes());
+        bytes.extend_from_slice(&self.destination_chain.to_be_bytes());
+        bytes.extend_from_slice(&self.nonce.to_be_bytes());
+        bytes.extend_from_slice(&self.sender);
+        bytes.extend_from_slice(&self.recipient);
+        bytes.extend_from_slice(&self.amount.to_be_bytes());
+        bytes
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self, MessageError> {
+        let (&version, body) = bytes.split_first().ok_or(MessageError::Length {
+            expected: Self::LEN,
+            actual: 0,
+        })?;
+        if version != VERSION {
+            return Err(MessageError::UnsupportedVersion(version));
+        }
+        if bytes.len() != Self::LEN {
+            return Err(MessageError::Length {
+                expected: Self::LEN,
+                actual: bytes.len(),
+            });
+        }
+
+        let mut reader = Reader(body);
+        Ok(Self {
+            source_chain: u32::from_be_bytes(reader.take()),
+            destination_chain: u32::from_be_bytes(reader.take()),
+            nonce: u64::from_be_bytes(reader.take()),
+            sender: reader.take(),
+            recipient: reader.take(),
+            amount: u128::from_be_bytes(reader.take()),
+        })
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl Reader<'_> {
+    fn take<const N: usize>(&mut self) -> [u8; N] {
+        let (head, rest) = self.0.split_at(N);
+        self.0 = rest;
+        head.try_into().expect("split_at returned N bytes")
+    }
+}
+
+/// Left-pad a 20-byte EVM address to the 32-byte address field.
+pub fn evm_address(address: [u8; 20]) -> [u8; 32] {
+    let mut padded = [0u8; 32];
+    padded[12..].copy_from_slice(&address);
+    padded
+}
+
+/// Convert a token amount with `decimals` to the canonical wire amount.
+pub fn to_canonical(amount: u64, decimals: u8) -> Result<u128, MessageError> {
+    if decimals > CANONICAL_DECIMALS {
+        return Err(MessageError::UnsupportedDecimals(decimals));
+    }
+    let scale = 10u128.pow((CANONICAL_DECIMALS - decimals) as u32);
+    // u64::MAX * 10^18 still fits in a u128
+    Ok(amount as u128 * scale)
+}
+
+/// Convert a canonical wire amount to a token amount with `decimals`. Fails
+/// rather than rounding if the amount has more precision than the token, or
+/// if it does not fit in a u64.
+pub fn from_canonical(amount: u128, decimals: u8) -> Result<u64, MessageError> {
+    if decimals > CANONICAL_DECIMALS {
+        return Err(MessageError::UnsupportedDecimals(decimals));
+    }
+    let scale = 10u128.pow((CANONICAL_DECIMALS - decimals) as u32);
+    if amount % scale != 0 {
+        return Err(MessageError::Precision { amount, decimals });
+    }
+    u64::try_from(amount / scale).map_err(|_| MessageError::Overflow(amount))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MessageError {
+    UnsupportedVersion(u8),
+    Length { expected: usize, actual: usize },
+    UnsupportedDecimals(u8),
+    Precision { amount: u128, decimals: u8 },
+    Overflow(u128),
+}
+
+impl fmt::Display for MessageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported message version {version}")
+            }
+            Self::Length { expected, actual } => {
+                write!(f, "message is {actual} bytes, expected {expected}")
+            }
+            Self::UnsupportedDecimals(decimals) => write!(
+                f,
+                "{decimals} decimals exceed the canonical {CANONICAL_DECIMALS}"
+            ),
+            Self::Precision { amount, decimals } => write!(
+                f,
+                "amount {amount} cannot be represented with {decimals} decimals"
+            ),
+            Self::Overflow(amount) => write!(f, "amount {amount} does not fit in a u64"),
+        }
+    }
+}
+
+impl std::error::Error for MessageError {}
@@ -0,0 +1,124 @@
+use cngn_messages::*;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Vectors {
+    version: u8,
+    canonical_decimals: u8,
+    messages: Vec<MessageVector>,
+    decimals: Vec<DecimalsVector>,
+    invalid: Vec<InvalidVector>,
+}
+
+#[derive(Deserialize)]
+struct MessageVector {
+    description: String,
+    source_chain: u32,
+    destination_chain: u32,
+    nonce: u64,
+    sender: String,
+    recipient: String,
+    amount: String,
+    encoded: String,
+}
+
+#[derive(Deserialize)]
+struct DecimalsVector {
+    amount: String,
+    decimals: u8,
+    canonical: String,
+}
+
+#[derive(Deserialize)]
+struct InvalidVector {
+    description: String,
+    encoded: String,
+    error: String,
+}
+
+fn vectors() -> Vectors {
+    serde_json::from_str(include_str!("../test-vectors.json")).unwrap()
+}
+
+fn bytes32(hex: &str) -> [u8; 32] {
+    hex::decode(hex).unwrap().try_into().unwrap()
+}
+
+#[test]
+fn encodes_and_decodes_vectors() {
+    let vectors = vectors();
+    assert_eq!(vectors.version, VERSION);
+    assert_eq!(vectors.canonical_decimals, CANONICAL_DECIMALS);
+
+    for vector in vectors.messages {
+        let message = TransferMessage {
+            source_chain: vector.source_chain,
+            destination_chain: vector.destination_chain,
+            nonce: vector.nonce,
+            sender: bytes32(&vector.sender),
+            recipient: bytes32(&vector.recipient),
+            amount: vector.amount.parse().unwrap(),
+        };
+        let encoded = hex::decode(&vector.encoded).unwrap();
+
+        assert_eq!(message.encode(), encoded, "{}", vector.description);
+        assert_eq!(encoded.len(), TransferMessage::LEN);
+        assert_eq!(
+            TransferMessage::decode(&encoded).unwrap(),
+            message,
+            "{}",
+            vector.description
+        );
+    }
+}
+
+#[test]
+fn normalizes_decimals() {
+    for vector in vectors().decimals {
+        let amount: u64 = vector.amount.parse().unwrap();
+        let canonical: u128 = vector.canonical.parse().unwrap();
+
+        assert_eq!(to_canonical(amount, vector.decimals).unwrap(), canonical);
+        assert_eq!(from_canonical(canonical, vector.decimals).unwrap(), amount);
+    }
+
+    assert_eq!(
+        from_canonical(10u128.pow(12) + 1, 6),
+        Err(MessageError::Precision {
+            amount: 10u128.pow(12) + 1,
+            decimals: 6
+        })
+    );
+    assert!(matches!(
+        from_canonical(u128::MAX - u128::MAX % 10, 17),
+        Err(MessageError::Overflow(_))
+    ));
+    assert_eq!(
+        to_canonical(1, 19),
+        Err(MessageError::UnsupportedDecimals(19))
+    );
+}
+
+#[test]
+fn rejects_invalid_messages() {
+    for vector in vectors().invalid {
+        let error = TransferMessage::decode(&hex::decode(&vector.encoded).unwrap()).unwrap_err();
+        let kind = match error {
+            MessageError::UnsupportedVersion(_) => "unsupported_version",
+            MessageError::Length { .. } => "length",
+            _ => "other",
+        };
+        assert_eq!(kind, vector.error, "{}", vector.description);
+    }
+}
+
+#[test]
+fn pads_evm_addresses_and_names_chains() {
+    let padded = evm_address([0xab; 20]);
+    assert_eq!(padded[..12], [0u8; 12]);
+    assert_eq!(padded[12..], [0xab; 20]);
+
+    assert_eq!(chain::name(chain::SOLANA), Some("solana"));
+    assert_eq!(chain::name(chain::BANTU), Some("bantu"));
+    assert_eq!(chain::name(0), None);
+}
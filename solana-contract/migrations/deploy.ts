@@ -4,11 +4,8 @@ import fs from 'fs';
 import { Keypair, PublicKey, } from '@solana/web3.js';
 import { calculatePDAs, TokenPDAs } from '../utils/helpers';
 import { initializeMultisig, initializeToken } from '../utils/token_initializer';
-import { transferMintAuthority } from '../utils/transfer_mint_authority';
 import { loadOrCreateKeypair } from '../app/utils/helpers';
-import { transferFreezeAuthority } from '../utils/transfer_freeze_authority';
 
-import { createMintAccountWithExtensions } from '../app/utils/metadata2022';
 import cngnidl from '../target/idl/cngn.json';
 require('dotenv').config();
 
@@ -43,6 +40,9 @@ async function main() {
   console.log("signers are",signer1.toString(),signer2.toString())
   const multisgOwners= [signer1,signer2]
 console.log("multisig",pdas.multisig.toString())
+  // base64 ElGamal key of whoever decrypts withheld confidential fees; it
+  // cannot be changed after initialization
+  const feeWithdrawElGamalPubkey = Array.from(Buffer.from(process.env.FEE_WITHDRAW_ELGAMAL_PUBKEY, 'base64'))
   try {
     console.log('Program ID: ', program.programId.toBase58());
     console.log("Wallet:", provider.wallet.publicKey.toString());
@@ -50,21 +50,11 @@ console.log("multisig",pdas.multisig.toString())
     // Initialize the token
 
 
-   // Step 2: Create token metadata
-   console.log("\n--- Step 2: Creating token metadata ---");
-    await createMintAccountWithExtensions(
-      provider,
-      cngnMintKeypair,
-      Cngnparams,
-      payer.publicKey, //permanent delegate
-      payer.publicKey,//hook authority
-      pdas.mintAuthority,//mint authority
-      program.programId
-    )
-   await initializeToken(program, provider, cngnMintKeypair, pdas,payer.publicKey);
+   // `initialize` creates the mint with its extensions and the mint
+   // authority PDA as every authority; metadata is written afterwards by the
+   // multisig with `initialize_metadata`.
+   await initializeToken(program, provider, cngnMintKeypair, pdas,payer.publicKey,feeWithdrawElGamalPubkey);
     await initializeMultisig(program,provider,cngnMintKeypair,pdas,multisgOwners,threshold)
-   // await transferMintAuthority(multisig, cngnMintKeypair.publicKey, payer, connection)
-   // await transferFreezeAuthority(multisig, cngnMintKeypair.publicKey, payer, connection)
   } catch (error) {
     console.error("Error during initialization:", error);
     if (error instanceof Error) {
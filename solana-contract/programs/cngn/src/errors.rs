@@ -188,4 +188,129 @@ pub enum ErrorCode {
         #[msg("Invalid threshold: must be > 0 and <= number of owners")]
     InvalidThreshold,
 
+    #[msg("Too many attesters: maximum is 10")]
+    TooManyAttesters,
+
+    #[msg("Duplicate attesters not allowed")]
+    DuplicateAttesters,
+
+    #[msg("Not enough bridge attestations")]
+    NotEnoughAttestations,
+
+    #[msg("Bridge is paused")]
+    BridgePaused,
+
+    #[msg("Invalid bridge recipient")]
+    InvalidBridgeRecipient,
+
+    #[msg("Invalid bridge chain")]
+    InvalidBridgeChain,
+
+    #[msg("Bridge amount cannot be represented with the token decimals")]
+    InvalidBridgeAmount,
+
+    #[msg("Mint decimals differ from the token config")]
+    DecimalsMismatch,
+
+    #[msg("Metadata field or value is empty or too long")]
+    InvalidMetadataField,
+
+    #[msg("Transfer hook invoked outside a transfer")]
+    NotTransferring,
+
+    #[msg("Initialization phase is out of order")]
+    InvalidInitPhase,
+
+    #[msg("Token setup is not complete")]
+    SetupIncomplete,
+
+    #[msg("Too many KYC operators: maximum is 10")]
+    TooManyKycOperators,
+
+    #[msg("Signer is not a KYC operator")]
+    KycOperatorNotAuthorized,
+
+    #[msg("KYC tier is out of range or reserved for whitelisted owners")]
+    InvalidKycTier,
+
+    #[msg("Per-transaction cap exceeds the daily cap")]
+    InvalidTransferLimits,
+
+    #[msg("Sender has no transfer tier assigned")]
+    TransferTierNotAssigned,
+
+    #[msg("Transfer exceeds the per-transaction limit for the sender's tier")]
+    PerTransactionLimitExceeded,
+
+    #[msg("Transfer exceeds the daily limit for the sender's tier")]
+    DailyLimitExceeded,
+
+    #[msg("Transfer fee cannot exceed 10000 basis points")]
+    InvalidTransferFee,
+
+    #[msg("Account is not the fee treasury")]
+    InvalidFeeTreasury,
+
+    #[msg("Owner is not exempt from transfer fees")]
+    NotFeeExempt,
+
+    #[msg("Confidential transfers are restricted to internally whitelisted owners")]
+    ConfidentialTransferNotAllowed,
+
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+
+    #[msg("Yield rate cannot exceed 10000 basis points and the period must be positive")]
+    InvalidYieldConfig,
+
+    #[msg("The current yield period has not elapsed")]
+    YieldPeriodNotElapsed,
+
+    #[msg("Yield would exceed the supply cap")]
+    YieldExceedsSupplyCap,
+
+    #[msg("Yield would exceed the reserves")]
+    YieldExceedsReserves,
+
+    #[msg("Escrow amount must be positive and its deadline in the future")]
+    InvalidEscrow,
+
+    #[msg("Escrow has already been settled or is not in the required state")]
+    EscrowNotOpen,
+
+    #[msg("Signer cannot perform this escrow action")]
+    EscrowNotAuthorized,
+
+    #[msg("Subscription amount and period must be positive")]
+    InvalidSubscription,
+
+    #[msg("Pull exceeds the subscription's allowance for this period")]
+    SubscriptionLimitExceeded,
+
+    #[msg("Batch is empty, too large, or its amounts do not match its recipients")]
+    InvalidBatch,
+
+    #[msg("Allowlist grace period exceeds 30 days")]
+    InvalidAllowlistGracePeriod,
+
+    #[msg("Owner is not on the external allowlist")]
+    NotAllowlisted,
+
+    #[msg("Case reference must be 1 to 64 bytes and the review and expiry dates in the future")]
+    InvalidBlacklistDetails,
+
+    #[msg("Blacklist entry account does not match the user or its payer")]
+    InvalidBlacklistEntry,
+
+    #[msg("Blacklist listing has not expired")]
+    BlacklistNotExpired,
+
+    #[msg("Transfer hook extra account list does not match the hook's accounts")]
+    InvalidExtraAccountMetas,
+
+    #[msg("Token config is already in the current layout")]
+    TokenConfigAlreadyMigrated,
+
+    #[msg("Owner's allowlist approval is still valid")]
+    AllowlistApprovalStillValid,
 }
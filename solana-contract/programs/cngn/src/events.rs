@@ -1,6 +1,12 @@
 // events.rs
+use crate::state::{BlacklistDetails, InitPhase, MetadataField};
 use anchor_lang::prelude::*;
 
+// Events for multisig-approved actions carry the multisig `nonce` the owners
+// signed for, the `approvers` whose signatures were counted and the
+// `timestamp` of the block that executed the action. `approvers` is a bitmap
+// over `Multisig.owners` at the time of approval: bit `i` is `owners[i]`.
+
 #[event]
 pub struct TokenInitializedEvent {
     pub mint: Pubkey,
@@ -9,24 +15,54 @@ pub struct TokenInitializedEvent {
     pub symbol: String,
     pub uri: String,
     pub decimals: u8,
+    pub timestamp: i64,
 }
 
 #[event]
 pub struct SecondaryInitializedEvent {
     pub mint: Pubkey,
     pub initializer: Pubkey,
+    pub timestamp: i64,
 }
 
 #[event]
 pub struct ThirdInitializedEvent {
     pub mint: Pubkey,
     pub initializer: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokenConfigMigratedEvent {
+    pub mint: Pubkey,
+    pub init_phase: InitPhase,
+    pub initializer: Pubkey,
+    pub payer: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TransferHookUpgradedEvent {
+    pub mint: Pubkey,
+    pub payer: Pubkey,
+    pub timestamp: i64,
 }
 #[event]
 pub struct TokensMintedEvent {
     pub mint: Pubkey,
     pub to: Pubkey,
     pub amount: u64,
+    pub minter: Pubkey,
+    pub timestamp: i64,
+}
+
+/// A minter's allowance was used up by `mint` and the minter removed
+#[event]
+pub struct MinterConsumed {
+    pub mint: Pubkey,
+    pub authority: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
 }
 
 #[event]
@@ -53,6 +89,9 @@ pub struct TokensTransferredEvent {
 pub struct TokenMintingPauseEvent {
     pub mint: Pubkey,
     pub mint_paused: bool,
+    pub nonce: u64,
+    pub approvers: u16,
+    pub timestamp: i64,
 }
 
 #[event]
@@ -81,12 +120,18 @@ pub struct RedemptionEvent {
 pub struct WhitelistedMinter {
     pub mint: Pubkey,
     pub authority: Pubkey,
+    pub nonce: u64,
+    pub approvers: u16,
+    pub timestamp: i64,
 }
 
 #[event]
 pub struct BlackListedMinter {
     pub mint: Pubkey,
     pub authority: Pubkey,
+    pub nonce: u64,
+    pub approvers: u16,
+    pub timestamp: i64,
 }
 
 #[event]
@@ -106,68 +151,110 @@ pub struct MintAmountRemoved {
 pub struct WhitelistedContract {
     pub mint: Pubkey,
     pub contract: Pubkey,
+    pub nonce: u64,
+    pub approvers: u16,
+    pub timestamp: i64,
 }
 
 #[event]
 pub struct BlackListedContract {
     pub mint: Pubkey,
     pub contract: Pubkey,
+    pub nonce: u64,
+    pub approvers: u16,
+    pub timestamp: i64,
 }
 
 #[event]
 pub struct AddedBlackList {
     pub mint: Pubkey,
     pub user: Pubkey,
+    // Memberships the user lost as a side effect of being blacklisted
+    pub removed_minter: bool,
+    pub removed_mint_allowance: u64,
+    pub removed_forwarder: bool,
+    pub removed_internal_whitelist: bool,
+    pub removed_external_whitelist: bool,
+    pub removed_trusted_contract: bool,
+    pub details: BlacklistDetails,
+    pub nonce: u64,
+    pub approvers: u16,
+    pub timestamp: i64,
 }
 
 #[event]
 pub struct RemovedBlackList {
     pub mint: Pubkey,
     pub user: Pubkey,
+    pub details: Option<BlacklistDetails>, // None for addresses listed without an entry
+    pub nonce: u64,
+    pub approvers: u16,
+    pub timestamp: i64,
 }
 
 #[event]
 pub struct WhitelistedInternalUser {
     pub mint: Pubkey,
     pub user: Pubkey,
+    pub nonce: u64,
+    pub approvers: u16,
+    pub timestamp: i64,
 }
 
 #[event]
 pub struct BlackListedInternalUser {
     pub mint: Pubkey,
     pub user: Pubkey,
+    pub nonce: u64,
+    pub approvers: u16,
+    pub timestamp: i64,
 }
 
 #[event]
 pub struct WhitelistedExternalSender {
     pub mint: Pubkey,
     pub user: Pubkey,
+    pub nonce: u64,
+    pub approvers: u16,
+    pub timestamp: i64,
 }
 
 #[event]
 pub struct BlackListedExternalSender {
     pub mint: Pubkey,
     pub user: Pubkey,
+    pub nonce: u64,
+    pub approvers: u16,
+    pub timestamp: i64,
 }
 
 #[event]
 pub struct MintAmountUpdatedEvent {
     pub mint: Pubkey,
     pub authority: Pubkey,
+    pub previous_amount: u64,
     pub amount: u64,
+    pub nonce: u64,
+    pub approvers: u16,
+    pub timestamp: i64,
 }
 
-// Add to your events.rs file
 #[event]
 pub struct WhitelistedForwarder {
     pub mint: Pubkey,
     pub forwarder: Pubkey,
+    pub nonce: u64,
+    pub approvers: u16,
+    pub timestamp: i64,
 }
 
 #[event]
 pub struct BlackListedForwarder {
     pub mint: Pubkey,
     pub forwarder: Pubkey,
+    pub nonce: u64,
+    pub approvers: u16,
+    pub timestamp: i64,
 }
 
 #[event]
@@ -183,4 +270,344 @@ pub struct AdminChangedEvent {
     pub new_admin: Pubkey,
     pub authority: Pubkey, // The key that signed the transaction (current admin)
     pub timestamp: i64,
-}
\ No newline at end of file
+}
+
+#[event]
+pub struct MultisigInitializedEvent {
+    pub mint: Pubkey,
+    pub multisig: Pubkey,
+    pub owners: Vec<Pubkey>,
+    pub threshold: u8,
+    pub initializer: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MultisigUpdatedEvent {
+    pub mint: Pubkey,
+    pub multisig: Pubkey,
+    // `approvers` indexes into the old owner set, which signed the change
+    pub old_owners: Vec<Pubkey>,
+    pub old_threshold: u8,
+    pub owners: Vec<Pubkey>,
+    pub threshold: u8,
+    pub nonce: u64,
+    pub approvers: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BridgeUpdatedEvent {
+    pub mint: Pubkey,
+    pub attesters: Vec<Pubkey>,
+    pub threshold: u8,
+    pub paused: bool,
+    pub nonce: u64,
+    pub approvers: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BridgeInEvent {
+    pub mint: Pubkey,
+    pub source_chain: u32,
+    pub nonce: u64, // Source chain's outbound nonce
+    pub sender: [u8; 32],
+    pub recipient: Pubkey,
+    pub token_account: Pubkey,
+    pub amount: u64,
+    pub attesters: u16, // Bitmap over `BridgeConfig.attesters`
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BridgeOutEvent {
+    pub mint: Pubkey,
+    pub sender: Pubkey,
+    pub destination_chain: u32,
+    pub recipient: [u8; 32], // Destination address, left-padded
+    pub amount: u64,
+    pub nonce: u64, // Sequence number of outbound transfers for the mint
+    pub message: Vec<u8>, // `cngn-messages` encoding for the attesters
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MetadataInitializedEvent {
+    pub mint: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub nonce: u64,
+    pub approvers: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MetadataUpdatedEvent {
+    pub mint: Pubkey,
+    pub field: MetadataField,
+    pub value: String,
+    pub nonce: u64,
+    pub approvers: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct KycOperatorAddedEvent {
+    pub mint: Pubkey,
+    pub operator: Pubkey,
+    pub nonce: u64,
+    pub approvers: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct KycOperatorRemovedEvent {
+    pub mint: Pubkey,
+    pub operator: Pubkey,
+    pub nonce: u64,
+    pub approvers: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct KycApprovedEvent {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub token_account: Pubkey,
+    pub tier: u8,
+    pub operator: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TransferTierConfiguredEvent {
+    pub mint: Pubkey,
+    pub tier: u8,
+    pub per_transaction_limit: u64,
+    pub daily_limit: u64,
+    pub nonce: u64,
+    pub approvers: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TransferTierAssignedEvent {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub tier: u8,
+    pub nonce: u64,
+    pub approvers: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TransferFeeUpdatedEvent {
+    pub mint: Pubkey,
+    pub basis_points: u16,
+    pub maximum_fee: u64,
+    pub nonce: u64,
+    pub approvers: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeeConfigUpdatedEvent {
+    pub mint: Pubkey,
+    pub treasury: Pubkey,
+    pub exempt_internal_whitelist: bool,
+    pub nonce: u64,
+    pub approvers: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TransferFeesWithdrawnEvent {
+    pub mint: Pubkey,
+    pub treasury: Pubkey,
+    pub amount: u64,          // Sent to the treasury
+    pub refunds_settled: u64, // Kept back to cover refunds already paid
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TransferFeeRefundedEvent {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub token_account: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ConfidentialAuditorUpdatedEvent {
+    pub mint: Pubkey,
+    pub auditor_elgamal_pubkey: Option<[u8; 32]>,
+    pub nonce: u64,
+    pub approvers: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ConfidentialAccountApprovedEvent {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub token_account: Pubkey,
+    pub operator: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct YieldConfigUpdatedEvent {
+    pub mint: Pubkey,
+    pub annual_rate_basis_points: u16,
+    pub period_seconds: u32,
+    pub supply_cap: u64,
+    pub reserves: u64,
+    pub nonce: u64,
+    pub approvers: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct YieldPeriodAccruedEvent {
+    pub mint: Pubkey,
+    pub period: u64,
+    pub annual_rate_basis_points: u16,
+    pub yield_index: u128,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct YieldOptedInEvent {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub token_account: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct YieldOptedOutEvent {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub token_account: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct YieldDistributedEvent {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub token_account: Pubkey,
+    pub amount: u64,
+    pub period: u64, // Last period included
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EscrowCreatedEvent {
+    pub mint: Pubkey,
+    pub escrow: Pubkey,
+    pub payer: Pubkey,
+    pub payee: Pubkey,
+    pub arbiter: Pubkey,
+    pub amount: u64,
+    pub deadline: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EscrowDisputedEvent {
+    pub mint: Pubkey,
+    pub escrow: Pubkey,
+    pub disputed_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EscrowReleasedEvent {
+    pub mint: Pubkey,
+    pub escrow: Pubkey,
+    pub to: Pubkey, // Payee's token account
+    pub amount: u64,
+    pub fee: u64, // Transfer fee taken out of `amount`
+    pub released_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EscrowRefundedEvent {
+    pub mint: Pubkey,
+    pub escrow: Pubkey,
+    pub to: Pubkey, // Payer's token account
+    pub amount: u64,
+    pub fee: u64, // Always 0: refunds are not charged the transfer fee
+    pub refunded_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SubscriptionCreatedEvent {
+    pub mint: Pubkey,
+    pub subscription: Pubkey,
+    pub payer: Pubkey,
+    pub payee: Pubkey,
+    pub amount_per_period: u64,
+    pub period_seconds: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SubscriptionPulledEvent {
+    pub mint: Pubkey,
+    pub subscription: Pubkey,
+    pub payer: Pubkey,
+    pub payee: Pubkey,
+    pub amount: u64,
+    pub fee: u64, // Transfer fee taken out of `amount`
+    pub period_start: i64,
+    pub pulled_in_period: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SubscriptionCancelledEvent {
+    pub mint: Pubkey,
+    pub subscription: Pubkey,
+    pub payer: Pubkey,
+    pub payee: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BatchTransferEvent {
+    pub mint: Pubkey,
+    pub sender: Pubkey,
+    pub paid: u16,
+    pub skipped: u16,
+    pub total_amount: u64,
+    pub total_fee: u64, // Transfer fee taken out of the payouts
+    pub merkle_root: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ExternalAllowlistUpdatedEvent {
+    pub mint: Pubkey,
+    pub merkle_root: [u8; 32],
+    pub previous_merkle_root: [u8; 32],
+    pub previous_root_expires_at: i64,
+    pub nonce: u64,
+    pub approvers: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AllowlistApprovalRevokedEvent {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub token_account: Pubkey,
+    pub merkle_root: [u8; 32], // Retired root the owner was approved against
+    pub timestamp: i64,
+}
@@ -3,6 +3,8 @@ use crate::errors::ErrorCode;
 use crate::events::*;
 use crate::state::*;
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Allocate, Assign, CreateAccount, Transfer};
+use anchor_lang::AccountsClose;
 use anchor_spl::token_interface::Mint;
 
 // ============================================================================
@@ -21,6 +23,7 @@ pub struct AddCanMint<'info> {
         mut,
         seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
         bump = token_config.bump,
+        constraint = token_config.is_setup_complete() @ ErrorCode::SetupIncomplete,
     )]
     pub token_config: Account<'info, TokenConfig>,
 
@@ -44,6 +47,7 @@ pub struct AddCanMint<'info> {
     pub trusted_contracts: Account<'info, TrustedContracts>,
 
     #[account(
+        mut,
         seeds = [Multisig::MULTISIG_SEED, mint.key().as_ref()],
         bump = multisig.bump
     )]
@@ -66,9 +70,21 @@ pub fn add_can_mint_handler(ctx: Context<AddCanMint>, user: Pubkey) -> Result<()
 
     // Build the message for multisig validation
     let message = build_add_can_mint_message(&ctx.accounts.can_mint.key(), &user, multisig.nonce);
+    let readable_message = build_readable_target_message(
+        "ADD_CAN_MINT",
+        &ctx.accounts.can_mint.key(),
+        &user,
+        multisig.nonce,
+    );
 
     // Validate multisig authorization
-    validate_multisig_authorization(multisig, &ctx.accounts.instructions, &message)?;
+    let nonce = multisig.nonce;
+    let approvers = validate_multisig_authorization(
+        multisig,
+        &ctx.accounts.instructions,
+        &message,
+        &readable_message,
+    )?;
 
     let blacklist = &ctx.accounts.blacklist;
     let can_mint = &mut ctx.accounts.can_mint;
@@ -85,6 +101,9 @@ pub fn add_can_mint_handler(ctx: Context<AddCanMint>, user: Pubkey) -> Result<()
         emit!(WhitelistedMinter {
             mint: ctx.accounts.token_config.mint,
             authority: user,
+            nonce,
+            approvers,
+            timestamp: Clock::get()?.unix_timestamp,
         });
     }
 
@@ -107,6 +126,7 @@ pub struct RemoveCanMint<'info> {
         mut,
         seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
         bump = token_config.bump,
+        constraint = token_config.is_setup_complete() @ ErrorCode::SetupIncomplete,
     )]
     pub token_config: Account<'info, TokenConfig>,
 
@@ -124,6 +144,7 @@ pub struct RemoveCanMint<'info> {
     pub trusted_contracts: Account<'info, TrustedContracts>,
 
     #[account(
+        mut,
         seeds = [Multisig::MULTISIG_SEED, mint.key().as_ref()],
         bump = multisig.bump
     )]
@@ -145,8 +166,20 @@ pub fn remove_can_mint_handler(ctx: Context<RemoveCanMint>, user: Pubkey) -> Res
 
     let message =
         build_remove_can_mint_message(&ctx.accounts.can_mint.key(), &user, multisig.nonce);
+    let readable_message = build_readable_target_message(
+        "REMOVE_CAN_MINT",
+        &ctx.accounts.can_mint.key(),
+        &user,
+        multisig.nonce,
+    );
 
-    validate_multisig_authorization(multisig, &ctx.accounts.instructions, &message)?;
+    let nonce = multisig.nonce;
+    let approvers = validate_multisig_authorization(
+        multisig,
+        &ctx.accounts.instructions,
+        &message,
+        &readable_message,
+    )?;
 
     let can_mint = &mut ctx.accounts.can_mint;
 
@@ -156,6 +189,9 @@ pub fn remove_can_mint_handler(ctx: Context<RemoveCanMint>, user: Pubkey) -> Res
         emit!(BlackListedMinter {
             mint: ctx.accounts.token_config.mint,
             authority: user,
+            nonce,
+            approvers,
+            timestamp: Clock::get()?.unix_timestamp,
         });
     }
 
@@ -178,6 +214,7 @@ pub struct AddCanForward<'info> {
         mut,
         seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
         bump = token_config.bump,
+        constraint = token_config.is_setup_complete() @ ErrorCode::SetupIncomplete,
     )]
     pub token_config: Account<'info, TokenConfig>,
 
@@ -195,6 +232,7 @@ pub struct AddCanForward<'info> {
     pub can_forward: Account<'info, CanForward>,
 
     #[account(
+        mut,
         seeds = [Multisig::MULTISIG_SEED, mint.key().as_ref()],
         bump = multisig.bump
     )]
@@ -216,8 +254,20 @@ pub fn add_can_forward_handler(ctx: Context<AddCanForward>, forwarder: Pubkey) -
 
     let message =
         build_add_can_forward_message(&ctx.accounts.can_forward.key(), &forwarder, multisig.nonce);
+    let readable_message = build_readable_target_message(
+        "ADD_CAN_FORWARD",
+        &ctx.accounts.can_forward.key(),
+        &forwarder,
+        multisig.nonce,
+    );
 
-    validate_multisig_authorization(multisig, &ctx.accounts.instructions, &message)?;
+    let nonce = multisig.nonce;
+    let approvers = validate_multisig_authorization(
+        multisig,
+        &ctx.accounts.instructions,
+        &message,
+        &readable_message,
+    )?;
 
     let blacklist = &ctx.accounts.blacklist;
     let can_forward = &mut ctx.accounts.can_forward;
@@ -232,6 +282,9 @@ pub fn add_can_forward_handler(ctx: Context<AddCanForward>, forwarder: Pubkey) -
         emit!(WhitelistedForwarder {
             mint: ctx.accounts.token_config.mint,
             forwarder,
+            nonce,
+            approvers,
+            timestamp: Clock::get()?.unix_timestamp,
         });
     }
 
@@ -254,6 +307,7 @@ pub struct RemoveCanForward<'info> {
         mut,
         seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
         bump = token_config.bump,
+        constraint = token_config.is_setup_complete() @ ErrorCode::SetupIncomplete,
     )]
     pub token_config: Account<'info, TokenConfig>,
 
@@ -265,6 +319,7 @@ pub struct RemoveCanForward<'info> {
     pub can_forward: Account<'info, CanForward>,
 
     #[account(
+        mut,
         seeds = [Multisig::MULTISIG_SEED, mint.key().as_ref()],
         bump = multisig.bump
     )]
@@ -289,8 +344,20 @@ pub fn remove_can_forward_handler(ctx: Context<RemoveCanForward>, forwarder: Pub
         &forwarder,
         multisig.nonce,
     );
+    let readable_message = build_readable_target_message(
+        "REMOVE_CAN_FORWARD",
+        &ctx.accounts.can_forward.key(),
+        &forwarder,
+        multisig.nonce,
+    );
 
-    validate_multisig_authorization(multisig, &ctx.accounts.instructions, &message)?;
+    let nonce = multisig.nonce;
+    let approvers = validate_multisig_authorization(
+        multisig,
+        &ctx.accounts.instructions,
+        &message,
+        &readable_message,
+    )?;
 
     let can_forward = &mut ctx.accounts.can_forward;
 
@@ -300,6 +367,9 @@ pub fn remove_can_forward_handler(ctx: Context<RemoveCanForward>, forwarder: Pub
         emit!(BlackListedForwarder {
             mint: ctx.accounts.token_config.mint,
             forwarder,
+            nonce,
+            approvers,
+            timestamp: Clock::get()?.unix_timestamp,
         });
     }
 
@@ -312,6 +382,10 @@ pub fn remove_can_forward_handler(ctx: Context<RemoveCanForward>, forwarder: Pub
 
 #[derive(Accounts)]
 pub struct AddBlackList<'info> {
+    /// Funds the blacklist entries
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
     #[account(
         mut,
         constraint = mint.key() == token_config.mint @ ErrorCode::MintMismatch,
@@ -322,28 +396,33 @@ pub struct AddBlackList<'info> {
         mut,
         seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
         bump = token_config.bump,
+        constraint = token_config.is_setup_complete() @ ErrorCode::SetupIncomplete,
     )]
     pub token_config: Account<'info, TokenConfig>,
 
     #[account(
+        mut,
         seeds = [CAN_MINT_SEED, token_config.mint.as_ref()],
         bump,
     )]
     pub can_mint: Account<'info, CanMint>,
 
     #[account(
+        mut,
         seeds = [b"internal-whitelist", token_config.mint.as_ref()],
         bump,
     )]
     pub internal_whitelist: Account<'info, InternalWhiteList>,
 
     #[account(
+        mut,
         seeds = [b"external-whitelist", token_config.mint.as_ref()],
         bump,
     )]
     pub external_whitelist: Account<'info, ExternalWhiteList>,
 
     #[account(
+        mut,
         seeds = [TRUSTED_CONTRACTS_SEED, token_config.mint.as_ref()],
         bump,
     )]
@@ -364,6 +443,7 @@ pub struct AddBlackList<'info> {
     pub blacklist: Account<'info, BlackList>,
 
     #[account(
+        mut,
         seeds = [Multisig::MULTISIG_SEED, mint.key().as_ref()],
         bump = multisig.bump
     )]
@@ -372,9 +452,22 @@ pub struct AddBlackList<'info> {
     /// CHECK: This is the instructions sysvar
     #[account(address = solana_program::sysvar::instructions::ID)]
     pub instructions: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+    // Remaining accounts: the `["blacklist-entry", mint, user]` entry of each
+    // user, writable, in order
 }
 
-pub fn add_blacklist_handler(ctx: Context<AddBlackList>, user: Pubkey) -> Result<()> {
+pub fn add_blacklist_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AddBlackList<'info>>,
+    user: Pubkey,
+    details: BlacklistDetails,
+) -> Result<()> {
+    let [entry] = ctx.remaining_accounts else {
+        return Err(ErrorCode::InvalidBlacklistEntry.into());
+    };
+    details.validate(Clock::get()?.unix_timestamp)?;
+
     let multisig = &mut ctx.accounts.multisig;
 
     require_keys_eq!(
@@ -383,48 +476,206 @@ pub fn add_blacklist_handler(ctx: Context<AddBlackList>, user: Pubkey) -> Result
         ErrorCode::Unauthorized
     );
 
-    let message = build_add_blacklist_message(&ctx.accounts.blacklist.key(), &user, multisig.nonce);
+    let message = build_add_blacklist_message(
+        &ctx.accounts.blacklist.key(),
+        &user,
+        &details,
+        multisig.nonce,
+    );
+    let readable_message = build_readable_add_blacklist_message(
+        &ctx.accounts.blacklist.key(),
+        &user,
+        &details,
+        multisig.nonce,
+    );
 
-    validate_multisig_authorization(multisig, &ctx.accounts.instructions, &message)?;
+    let nonce = multisig.nonce;
+    let approvers = validate_multisig_authorization(
+        multisig,
+        &ctx.accounts.instructions,
+        &message,
+        &readable_message,
+    )?;
 
-    let blacklist = &mut ctx.accounts.blacklist;
-    let can_mint = &mut ctx.accounts.can_mint;
-    let internal_whitelist = &mut ctx.accounts.internal_whitelist;
-    let external_whitelist = &mut ctx.accounts.external_whitelist;
-    let trusted_contracts = &mut ctx.accounts.trusted_contracts;
-    let can_forward = &mut ctx.accounts.can_forward;
+    blacklist_user(ctx.accounts, entry, user, &details, nonce, approvers)
+}
 
-    if blacklist.is_blacklisted(&user) {
+/// Blacklist `user`, record `details` in its entry and strip it from every
+/// other list, as approved under `nonce`
+pub(crate) fn blacklist_user<'info>(
+    accounts: &mut AddBlackList<'info>,
+    entry: &AccountInfo<'info>,
+    user: Pubkey,
+    details: &BlacklistDetails,
+    nonce: u64,
+    approvers: u16,
+) -> Result<()> {
+    // A lapsed listing stays until it is pruned, and so does its entry
+    if accounts.blacklist.is_blacklisted(&user) {
         return Err(ErrorCode::UserBlacklisted.into());
     }
 
+    create_blacklist_entry(accounts, entry, user, details)?;
+
+    let blacklist = &mut accounts.blacklist;
+    let can_mint = &mut accounts.can_mint;
+    let internal_whitelist = &mut accounts.internal_whitelist;
+    let external_whitelist = &mut accounts.external_whitelist;
+    let trusted_contracts = &mut accounts.trusted_contracts;
+    let can_forward = &mut accounts.can_forward;
+
     blacklist.add(&user)?;
 
     // Remove user from other lists if present
-    if can_mint.can_mint(&user) {
+    let removed_minter = can_mint.can_mint(&user);
+    let mut removed_mint_allowance = 0;
+    if removed_minter {
+        removed_mint_allowance = can_mint.get_mint_amount(&user)?;
         can_mint.remove_authority(&user)?;
     }
-    if internal_whitelist.is_whitelisted(&user) {
+    let removed_internal_whitelist = internal_whitelist.is_whitelisted(&user);
+    if removed_internal_whitelist {
         internal_whitelist.remove(&user)?;
     }
-    if external_whitelist.is_whitelisted(&user) {
+    let removed_external_whitelist = external_whitelist.is_whitelisted(&user);
+    if removed_external_whitelist {
         external_whitelist.remove(&user)?;
     }
-    if can_forward.is_trusted_forwarder(&user) {
+    let removed_forwarder = can_forward.is_trusted_forwarder(&user);
+    if removed_forwarder {
         can_forward.remove(&user)?;
     }
-    if trusted_contracts.is_trusted_contract(&user) {
+    let removed_trusted_contract = trusted_contracts.is_trusted_contract(&user);
+    if removed_trusted_contract {
         trusted_contracts.remove(&user)?;
     }
 
     emit!(AddedBlackList {
-        mint: ctx.accounts.token_config.mint,
+        mint: accounts.token_config.mint,
         user,
+        removed_minter,
+        removed_mint_allowance,
+        removed_forwarder,
+        removed_internal_whitelist,
+        removed_external_whitelist,
+        removed_trusted_contract,
+        details: details.clone(),
+        nonce,
+        approvers,
+        timestamp: Clock::get()?.unix_timestamp,
     });
 
     Ok(())
 }
 
+/// Create `user`'s blacklist entry at `entry`, funded by the payer
+fn create_blacklist_entry<'info>(
+    accounts: &AddBlackList<'info>,
+    entry: &AccountInfo<'info>,
+    user: Pubkey,
+    details: &BlacklistDetails,
+) -> Result<()> {
+    let mint = accounts.token_config.mint;
+    let (address, bump) = BlacklistEntry::address(&mint, &user);
+    require_keys_eq!(entry.key(), address, ErrorCode::InvalidBlacklistEntry);
+    require!(entry.data_is_empty(), ErrorCode::InvalidBlacklistEntry);
+
+    let seeds = &[BLACKLIST_ENTRY_SEED, mint.as_ref(), user.as_ref(), &[bump]];
+    let signer_seeds = &[&seeds[..]];
+    let system_program = accounts.system_program.to_account_info();
+    let payer = accounts.payer.to_account_info();
+    let space = BlacklistEntry::LEN;
+    let rent = Rent::get()?.minimum_balance(space);
+
+    // Anyone can send lamports to the address first, which would make
+    // `create_account` fail, so a funded address is topped up and taken over
+    if entry.lamports() == 0 {
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program,
+                CreateAccount {
+                    from: payer,
+                    to: entry.clone(),
+                },
+                signer_seeds,
+            ),
+            rent,
+            space as u64,
+            &crate::ID,
+        )?;
+    } else {
+        let shortfall = rent.saturating_sub(entry.lamports());
+        if shortfall > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    system_program.clone(),
+                    Transfer {
+                        from: payer,
+                        to: entry.clone(),
+                    },
+                ),
+                shortfall,
+            )?;
+        }
+        system_program::allocate(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                Allocate {
+                    account_to_allocate: entry.clone(),
+                },
+                signer_seeds,
+            ),
+            space as u64,
+        )?;
+        system_program::assign(
+            CpiContext::new_with_signer(
+                system_program,
+                Assign {
+                    account_to_assign: entry.clone(),
+                },
+                signer_seeds,
+            ),
+            &crate::ID,
+        )?;
+    }
+
+    let record = BlacklistEntry {
+        mint,
+        user,
+        details: details.clone(),
+        listed_at: Clock::get()?.unix_timestamp,
+        payer: accounts.payer.key(),
+        bump,
+    };
+    record.try_serialize(&mut &mut entry.try_borrow_mut_data()?[..])
+}
+
+/// Close `user`'s blacklist entry and refund its payer, returning the
+/// details it held; addresses blacklisted before entries existed have none
+pub(crate) fn close_blacklist_entry<'info>(
+    entry: &'info AccountInfo<'info>,
+    rent_recipient: &AccountInfo<'info>,
+    mint: &Pubkey,
+    user: &Pubkey,
+) -> Result<Option<BlacklistDetails>> {
+    let (address, _) = BlacklistEntry::address(mint, user);
+    require_keys_eq!(entry.key(), address, ErrorCode::InvalidBlacklistEntry);
+    if entry.data_is_empty() {
+        return Ok(None);
+    }
+
+    let record = Account::<BlacklistEntry>::try_from(entry)?;
+    require_keys_eq!(
+        record.payer,
+        rent_recipient.key(),
+        ErrorCode::InvalidBlacklistEntry
+    );
+    let details = record.details.clone();
+    record.close(rent_recipient.clone())?;
+
+    Ok(Some(details))
+}
+
 // ============================================================================
 // Remove Blacklist (with Multisig)
 // ============================================================================
@@ -441,6 +692,7 @@ pub struct RemoveBlackList<'info> {
         mut,
         seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
         bump = token_config.bump,
+        constraint = token_config.is_setup_complete() @ ErrorCode::SetupIncomplete,
     )]
     pub token_config: Account<'info, TokenConfig>,
 
@@ -452,17 +704,33 @@ pub struct RemoveBlackList<'info> {
     pub blacklist: Account<'info, BlackList>,
 
     #[account(
+        mut,
         seeds = [Multisig::MULTISIG_SEED, mint.key().as_ref()],
         bump = multisig.bump
     )]
     pub multisig: Account<'info, Multisig>,
 
+    /// CHECK: the payer recorded in the closed entry, which gets its rent
+    /// back. Batches ignore it and pair a recipient with each entry instead.
+    #[account(mut)]
+    pub rent_recipient: UncheckedAccount<'info>,
+
     /// CHECK: This is the instructions sysvar
     #[account(address = solana_program::sysvar::instructions::ID)]
     pub instructions: AccountInfo<'info>,
+    // Remaining accounts: the user's `["blacklist-entry", mint, user]` entry,
+    // writable. Batches take each user's entry followed by the payer recorded
+    // in it, both writable, in order.
 }
 
-pub fn remove_blacklist_handler(ctx: Context<RemoveBlackList>, user: Pubkey) -> Result<()> {
+pub fn remove_blacklist_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RemoveBlackList<'info>>,
+    user: Pubkey,
+) -> Result<()> {
+    let [entry] = ctx.remaining_accounts else {
+        return Err(ErrorCode::InvalidBlacklistEntry.into());
+    };
+
     let multisig = &mut ctx.accounts.multisig;
 
     require_keys_eq!(
@@ -473,8 +741,20 @@ pub fn remove_blacklist_handler(ctx: Context<RemoveBlackList>, user: Pubkey) ->
 
     let message =
         build_remove_blacklist_message(&ctx.accounts.blacklist.key(), &user, multisig.nonce);
+    let readable_message = build_readable_target_message(
+        "REMOVE_BLACKLIST",
+        &ctx.accounts.blacklist.key(),
+        &user,
+        multisig.nonce,
+    );
 
-    validate_multisig_authorization(multisig, &ctx.accounts.instructions, &message)?;
+    let nonce = multisig.nonce;
+    let approvers = validate_multisig_authorization(
+        multisig,
+        &ctx.accounts.instructions,
+        &message,
+        &readable_message,
+    )?;
 
     let blacklist = &mut ctx.accounts.blacklist;
 
@@ -482,11 +762,84 @@ pub fn remove_blacklist_handler(ctx: Context<RemoveBlackList>, user: Pubkey) ->
         return Ok(());
     }
 
+    blacklist.remove(&user)?;
+    let details = close_blacklist_entry(
+        entry,
+        &ctx.accounts.rent_recipient,
+        &ctx.accounts.token_config.mint,
+        &user,
+    )?;
+
+    emit!(RemovedBlackList {
+        mint: ctx.accounts.token_config.mint,
+        user,
+        details,
+        nonce,
+        approvers,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// ============================================================================
+// Prune Expired Blacklist (permissionless)
+// ============================================================================
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct PruneExpiredBlacklist<'info> {
+    #[account(
+        seeds = [TOKEN_CONFIG_SEED, token_config.mint.as_ref()],
+        bump = token_config.bump,
+        constraint = token_config.is_setup_complete() @ ErrorCode::SetupIncomplete,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        mut,
+        seeds = [BLACK_LIST_SEED, token_config.mint.as_ref()],
+        bump = blacklist.bump,
+    )]
+    pub blacklist: Account<'info, BlackList>,
+
+    #[account(
+        mut,
+        close = rent_recipient,
+        seeds = [BLACKLIST_ENTRY_SEED, token_config.mint.as_ref(), user.as_ref()],
+        bump = blacklist_entry.bump,
+        constraint = blacklist_entry.payer == rent_recipient.key() @ ErrorCode::InvalidBlacklistEntry,
+    )]
+    pub blacklist_entry: Account<'info, BlacklistEntry>,
+
+    /// CHECK: the payer recorded in the entry, which gets its rent back
+    #[account(mut)]
+    pub rent_recipient: UncheckedAccount<'info>,
+}
+
+/// Lift `user`'s listing once its `expires_at` has passed. Anyone can call
+/// it; the event carries no approval, so its nonce and approvers are 0.
+pub fn prune_expired_blacklist_handler(
+    ctx: Context<PruneExpiredBlacklist>,
+    user: Pubkey,
+) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    let blacklist = &mut ctx.accounts.blacklist;
+
+    require!(
+        blacklist.is_blacklisted(&user) && ctx.accounts.blacklist_entry.is_lapsed(timestamp),
+        ErrorCode::BlacklistNotExpired
+    );
+
     blacklist.remove(&user)?;
 
     emit!(RemovedBlackList {
         mint: ctx.accounts.token_config.mint,
         user,
+        details: Some(ctx.accounts.blacklist_entry.details.clone()),
+        nonce: 0,
+        approvers: 0,
+        timestamp,
     });
 
     Ok(())
@@ -499,6 +852,7 @@ pub fn remove_blacklist_handler(ctx: Context<RemoveBlackList>, user: Pubkey) ->
 #[derive(Accounts)]
 pub struct SetMintAmount<'info> {
     #[account(
+        mut,
         seeds = [Multisig::MULTISIG_SEED, mint.key().as_ref()],
         bump = multisig.bump
     )]
@@ -514,6 +868,7 @@ pub struct SetMintAmount<'info> {
         mut,
         seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
         bump = token_config.bump,
+        constraint = token_config.is_setup_complete() @ ErrorCode::SetupIncomplete,
     )]
     pub token_config: Account<'info, TokenConfig>,
 
@@ -542,8 +897,20 @@ pub fn set_mint_amount_handler(
 
     let message =
         build_set_mint_amount_message(&ctx.accounts.can_mint.key(), &user, multisig.nonce);
+    let readable_message = build_readable_set_mint_amount_message(
+        &ctx.accounts.can_mint.key(),
+        &user,
+        amount,
+        multisig.nonce,
+    );
 
-    validate_multisig_authorization(multisig, &ctx.accounts.instructions, &message)?;
+    let nonce = multisig.nonce;
+    let approvers = validate_multisig_authorization(
+        multisig,
+        &ctx.accounts.instructions,
+        &message,
+        &readable_message,
+    )?;
 
     let can_mint = &mut ctx.accounts.can_mint;
 
@@ -551,12 +918,17 @@ pub fn set_mint_amount_handler(
         return Err(ErrorCode::AdminNotFound.into());
     }
 
+    let previous_amount = can_mint.get_mint_amount(&user)?;
     can_mint.set_mint_amount(&user, amount)?;
 
     emit!(MintAmountUpdatedEvent {
         mint: ctx.accounts.token_config.mint,
         authority: user,
+        previous_amount,
         amount,
+        nonce,
+        approvers,
+        timestamp: Clock::get()?.unix_timestamp,
     });
 
     Ok(())
@@ -565,6 +937,7 @@ pub fn set_mint_amount_handler(
 #[derive(Accounts)]
 pub struct RemoveMintAmount<'info> {
     #[account(
+        mut,
         seeds = [Multisig::MULTISIG_SEED, mint.key().as_ref()],
         bump = multisig.bump
     )]
@@ -580,6 +953,7 @@ pub struct RemoveMintAmount<'info> {
         mut,
         seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
         bump = token_config.bump,
+        constraint = token_config.is_setup_complete() @ ErrorCode::SetupIncomplete,
     )]
     pub token_config: Account<'info, TokenConfig>,
 
@@ -603,8 +977,20 @@ pub fn remove_mint_amount_handler(ctx: Context<RemoveMintAmount>, user: Pubkey)
     );
     let message =
         build_remove_mint_amount_message(&ctx.accounts.can_mint.key(), &user, multisig.nonce);
+    let readable_message = build_readable_target_message(
+        "REMOVE_MINT_AMOUNT",
+        &ctx.accounts.can_mint.key(),
+        &user,
+        multisig.nonce,
+    );
 
-    validate_multisig_authorization(multisig, &ctx.accounts.instructions, &message)?;
+    let nonce = multisig.nonce;
+    let approvers = validate_multisig_authorization(
+        multisig,
+        &ctx.accounts.instructions,
+        &message,
+        &readable_message,
+    )?;
 
     let can_mint = &mut ctx.accounts.can_mint;
     let amount: u64 = 0;
@@ -613,12 +999,17 @@ pub fn remove_mint_amount_handler(ctx: Context<RemoveMintAmount>, user: Pubkey)
         return Err(ErrorCode::AdminNotFound.into());
     }
 
+    let previous_amount = can_mint.get_mint_amount(&user)?;
     can_mint.set_mint_amount(&user, amount)?;
 
     emit!(MintAmountUpdatedEvent {
         mint: ctx.accounts.token_config.mint,
         authority: user,
+        previous_amount,
         amount,
+        nonce,
+        approvers,
+        timestamp: Clock::get()?.unix_timestamp,
     });
 
     Ok(())
@@ -648,7 +1039,7 @@ pub fn get_mint_amount_handler(ctx: Context<GetMintAmount>, user: Pubkey) -> Res
         return Err(ErrorCode::UserNotFound.into());
     }
 
-    return can_mint.get_mint_amount(&user);
+    can_mint.get_mint_amount(&user)
 }
 
 // ============================================================================
@@ -667,6 +1058,7 @@ pub struct AddTrustedContract<'info> {
         mut,
         seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
         bump = token_config.bump,
+        constraint = token_config.is_setup_complete() @ ErrorCode::SetupIncomplete,
     )]
     pub token_config: Account<'info, TokenConfig>,
 
@@ -678,6 +1070,7 @@ pub struct AddTrustedContract<'info> {
     pub trusted_contracts: Account<'info, TrustedContracts>,
 
     #[account(
+        mut,
         seeds = [Multisig::MULTISIG_SEED, mint.key().as_ref()],
         bump = multisig.bump
     )]
@@ -705,8 +1098,20 @@ pub fn add_trusted_contract_handler(
         &contract,
         multisig.nonce,
     );
+    let readable_message = build_readable_target_message(
+        "ADD_TRUSTED_CONTRACT",
+        &ctx.accounts.trusted_contracts.key(),
+        &contract,
+        multisig.nonce,
+    );
 
-    validate_multisig_authorization(multisig, &ctx.accounts.instructions, &message)?;
+    let nonce = multisig.nonce;
+    let approvers = validate_multisig_authorization(
+        multisig,
+        &ctx.accounts.instructions,
+        &message,
+        &readable_message,
+    )?;
 
     let trusted_contracts = &mut ctx.accounts.trusted_contracts;
 
@@ -719,6 +1124,9 @@ pub fn add_trusted_contract_handler(
     emit!(WhitelistedContract {
         mint: ctx.accounts.token_config.mint,
         contract,
+        nonce,
+        approvers,
+        timestamp: Clock::get()?.unix_timestamp,
     });
 
     Ok(())
@@ -740,6 +1148,7 @@ pub struct RemoveTrustedContract<'info> {
         mut,
         seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
         bump = token_config.bump,
+        constraint = token_config.is_setup_complete() @ ErrorCode::SetupIncomplete,
     )]
     pub token_config: Account<'info, TokenConfig>,
 
@@ -751,6 +1160,7 @@ pub struct RemoveTrustedContract<'info> {
     pub trusted_contracts: Account<'info, TrustedContracts>,
 
     #[account(
+        mut,
         seeds = [Multisig::MULTISIG_SEED, mint.key().as_ref()],
         bump = multisig.bump
     )]
@@ -778,8 +1188,20 @@ pub fn remove_trusted_contract_handler(
         &contract,
         multisig.nonce,
     );
+    let readable_message = build_readable_target_message(
+        "REMOVE_TRUSTED_CONTRACT",
+        &ctx.accounts.trusted_contracts.key(),
+        &contract,
+        multisig.nonce,
+    );
 
-    validate_multisig_authorization(multisig, &ctx.accounts.instructions, &message)?;
+    let nonce = multisig.nonce;
+    let approvers = validate_multisig_authorization(
+        multisig,
+        &ctx.accounts.instructions,
+        &message,
+        &readable_message,
+    )?;
 
     let trusted_contracts = &mut ctx.accounts.trusted_contracts;
 
@@ -792,6 +1214,9 @@ pub fn remove_trusted_contract_handler(
     emit!(BlackListedContract {
         mint: ctx.accounts.token_config.mint,
         contract,
+        nonce,
+        approvers,
+        timestamp: Clock::get()?.unix_timestamp,
     });
 
     Ok(())
@@ -813,6 +1238,7 @@ pub struct WhitelistInternalUser<'info> {
         mut,
         seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
         bump = token_config.bump,
+        constraint = token_config.is_setup_complete() @ ErrorCode::SetupIncomplete,
     )]
     pub token_config: Account<'info, TokenConfig>,
 
@@ -830,6 +1256,7 @@ pub struct WhitelistInternalUser<'info> {
     pub blacklist: Account<'info, BlackList>,
 
     #[account(
+        mut,
         seeds = [Multisig::MULTISIG_SEED, mint.key().as_ref()],
         bump = multisig.bump
     )]
@@ -857,8 +1284,20 @@ pub fn whitelist_internal_user_handler(
         &user,
         multisig.nonce,
     );
+    let readable_message = build_readable_target_message(
+        "WHITELIST_INTERNAL",
+        &ctx.accounts.internal_whitelist.key(),
+        &user,
+        multisig.nonce,
+    );
 
-    validate_multisig_authorization(multisig, &ctx.accounts.instructions, &message)?;
+    let nonce = multisig.nonce;
+    let approvers = validate_multisig_authorization(
+        multisig,
+        &ctx.accounts.instructions,
+        &message,
+        &readable_message,
+    )?;
 
     let internal_whitelist = &mut ctx.accounts.internal_whitelist;
     let blacklist = &ctx.accounts.blacklist;
@@ -875,7 +1314,10 @@ pub fn whitelist_internal_user_handler(
 
     emit!(WhitelistedInternalUser {
         mint: ctx.accounts.token_config.mint,
-        user: user
+        user,
+        nonce,
+        approvers,
+        timestamp: Clock::get()?.unix_timestamp,
     });
 
     Ok(())
@@ -897,6 +1339,7 @@ pub struct WhitelistExternalUser<'info> {
         mut,
         seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
         bump = token_config.bump,
+        constraint = token_config.is_setup_complete() @ ErrorCode::SetupIncomplete,
     )]
     pub token_config: Account<'info, TokenConfig>,
 
@@ -914,6 +1357,7 @@ pub struct WhitelistExternalUser<'info> {
     pub blacklist: Account<'info, BlackList>,
 
     #[account(
+        mut,
         seeds = [Multisig::MULTISIG_SEED, mint.key().as_ref()],
         bump = multisig.bump
     )]
@@ -941,8 +1385,20 @@ pub fn whitelist_external_user_handler(
         &user,
         multisig.nonce,
     );
+    let readable_message = build_readable_target_message(
+        "WHITELIST_EXTERNAL",
+        &ctx.accounts.external_whitelist.key(),
+        &user,
+        multisig.nonce,
+    );
 
-    validate_multisig_authorization(multisig, &ctx.accounts.instructions, &message)?;
+    let nonce = multisig.nonce;
+    let approvers = validate_multisig_authorization(
+        multisig,
+        &ctx.accounts.instructions,
+        &message,
+        &readable_message,
+    )?;
 
     let external_whitelist = &mut ctx.accounts.external_whitelist;
     let blacklist = &ctx.accounts.blacklist;
@@ -959,7 +1415,10 @@ pub fn whitelist_external_user_handler(
 
     emit!(WhitelistedExternalSender {
         mint: ctx.accounts.token_config.mint,
-        user: user,
+        user,
+        nonce,
+        approvers,
+        timestamp: Clock::get()?.unix_timestamp,
     });
 
     Ok(())
@@ -981,6 +1440,7 @@ pub struct BlacklistInternalUser<'info> {
         mut,
         seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
         bump = token_config.bump,
+        constraint = token_config.is_setup_complete() @ ErrorCode::SetupIncomplete,
     )]
     pub token_config: Account<'info, TokenConfig>,
 
@@ -998,6 +1458,7 @@ pub struct BlacklistInternalUser<'info> {
     pub trusted_contracts: Account<'info, TrustedContracts>,
 
     #[account(
+        mut,
         seeds = [Multisig::MULTISIG_SEED, mint.key().as_ref()],
         bump = multisig.bump
     )]
@@ -1025,8 +1486,20 @@ pub fn blacklist_internal_user_handler(
         &user,
         multisig.nonce,
     );
+    let readable_message = build_readable_target_message(
+        "BLACKLIST_INTERNAL",
+        &ctx.accounts.internal_whitelist.key(),
+        &user,
+        multisig.nonce,
+    );
 
-    validate_multisig_authorization(multisig, &ctx.accounts.instructions, &message)?;
+    let nonce = multisig.nonce;
+    let approvers = validate_multisig_authorization(
+        multisig,
+        &ctx.accounts.instructions,
+        &message,
+        &readable_message,
+    )?;
 
     let internal_whitelist = &mut ctx.accounts.internal_whitelist;
 
@@ -1038,7 +1511,10 @@ pub fn blacklist_internal_user_handler(
 
     emit!(BlackListedInternalUser {
         mint: ctx.accounts.token_config.mint,
-        user: user,
+        user,
+        nonce,
+        approvers,
+        timestamp: Clock::get()?.unix_timestamp,
     });
 
     Ok(())
@@ -1060,6 +1536,7 @@ pub struct BlacklistExternalUser<'info> {
         mut,
         seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
         bump = token_config.bump,
+        constraint = token_config.is_setup_complete() @ ErrorCode::SetupIncomplete,
     )]
     pub token_config: Account<'info, TokenConfig>,
 
@@ -1071,6 +1548,7 @@ pub struct BlacklistExternalUser<'info> {
     pub external_whitelist: Account<'info, ExternalWhiteList>,
 
     #[account(
+        mut,
         seeds = [Multisig::MULTISIG_SEED, mint.key().as_ref()],
         bump = multisig.bump
     )]
@@ -1098,8 +1576,20 @@ pub fn blacklist_external_user_handler(
         &user,
         multisig.nonce,
     );
+    let readable_message = build_readable_target_message(
+        "BLACKLIST_EXTERNAL",
+        &ctx.accounts.external_whitelist.key(),
+        &user,
+        multisig.nonce,
+    );
 
-    validate_multisig_authorization(multisig, &ctx.accounts.instructions, &message)?;
+    let nonce = multisig.nonce;
+    let approvers = validate_multisig_authorization(
+        multisig,
+        &ctx.accounts.instructions,
+        &message,
+        &readable_message,
+    )?;
 
     let external_whitelist = &mut ctx.accounts.external_whitelist;
 
@@ -1111,7 +1601,10 @@ pub fn blacklist_external_user_handler(
 
     emit!(BlackListedExternalSender {
         mint: ctx.accounts.token_config.mint,
-        user: user,
+        user,
+        nonce,
+        approvers,
+        timestamp: Clock::get()?.unix_timestamp,
     });
 
     Ok(())
@@ -1131,6 +1624,7 @@ pub struct ChangeAdmin<'info> {
         mut,
        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
         bump = token_config.bump,
+        constraint = token_config.is_setup_complete() @ ErrorCode::SetupIncomplete,
     )]
     pub token_config: Account<'info, TokenConfig>,
 
@@ -1186,8 +1680,8 @@ pub fn change_admin_handler(ctx: Context<ChangeAdmin>, new_admin: Pubkey) -> Res
     // 4. Emit the event
     emit!(AdminChangedEvent {
         mint: token_config.mint,
-        old_admin: old_admin,
-        new_admin: new_admin,
+        old_admin,
+        new_admin,
         authority: ctx.accounts.authority.key(),
         timestamp: Clock::get()?.unix_timestamp,
     });
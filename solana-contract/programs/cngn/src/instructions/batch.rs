@@ -0,0 +1,265 @@
+// src/instructions/batch.rs
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::instructions::transfer_fee::{burn_and_mint_fee, collect_fee, load_fee_config};
+use crate::instructions::transfer_hook::record_transfer;
+use crate::instructions::yield_distribution::record_yield_outflow;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::extension::StateWithExtensions;
+use anchor_spl::token_2022::spl_token_2022::state::{Account as SplAccount, AccountState};
+use anchor_spl::token_interface::{self, Burn, Mint, MintTo, TokenAccount, TokenInterface};
+
+// Payouts move funds the way escrow does, by burning the total from the
+// sender and minting each amount to its recipient, since a transfer of this
+// mint cannot be made from inside the program (see escrow.rs). The transfer
+// fee is taken out of each payout and minted to the fee treasury.
+
+#[derive(Accounts)]
+pub struct BatchTransfer<'info> {
+    pub sender: Signer<'info>,
+
+    #[account(
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        bump = token_config.bump,
+        constraint = !token_config.transfer_paused @ ErrorCode::TransfersPaused,
+        constraint = token_config.is_setup_complete() @ ErrorCode::SetupIncomplete,
+    )]
+    pub token_config: Box<Account<'info, TokenConfig>>,
+
+    #[account(
+        mut,
+        constraint = mint.key() == token_config.mint @ ErrorCode::MintMismatch,
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, mint.key().as_ref()],
+        bump = mint_authority.bump,
+    )]
+    pub mint_authority: Box<Account<'info, MintAuthority>>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = sender,
+    )]
+    pub sender_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [BLACK_LIST_SEED, mint.key().as_ref()],
+        bump = blacklist.bump,
+    )]
+    pub blacklist: Box<Account<'info, BlackList>>,
+
+    #[account(
+        seeds = [INTERNAL_WHITELIST_SEED, mint.key().as_ref()],
+        bump = internal_whitelist.bump,
+    )]
+    pub internal_whitelist: Box<Account<'info, InternalWhiteList>>,
+
+    #[account(
+        seeds = [TRANSFER_TIERS_SEED, mint.key().as_ref()],
+        bump = transfer_tiers.bump,
+    )]
+    pub transfer_tiers: Box<Account<'info, TransferTiers>>,
+
+    /// CHECK: the sender's `TransferLimits`, which may not exist; loaded in
+    /// the handler
+    #[account(
+        mut,
+        seeds = [TRANSFER_LIMITS_SEED, mint.key().as_ref(), sender.key().as_ref()],
+        bump,
+    )]
+    pub transfer_limits: UncheckedAccount<'info>,
+
+    /// CHECK: the `YieldPosition` of the sender's token account, which may
+    /// not exist; loaded in the handler
+    #[account(
+        mut,
+        seeds = [YIELD_POSITION_SEED, mint.key().as_ref(), sender_token_account.key().as_ref()],
+        bump,
+    )]
+    pub yield_position: UncheckedAccount<'info>,
+
+    /// CHECK: the `FeeConfig`, which may not exist; loaded in the handler
+    #[account(
+        seeds = [FEE_CONFIG_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub fee_config: UncheckedAccount<'info>,
+
+    /// CHECK: the fee config's treasury; checked in the handler when a fee
+    /// is charged
+    #[account(mut)]
+    pub fee_treasury: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    // Remaining accounts: recipient token accounts, writable, in the order
+    // of `amounts`, then the `BlacklistEntry` of any blacklisted sender or
+    // recipient owner
+}
+
+/// Pay `amounts[i]` to the i-th remaining account, less the transfer fee.
+/// The whole batch counts as one transfer against the sender's limits.
+pub fn batch_transfer_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BatchTransfer<'info>>,
+    amounts: Vec<u64>,
+    mode: BatchMode,
+) -> Result<()> {
+    require!(
+        !amounts.is_empty()
+            && amounts.len() <= ctx.remaining_accounts.len()
+            && amounts.len() <= MAX_BATCH_SIZE,
+        ErrorCode::InvalidBatch
+    );
+    let (recipients, entries) = ctx.remaining_accounts.split_at(amounts.len());
+
+    let clock = Clock::get()?;
+    let sender = ctx.accounts.sender.key();
+    require!(
+        !ctx.accounts
+            .blacklist
+            .is_blocked(&sender, entries, &clock)?,
+        ErrorCode::SignerBlacklisted
+    );
+
+    let mint_key = ctx.accounts.mint.key();
+    let mut payouts = Vec::with_capacity(recipients.len());
+    for (recipient, &amount) in recipients.iter().zip(&amounts) {
+        match check_recipient(
+            recipient,
+            &mint_key,
+            &ctx.accounts.blacklist,
+            entries,
+            &clock,
+        ) {
+            Ok(owner) if amount > 0 => payouts.push((recipient, owner, amount)),
+            Ok(_) => require!(mode == BatchMode::SkipInvalid, ErrorCode::InvalidBatch),
+            Err(error) if mode == BatchMode::Atomic => return Err(error),
+            Err(_) => {}
+        }
+    }
+
+    let total = payouts
+        .iter()
+        .try_fold(0u64, |total, (_, _, amount)| total.checked_add(*amount))
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    if total > 0 {
+        if !ctx.accounts.internal_whitelist.is_whitelisted(&sender) {
+            record_transfer(
+                &ctx.accounts.transfer_tiers,
+                &ctx.accounts.transfer_limits,
+                total,
+            )?;
+        }
+
+        let cpi_accounts = Burn {
+            mint: ctx.accounts.mint.to_account_info(),
+            from: ctx.accounts.sender_token_account.to_account_info(),
+            authority: ctx.accounts.sender.to_account_info(),
+        };
+        token_interface::burn(
+            CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts),
+            total,
+        )?;
+        ctx.accounts.sender_token_account.reload()?;
+        record_yield_outflow(
+            &ctx.accounts.yield_position,
+            ctx.accounts.sender_token_account.amount,
+        )?;
+    }
+
+    let seeds = &[
+        MINT_AUTHORITY_SEED,
+        mint_key.as_ref(),
+        &[ctx.accounts.mint_authority.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let fee_config = load_fee_config(&ctx.accounts.fee_config)?;
+    let mut total_fee = 0u64;
+    let mut leaves = Vec::with_capacity(payouts.len());
+    for (recipient, owner, amount) in &payouts {
+        let fee = burn_and_mint_fee(
+            &ctx.accounts.mint.to_account_info(),
+            fee_config.as_ref(),
+            &ctx.accounts.internal_whitelist,
+            &ctx.accounts.sender_token_account.owner,
+            owner,
+            *amount,
+        )?;
+        total_fee = total_fee.checked_add(fee).ok_or(ErrorCode::MathOverflow)?;
+
+        let cpi_accounts = MintTo {
+            mint: ctx.accounts.mint.to_account_info(),
+            to: (*recipient).clone(),
+            authority: ctx.accounts.mint_authority.to_account_info(),
+        };
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds,
+            ),
+            amount - fee,
+        )?;
+
+        leaves.push(payout_leaf(recipient.key, *amount));
+    }
+
+    collect_fee(
+        total_fee,
+        fee_config.as_ref(),
+        &ctx.accounts.fee_treasury,
+        &ctx.accounts.mint.to_account_info(),
+        &ctx.accounts.mint_authority.to_account_info(),
+        &ctx.accounts.token_program.to_account_info(),
+        signer_seeds,
+    )?;
+
+    emit!(BatchTransferEvent {
+        mint: mint_key,
+        sender,
+        paid: payouts.len() as u16,
+        skipped: (amounts.len() - payouts.len()) as u16,
+        total_amount: total,
+        total_fee,
+        merkle_root: merkle_root(leaves),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// A recipient must be a thawed token account of this mint whose owner is
+/// not blacklisted, or whose listing has expired. Returns the owner.
+fn check_recipient(
+    recipient: &AccountInfo,
+    mint: &Pubkey,
+    blacklist: &BlackList,
+    entries: &[AccountInfo],
+    clock: &Clock,
+) -> Result<Pubkey> {
+    require_keys_eq!(
+        *recipient.owner,
+        anchor_spl::token_2022::ID,
+        ErrorCode::InvalidTokenAccount
+    );
+    require!(recipient.is_writable, ErrorCode::InvalidTokenAccount);
+
+    let data = recipient.try_borrow_data()?;
+    let account = StateWithExtensions::<SplAccount>::unpack(&data)?;
+    require_keys_eq!(account.base.mint, *mint, ErrorCode::InvalidTokenAccount);
+    require!(
+        account.base.state == AccountState::Initialized,
+        ErrorCode::InvalidTokenAccount
+    );
+    require!(
+        !blacklist.is_blocked(&account.base.owner, entries, clock)?,
+        ErrorCode::ReceiverBlacklisted
+    );
+
+    Ok(account.base.owner)
+}
@@ -0,0 +1,375 @@
+// src/instructions/batch_admin.rs
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::instructions::admin::*;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+// Batch versions of the list updates in admin.rs. They take the same
+// accounts, and one multisig approval covers the whole address list. Each
+// address still gets the event the single instruction emits, all carrying
+// the batch's nonce and approvers. A batch applies in full or not at all.
+
+/// Check the batch size and the owners' approval of `action` over
+/// `addresses`, returning the nonce consumed and the approvers
+fn authorize_batch(
+    multisig: &mut Account<Multisig>,
+    admin: &Pubkey,
+    instructions: &AccountInfo,
+    action: &str,
+    list_account: &Pubkey,
+    addresses: &[Pubkey],
+) -> Result<(u64, u16)> {
+    require!(
+        !addresses.is_empty() && addresses.len() <= MAX_ADMIN_BATCH_SIZE,
+        ErrorCode::InvalidBatch
+    );
+    require_keys_eq!(multisig.key(), *admin, ErrorCode::Unauthorized);
+
+    let message = build_batch_admin_message(action, list_account, addresses, multisig.nonce);
+    let readable_message =
+        build_readable_batch_admin_message(action, list_account, addresses, multisig.nonce);
+
+    let nonce = multisig.nonce;
+    let approvers =
+        validate_multisig_authorization(multisig, instructions, &message, &readable_message)?;
+
+    Ok((nonce, approvers))
+}
+
+// ============================================================================
+// Blacklist
+// ============================================================================
+
+pub fn batch_add_blacklist_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AddBlackList<'info>>,
+    users: Vec<Pubkey>,
+    details: BlacklistDetails,
+) -> Result<()> {
+    require!(
+        !users.is_empty() && users.len() <= MAX_ADMIN_BATCH_SIZE,
+        ErrorCode::InvalidBatch
+    );
+    require!(
+        ctx.remaining_accounts.len() == users.len(),
+        ErrorCode::InvalidBlacklistEntry
+    );
+    details.validate(Clock::get()?.unix_timestamp)?;
+
+    let multisig = &mut ctx.accounts.multisig;
+    require_keys_eq!(
+        multisig.key(),
+        ctx.accounts.token_config.admin,
+        ErrorCode::Unauthorized
+    );
+
+    let list_account = ctx.accounts.blacklist.key();
+    let message =
+        build_batch_add_blacklist_message(&list_account, &users, &details, multisig.nonce);
+    let readable_message =
+        build_readable_batch_add_blacklist_message(&list_account, &users, &details, multisig.nonce);
+
+    let nonce = multisig.nonce;
+    let approvers = validate_multisig_authorization(
+        multisig,
+        &ctx.accounts.instructions,
+        &message,
+        &readable_message,
+    )?;
+
+    for (user, entry) in users.into_iter().zip(ctx.remaining_accounts) {
+        blacklist_user(ctx.accounts, entry, user, &details, nonce, approvers)?;
+    }
+
+    Ok(())
+}
+
+pub fn batch_remove_blacklist_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RemoveBlackList<'info>>,
+    users: Vec<Pubkey>,
+) -> Result<()> {
+    require!(
+        ctx.remaining_accounts.len() == users.len() * 2,
+        ErrorCode::InvalidBlacklistEntry
+    );
+
+    let list_account = ctx.accounts.blacklist.key();
+    let (nonce, approvers) = authorize_batch(
+        &mut ctx.accounts.multisig,
+        &ctx.accounts.token_config.admin,
+        &ctx.accounts.instructions,
+        "BATCH_REMOVE_BLACKLIST",
+        &list_account,
+        &users,
+    )?;
+
+    let blacklist = &mut ctx.accounts.blacklist;
+    let timestamp = Clock::get()?.unix_timestamp;
+    for (user, accounts) in users
+        .into_iter()
+        .zip(ctx.remaining_accounts.chunks_exact(2))
+    {
+        if !blacklist.is_blacklisted(&user) {
+            continue;
+        }
+
+        let [entry, rent_recipient] = accounts else {
+            unreachable!("chunks_exact yields pairs");
+        };
+        blacklist.remove(&user)?;
+        let details = close_blacklist_entry(
+            entry,
+            rent_recipient,
+            &ctx.accounts.token_config.mint,
+            &user,
+        )?;
+
+        emit!(RemovedBlackList {
+            mint: ctx.accounts.token_config.mint,
+            user,
+            details,
+            nonce,
+            approvers,
+            timestamp,
+        });
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// Whitelists
+// ============================================================================
+
+pub fn batch_whitelist_internal_handler(
+    ctx: Context<WhitelistInternalUser>,
+    users: Vec<Pubkey>,
+) -> Result<()> {
+    let list_account = ctx.accounts.internal_whitelist.key();
+    let (nonce, approvers) = authorize_batch(
+        &mut ctx.accounts.multisig,
+        &ctx.accounts.token_config.admin,
+        &ctx.accounts.instructions,
+        "BATCH_WHITELIST_INTERNAL",
+        &list_account,
+        &users,
+    )?;
+
+    let internal_whitelist = &mut ctx.accounts.internal_whitelist;
+    let blacklist = &ctx.accounts.blacklist;
+    let timestamp = Clock::get()?.unix_timestamp;
+    for user in users {
+        if blacklist.is_blacklisted(&user) {
+            return Err(ErrorCode::UserBlacklisted.into());
+        }
+
+        if internal_whitelist.is_whitelisted(&user) {
+            continue;
+        }
+
+        internal_whitelist.add(&user)?;
+
+        emit!(WhitelistedInternalUser {
+            mint: ctx.accounts.token_config.mint,
+            user,
+            nonce,
+            approvers,
+            timestamp,
+        });
+    }
+
+    Ok(())
+}
+
+pub fn batch_whitelist_external_handler(
+    ctx: Context<WhitelistExternalUser>,
+    users: Vec<Pubkey>,
+) -> Result<()> {
+    let list_account = ctx.accounts.external_whitelist.key();
+    let (nonce, approvers) = authorize_batch(
+        &mut ctx.accounts.multisig,
+        &ctx.accounts.token_config.admin,
+        &ctx.accounts.instructions,
+        "BATCH_WHITELIST_EXTERNAL",
+        &list_account,
+        &users,
+    )?;
+
+    let external_whitelist = &mut ctx.accounts.external_whitelist;
+    let blacklist = &ctx.accounts.blacklist;
+    let timestamp = Clock::get()?.unix_timestamp;
+    for user in users {
+        if blacklist.is_blacklisted(&user) {
+            return Err(ErrorCode::UserBlacklisted.into());
+        }
+
+        if external_whitelist.is_whitelisted(&user) {
+            continue;
+        }
+
+        external_whitelist.add(&user)?;
+
+        emit!(WhitelistedExternalSender {
+            mint: ctx.accounts.token_config.mint,
+            user,
+            nonce,
+            approvers,
+            timestamp,
+        });
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// Forwarders
+// ============================================================================
+
+pub fn batch_add_can_forward_handler(
+    ctx: Context<AddCanForward>,
+    forwarders: Vec<Pubkey>,
+) -> Result<()> {
+    let list_account = ctx.accounts.can_forward.key();
+    let (nonce, approvers) = authorize_batch(
+        &mut ctx.accounts.multisig,
+        &ctx.accounts.token_config.admin,
+        &ctx.accounts.instructions,
+        "BATCH_ADD_CAN_FORWARD",
+        &list_account,
+        &forwarders,
+    )?;
+
+    let can_forward = &mut ctx.accounts.can_forward;
+    let blacklist = &ctx.accounts.blacklist;
+    let timestamp = Clock::get()?.unix_timestamp;
+    for forwarder in forwarders {
+        if blacklist.is_blacklisted(&forwarder) {
+            return Err(ErrorCode::UserBlacklisted.into());
+        }
+
+        if can_forward.is_trusted_forwarder(&forwarder) {
+            continue;
+        }
+
+        can_forward.add(&forwarder)?;
+
+        emit!(WhitelistedForwarder {
+            mint: ctx.accounts.token_config.mint,
+            forwarder,
+            nonce,
+            approvers,
+            timestamp,
+        });
+    }
+
+    Ok(())
+}
+
+pub fn batch_remove_can_forward_handler(
+    ctx: Context<RemoveCanForward>,
+    forwarders: Vec<Pubkey>,
+) -> Result<()> {
+    let list_account = ctx.accounts.can_forward.key();
+    let (nonce, approvers) = authorize_batch(
+        &mut ctx.accounts.multisig,
+        &ctx.accounts.token_config.admin,
+        &ctx.accounts.instructions,
+        "BATCH_REMOVE_CAN_FORWARD",
+        &list_account,
+        &forwarders,
+    )?;
+
+    let can_forward = &mut ctx.accounts.can_forward;
+    let timestamp = Clock::get()?.unix_timestamp;
+    for forwarder in forwarders {
+        if !can_forward.is_trusted_forwarder(&forwarder) {
+            continue;
+        }
+
+        can_forward.remove(&forwarder)?;
+
+        emit!(BlackListedForwarder {
+            mint: ctx.accounts.token_config.mint,
+            forwarder,
+            nonce,
+            approvers,
+            timestamp,
+        });
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// Trusted Contracts
+// ============================================================================
+
+pub fn batch_add_trusted_contract_handler(
+    ctx: Context<AddTrustedContract>,
+    contracts: Vec<Pubkey>,
+) -> Result<()> {
+    let list_account = ctx.accounts.trusted_contracts.key();
+    let (nonce, approvers) = authorize_batch(
+        &mut ctx.accounts.multisig,
+        &ctx.accounts.token_config.admin,
+        &ctx.accounts.instructions,
+        "BATCH_ADD_TRUSTED_CONTRACT",
+        &list_account,
+        &contracts,
+    )?;
+
+    let trusted_contracts = &mut ctx.accounts.trusted_contracts;
+    let timestamp = Clock::get()?.unix_timestamp;
+    for contract in contracts {
+        if trusted_contracts.is_trusted_contract(&contract) {
+            continue;
+        }
+
+        trusted_contracts.add(&contract)?;
+
+        emit!(WhitelistedContract {
+            mint: ctx.accounts.token_config.mint,
+            contract,
+            nonce,
+            approvers,
+            timestamp,
+        });
+    }
+
+    Ok(())
+}
+
+pub fn batch_remove_trusted_contract_handler(
+    ctx: Context<RemoveTrustedContract>,
+    contracts: Vec<Pubkey>,
+) -> Result<()> {
+    let list_account = ctx.accounts.trusted_contracts.key();
+    let (nonce, approvers) = authorize_batch(
+        &mut ctx.accounts.multisig,
+        &ctx.accounts.token_config.admin,
+        &ctx.accounts.instructions,
+        "BATCH_REMOVE_TRUSTED_CONTRACT",
+        &list_account,
+        &contracts,
+    )?;
+
+    let trusted_contracts = &mut ctx.accounts.trusted_contracts;
+    let timestamp = Clock::get()?.unix_timestamp;
+    for contract in contracts {
+        if !trusted_contracts.is_trusted_contract(&contract) {
+            continue;
+        }
+
+        trusted_contracts.remove(&contract)?;
+
+        emit!(BlackListedContract {
+            mint: ctx.accounts.token_config.mint,
+            contract,
+            nonce,
+            approvers,
+            timestamp,
+        });
+    }
+
+    Ok(())
+}
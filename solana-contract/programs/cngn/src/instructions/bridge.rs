@@ -0,0 +1,438 @@
+// src/instructions/bridge.rs
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::instructions::yield_distribution::record_yield_outflow;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Burn, Mint, MintTo, TokenAccount, TokenInterface};
+use cngn_messages::{chain, from_canonical, to_canonical, TransferMessage};
+
+// ============================================================================
+// Initialize Bridge
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct InitializeBridge<'info> {
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+
+    #[account(
+        constraint = mint.key() == token_config.mint @ ErrorCode::MintMismatch,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        bump = token_config.bump,
+        constraint = token_config.is_setup_complete() @ ErrorCode::SetupIncomplete,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        init,
+        payer = initializer,
+        space = BridgeConfig::LEN,
+        seeds = [BRIDGE_CONFIG_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub bridge_config: Account<'info, BridgeConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Create the bridge config paused and without attesters; the multisig
+/// enables it with `update_bridge`.
+pub fn initialize_bridge_handler(ctx: Context<InitializeBridge>) -> Result<()> {
+    let bridge_config = &mut ctx.accounts.bridge_config;
+    bridge_config.mint = ctx.accounts.mint.key();
+    bridge_config.attesters = Vec::new();
+    bridge_config.threshold = 0;
+    bridge_config.paused = true;
+    bridge_config.outbound_nonce = 0;
+    bridge_config.bump = ctx.bumps.bridge_config;
+
+    Ok(())
+}
+
+// ============================================================================
+// Update Bridge (with Multisig)
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct UpdateBridge<'info> {
+    #[account(
+        constraint = mint.key() == token_config.mint @ ErrorCode::MintMismatch,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        bump = token_config.bump,
+        constraint = token_config.is_setup_complete() @ ErrorCode::SetupIncomplete,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        mut,
+        seeds = [BRIDGE_CONFIG_SEED, mint.key().as_ref()],
+        bump = bridge_config.bump,
+    )]
+    pub bridge_config: Account<'info, BridgeConfig>,
+
+    #[account(
+        mut,
+        seeds = [Multisig::MULTISIG_SEED, mint.key().as_ref()],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    /// CHECK: This is the instructions sysvar
+    #[account(address = solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+}
+
+pub fn update_bridge_handler(
+    ctx: Context<UpdateBridge>,
+    attesters: Vec<Pubkey>,
+    threshold: u8,
+    paused: bool,
+) -> Result<()> {
+    let multisig = &mut ctx.accounts.multisig;
+
+    require_keys_eq!(
+        multisig.key(),
+        ctx.accounts.token_config.admin,
+        ErrorCode::Unauthorized
+    );
+
+    let message = build_update_bridge_message(
+        &ctx.accounts.bridge_config.key(),
+        &attesters,
+        threshold,
+        paused,
+        multisig.nonce,
+    );
+    let readable_message = build_readable_update_bridge_message(
+        &ctx.accounts.bridge_config.key(),
+        &attesters,
+        threshold,
+        paused,
+        multisig.nonce,
+    );
+
+    let nonce = multisig.nonce;
+    let approvers = validate_multisig_authorization(
+        multisig,
+        &ctx.accounts.instructions,
+        &message,
+        &readable_message,
+    )?;
+
+    let bridge_config = &mut ctx.accounts.bridge_config;
+    bridge_config.set_attesters(attesters, threshold)?;
+    bridge_config.paused = paused;
+
+    emit!(BridgeUpdatedEvent {
+        mint: bridge_config.mint,
+        attesters: bridge_config.attesters.clone(),
+        threshold,
+        paused,
+        nonce,
+        approvers,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// ============================================================================
+// Bridge In
+// ============================================================================
+
+#[derive(Accounts)]
+#[instruction(source_chain: u32, nonce: u64)]
+pub struct BridgeIn<'info> {
+    /// Relayer submitting the attestations; pays for the processed marker
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        bump = token_config.bump,
+        constraint = !token_config.mint_paused @ ErrorCode::MintingPaused,
+        constraint = token_config.is_setup_complete() @ ErrorCode::SetupIncomplete,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        seeds = [BRIDGE_CONFIG_SEED, mint.key().as_ref()],
+        bump = bridge_config.bump,
+        constraint = !bridge_config.paused @ ErrorCode::BridgePaused,
+    )]
+    pub bridge_config: Account<'info, BridgeConfig>,
+
+    #[account(
+        mut,
+        constraint = mint.key() == token_config.mint @ ErrorCode::MintMismatch,
+        constraint = mint.decimals == token_config.decimals @ ErrorCode::DecimalsMismatch,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, mint.key().as_ref()],
+        bump = mint_authority.bump,
+    )]
+    pub mint_authority: Account<'info, MintAuthority>,
+
+    #[account(
+        mut,
+        constraint = token_account.mint == mint.key() @ ErrorCode::MintMismatch,
+    )]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [BLACK_LIST_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub blacklist: Account<'info, BlackList>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = ProcessedBridgeMessage::LEN,
+        seeds = [
+            BRIDGE_MESSAGE_SEED,
+            mint.key().as_ref(),
+            &source_chain.to_le_bytes(),
+            &nonce.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub processed_message: Account<'info, ProcessedBridgeMessage>,
+
+    /// CHECK: This is the instructions sysvar
+    #[account(address = solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    // Remaining accounts: the recipient's `BlacklistEntry` if they are
+    // blacklisted, so an expired listing no longer holds up the transfer
+}
+
+/// Mint an inbound transfer from `source_chain` to the owner of
+/// `token_account`, once enough attesters have signed its `cngn-messages`
+/// encoding. `amount` is the canonical wire amount. The processed marker PDA
+/// makes each (source chain, nonce) redeemable once.
+pub fn bridge_in_handler(
+    ctx: Context<BridgeIn>,
+    source_chain: u32,
+    nonce: u64,
+    sender: [u8; 32],
+    amount: u128,
+) -> Result<()> {
+    let recipient = ctx.accounts.token_account.owner;
+    let bridge_config = &ctx.accounts.bridge_config;
+
+    require!(source_chain != chain::SOLANA, ErrorCode::InvalidBridgeChain);
+    let local_amount = from_canonical(amount, ctx.accounts.token_config.decimals)
+        .map_err(|_| error!(ErrorCode::InvalidBridgeAmount))?;
+    require!(local_amount > 0, ErrorCode::InvalidMintAmount);
+    require!(
+        !ctx.accounts
+            .blacklist
+            .is_blocked(&recipient, ctx.remaining_accounts, &Clock::get()?)?,
+        ErrorCode::ReceiverBlacklisted
+    );
+
+    let transfer = TransferMessage {
+        source_chain,
+        destination_chain: chain::SOLANA,
+        nonce,
+        sender,
+        recipient: recipient.to_bytes(),
+        amount,
+    };
+    let message = build_bridge_in_message(&bridge_config.key(), &transfer);
+    let attesters = collect_ed25519_approvals(
+        &bridge_config.attesters,
+        bridge_config.threshold,
+        &ctx.accounts.instructions,
+        &[&message],
+    )?;
+    // A zero threshold means no attesters are configured
+    require!(
+        bridge_config.threshold > 0 && attesters.count_ones() >= bridge_config.threshold as u32,
+        ErrorCode::NotEnoughAttestations
+    );
+
+    let mint_key = ctx.accounts.mint.key();
+    let seeds = &[
+        MINT_AUTHORITY_SEED,
+        mint_key.as_ref(),
+        &[ctx.accounts.mint_authority.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_accounts = MintTo {
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.token_account.to_account_info(),
+        authority: ctx.accounts.mint_authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token_interface::mint_to(cpi_ctx, local_amount)?;
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    let processed_message = &mut ctx.accounts.processed_message;
+    processed_message.source_chain = source_chain;
+    processed_message.nonce = nonce;
+    processed_message.sender = sender;
+    processed_message.recipient = recipient;
+    processed_message.amount = local_amount;
+    processed_message.processed_at = timestamp;
+    processed_message.bump = ctx.bumps.processed_message;
+
+    emit!(BridgeInEvent {
+        mint: mint_key,
+        source_chain,
+        nonce,
+        sender,
+        recipient,
+        token_account: ctx.accounts.token_account.key(),
+        amount: local_amount,
+        attesters,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+// ============================================================================
+// Bridge Out
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct BridgeOut<'info> {
+    pub sender: Signer<'info>,
+
+    #[account(
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        bump = token_config.bump,
+        constraint = token_config.is_setup_complete() @ ErrorCode::SetupIncomplete,
+        constraint = !token_config.transfer_paused @ ErrorCode::TransfersPaused,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        mut,
+        seeds = [BRIDGE_CONFIG_SEED, mint.key().as_ref()],
+        bump = bridge_config.bump,
+        constraint = !bridge_config.paused @ ErrorCode::BridgePaused,
+    )]
+    pub bridge_config: Account<'info, BridgeConfig>,
+
+    #[account(
+        mut,
+        constraint = mint.key() == token_config.mint @ ErrorCode::MintMismatch,
+        constraint = mint.decimals == token_config.decimals @ ErrorCode::DecimalsMismatch,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = token_account.mint == mint.key() @ ErrorCode::MintMismatch,
+        constraint = token_account.owner == sender.key() @ ErrorCode::Unauthorized,
+    )]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [BLACK_LIST_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub blacklist: Account<'info, BlackList>,
+
+    /// CHECK: the `YieldPosition` of the sender's token account, which may
+    /// not exist; loaded in the handler
+    #[account(
+        mut,
+        seeds = [YIELD_POSITION_SEED, mint.key().as_ref(), token_account.key().as_ref()],
+        bump,
+    )]
+    pub yield_position: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    // Remaining accounts: the sender's `BlacklistEntry` if they are
+    // blacklisted
+}
+
+/// Burn `amount` from the sender and emit a `BridgeOutEvent` carrying the
+/// `cngn-messages` encoding of the transfer, for the attesters to release it
+/// to `recipient` on `destination_chain`. `recipient` is the destination
+/// address left-padded to 32 bytes.
+pub fn bridge_out_handler(
+    ctx: Context<BridgeOut>,
+    destination_chain: u32,
+    recipient: [u8; 32],
+    amount: u64,
+) -> Result<()> {
+    let sender = ctx.accounts.sender.key();
+
+    require!(amount > 0, ErrorCode::InvalidMintAmount);
+    require!(
+        destination_chain != chain::SOLANA,
+        ErrorCode::InvalidBridgeChain
+    );
+    require!(recipient != [0u8; 32], ErrorCode::InvalidBridgeRecipient);
+    let canonical_amount = to_canonical(amount, ctx.accounts.token_config.decimals)
+        .map_err(|_| error!(ErrorCode::InvalidBridgeAmount))?;
+    require!(
+        !ctx.accounts
+            .blacklist
+            .is_blocked(&sender, ctx.remaining_accounts, &Clock::get()?)?,
+        ErrorCode::SignerBlacklisted
+    );
+
+    let cpi_accounts = Burn {
+        mint: ctx.accounts.mint.to_account_info(),
+        from: ctx.accounts.token_account.to_account_info(),
+        authority: ctx.accounts.sender.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token_interface::burn(cpi_ctx, amount)?;
+
+    ctx.accounts.token_account.reload()?;
+    record_yield_outflow(
+        &ctx.accounts.yield_position,
+        ctx.accounts.token_account.amount,
+    )?;
+
+    let bridge_config = &mut ctx.accounts.bridge_config;
+    let nonce = bridge_config.outbound_nonce;
+    bridge_config.outbound_nonce += 1;
+
+    let transfer = TransferMessage {
+        source_chain: chain::SOLANA,
+        destination_chain,
+        nonce,
+        sender: sender.to_bytes(),
+        recipient,
+        amount: canonical_amount,
+    };
+
+    emit!(BridgeOutEvent {
+        mint: bridge_config.mint,
+        sender,
+        destination_chain,
+        recipient,
+        amount,
+        nonce,
+        message: transfer.encode(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,228 @@
+// src/instructions/confidential.rs
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::token_2022::spl_token_2022::extension::confidential_transfer::instruction::{
+    approve_account, update_mint,
+};
+use anchor_spl::token_2022::spl_token_2022::solana_zk_sdk::encryption::pod::elgamal::PodElGamalPubkey;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+// ============================================================================
+// Update Confidential Auditor (with Multisig)
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct UpdateConfidentialAuditor<'info> {
+    #[account(
+        mut,
+        constraint = mint.key() == token_config.mint @ ErrorCode::MintMismatch,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        bump = token_config.bump,
+        constraint = token_config.is_setup_complete() @ ErrorCode::SetupIncomplete,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, mint.key().as_ref()],
+        bump = mint_authority.bump,
+    )]
+    pub mint_authority: Account<'info, MintAuthority>,
+
+    #[account(
+        mut,
+        seeds = [Multisig::MULTISIG_SEED, mint.key().as_ref()],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    /// CHECK: This is the instructions sysvar
+    #[account(address = solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Set the ElGamal key every confidential transfer amount is also encrypted
+/// to, or clear it with `None`. Accounts are never auto-approved.
+pub fn update_confidential_auditor_handler(
+    ctx: Context<UpdateConfidentialAuditor>,
+    auditor_elgamal_pubkey: Option<[u8; 32]>,
+) -> Result<()> {
+    let multisig = &mut ctx.accounts.multisig;
+
+    require_keys_eq!(
+        multisig.key(),
+        ctx.accounts.token_config.admin,
+        ErrorCode::Unauthorized
+    );
+
+    let mint_key = ctx.accounts.mint.key();
+    let message = build_update_confidential_auditor_message(
+        &mint_key,
+        auditor_elgamal_pubkey,
+        multisig.nonce,
+    );
+    let readable_message = build_readable_update_confidential_auditor_message(
+        &mint_key,
+        auditor_elgamal_pubkey,
+        multisig.nonce,
+    );
+
+    let nonce = multisig.nonce;
+    let approvers = validate_multisig_authorization(
+        multisig,
+        &ctx.accounts.instructions,
+        &message,
+        &readable_message,
+    )?;
+
+    let seeds = &[
+        MINT_AUTHORITY_SEED,
+        mint_key.as_ref(),
+        &[ctx.accounts.mint_authority.bump],
+    ];
+
+    let ix = update_mint(
+        ctx.accounts.token_program.key,
+        &mint_key,
+        &ctx.accounts.mint_authority.key(),
+        &[],
+        false,
+        auditor_elgamal_pubkey.map(PodElGamalPubkey::from),
+    )?;
+    invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.mint.to_account_info(),
+            ctx.accounts.mint_authority.to_account_info(),
+        ],
+        &[&seeds[..]],
+    )?;
+
+    emit!(ConfidentialAuditorUpdatedEvent {
+        mint: mint_key,
+        auditor_elgamal_pubkey,
+        nonce,
+        approvers,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// ============================================================================
+// Approve Confidential Account
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct ApproveConfidentialAccount<'info> {
+    pub operator: Signer<'info>,
+
+    #[account(
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        bump = token_config.bump,
+        constraint = token_config.is_setup_complete() @ ErrorCode::SetupIncomplete,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        constraint = mint.key() == token_config.mint @ ErrorCode::MintMismatch,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, mint.key().as_ref()],
+        bump = mint_authority.bump,
+    )]
+    pub mint_authority: Account<'info, MintAuthority>,
+
+    #[account(
+        seeds = [KYC_OPERATORS_SEED, mint.key().as_ref()],
+        bump = kyc_operators.bump,
+        constraint = kyc_operators.is_operator(&operator.key()) @ ErrorCode::KycOperatorNotAuthorized,
+    )]
+    pub kyc_operators: Account<'info, KycOperators>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+    )]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [BLACK_LIST_SEED, mint.key().as_ref()],
+        bump = blacklist.bump,
+    )]
+    pub blacklist: Account<'info, BlackList>,
+
+    #[account(
+        seeds = [INTERNAL_WHITELIST_SEED, mint.key().as_ref()],
+        bump = internal_whitelist.bump,
+    )]
+    pub internal_whitelist: Account<'info, InternalWhiteList>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    // Remaining accounts: the owner's `BlacklistEntry` if they are
+    // blacklisted
+}
+
+/// Approve a token account the owner has configured for confidential
+/// transfers. Only internal-whitelisted owners that are not blacklisted
+/// qualify.
+pub fn approve_confidential_account_handler(
+    ctx: Context<ApproveConfidentialAccount>,
+) -> Result<()> {
+    let owner = ctx.accounts.token_account.owner;
+
+    require!(
+        !ctx.accounts
+            .blacklist
+            .is_blocked(&owner, ctx.remaining_accounts, &Clock::get()?)?,
+        ErrorCode::UserBlacklisted
+    );
+    require!(
+        ctx.accounts.internal_whitelist.is_whitelisted(&owner),
+        ErrorCode::ConfidentialTransferNotAllowed
+    );
+
+    let mint_key = ctx.accounts.mint.key();
+    let seeds = &[
+        MINT_AUTHORITY_SEED,
+        mint_key.as_ref(),
+        &[ctx.accounts.mint_authority.bump],
+    ];
+
+    let ix = approve_account(
+        ctx.accounts.token_program.key,
+        &ctx.accounts.token_account.key(),
+        &mint_key,
+        &ctx.accounts.mint_authority.key(),
+        &[],
+    )?;
+    invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.token_account.to_account_info(),
+            ctx.accounts.mint.to_account_info(),
+            ctx.accounts.mint_authority.to_account_info(),
+        ],
+        &[&seeds[..]],
+    )?;
+
+    emit!(ConfidentialAccountApprovedEvent {
+        mint: mint_key,
+        owner,
+        token_account: ctx.accounts.token_account.key(),
+        operator: ctx.accounts.operator.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
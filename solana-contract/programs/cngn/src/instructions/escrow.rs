@@ -0,0 +1,529 @@
+// src/instructions/escrow.rs
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::instructions::transfer_fee::{burn_and_mint_fee, collect_fee, load_fee_config};
+use crate::instructions::transfer_hook::record_transfer;
+use crate::instructions::yield_distribution::record_yield_outflow;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{
+    self, harvest_withheld_tokens_to_mint, Burn, CloseAccount, HarvestWithheldTokensToMint, Mint,
+    MintTo, ThawAccount, TokenAccount, TokenInterface,
+};
+
+// A Token-2022 transfer of this mint would call the transfer hook back into
+// this program, which Solana does not allow from inside a CPI. Escrow moves
+// funds by burning them from one account and minting them to the other
+// through the mint-authority PDA instead, so the supply is unchanged. The
+// pause, blacklist and tier-limit checks the hook would make are done here,
+// and the transfer fee is charged when the funds leave the escrow.
+
+// ============================================================================
+// Create Escrow
+// ============================================================================
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct CreateEscrow<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        bump = token_config.bump,
+        constraint = !token_config.transfer_paused @ ErrorCode::TransfersPaused,
+        constraint = token_config.is_setup_complete() @ ErrorCode::SetupIncomplete,
+    )]
+    pub token_config: Box<Account<'info, TokenConfig>>,
+
+    #[account(
+        mut,
+        constraint = mint.key() == token_config.mint @ ErrorCode::MintMismatch,
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, mint.key().as_ref()],
+        bump = mint_authority.bump,
+    )]
+    pub mint_authority: Box<Account<'info, MintAuthority>>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = payer,
+    )]
+    pub payer_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = Escrow::LEN,
+        seeds = [ESCROW_SEED, mint.key().as_ref(), payer.key().as_ref(), &id.to_le_bytes()],
+        bump
+    )]
+    pub escrow: Box<Account<'info, Escrow>>,
+
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [BLACK_LIST_SEED, mint.key().as_ref()],
+        bump = blacklist.bump,
+    )]
+    pub blacklist: Box<Account<'info, BlackList>>,
+
+    #[account(
+        seeds = [INTERNAL_WHITELIST_SEED, mint.key().as_ref()],
+        bump = internal_whitelist.bump,
+    )]
+    pub internal_whitelist: Box<Account<'info, InternalWhiteList>>,
+
+    #[account(
+        seeds = [TRANSFER_TIERS_SEED, mint.key().as_ref()],
+        bump = transfer_tiers.bump,
+    )]
+    pub transfer_tiers: Box<Account<'info, TransferTiers>>,
+
+    /// CHECK: the payer's `TransferLimits`, which may not exist; loaded in
+    /// the handler
+    #[account(
+        mut,
+        seeds = [TRANSFER_LIMITS_SEED, mint.key().as_ref(), payer.key().as_ref()],
+        bump,
+    )]
+    pub transfer_limits: UncheckedAccount<'info>,
+
+    /// CHECK: the `YieldPosition` of the payer's token account, which may
+    /// not exist; loaded in the handler
+    #[account(
+        mut,
+        seeds = [YIELD_POSITION_SEED, mint.key().as_ref(), payer_token_account.key().as_ref()],
+        bump,
+    )]
+    pub yield_position: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    // Remaining accounts: the `BlacklistEntry` of the payer or the payee if
+    // either is blacklisted, so an expired listing no longer blocks them
+}
+
+/// Lock `amount` from the payer's token account until it is released to the
+/// payee or refunded.
+pub fn create_escrow_handler(
+    ctx: Context<CreateEscrow>,
+    id: u64,
+    payee: Pubkey,
+    arbiter: Pubkey,
+    amount: u64,
+    deadline: i64,
+) -> Result<()> {
+    let payer = ctx.accounts.payer.key();
+    let clock = Clock::get()?;
+    let timestamp = clock.unix_timestamp;
+
+    require!(amount > 0, ErrorCode::InvalidEscrow);
+    require!(deadline > timestamp, ErrorCode::InvalidEscrow);
+
+    let blacklist = &ctx.accounts.blacklist;
+    require!(
+        !blacklist.is_blocked(&payer, ctx.remaining_accounts, &clock)?,
+        ErrorCode::SignerBlacklisted
+    );
+    require!(
+        !blacklist.is_blocked(&payee, ctx.remaining_accounts, &clock)?,
+        ErrorCode::ReceiverBlacklisted
+    );
+
+    // Locking counts against the payer's limits like any other transfer
+    if !ctx.accounts.internal_whitelist.is_whitelisted(&payer) {
+        record_transfer(
+            &ctx.accounts.transfer_tiers,
+            &ctx.accounts.transfer_limits,
+            amount,
+        )?;
+    }
+
+    let mint_key = ctx.accounts.mint.key();
+    let seeds = &[
+        MINT_AUTHORITY_SEED,
+        mint_key.as_ref(),
+        &[ctx.accounts.mint_authority.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    // The vault starts frozen like every new token account
+    let cpi_accounts = ThawAccount {
+        account: ctx.accounts.vault.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        authority: ctx.accounts.mint_authority.to_account_info(),
+    };
+    token_interface::thaw_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    ))?;
+
+    let cpi_accounts = Burn {
+        mint: ctx.accounts.mint.to_account_info(),
+        from: ctx.accounts.payer_token_account.to_account_info(),
+        authority: ctx.accounts.payer.to_account_info(),
+    };
+    token_interface::burn(
+        CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts),
+        amount,
+    )?;
+
+    ctx.accounts.payer_token_account.reload()?;
+    record_yield_outflow(
+        &ctx.accounts.yield_position,
+        ctx.accounts.payer_token_account.amount,
+    )?;
+
+    let cpi_accounts = MintTo {
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.vault.to_account_info(),
+        authority: ctx.accounts.mint_authority.to_account_info(),
+    };
+    token_interface::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        ),
+        amount,
+    )?;
+
+    let escrow = &mut ctx.accounts.escrow;
+    escrow.mint = mint_key;
+    escrow.payer = payer;
+    escrow.payee = payee;
+    escrow.arbiter = arbiter;
+    escrow.id = id;
+    escrow.amount = amount;
+    escrow.deadline = deadline;
+    escrow.status = EscrowStatus::Locked;
+    escrow.bump = ctx.bumps.escrow;
+
+    emit!(EscrowCreatedEvent {
+        mint: mint_key,
+        escrow: escrow.key(),
+        payer,
+        payee,
+        arbiter,
+        amount,
+        deadline,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+// ============================================================================
+// Dispute Escrow
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct DisputeEscrow<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, escrow.mint.as_ref(), escrow.payer.as_ref(), &escrow.id.to_le_bytes()],
+        bump = escrow.bump,
+        constraint = escrow.status == EscrowStatus::Locked @ ErrorCode::EscrowNotOpen,
+    )]
+    pub escrow: Account<'info, Escrow>,
+}
+
+/// Flag a locked escrow as disputed, leaving settlement to the arbiter.
+/// Either party can raise it.
+pub fn dispute_escrow_handler(ctx: Context<DisputeEscrow>) -> Result<()> {
+    let authority = ctx.accounts.authority.key();
+    let escrow = &mut ctx.accounts.escrow;
+
+    require!(
+        authority == escrow.payer || authority == escrow.payee,
+        ErrorCode::EscrowNotAuthorized
+    );
+
+    escrow.status = EscrowStatus::Disputed;
+
+    emit!(EscrowDisputedEvent {
+        mint: escrow.mint,
+        escrow: escrow.key(),
+        disputed_by: authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// ============================================================================
+// Settle Escrow (release or refund)
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct SettleEscrow<'info> {
+    pub authority: Signer<'info>,
+
+    /// CHECK: the escrow's payer, who funded the escrow and vault rent and
+    /// gets it back when they are closed
+    #[account(
+        mut,
+        address = escrow.payer @ ErrorCode::EscrowNotAuthorized,
+    )]
+    pub payer: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        bump = token_config.bump,
+        constraint = !token_config.transfer_paused @ ErrorCode::TransfersPaused,
+        constraint = token_config.is_setup_complete() @ ErrorCode::SetupIncomplete,
+    )]
+    pub token_config: Box<Account<'info, TokenConfig>>,
+
+    #[account(
+        mut,
+        constraint = mint.key() == token_config.mint @ ErrorCode::MintMismatch,
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, mint.key().as_ref()],
+        bump = mint_authority.bump,
+    )]
+    pub mint_authority: Box<Account<'info, MintAuthority>>,
+
+    #[account(
+        mut,
+        close = payer,
+        seeds = [ESCROW_SEED, mint.key().as_ref(), escrow.payer.as_ref(), &escrow.id.to_le_bytes()],
+        bump = escrow.bump,
+        constraint = escrow.is_open() @ ErrorCode::EscrowNotOpen,
+    )]
+    pub escrow: Box<Account<'info, Escrow>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Payee's token account on release, payer's on refund
+    #[account(
+        mut,
+        token::mint = mint,
+    )]
+    pub recipient_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [BLACK_LIST_SEED, mint.key().as_ref()],
+        bump = blacklist.bump,
+    )]
+    pub blacklist: Box<Account<'info, BlackList>>,
+
+    #[account(
+        seeds = [INTERNAL_WHITELIST_SEED, mint.key().as_ref()],
+        bump = internal_whitelist.bump,
+    )]
+    pub internal_whitelist: Box<Account<'info, InternalWhiteList>>,
+
+    /// CHECK: the `FeeConfig`, which may not exist; loaded in the handler
+    #[account(
+        seeds = [FEE_CONFIG_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub fee_config: UncheckedAccount<'info>,
+
+    /// CHECK: the fee config's treasury; checked in the handler when a fee
+    /// is charged
+    #[account(mut)]
+    pub fee_treasury: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    // Remaining accounts: the `BlacklistEntry` of a blacklisted signer or
+    // recipient
+}
+
+/// Pay the escrowed funds, less the transfer fee, to the payee and close the
+/// escrow and its vault.
+pub fn release_escrow_handler(ctx: Context<SettleEscrow>) -> Result<()> {
+    let authority = ctx.accounts.authority.key();
+    require!(
+        ctx.accounts.escrow.can_release(&authority),
+        ErrorCode::EscrowNotAuthorized
+    );
+
+    let payee = ctx.accounts.escrow.payee;
+    settle(ctx, payee, EscrowStatus::Released)
+}
+
+/// Return the escrowed funds in full to the payer and close the escrow and
+/// its vault.
+pub fn refund_escrow_handler(ctx: Context<SettleEscrow>) -> Result<()> {
+    let authority = ctx.accounts.authority.key();
+    require!(
+        ctx.accounts
+            .escrow
+            .can_refund(&authority, Clock::get()?.unix_timestamp),
+        ErrorCode::EscrowNotAuthorized
+    );
+
+    let payer = ctx.accounts.escrow.payer;
+    settle(ctx, payer, EscrowStatus::Refunded)
+}
+
+fn settle(ctx: Context<SettleEscrow>, recipient: Pubkey, status: EscrowStatus) -> Result<()> {
+    require_keys_eq!(
+        ctx.accounts.recipient_token_account.owner,
+        recipient,
+        ErrorCode::InvalidTokenAccount
+    );
+
+    let authority = ctx.accounts.authority.key();
+    let clock = Clock::get()?;
+    let blacklist = &ctx.accounts.blacklist;
+    require!(
+        !blacklist.is_blocked(&authority, ctx.remaining_accounts, &clock)?,
+        ErrorCode::SignerBlacklisted
+    );
+    require!(
+        !blacklist.is_blocked(&recipient, ctx.remaining_accounts, &clock)?,
+        ErrorCode::ReceiverBlacklisted
+    );
+
+    let mint_key = ctx.accounts.mint.key();
+    let escrow = &ctx.accounts.escrow;
+    // The whole vault is paid out so it can be closed, including anything
+    // sent to it directly
+    let amount = ctx.accounts.vault.amount;
+    // A refund hands the payer back their own funds, so only a release pays
+    // the transfer fee
+    let charged = status == EscrowStatus::Released;
+    let fee_config = load_fee_config(&ctx.accounts.fee_config)?;
+    let fee = if charged {
+        burn_and_mint_fee(
+            &ctx.accounts.mint.to_account_info(),
+            fee_config.as_ref(),
+            &ctx.accounts.internal_whitelist,
+            &escrow.payer,
+            &recipient,
+            amount,
+        )?
+    } else {
+        0
+    };
+
+    let id = escrow.id.to_le_bytes();
+    let escrow_seeds = &[
+        ESCROW_SEED,
+        mint_key.as_ref(),
+        escrow.payer.as_ref(),
+        id.as_ref(),
+        &[escrow.bump],
+    ];
+    let cpi_accounts = Burn {
+        mint: ctx.accounts.mint.to_account_info(),
+        from: ctx.accounts.vault.to_account_info(),
+        authority: ctx.accounts.escrow.to_account_info(),
+    };
+    token_interface::burn(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            &[&escrow_seeds[..]],
+        ),
+        amount,
+    )?;
+
+    // Fees withheld on transfers into the vault would block closing it
+    let cpi_accounts = HarvestWithheldTokensToMint {
+        token_program_id: ctx.accounts.token_program.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+    };
+    harvest_withheld_tokens_to_mint(
+        CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts),
+        vec![ctx.accounts.vault.to_account_info()],
+    )?;
+
+    let cpi_accounts = CloseAccount {
+        account: ctx.accounts.vault.to_account_info(),
+        destination: ctx.accounts.payer.to_account_info(),
+        authority: ctx.accounts.escrow.to_account_info(),
+    };
+    token_interface::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        &[&escrow_seeds[..]],
+    ))?;
+
+    let mint_authority_seeds = &[
+        MINT_AUTHORITY_SEED,
+        mint_key.as_ref(),
+        &[ctx.accounts.mint_authority.bump],
+    ];
+    let cpi_accounts = MintTo {
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.recipient_token_account.to_account_info(),
+        authority: ctx.accounts.mint_authority.to_account_info(),
+    };
+    token_interface::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            &[&mint_authority_seeds[..]],
+        ),
+        amount - fee,
+    )?;
+
+    if charged {
+        collect_fee(
+            fee,
+            fee_config.as_ref(),
+            &ctx.accounts.fee_treasury,
+            &ctx.accounts.mint.to_account_info(),
+            &ctx.accounts.mint_authority.to_account_info(),
+            &ctx.accounts.token_program.to_account_info(),
+            &[&mint_authority_seeds[..]],
+        )?;
+    }
+
+    let timestamp = clock.unix_timestamp;
+    let event_escrow = ctx.accounts.escrow.key();
+    let to = ctx.accounts.recipient_token_account.key();
+    if status == EscrowStatus::Released {
+        emit!(EscrowReleasedEvent {
+            mint: mint_key,
+            escrow: event_escrow,
+            to,
+            amount,
+            fee,
+            released_by: authority,
+            timestamp,
+        });
+    } else {
+        emit!(EscrowRefundedEvent {
+            mint: mint_key,
+            escrow: event_escrow,
+            to,
+            amount,
+            fee,
+            refunded_by: authority,
+            timestamp,
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,221 @@
+// src/instructions/external_allowlist.rs
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, FreezeAccount, Mint, TokenAccount, TokenInterface};
+
+// ============================================================================
+// Update External Allowlist (with Multisig)
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct UpdateExternalAllowlist<'info> {
+    /// Funds the allowlist the first time
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        constraint = mint.key() == token_config.mint @ ErrorCode::MintMismatch,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        bump = token_config.bump,
+        constraint = token_config.is_setup_complete() @ ErrorCode::SetupIncomplete,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = ExternalAllowlist::LEN,
+        seeds = [EXTERNAL_ALLOWLIST_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub external_allowlist: Account<'info, ExternalAllowlist>,
+
+    #[account(
+        mut,
+        seeds = [Multisig::MULTISIG_SEED, mint.key().as_ref()],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    /// CHECK: This is the instructions sysvar
+    #[account(address = solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Publish a new allowlist root. Proofs against the root it replaces are
+/// accepted for another `grace_period_seconds`; an all-zero root withdraws
+/// the allowlist once the grace period ends.
+pub fn update_external_allowlist_handler(
+    ctx: Context<UpdateExternalAllowlist>,
+    merkle_root: [u8; 32],
+    grace_period_seconds: u32,
+) -> Result<()> {
+    let multisig = &mut ctx.accounts.multisig;
+
+    require_keys_eq!(
+        multisig.key(),
+        ctx.accounts.token_config.admin,
+        ErrorCode::Unauthorized
+    );
+    require!(
+        grace_period_seconds <= ExternalAllowlist::MAX_GRACE_PERIOD_SECONDS,
+        ErrorCode::InvalidAllowlistGracePeriod
+    );
+
+    let external_allowlist_key = ctx.accounts.external_allowlist.key();
+    let message = build_update_external_allowlist_message(
+        &external_allowlist_key,
+        &merkle_root,
+        grace_period_seconds,
+        multisig.nonce,
+    );
+    let readable_message = build_readable_update_external_allowlist_message(
+        &external_allowlist_key,
+        &merkle_root,
+        grace_period_seconds,
+        multisig.nonce,
+    );
+
+    let nonce = multisig.nonce;
+    let approvers = validate_multisig_authorization(
+        multisig,
+        &ctx.accounts.instructions,
+        &message,
+        &readable_message,
+    )?;
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    let external_allowlist = &mut ctx.accounts.external_allowlist;
+    if external_allowlist.mint == Pubkey::default() {
+        external_allowlist.mint = ctx.accounts.mint.key();
+        external_allowlist.bump = ctx.bumps.external_allowlist;
+    }
+    external_allowlist.rotate(merkle_root, grace_period_seconds, timestamp);
+
+    emit!(ExternalAllowlistUpdatedEvent {
+        mint: ctx.accounts.mint.key(),
+        merkle_root,
+        previous_merkle_root: external_allowlist.previous_merkle_root,
+        previous_root_expires_at: external_allowlist.previous_root_expires_at,
+        nonce,
+        approvers,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+// ============================================================================
+// Revoke Allowlist Approval
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct RevokeAllowlistApproval<'info> {
+    #[account(
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        bump = token_config.bump,
+        constraint = token_config.is_setup_complete() @ ErrorCode::SetupIncomplete,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        constraint = mint.key() == token_config.mint @ ErrorCode::MintMismatch,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, mint.key().as_ref()],
+        bump = mint_authority.bump,
+    )]
+    pub mint_authority: Account<'info, MintAuthority>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+    )]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [ALLOWLIST_APPROVAL_SEED, mint.key().as_ref(), token_account.owner.as_ref()],
+        bump = allowlist_approval.bump,
+    )]
+    pub allowlist_approval: Account<'info, AllowlistApproval>,
+
+    #[account(
+        seeds = [EXTERNAL_ALLOWLIST_SEED, mint.key().as_ref()],
+        bump = external_allowlist.bump,
+    )]
+    pub external_allowlist: Account<'info, ExternalAllowlist>,
+
+    #[account(
+        seeds = [INTERNAL_WHITELIST_SEED, mint.key().as_ref()],
+        bump = internal_whitelist.bump,
+    )]
+    pub internal_whitelist: Account<'info, InternalWhiteList>,
+
+    #[account(
+        seeds = [EXTERNAL_WHITELIST_SEED, mint.key().as_ref()],
+        bump = external_whitelist.bump,
+    )]
+    pub external_whitelist: Account<'info, ExternalWhiteList>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Freeze a token account of an owner approved through the allowlist once
+/// the root they proved against is no longer accepted and they are not
+/// whitelisted. Anyone can call it. The owner needs a proof against an
+/// accepted root to be thawed again.
+pub fn revoke_allowlist_approval_handler(ctx: Context<RevokeAllowlistApproval>) -> Result<()> {
+    let owner = ctx.accounts.token_account.owner;
+    let timestamp = Clock::get()?.unix_timestamp;
+    let merkle_root = ctx.accounts.allowlist_approval.merkle_root;
+    require!(
+        !ctx.accounts
+            .external_allowlist
+            .accepts_root(&merkle_root, timestamp)
+            && !ctx.accounts.internal_whitelist.is_whitelisted(&owner)
+            && !ctx.accounts.external_whitelist.is_whitelisted(&owner),
+        ErrorCode::AllowlistApprovalStillValid
+    );
+
+    if !ctx.accounts.token_account.is_frozen() {
+        let mint_key = ctx.accounts.mint.key();
+        let seeds = &[
+            MINT_AUTHORITY_SEED,
+            mint_key.as_ref(),
+            &[ctx.accounts.mint_authority.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_accounts = FreezeAccount {
+            account: ctx.accounts.token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            authority: ctx.accounts.mint_authority.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token_interface::freeze_account(cpi_ctx)?;
+    }
+
+    emit!(AllowlistApprovalRevokedEvent {
+        mint: ctx.accounts.mint.key(),
+        owner,
+        token_account: ctx.accounts.token_account.key(),
+        merkle_root,
+        timestamp,
+    });
+
+    Ok(())
+}
@@ -3,31 +3,41 @@ use crate::errors::ErrorCode;
 use crate::events::*;
 use crate::state::*;
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface::{Mint, };
+use anchor_lang::solana_program::program::invoke;
+use anchor_lang::system_program::{self, CreateAccount};
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    confidential_transfer, confidential_transfer_fee, ExtensionType,
+};
+use anchor_spl::token_2022::spl_token_2022::solana_zk_sdk::encryption::pod::elgamal::PodElGamalPubkey;
+use anchor_spl::token_2022::spl_token_2022::state::{AccountState, Mint as SplMint};
+use anchor_spl::token_2022::{self, InitializeMint2};
+use anchor_spl::token_interface::{
+    default_account_state_initialize, metadata_pointer_initialize, permanent_delegate_initialize,
+    transfer_fee_initialize, transfer_hook_initialize, DefaultAccountStateInitialize, Mint,
+    MetadataPointerInitialize, PermanentDelegateInitialize, TransferFeeInitialize,
+    TransferHookInitialize,
+};
 
 use spl_tlv_account_resolution::{
     account::ExtraAccountMeta, seeds::Seed, state::ExtraAccountMetaList,
 };
 use spl_transfer_hook_interface::instruction::ExecuteInstruction;
-use anchor_spl::{
-    associated_token::{
-        spl_associated_token_account::instruction::*,
-      
-    },
-    token_2022::{
-        
-        Token2022,
-    },
-
-};
+use anchor_spl::token_2022::Token2022;
 // Split the accounts into multiple contexts to reduce stack usage
 #[derive(Accounts)]
-#[instruction(name: String, symbol: String,uri: String, decimals: u8)]
+#[instruction(name: String, symbol: String,uri: String, decimals: u8, fee_withdraw_elgamal_pubkey: [u8; 32])]
 pub struct Initialize<'info> {
     #[account(mut)]
     pub initializer: Signer<'info>,
-    /// CHECK: extra metas account
-    #[account(mut)]
+    /// CHECK: admin until `initialize_multisig` runs; either the initializer
+    /// or this mint's multisig PDA
+    #[account(
+        constraint = admin.key() == initializer.key()
+            || admin.key() == Pubkey::find_program_address(
+                &[Multisig::MULTISIG_SEED, mint.key().as_ref()],
+                &crate::ID,
+            ).0 @ ErrorCode::InvalidAdmin,
+    )]
     pub admin: UncheckedAccount<'info>,
 
     #[account(
@@ -48,20 +58,9 @@ pub struct Initialize<'info> {
     )]
     pub mint_authority: Account<'info, MintAuthority>,
 
-    #[account(
-        mut,
-    //     init,
-    //     payer = initializer,
-    //     mint::decimals = decimals,
-    //     mint::authority = mint_authority.key(),
-    //     mint::freeze_authority = mint_authority.key(),
-    //     extensions::permanent_delegate::delegate = mint_authority.key(),
-    //    // extensions::transfer_hook::authority = mint_authority.key(),
-    //    // extensions::transfer_hook::program_id = crate::id(),
-    //     extensions::metadata_pointer::authority = mint_authority.key(),
-    //     extensions::metadata_pointer::metadata_address = mint.key(),
-    )]
-    pub mint: InterfaceAccount<'info, Mint>,
+    /// New mint keypair; created and configured by `initialize_handler`
+    #[account(mut)]
+    pub mint: Signer<'info>,
 
     #[account(
         init,
@@ -96,6 +95,15 @@ pub struct InitializeSecondary<'info> {
     #[account(mut)]
     pub mint: InterfaceAccount<'info, Mint>,
 
+    #[account(
+        mut,
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        bump = token_config.bump,
+        constraint = token_config.initializer == initializer.key() @ ErrorCode::Unauthorized,
+        constraint = token_config.init_phase == InitPhase::AwaitingSecondary @ ErrorCode::InvalidInitPhase,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
     #[account(
         init,
         payer = initializer,
@@ -126,6 +134,97 @@ pub struct InitializeSecondary<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct MigrateTokenConfig<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: a `TokenConfig` in the layout before setup was tracked, which
+    /// `Account` cannot load; checked and rewritten in the handler
+    #[account(
+        mut,
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub token_config: UncheckedAccount<'info>,
+
+    // `initialize` records its signer as the mint authority's `authority`
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, mint.key().as_ref()],
+        bump = mint_authority.bump,
+    )]
+    pub mint_authority: Account<'info, MintAuthority>,
+
+    /// CHECK: only checked for existence, which shows `initialize_secondary` ran
+    #[account(
+        seeds = [BLACK_LIST_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub blacklist: UncheckedAccount<'info>,
+
+    /// CHECK: only checked for existence, which shows `initialize_third` ran
+    #[account(
+        seeds = [INTERNAL_WHITELIST_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub internal_whitelist: UncheckedAccount<'info>,
+
+    /// CHECK: only checked for existence, which shows `initialize_third` ran
+    #[account(
+        seeds = [EXTERNAL_WHITELIST_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub external_whitelist: UncheckedAccount<'info>,
+
+    /// CHECK: created by `initialize` for fewer metas; grown in the handler
+    #[account(
+        mut,
+        seeds = [META_LIST_ACCOUNT_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub extra_metas_account: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpgradeTransferHook<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        bump = token_config.bump,
+        constraint = token_config.init_phase == InitPhase::AwaitingHookUpgrade @ ErrorCode::InvalidInitPhase,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    /// CHECK: extra metas account, holding the list `initialize_third` wrote
+    /// before the hook took its current accounts
+    #[account(
+        mut,
+        seeds = [META_LIST_ACCOUNT_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub extra_metas_account: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = TransferTiers::LEN,
+        seeds = [TRANSFER_TIERS_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub transfer_tiers: Box<Account<'info, TransferTiers>>,
+
+    pub system_program: Program<'info, System>,
+}
+
 // Third accounts context for the remaining accounts
 #[derive(Accounts)]
 pub struct InitializeThird<'info> {
@@ -146,8 +245,11 @@ pub struct InitializeThird<'info> {
 
     // TokenConfig is needed as a reference in the Meta List
     #[account(
+        mut,
         seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
         bump = token_config.bump,
+        constraint = token_config.initializer == initializer.key() @ ErrorCode::Unauthorized,
+        constraint = token_config.init_phase == InitPhase::AwaitingThird @ ErrorCode::InvalidInitPhase,
     )]
     pub token_config: Account<'info, TokenConfig>,
 
@@ -169,6 +271,15 @@ pub struct InitializeThird<'info> {
     )]
     pub internal_whitelist: Account<'info, InternalWhiteList>,
 
+    #[account(
+        init,
+        payer = initializer,
+        space = TransferTiers::LEN,
+        seeds = [TRANSFER_TIERS_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub transfer_tiers: Box<Account<'info, TransferTiers>>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -179,7 +290,10 @@ pub fn initialize_handler(
     symbol: String,
     uri: String,
     decimals: u8,
+    fee_withdraw_elgamal_pubkey: [u8; 32],
 ) -> Result<()> {
+    create_mint(&ctx, decimals, fee_withdraw_elgamal_pubkey)?;
+
     let token_config = &mut ctx.accounts.token_config;
     let mint = &ctx.accounts.mint;
     let can_mint = &mut ctx.accounts.can_mint;
@@ -194,10 +308,14 @@ pub fn initialize_handler(
     token_config.mint_paused = false;
     token_config.transfer_paused = false;
     token_config.bump = ctx.bumps.token_config;
+    token_config.init_phase = InitPhase::AwaitingSecondary;
+    token_config.initializer = ctx.accounts.initializer.key();
 
     // Initialize mint authority data
     mint_authority.mint = mint.key();
     mint_authority.authority = ctx.accounts.initializer.key();
+    mint_authority.update_authority = mint_authority.key();
+    mint_authority.freeze_authority = mint_authority.key();
     mint_authority.bump = ctx.bumps.mint_authority;
 
     // Initialize can_mint with a single element to reduce memory usage
@@ -213,12 +331,142 @@ pub fn initialize_handler(
         name: name.clone(),
         symbol: symbol.clone(),
         uri: uri.clone(),
-        decimals
+        decimals,
+        timestamp: Clock::get()?.unix_timestamp,
     });
 
     Ok(())
 }
 
+/// Mint extensions set up by `initialize`
+pub const MINT_EXTENSIONS: [ExtensionType; 7] = [
+    ExtensionType::TransferHook,
+    ExtensionType::PermanentDelegate,
+    ExtensionType::MetadataPointer,
+    ExtensionType::DefaultAccountState,
+    ExtensionType::TransferFeeConfig,
+    ExtensionType::ConfidentialTransferMint,
+    ExtensionType::ConfidentialTransferFeeConfig,
+];
+
+// Create the Token-2022 mint with every extension pointing at the mint
+// authority PDA, so no external key ever controls it. Kept out of the
+// handler to limit its stack frame.
+#[inline(never)]
+fn create_mint(
+    ctx: &Context<Initialize>,
+    decimals: u8,
+    fee_withdraw_elgamal_pubkey: [u8; 32],
+) -> Result<()> {
+    let mint = ctx.accounts.mint.to_account_info();
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let authority = ctx.accounts.mint_authority.key();
+
+    let space = ExtensionType::try_calculate_account_len::<SplMint>(&MINT_EXTENSIONS)?;
+    system_program::create_account(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            CreateAccount {
+                from: ctx.accounts.initializer.to_account_info(),
+                to: mint.clone(),
+            },
+        ),
+        Rent::get()?.minimum_balance(space),
+        space as u64,
+        &token_program.key(),
+    )?;
+
+    transfer_hook_initialize(
+        CpiContext::new(
+            token_program.clone(),
+            TransferHookInitialize {
+                token_program_id: token_program.clone(),
+                mint: mint.clone(),
+            },
+        ),
+        Some(authority),
+        Some(crate::ID),
+    )?;
+    permanent_delegate_initialize(
+        CpiContext::new(
+            token_program.clone(),
+            PermanentDelegateInitialize {
+                token_program_id: token_program.clone(),
+                mint: mint.clone(),
+            },
+        ),
+        &authority,
+    )?;
+    metadata_pointer_initialize(
+        CpiContext::new(
+            token_program.clone(),
+            MetadataPointerInitialize {
+                token_program_id: token_program.clone(),
+                mint: mint.clone(),
+            },
+        ),
+        Some(authority),
+        Some(mint.key()),
+    )?;
+    // No fee until the multisig sets one with `set_transfer_fee`
+    transfer_fee_initialize(
+        CpiContext::new(
+            token_program.clone(),
+            TransferFeeInitialize {
+                token_program_id: token_program.clone(),
+                mint: mint.clone(),
+            },
+        ),
+        Some(&authority),
+        Some(&authority),
+        0,
+        0,
+    )?;
+    // Confidential accounts need explicit approval and there is no auditor
+    // until the multisig sets one with `update_confidential_auditor`
+    invoke(
+        &confidential_transfer::instruction::initialize_mint(
+            &token_program.key(),
+            &mint.key(),
+            Some(authority),
+            false,
+            None,
+        )?,
+        std::slice::from_ref(&mint),
+    )?;
+    // Token-2022 requires this alongside transfer fees; the ElGamal key
+    // cannot be changed after initialization
+    invoke(
+        &confidential_transfer_fee::instruction::initialize_confidential_transfer_fee_config(
+            &token_program.key(),
+            &mint.key(),
+            Some(authority),
+            &PodElGamalPubkey::from(fee_withdraw_elgamal_pubkey),
+        )?,
+        std::slice::from_ref(&mint),
+    )?;
+    // New token accounts start frozen until a KYC operator thaws them with
+    // `kyc_approve`
+    default_account_state_initialize(
+        CpiContext::new(
+            token_program.clone(),
+            DefaultAccountStateInitialize {
+                token_program_id: token_program.clone(),
+                mint: mint.clone(),
+            },
+        ),
+        &AccountState::Frozen,
+    )?;
+    token_2022::initialize_mint2(
+        CpiContext::new(token_program, InitializeMint2 { mint }),
+        decimals,
+        &authority,
+        Some(&authority),
+    )?;
+
+    Ok(())
+}
+
 // Secondary initialization handler
 pub fn initialize_secondary_handler(ctx: Context<InitializeSecondary>) -> Result<()> {
     let blacklist = &mut ctx.accounts.blacklist;
@@ -240,10 +488,13 @@ pub fn initialize_secondary_handler(ctx: Context<InitializeSecondary>) -> Result
     trusted_contracts.contracts = Vec::new();
     trusted_contracts.bump = ctx.bumps.trusted_contracts;
 
+    ctx.accounts.token_config.init_phase = InitPhase::AwaitingThird;
+
     // Emit secondary initialization event
     emit!(SecondaryInitializedEvent {
         mint: ctx.accounts.mint.key(),
         initializer: ctx.accounts.initializer.key(),
+        timestamp: Clock::get()?.unix_timestamp,
     });
 
     Ok(())
@@ -262,6 +513,33 @@ pub fn initialize_third_handler(ctx: Context<InitializeThird>) -> Result<()> {
     internal_whitelist.whitelist = Vec::new();
     internal_whitelist.bump = ctx.bumps.internal_whitelist;
 
+    // Tiers start without caps until the multisig configures them
+    let transfer_tiers = &mut ctx.accounts.transfer_tiers;
+    transfer_tiers.mint = ctx.accounts.mint.key();
+    transfer_tiers.tiers = [TierLimits::UNLIMITED; TransferTiers::TIER_COUNT];
+    transfer_tiers.bump = ctx.bumps.transfer_tiers;
+
+    let account_metas = extra_account_metas()?;
+
+    // Initialize the ExtraAccountMetaList
+    let extra_meta_account_info = ctx.accounts.extra_metas_account.to_account_info();
+    let mut data = extra_meta_account_info.try_borrow_mut_data()?;
+
+    ExtraAccountMetaList::init::<ExecuteInstruction>(&mut data, &account_metas)?;
+
+    ctx.accounts.token_config.init_phase = InitPhase::Complete;
+
+    emit!(ThirdInitializedEvent {
+        mint: ctx.accounts.mint.key(),
+        initializer: ctx.accounts.initializer.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// The extra accounts the transfer hook reads, in the order it reads them
+fn extra_account_metas() -> Result<Vec<ExtraAccountMeta>> {
     // Build the list of extra accounts using SEEDS (not static pubkeys)
     let account_metas = vec![
         // Account 1: TokenConfig (PDA)
@@ -297,45 +575,280 @@ pub fn initialize_third_handler(ctx: Context<InitializeThird>) -> Result<()> {
             false,
             false,
         )?,
-        // Account 4: External Whitelist (PDA)
+        // Account 4: Transfer Tiers (PDA)
+        ExtraAccountMeta::new_with_seeds(
+            &[
+                Seed::Literal {
+                    bytes: TRANSFER_TIERS_SEED.to_vec(),
+                },
+                Seed::AccountKey { index: 1 },
+            ],
+            false,
+            false,
+        )?,
+        // Account 5: Transfer Limits of the source owner (PDA)
+        ExtraAccountMeta::new_with_seeds(
+            &[
+                Seed::Literal {
+                    bytes: TRANSFER_LIMITS_SEED.to_vec(),
+                },
+                Seed::AccountKey { index: 1 },
+                // owner field of the source token account
+                Seed::AccountData {
+                    account_index: 0,
+                    data_index: 32,
+                    length: 32,
+                },
+            ],
+            false,
+            true, // writable: the hook records usage
+        )?,
+        // Account 6: Yield Position of the source token account (PDA)
+        ExtraAccountMeta::new_with_seeds(
+            &[
+                Seed::Literal {
+                    bytes: YIELD_POSITION_SEED.to_vec(),
+                },
+                Seed::AccountKey { index: 1 },
+                Seed::AccountKey { index: 0 }, // source token account
+            ],
+            false,
+            true, // writable: the hook tracks the lowest balance
+        )?,
+        // Account 7: Fee Config (PDA)
         ExtraAccountMeta::new_with_seeds(
             &[
                 Seed::Literal {
-                    bytes: EXTERNAL_WHITELIST_SEED.to_vec(),
+                    bytes: FEE_CONFIG_SEED.to_vec(),
                 },
                 Seed::AccountKey { index: 1 },
             ],
             false,
             false,
         )?,
-        // Account 5: Can Forward (PDA)
+        // Account 8: Fee Refund of the source token account (PDA)
         ExtraAccountMeta::new_with_seeds(
             &[
                 Seed::Literal {
-                    bytes: CAN_FORWARD_SEED.to_vec(),
+                    bytes: FEE_REFUND_SEED.to_vec(),
                 },
                 Seed::AccountKey { index: 1 },
+                Seed::AccountKey { index: 0 }, // source token account
+            ],
+            false,
+            true, // writable: the hook credits exempt fees
+        )?,
+        // Account 9: Fee Refund of the destination token account (PDA)
+        ExtraAccountMeta::new_with_seeds(
+            &[
+                Seed::Literal {
+                    bytes: FEE_REFUND_SEED.to_vec(),
+                },
+                Seed::AccountKey { index: 1 },
+                Seed::AccountKey { index: 2 }, // destination token account
+            ],
+            false,
+            true,
+        )?,
+        // Account 10: Blacklist Entry of the authority (PDA)
+        ExtraAccountMeta::new_with_seeds(
+            &[
+                Seed::Literal {
+                    bytes: BLACKLIST_ENTRY_SEED.to_vec(),
+                },
+                Seed::AccountKey { index: 1 },
+                Seed::AccountKey { index: 3 }, // owner or delegate
+            ],
+            false,
+            false,
+        )?,
+        // Account 11: Blacklist Entry of the source owner (PDA)
+        ExtraAccountMeta::new_with_seeds(
+            &[
+                Seed::Literal {
+                    bytes: BLACKLIST_ENTRY_SEED.to_vec(),
+                },
+                Seed::AccountKey { index: 1 },
+                Seed::AccountData {
+                    account_index: 0,
+                    data_index: 32,
+                    length: 32,
+                },
+            ],
+            false,
+            false,
+        )?,
+        // Account 12: Blacklist Entry of the destination owner (PDA)
+        ExtraAccountMeta::new_with_seeds(
+            &[
+                Seed::Literal {
+                    bytes: BLACKLIST_ENTRY_SEED.to_vec(),
+                },
+                Seed::AccountKey { index: 1 },
+                Seed::AccountData {
+                    account_index: 2,
+                    data_index: 32,
+                    length: 32,
+                },
             ],
             false,
             false,
         )?,
     ];
 
-    // Initialize the ExtraAccountMetaList
+    require_eq!(
+        account_metas.len(),
+        EXTRA_ACCOUNT_META_COUNT,
+        ErrorCode::InvalidExtraAccountMetas
+    );
+
+    Ok(account_metas)
+}
+
+/// Grow a `TokenConfig` created before setup was tracked to the current
+/// layout, and the extra-metas account to the size of the current list. The
+/// phase is inferred from the accounts the later phases create and the
+/// initializer from the mint authority, so anyone can run it. A mint whose
+/// `initialize_third` already ran still has the old list and no transfer
+/// tiers; it waits for `upgrade_transfer_hook`.
+pub fn migrate_token_config_handler(ctx: Context<MigrateTokenConfig>) -> Result<()> {
+    let token_config_info = ctx.accounts.token_config.to_account_info();
+    require_keys_eq!(
+        *token_config_info.owner,
+        crate::ID,
+        anchor_lang::error::ErrorCode::AccountOwnedByWrongProgram
+    );
+
+    let legacy = {
+        let data = token_config_info.try_borrow_data()?;
+        require!(
+            data.len() < TokenConfig::LEN,
+            ErrorCode::TokenConfigAlreadyMigrated
+        );
+        require!(
+            data.starts_with(TokenConfig::DISCRIMINATOR),
+            anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+        );
+        LegacyTokenConfig::deserialize(&mut &data[8..])?
+    };
+
+    let init_phase = if ctx.accounts.internal_whitelist.owner == &crate::ID
+        && ctx.accounts.external_whitelist.owner == &crate::ID
+    {
+        InitPhase::AwaitingHookUpgrade
+    } else if ctx.accounts.blacklist.owner == &crate::ID {
+        InitPhase::AwaitingThird
+    } else {
+        InitPhase::AwaitingSecondary
+    };
+    let initializer = ctx.accounts.mint_authority.authority;
+
+    let token_config = TokenConfig {
+        name: legacy.name,
+        symbol: legacy.symbol,
+        decimals: legacy.decimals,
+        mint: legacy.mint,
+        admin: legacy.admin,
+        mint_paused: legacy.mint_paused,
+        transfer_paused: legacy.transfer_paused,
+        bump: legacy.bump,
+        init_phase,
+        initializer,
+    };
+
+    let payer = ctx.accounts.payer.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    grow_account(
+        &token_config_info,
+        TokenConfig::LEN,
+        &payer,
+        &system_program,
+    )?;
+    {
+        let mut data = token_config_info.try_borrow_mut_data()?;
+        token_config.try_serialize(&mut &mut data[..])?;
+    }
+
+    let extra_metas_info = ctx.accounts.extra_metas_account.to_account_info();
+    require_keys_eq!(
+        *extra_metas_info.owner,
+        crate::ID,
+        anchor_lang::error::ErrorCode::AccountOwnedByWrongProgram
+    );
+    grow_account(
+        &extra_metas_info,
+        get_meta_list_size()?,
+        &payer,
+        &system_program,
+    )?;
+
+    emit!(TokenConfigMigratedEvent {
+        mint: ctx.accounts.mint.key(),
+        init_phase,
+        initializer,
+        payer: ctx.accounts.payer.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Write the current extra-metas list over the one a migrated mint's
+/// `initialize_third` wrote, create its transfer tiers and complete its
+/// setup. Anyone can run it; the caller pays for the tiers.
+pub fn upgrade_transfer_hook_handler(ctx: Context<UpgradeTransferHook>) -> Result<()> {
+    let transfer_tiers = &mut ctx.accounts.transfer_tiers;
+    transfer_tiers.mint = ctx.accounts.mint.key();
+    transfer_tiers.tiers = [TierLimits::UNLIMITED; TransferTiers::TIER_COUNT];
+    transfer_tiers.bump = ctx.bumps.transfer_tiers;
+
+    let account_metas = extra_account_metas()?;
+
+    // `migrate_token_config` already grew the account to the current size
     let extra_meta_account_info = ctx.accounts.extra_metas_account.to_account_info();
     let mut data = extra_meta_account_info.try_borrow_mut_data()?;
 
-    ExtraAccountMetaList::init::<ExecuteInstruction>(&mut data, &account_metas)?;
+    ExtraAccountMetaList::update::<ExecuteInstruction>(&mut data, &account_metas)?;
 
-    emit!(ThirdInitializedEvent {
+    ctx.accounts.token_config.init_phase = InitPhase::Complete;
+
+    emit!(TransferHookUpgradedEvent {
         mint: ctx.accounts.mint.key(),
-        initializer: ctx.accounts.initializer.key(),
+        payer: ctx.accounts.payer.key(),
+        timestamp: Clock::get()?.unix_timestamp,
     });
 
     Ok(())
 }
 
+/// Resize `account` to `len`, with `payer` topping up its rent
+fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    len: usize,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let required = Rent::get()?
+        .minimum_balance(len)
+        .saturating_sub(account.lamports());
+    if required > 0 {
+        let cpi_accounts = system_program::Transfer {
+            from: payer.clone(),
+            to: account.clone(),
+        };
+        let cpi_ctx = CpiContext::new(system_program.clone(), cpi_accounts);
+        system_program::transfer(cpi_ctx, required)?;
+    }
+
+    account.resize(len)?;
+
+    Ok(())
+}
+
+/// Number of extra accounts `initialize_third` registers for the hook
+pub const EXTRA_ACCOUNT_META_COUNT: usize = 12;
+
 pub fn get_meta_list_size() -> Result<usize> {
-    // We have 5 extra accounts, so the size is 5
-    Ok(ExtraAccountMetaList::size_of(5).unwrap())
+    ExtraAccountMetaList::size_of(EXTRA_ACCOUNT_META_COUNT)
+        .map_err(|_| error!(ErrorCode::InvalidExtraAccountMetas))
 }
@@ -0,0 +1,470 @@
+// src/instructions/kyc.rs
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, ThawAccount, TokenAccount, TokenInterface};
+
+// ============================================================================
+// Initialize KYC
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct InitializeKyc<'info> {
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+
+    #[account(
+        constraint = mint.key() == token_config.mint @ ErrorCode::MintMismatch,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        bump = token_config.bump,
+        constraint = token_config.is_setup_complete() @ ErrorCode::SetupIncomplete,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        init,
+        payer = initializer,
+        space = KycOperators::LEN,
+        seeds = [KYC_OPERATORS_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub kyc_operators: Account<'info, KycOperators>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Create an empty KYC operator list; the multisig fills it with
+/// `add_kyc_operator`.
+pub fn initialize_kyc_handler(ctx: Context<InitializeKyc>) -> Result<()> {
+    let kyc_operators = &mut ctx.accounts.kyc_operators;
+    kyc_operators.mint = ctx.accounts.mint.key();
+    kyc_operators.operators = Vec::new();
+    kyc_operators.bump = ctx.bumps.kyc_operators;
+
+    Ok(())
+}
+
+// ============================================================================
+// Add KYC Operator (with Multisig)
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct AddKycOperator<'info> {
+    #[account(
+        constraint = mint.key() == token_config.mint @ ErrorCode::MintMismatch,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        bump = token_config.bump,
+        constraint = token_config.is_setup_complete() @ ErrorCode::SetupIncomplete,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        seeds = [BLACK_LIST_SEED, mint.key().as_ref()],
+        bump = blacklist.bump,
+    )]
+    pub blacklist: Account<'info, BlackList>,
+
+    #[account(
+        mut,
+        seeds = [KYC_OPERATORS_SEED, mint.key().as_ref()],
+        bump = kyc_operators.bump,
+    )]
+    pub kyc_operators: Account<'info, KycOperators>,
+
+    #[account(
+        mut,
+        seeds = [Multisig::MULTISIG_SEED, mint.key().as_ref()],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    /// CHECK: This is the instructions sysvar
+    #[account(address = solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+}
+
+pub fn add_kyc_operator_handler(ctx: Context<AddKycOperator>, operator: Pubkey) -> Result<()> {
+    let multisig = &mut ctx.accounts.multisig;
+
+    require_keys_eq!(
+        multisig.key(),
+        ctx.accounts.token_config.admin,
+        ErrorCode::Unauthorized
+    );
+
+    let message = build_add_kyc_operator_message(
+        &ctx.accounts.kyc_operators.key(),
+        &operator,
+        multisig.nonce,
+    );
+    let readable_message = build_readable_target_message(
+        "ADD_KYC_OPERATOR",
+        &ctx.accounts.kyc_operators.key(),
+        &operator,
+        multisig.nonce,
+    );
+
+    let nonce = multisig.nonce;
+    let approvers = validate_multisig_authorization(
+        multisig,
+        &ctx.accounts.instructions,
+        &message,
+        &readable_message,
+    )?;
+
+    require!(
+        !ctx.accounts.blacklist.is_blacklisted(&operator),
+        ErrorCode::UserBlacklisted
+    );
+
+    let kyc_operators = &mut ctx.accounts.kyc_operators;
+    if !kyc_operators.is_operator(&operator) {
+        kyc_operators.add(&operator)?;
+
+        emit!(KycOperatorAddedEvent {
+            mint: kyc_operators.mint,
+            operator,
+            nonce,
+            approvers,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// Remove KYC Operator (with Multisig)
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct RemoveKycOperator<'info> {
+    #[account(
+        constraint = mint.key() == token_config.mint @ ErrorCode::MintMismatch,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        bump = token_config.bump,
+        constraint = token_config.is_setup_complete() @ ErrorCode::SetupIncomplete,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        mut,
+        seeds = [KYC_OPERATORS_SEED, mint.key().as_ref()],
+        bump = kyc_operators.bump,
+    )]
+    pub kyc_operators: Account<'info, KycOperators>,
+
+    #[account(
+        mut,
+        seeds = [Multisig::MULTISIG_SEED, mint.key().as_ref()],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    /// CHECK: This is the instructions sysvar
+    #[account(address = solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+}
+
+pub fn remove_kyc_operator_handler(
+    ctx: Context<RemoveKycOperator>,
+    operator: Pubkey,
+) -> Result<()> {
+    let multisig = &mut ctx.accounts.multisig;
+
+    require_keys_eq!(
+        multisig.key(),
+        ctx.accounts.token_config.admin,
+        ErrorCode::Unauthorized
+    );
+
+    let message = build_remove_kyc_operator_message(
+        &ctx.accounts.kyc_operators.key(),
+        &operator,
+        multisig.nonce,
+    );
+    let readable_message = build_readable_target_message(
+        "REMOVE_KYC_OPERATOR",
+        &ctx.accounts.kyc_operators.key(),
+        &operator,
+        multisig.nonce,
+    );
+
+    let nonce = multisig.nonce;
+    let approvers = validate_multisig_authorization(
+        multisig,
+        &ctx.accounts.instructions,
+        &message,
+        &readable_message,
+    )?;
+
+    let kyc_operators = &mut ctx.accounts.kyc_operators;
+    if kyc_operators.is_operator(&operator) {
+        kyc_operators.remove(&operator);
+
+        emit!(KycOperatorRemovedEvent {
+            mint: kyc_operators.mint,
+            operator,
+            nonce,
+            approvers,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// KYC Approve
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct KycApprove<'info> {
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    #[account(
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        bump = token_config.bump,
+        constraint = token_config.is_setup_complete() @ ErrorCode::SetupIncomplete,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        constraint = mint.key() == token_config.mint @ ErrorCode::MintMismatch,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, mint.key().as_ref()],
+        bump = mint_authority.bump,
+    )]
+    pub mint_authority: Account<'info, MintAuthority>,
+
+    #[account(
+        seeds = [KYC_OPERATORS_SEED, mint.key().as_ref()],
+        bump = kyc_operators.bump,
+        constraint = kyc_operators.is_operator(&operator.key()) @ ErrorCode::KycOperatorNotAuthorized,
+    )]
+    pub kyc_operators: Account<'info, KycOperators>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+    )]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [BLACK_LIST_SEED, mint.key().as_ref()],
+        bump = blacklist.bump,
+    )]
+    pub blacklist: Account<'info, BlackList>,
+
+    #[account(
+        seeds = [INTERNAL_WHITELIST_SEED, mint.key().as_ref()],
+        bump = internal_whitelist.bump,
+    )]
+    pub internal_whitelist: Account<'info, InternalWhiteList>,
+
+    #[account(
+        seeds = [EXTERNAL_WHITELIST_SEED, mint.key().as_ref()],
+        bump = external_whitelist.bump,
+    )]
+    pub external_whitelist: Account<'info, ExternalWhiteList>,
+
+    #[account(
+        init_if_needed,
+        payer = operator,
+        space = KycRecord::LEN,
+        seeds = [KYC_RECORD_SEED, mint.key().as_ref(), token_account.owner.as_ref()],
+        bump
+    )]
+    pub kyc_record: Account<'info, KycRecord>,
+
+    /// The owner's transfer limits, which take the KYC tier
+    #[account(
+        init_if_needed,
+        payer = operator,
+        space = TransferLimits::LEN,
+        seeds = [TRANSFER_LIMITS_SEED, mint.key().as_ref(), token_account.owner.as_ref()],
+        bump
+    )]
+    pub transfer_limits: Box<Account<'info, TransferLimits>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    // Remaining accounts: the owner's `BlacklistEntry` if they are
+    // blacklisted
+}
+
+/// Record the owner's KYC tier, in the KYC record and in their transfer
+/// limits, and thaw the token account, which the DefaultAccountState
+/// extension creates frozen. The tier is set when the owner's limits account
+/// is created; calling it again for the owner's other token accounts thaws
+/// those and keeps the tier already assigned.
+pub fn kyc_approve_handler(ctx: Context<KycApprove>, tier: u8) -> Result<()> {
+    let owner = ctx.accounts.token_account.owner;
+    let whitelisted = ctx.accounts.internal_whitelist.is_whitelisted(&owner)
+        || ctx.accounts.external_whitelist.is_whitelisted(&owner);
+
+    approve(
+        ctx.accounts,
+        &ctx.bumps,
+        ctx.remaining_accounts,
+        tier,
+        whitelisted,
+    )
+}
+
+fn approve(
+    accounts: &mut KycApprove,
+    bumps: &KycApproveBumps,
+    blacklist_entries: &[AccountInfo],
+    tier: u8,
+    whitelisted: bool,
+) -> Result<()> {
+    let owner = accounts.token_account.owner;
+    let clock = Clock::get()?;
+
+    require!(
+        !accounts
+            .blacklist
+            .is_blocked(&owner, blacklist_entries, &clock)?,
+        ErrorCode::UserBlacklisted
+    );
+    require!(
+        KycRecord::is_valid_tier(tier, whitelisted),
+        ErrorCode::InvalidKycTier
+    );
+
+    if accounts.token_account.is_frozen() {
+        let mint_key = accounts.mint.key();
+        let seeds = &[
+            MINT_AUTHORITY_SEED,
+            mint_key.as_ref(),
+            &[accounts.mint_authority.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_accounts = ThawAccount {
+            account: accounts.token_account.to_account_info(),
+            mint: accounts.mint.to_account_info(),
+            authority: accounts.mint_authority.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token_interface::thaw_account(cpi_ctx)?;
+    }
+
+    let timestamp = clock.unix_timestamp;
+
+    // The tier is only taken when the limits account is created; after that
+    // the multisig changes it through `set_transfer_tier`
+    let transfer_limits = &mut accounts.transfer_limits;
+    if transfer_limits.owner == Pubkey::default() {
+        transfer_limits.mint = accounts.mint.key();
+        transfer_limits.owner = owner;
+        transfer_limits.tier = tier;
+        transfer_limits.bump = bumps.transfer_limits;
+    }
+    let tier = transfer_limits.tier;
+    // An owner who was allowlisted at the whitelisted tier must prove it again
+    require!(
+        KycRecord::is_valid_tier(tier, whitelisted),
+        ErrorCode::InvalidKycTier
+    );
+
+    let kyc_record = &mut accounts.kyc_record;
+    kyc_record.mint = accounts.mint.key();
+    kyc_record.owner = owner;
+    kyc_record.tier = tier;
+    kyc_record.approved_by = accounts.operator.key();
+    kyc_record.approved_at = timestamp;
+    kyc_record.bump = bumps.kyc_record;
+
+    emit!(KycApprovedEvent {
+        mint: kyc_record.mint,
+        owner,
+        token_account: accounts.token_account.key(),
+        tier,
+        operator: kyc_record.approved_by,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+// ============================================================================
+// KYC Approve Allowlisted
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct KycApproveAllowlisted<'info> {
+    pub kyc_approve: KycApprove<'info>,
+
+    #[account(
+        seeds = [EXTERNAL_ALLOWLIST_SEED, kyc_approve.mint.key().as_ref()],
+        bump = external_allowlist.bump,
+    )]
+    pub external_allowlist: Account<'info, ExternalAllowlist>,
+
+    #[account(
+        init_if_needed,
+        payer = kyc_approve.operator,
+        space = AllowlistApproval::LEN,
+        seeds = [ALLOWLIST_APPROVAL_SEED, kyc_approve.mint.key().as_ref(), kyc_approve.token_account.owner.as_ref()],
+        bump
+    )]
+    pub allowlist_approval: Account<'info, AllowlistApproval>,
+
+    pub system_program: Program<'info, System>,
+    // Remaining accounts: as for `kyc_approve`
+}
+
+/// `kyc_approve` at the whitelisted tier for an owner on the external
+/// allowlist rather than the external whitelist. `proof` is the owner's
+/// Merkle proof against the allowlist's current or previous root, which is
+/// recorded in their allowlist approval.
+pub fn kyc_approve_allowlisted_handler(
+    ctx: Context<KycApproveAllowlisted>,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    let owner = ctx.accounts.kyc_approve.token_account.owner;
+    let timestamp = Clock::get()?.unix_timestamp;
+    require!(
+        ctx.accounts
+            .external_allowlist
+            .is_allowlisted(&owner, &proof, timestamp),
+        ErrorCode::NotAllowlisted
+    );
+
+    let allowlist_approval = &mut ctx.accounts.allowlist_approval;
+    allowlist_approval.mint = ctx.accounts.kyc_approve.mint.key();
+    allowlist_approval.owner = owner;
+    allowlist_approval.merkle_root = allowlist_proof_root(&owner, &proof);
+    allowlist_approval.bump = ctx.bumps.allowlist_approval;
+
+    approve(
+        &mut ctx.accounts.kyc_approve,
+        &ctx.bumps.kyc_approve,
+        ctx.remaining_accounts,
+        KycRecord::WHITELISTED_TIER,
+        true,
+    )
+}
@@ -0,0 +1,292 @@
+// src/instructions/metadata.rs
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    BaseStateWithExtensions, StateWithExtensions,
+};
+use anchor_spl::token_2022::spl_token_2022::state::Mint as SplMint;
+use anchor_spl::token_interface::spl_token_metadata_interface::state::TokenMetadata;
+use anchor_spl::token_interface::{
+    token_metadata_initialize, token_metadata_update_field, Mint, TokenInterface,
+    TokenMetadataInitialize, TokenMetadataUpdateField,
+};
+
+// ============================================================================
+// Initialize Metadata (with Multisig)
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct InitializeMetadata<'info> {
+    /// Funds the rent for the metadata the mint grows by
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = mint.key() == token_config.mint @ ErrorCode::MintMismatch,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        bump = token_config.bump,
+        constraint = token_config.is_setup_complete() @ ErrorCode::SetupIncomplete,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, mint.key().as_ref()],
+        bump = mint_authority.bump,
+    )]
+    pub mint_authority: Account<'info, MintAuthority>,
+
+    #[account(
+        mut,
+        seeds = [Multisig::MULTISIG_SEED, mint.key().as_ref()],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    /// CHECK: This is the instructions sysvar
+    #[account(address = solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Write the Token-2022 metadata onto the mint, taking the name and symbol
+/// from the token config. The mint must carry a metadata pointer to itself;
+/// the mint-authority PDA becomes the metadata update authority.
+pub fn initialize_metadata_handler(ctx: Context<InitializeMetadata>, uri: String) -> Result<()> {
+    let multisig = &mut ctx.accounts.multisig;
+
+    require_keys_eq!(
+        multisig.key(),
+        ctx.accounts.token_config.admin,
+        ErrorCode::Unauthorized
+    );
+    MetadataField::Uri.validate(&uri)?;
+
+    let mint_key = ctx.accounts.mint.key();
+    let message = build_initialize_metadata_message(&mint_key, &uri, multisig.nonce);
+    let readable_message =
+        build_readable_initialize_metadata_message(&mint_key, &uri, multisig.nonce);
+
+    let nonce = multisig.nonce;
+    let approvers = validate_multisig_authorization(
+        multisig,
+        &ctx.accounts.instructions,
+        &message,
+        &readable_message,
+    )?;
+
+    let token_config = &ctx.accounts.token_config;
+    let metadata = TokenMetadata {
+        name: token_config.name.clone(),
+        symbol: token_config.symbol.clone(),
+        uri: uri.clone(),
+        ..Default::default()
+    };
+    let mint_info = ctx.accounts.mint.to_account_info();
+    let new_len = mint_info.data_len() + metadata.tlv_size_of()?;
+    fund_mint_realloc(
+        &ctx.accounts.payer,
+        &mint_info,
+        &ctx.accounts.system_program,
+        new_len,
+    )?;
+
+    let seeds = &[
+        MINT_AUTHORITY_SEED,
+        mint_key.as_ref(),
+        &[ctx.accounts.mint_authority.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_accounts = TokenMetadataInitialize {
+        program_id: ctx.accounts.token_program.to_account_info(),
+        metadata: mint_info.clone(),
+        update_authority: ctx.accounts.mint_authority.to_account_info(),
+        mint_authority: ctx.accounts.mint_authority.to_account_info(),
+        mint: mint_info,
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token_metadata_initialize(
+        cpi_ctx,
+        metadata.name.clone(),
+        metadata.symbol.clone(),
+        uri.clone(),
+    )?;
+
+    emit!(MetadataInitializedEvent {
+        mint: mint_key,
+        name: metadata.name,
+        symbol: metadata.symbol,
+        uri,
+        nonce,
+        approvers,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// ============================================================================
+// Update Metadata Field (with Multisig)
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct UpdateMetadataField<'info> {
+    /// Funds the rent if the metadata grows
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = mint.key() == token_config.mint @ ErrorCode::MintMismatch,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        bump = token_config.bump,
+        constraint = token_config.is_setup_complete() @ ErrorCode::SetupIncomplete,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, mint.key().as_ref()],
+        bump = mint_authority.bump,
+    )]
+    pub mint_authority: Account<'info, MintAuthority>,
+
+    #[account(
+        mut,
+        seeds = [Multisig::MULTISIG_SEED, mint.key().as_ref()],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    /// CHECK: This is the instructions sysvar
+    #[account(address = solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Set one metadata field, adding `Key` fields that do not exist yet. Name
+/// and symbol changes are mirrored into the token config.
+pub fn update_metadata_field_handler(
+    ctx: Context<UpdateMetadataField>,
+    field: MetadataField,
+    value: String,
+) -> Result<()> {
+    let multisig = &mut ctx.accounts.multisig;
+
+    require_keys_eq!(
+        multisig.key(),
+        ctx.accounts.token_config.admin,
+        ErrorCode::Unauthorized
+    );
+    field.validate(&value)?;
+
+    let mint_key = ctx.accounts.mint.key();
+    let message = build_update_metadata_field_message(&mint_key, &field, &value, multisig.nonce);
+    let readable_message =
+        build_readable_update_metadata_field_message(&mint_key, &field, &value, multisig.nonce);
+
+    let nonce = multisig.nonce;
+    let approvers = validate_multisig_authorization(
+        multisig,
+        &ctx.accounts.instructions,
+        &message,
+        &readable_message,
+    )?;
+
+    // Token-2022 reallocates the mint to the updated metadata's size
+    let mint_info = ctx.accounts.mint.to_account_info();
+    let new_len = {
+        let data = mint_info.try_borrow_data()?;
+        let state = StateWithExtensions::<SplMint>::unpack(&data)?;
+        let mut metadata = state.get_variable_len_extension::<TokenMetadata>()?;
+        let old_size = metadata.tlv_size_of()?;
+        metadata.update(field.clone().into(), value.clone());
+        (data.len() + metadata.tlv_size_of()?).saturating_sub(old_size)
+    };
+    fund_mint_realloc(
+        &ctx.accounts.payer,
+        &mint_info,
+        &ctx.accounts.system_program,
+        new_len,
+    )?;
+
+    let seeds = &[
+        MINT_AUTHORITY_SEED,
+        mint_key.as_ref(),
+        &[ctx.accounts.mint_authority.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_accounts = TokenMetadataUpdateField {
+        program_id: ctx.accounts.token_program.to_account_info(),
+        metadata: mint_info,
+        update_authority: ctx.accounts.mint_authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token_metadata_update_field(cpi_ctx, field.clone().into(), value.clone())?;
+
+    let token_config = &mut ctx.accounts.token_config;
+    match field {
+        MetadataField::Name => token_config.name = value.clone(),
+        MetadataField::Symbol => token_config.symbol = value.clone(),
+        _ => {}
+    }
+
+    emit!(MetadataUpdatedEvent {
+        mint: mint_key,
+        field,
+        value,
+        nonce,
+        approvers,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Top up the mint so it stays rent exempt once Token-2022 grows it to
+/// `new_len` bytes.
+fn fund_mint_realloc<'info>(
+    payer: &Signer<'info>,
+    mint: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    new_len: usize,
+) -> Result<()> {
+    let required = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(mint.lamports());
+    if required > 0 {
+        let cpi_accounts = Transfer {
+            from: payer.to_account_info(),
+            to: mint.clone(),
+        };
+        let cpi_ctx = CpiContext::new(system_program.to_account_info(), cpi_accounts);
+        system_program::transfer(cpi_ctx, required)?;
+    }
+
+    Ok(())
+}
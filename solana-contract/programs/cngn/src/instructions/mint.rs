@@ -10,7 +10,8 @@ pub struct MintTokens<'info> {
     pub authority: Signer<'info>,
 
     #[account(
-        constraint = !token_config.mint_paused @ ErrorCode::MintingPaused)]
+        constraint = !token_config.mint_paused @ ErrorCode::MintingPaused,
+        constraint = token_config.is_setup_complete() @ ErrorCode::SetupIncomplete)]
     pub token_config: Account<'info, TokenConfig>,
 
     #[account(
@@ -38,21 +39,28 @@ pub struct MintTokens<'info> {
     pub trusted_contracts: Account<'info, TrustedContracts>,
 
     pub token_program: Interface<'info, TokenInterface>,
+    // Remaining accounts: the `BlacklistEntry` of a blacklisted signer or
+    // receiver, whose listing no longer applies once it has expired
 }
 
 pub fn handler(ctx: Context<MintTokens>, amount: u64) -> Result<()> {
     let signer = ctx.accounts.authority.key();
     let mint_to = ctx.accounts.token_account.owner;
+    let clock = Clock::get()?;
 
     // Check if signer is blacklisted
     require!(
-        !ctx.accounts.blacklist.is_blacklisted(&signer),
+        !ctx.accounts
+            .blacklist
+            .is_blocked(&signer, ctx.remaining_accounts, &clock)?,
         ErrorCode::SignerBlacklisted
     );
 
     // Check if receiver is blacklisted
     require!(
-        !ctx.accounts.blacklist.is_blacklisted(&mint_to),
+        !ctx.accounts
+            .blacklist
+            .is_blocked(&mint_to, ctx.remaining_accounts, &clock)?,
         ErrorCode::ReceiverBlacklisted
     );
 
@@ -92,15 +100,19 @@ pub fn handler(ctx: Context<MintTokens>, amount: u64) -> Result<()> {
 
     token_interface::mint_to(cpi_ctx, amount)?;
 
+    let timestamp = Clock::get()?.unix_timestamp;
+
     // After successful minting, remove the authority
     if ctx.accounts.can_mint.can_mint(&signer) {
         // Remove authority from can_mint (also sets mint amount to 0)
         ctx.accounts.can_mint.remove_authority(&signer)?;
 
-        // Emit can mint removed event
-        emit!(BlackListedMinter {
+        // The allowance was used up, not revoked by the admin
+        emit!(MinterConsumed {
             mint: ctx.accounts.token_config.mint,
             authority: signer,
+            amount,
+            timestamp,
         });
     }
 
@@ -109,6 +121,8 @@ pub fn handler(ctx: Context<MintTokens>, amount: u64) -> Result<()> {
         mint: ctx.accounts.mint.key(),
         to: ctx.accounts.token_account.key(),
         amount,
+        minter: signer,
+        timestamp,
     });
 
     Ok(())
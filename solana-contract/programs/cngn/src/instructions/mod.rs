@@ -4,10 +4,38 @@ pub mod mint;
 pub mod pause;
 pub mod admin;
 pub mod multisig;
+pub mod view;
+pub mod bridge;
+pub mod metadata;
+pub mod transfer_hook;
+pub mod kyc;
+pub mod transfer_limits;
+pub mod transfer_fee;
+pub mod confidential;
+pub mod yield_distribution;
+pub mod escrow;
+pub mod subscription;
+pub mod batch;
+pub mod batch_admin;
+pub mod external_allowlist;
 
 
 pub use initialize::*;
 pub use mint::*;
 pub use pause::*;
 pub use admin::*;
-pub use multisig::*;
\ No newline at end of file
+pub use multisig::*;
+pub use view::*;
+pub use bridge::*;
+pub use metadata::*;
+pub use transfer_hook::*;
+pub use kyc::*;
+pub use transfer_limits::*;
+pub use transfer_fee::*;
+pub use confidential::*;
+pub use yield_distribution::*;
+pub use escrow::*;
+pub use subscription::*;
+pub use batch::*;
+pub use batch_admin::*;
+pub use external_allowlist::*;
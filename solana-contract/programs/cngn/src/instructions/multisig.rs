@@ -1,5 +1,6 @@
 // src/instructions/multisig.rs
 use crate::errors::ErrorCode;
+use crate::events::{MultisigInitializedEvent, MultisigUpdatedEvent};
 use crate::state::{multisig as ms, Multisig, TokenConfig, TOKEN_CONFIG_SEED};
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::Mint;
@@ -22,6 +23,8 @@ pub struct InitializeMultisig<'info> {
         mut,
         seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
         bump = token_config.bump,
+        constraint = token_config.is_setup_complete() @ ErrorCode::SetupIncomplete,
+        constraint = token_config.initializer == payer.key() @ ErrorCode::Unauthorized,
     )]
     pub token_config: Account<'info, TokenConfig>,
 
@@ -78,6 +81,16 @@ pub fn initialize_multisig_handler(
     multisig.nonce = 0;
     multisig.bump = ctx.bumps.multisig;
     token_config.admin = multisig.key();
+
+    emit!(MultisigInitializedEvent {
+        mint: ctx.accounts.mint.key(),
+        multisig: multisig.key(),
+        owners: multisig.owners.clone(),
+        threshold,
+        initializer: ctx.accounts.payer.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
     Ok(())
 }
 
@@ -103,9 +116,36 @@ pub fn update_multisig_handler(
         multisig.nonce,
     );
 
-    ms::validate_multisig_authorization(multisig, &ctx.accounts.instructions, &message)?;
+    let readable_message = ms::build_readable_update_multisig_message(
+        &multisig.key(),
+        &new_owners,
+        new_threshold,
+        multisig.nonce,
+    );
 
+    let nonce = multisig.nonce;
+    let approvers = ms::validate_multisig_authorization(
+        multisig,
+        &ctx.accounts.instructions,
+        &message,
+        &readable_message,
+    )?;
+
+    let old_owners = multisig.owners.clone();
+    let old_threshold = multisig.threshold;
     multisig.rotate_owners(new_owners, new_threshold)?;
 
+    emit!(MultisigUpdatedEvent {
+        mint: ctx.accounts.mint.key(),
+        multisig: multisig.key(),
+        old_owners,
+        old_threshold,
+        owners: multisig.owners.clone(),
+        threshold: multisig.threshold,
+        nonce,
+        approvers,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
     Ok(())
 }
@@ -7,6 +7,7 @@ use anchor_lang::prelude::*;
 #[derive(Accounts)]
 pub struct PauseMint<'info> {
        #[account(
+        mut,
         seeds = [Multisig::MULTISIG_SEED,token_config.mint.key().as_ref()],
         bump = multisig.bump
     )]
@@ -16,6 +17,7 @@ pub struct PauseMint<'info> {
         mut,
     seeds = [TOKEN_CONFIG_SEED, token_config.mint.as_ref()],
         bump = token_config.bump,
+        constraint = token_config.is_setup_complete() @ ErrorCode::SetupIncomplete,
     )]
     pub token_config: Account<'info, TokenConfig>,
 
@@ -32,8 +34,19 @@ pub fn pause_mint_handler(ctx: Context<PauseMint>, pause_mint: bool) -> Result<(
         ErrorCode::Unauthorized
     );
     let message = build_pause_mint_message(&ctx.accounts.token_config.key(), multisig.nonce);
+    let readable_message = build_readable_pause_mint_message(
+        &ctx.accounts.token_config.key(),
+        pause_mint,
+        multisig.nonce,
+    );
 
-    validate_multisig_authorization(multisig, &ctx.accounts.instructions, &message)?;
+    let nonce = multisig.nonce;
+    let approvers = validate_multisig_authorization(
+        multisig,
+        &ctx.accounts.instructions,
+        &message,
+        &readable_message,
+    )?;
     let token_config = &mut ctx.accounts.token_config;
 
     if pause_mint == token_config.mint_paused {
@@ -47,6 +60,9 @@ pub fn pause_mint_handler(ctx: Context<PauseMint>, pause_mint: bool) -> Result<(
     emit!(TokenMintingPauseEvent {
         mint: token_config.mint,
         mint_paused: token_config.mint_paused,
+        nonce,
+        approvers,
+        timestamp: Clock::get()?.unix_timestamp,
     });
 
     Ok(())
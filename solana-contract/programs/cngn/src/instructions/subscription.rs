@@ -0,0 +1,431 @@
+// src/instructions/subscription.rs
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::instructions::transfer_fee::{burn_and_mint_fee, collect_fee, load_fee_config};
+use crate::instructions::transfer_hook::record_transfer;
+use crate::instructions::yield_distribution::record_yield_outflow;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Burn, Mint, MintTo, Revoke, TokenAccount, TokenInterface};
+
+// Pulls move funds the way escrow does, by burning from the payer through
+// the pull delegate and minting to the payee, since a transfer of this mint
+// cannot be made from inside the program (see escrow.rs). The transfer fee is
+// taken out of each pull and minted to the fee treasury.
+//
+// Each subscription caps its own pulls per period. The payer's approval of
+// the pull delegate is shared by all subscriptions on a token account and
+// caps them together; `PullAllowance` counts them so the approval is revoked
+// with the last one.
+
+// ============================================================================
+// Create Subscription
+// ============================================================================
+
+#[derive(Accounts)]
+#[instruction(payee: Pubkey)]
+pub struct CreateSubscription<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        bump = token_config.bump,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        constraint = mint.key() == token_config.mint @ ErrorCode::MintMismatch,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        token::mint = mint,
+        token::authority = payer,
+    )]
+    pub payer_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = Subscription::LEN,
+        seeds = [SUBSCRIPTION_SEED, mint.key().as_ref(), payer.key().as_ref(), payee.as_ref()],
+        bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = PullAllowance::LEN,
+        seeds = [PULL_ALLOWANCE_SEED, mint.key().as_ref(), payer_token_account.key().as_ref()],
+        bump
+    )]
+    pub pull_allowance: Account<'info, PullAllowance>,
+
+    #[account(
+        seeds = [BLACK_LIST_SEED, mint.key().as_ref()],
+        bump = blacklist.bump,
+    )]
+    pub blacklist: Account<'info, BlackList>,
+
+    pub system_program: Program<'info, System>,
+    // Remaining accounts: the `BlacklistEntry` of the payer or the payee if
+    // either is blacklisted
+}
+
+/// Authorize `payee` to pull up to `amount_per_period` from the payer's token
+/// account every `period_seconds`, starting now. The payer must also approve
+/// the pull delegate on that token account for the total they allow.
+pub fn create_subscription_handler(
+    ctx: Context<CreateSubscription>,
+    payee: Pubkey,
+    amount_per_period: u64,
+    period_seconds: u32,
+) -> Result<()> {
+    require!(
+        amount_per_period > 0 && period_seconds > 0,
+        ErrorCode::InvalidSubscription
+    );
+
+    let payer = ctx.accounts.payer.key();
+    let clock = Clock::get()?;
+    let blacklist = &ctx.accounts.blacklist;
+    require!(
+        !blacklist.is_blocked(&payer, ctx.remaining_accounts, &clock)?,
+        ErrorCode::SignerBlacklisted
+    );
+    require!(
+        !blacklist.is_blocked(&payee, ctx.remaining_accounts, &clock)?,
+        ErrorCode::ReceiverBlacklisted
+    );
+
+    let timestamp = clock.unix_timestamp;
+
+    let subscription = &mut ctx.accounts.subscription;
+    subscription.mint = ctx.accounts.mint.key();
+    subscription.payer = payer;
+    subscription.payer_token_account = ctx.accounts.payer_token_account.key();
+    subscription.payee = payee;
+    subscription.amount_per_period = amount_per_period;
+    subscription.period_seconds = period_seconds;
+    subscription.period_start = timestamp;
+    subscription.pulled_in_period = 0;
+    subscription.bump = ctx.bumps.subscription;
+
+    let pull_allowance = &mut ctx.accounts.pull_allowance;
+    pull_allowance.token_account = subscription.payer_token_account;
+    pull_allowance.subscriptions = pull_allowance
+        .subscriptions
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+    pull_allowance.bump = ctx.bumps.pull_allowance;
+
+    emit!(SubscriptionCreatedEvent {
+        mint: subscription.mint,
+        subscription: subscription.key(),
+        payer,
+        payee,
+        amount_per_period,
+        period_seconds,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+// ============================================================================
+// Execute Pull
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct ExecutePull<'info> {
+    pub payee: Signer<'info>,
+
+    #[account(
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        bump = token_config.bump,
+        constraint = !token_config.transfer_paused @ ErrorCode::TransfersPaused,
+        constraint = token_config.is_setup_complete() @ ErrorCode::SetupIncomplete,
+    )]
+    pub token_config: Box<Account<'info, TokenConfig>>,
+
+    #[account(
+        mut,
+        constraint = mint.key() == token_config.mint @ ErrorCode::MintMismatch,
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, mint.key().as_ref()],
+        bump = mint_authority.bump,
+    )]
+    pub mint_authority: Box<Account<'info, MintAuthority>>,
+
+    /// CHECK: PDA signing as the delegate the payer approved
+    #[account(
+        seeds = [PULL_DELEGATE_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub pull_delegate: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [SUBSCRIPTION_SEED, mint.key().as_ref(), subscription.payer.as_ref(), payee.key().as_ref()],
+        bump = subscription.bump,
+    )]
+    pub subscription: Box<Account<'info, Subscription>>,
+
+    #[account(
+        mut,
+        address = subscription.payer_token_account @ ErrorCode::InvalidTokenAccount,
+    )]
+    pub payer_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = payee,
+    )]
+    pub payee_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [BLACK_LIST_SEED, mint.key().as_ref()],
+        bump = blacklist.bump,
+    )]
+    pub blacklist: Box<Account<'info, BlackList>>,
+
+    #[account(
+        seeds = [INTERNAL_WHITELIST_SEED, mint.key().as_ref()],
+        bump = internal_whitelist.bump,
+    )]
+    pub internal_whitelist: Box<Account<'info, InternalWhiteList>>,
+
+    #[account(
+        seeds = [TRANSFER_TIERS_SEED, mint.key().as_ref()],
+        bump = transfer_tiers.bump,
+    )]
+    pub transfer_tiers: Box<Account<'info, TransferTiers>>,
+
+    /// CHECK: the payer's `TransferLimits`, which may not exist; loaded in
+    /// the handler
+    #[account(
+        mut,
+        seeds = [TRANSFER_LIMITS_SEED, mint.key().as_ref(), subscription.payer.as_ref()],
+        bump,
+    )]
+    pub transfer_limits: UncheckedAccount<'info>,
+
+    /// CHECK: the `YieldPosition` of the payer's token account, which may
+    /// not exist; loaded in the handler
+    #[account(
+        mut,
+        seeds = [YIELD_POSITION_SEED, mint.key().as_ref(), payer_token_account.key().as_ref()],
+        bump,
+    )]
+    pub yield_position: UncheckedAccount<'info>,
+
+    /// CHECK: the `FeeConfig`, which may not exist; loaded in the handler
+    #[account(
+        seeds = [FEE_CONFIG_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub fee_config: UncheckedAccount<'info>,
+
+    /// CHECK: the fee config's treasury; checked in the handler when a fee
+    /// is charged
+    #[account(mut)]
+    pub fee_treasury: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    // Remaining accounts: the `BlacklistEntry` of a blacklisted payer or
+    // payee
+}
+
+/// Pull `amount` from the payer within the current period's allowance and
+/// pay it, less the transfer fee, to the payee. Only the payee can pull.
+pub fn execute_pull_handler(ctx: Context<ExecutePull>, amount: u64) -> Result<()> {
+    let payer = ctx.accounts.subscription.payer;
+    let payee = ctx.accounts.payee.key();
+
+    let clock = Clock::get()?;
+    let blacklist = &ctx.accounts.blacklist;
+    require!(
+        !blacklist.is_blocked(&payer, ctx.remaining_accounts, &clock)?,
+        ErrorCode::SignerBlacklisted
+    );
+    require!(
+        !blacklist.is_blocked(&payee, ctx.remaining_accounts, &clock)?,
+        ErrorCode::ReceiverBlacklisted
+    );
+
+    let timestamp = clock.unix_timestamp;
+    ctx.accounts.subscription.pull(amount, timestamp)?;
+
+    // A pull counts against the payer's limits like any other transfer
+    if !ctx.accounts.internal_whitelist.is_whitelisted(&payer) {
+        record_transfer(
+            &ctx.accounts.transfer_tiers,
+            &ctx.accounts.transfer_limits,
+            amount,
+        )?;
+    }
+
+    let mint_key = ctx.accounts.mint.key();
+    let delegate_seeds = &[
+        PULL_DELEGATE_SEED,
+        mint_key.as_ref(),
+        &[ctx.bumps.pull_delegate],
+    ];
+    let cpi_accounts = Burn {
+        mint: ctx.accounts.mint.to_account_info(),
+        from: ctx.accounts.payer_token_account.to_account_info(),
+        authority: ctx.accounts.pull_delegate.to_account_info(),
+    };
+    token_interface::burn(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            &[&delegate_seeds[..]],
+        ),
+        amount,
+    )?;
+
+    ctx.accounts.payer_token_account.reload()?;
+    record_yield_outflow(
+        &ctx.accounts.yield_position,
+        ctx.accounts.payer_token_account.amount,
+    )?;
+
+    let fee_config = load_fee_config(&ctx.accounts.fee_config)?;
+    let fee = burn_and_mint_fee(
+        &ctx.accounts.mint.to_account_info(),
+        fee_config.as_ref(),
+        &ctx.accounts.internal_whitelist,
+        &ctx.accounts.payer_token_account.owner,
+        &payee,
+        amount,
+    )?;
+
+    let mint_authority_seeds = &[
+        MINT_AUTHORITY_SEED,
+        mint_key.as_ref(),
+        &[ctx.accounts.mint_authority.bump],
+    ];
+    let cpi_accounts = MintTo {
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.payee_token_account.to_account_info(),
+        authority: ctx.accounts.mint_authority.to_account_info(),
+    };
+    token_interface::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            &[&mint_authority_seeds[..]],
+        ),
+        amount - fee,
+    )?;
+
+    collect_fee(
+        fee,
+        fee_config.as_ref(),
+        &ctx.accounts.fee_treasury,
+        &ctx.accounts.mint.to_account_info(),
+        &ctx.accounts.mint_authority.to_account_info(),
+        &ctx.accounts.token_program.to_account_info(),
+        &[&mint_authority_seeds[..]],
+    )?;
+
+    let subscription = &ctx.accounts.subscription;
+    emit!(SubscriptionPulledEvent {
+        mint: mint_key,
+        subscription: subscription.key(),
+        payer,
+        payee,
+        amount,
+        fee,
+        period_start: subscription.period_start,
+        pulled_in_period: subscription.pulled_in_period,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+// ============================================================================
+// Cancel Subscription
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct CancelSubscription<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        close = payer,
+        seeds = [SUBSCRIPTION_SEED, subscription.mint.as_ref(), payer.key().as_ref(), subscription.payee.as_ref()],
+        bump = subscription.bump,
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    #[account(
+        mut,
+        address = subscription.payer_token_account @ ErrorCode::InvalidTokenAccount,
+    )]
+    pub payer_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [PULL_ALLOWANCE_SEED, subscription.mint.as_ref(), payer_token_account.key().as_ref()],
+        bump = pull_allowance.bump,
+    )]
+    pub pull_allowance: Account<'info, PullAllowance>,
+
+    /// CHECK: PDA the payer approved as delegate
+    #[account(
+        seeds = [PULL_DELEGATE_SEED, subscription.mint.as_ref()],
+        bump,
+    )]
+    pub pull_delegate: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Stop all further pulls and reclaim the subscription's rent. Cancelling the
+/// last subscription on the token account also revokes the pull delegate's
+/// approval and closes the allowance counter.
+pub fn cancel_subscription_handler(ctx: Context<CancelSubscription>) -> Result<()> {
+    let pull_allowance = &mut ctx.accounts.pull_allowance;
+    pull_allowance.subscriptions = pull_allowance.subscriptions.saturating_sub(1);
+
+    if pull_allowance.subscriptions == 0 {
+        let delegate = ctx.accounts.pull_delegate.key();
+        if Option::<Pubkey>::from(ctx.accounts.payer_token_account.delegate) == Some(delegate) {
+            let cpi_accounts = Revoke {
+                source: ctx.accounts.payer_token_account.to_account_info(),
+                authority: ctx.accounts.payer.to_account_info(),
+            };
+            token_interface::revoke(CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts,
+            ))?;
+        }
+
+        ctx.accounts
+            .pull_allowance
+            .close(ctx.accounts.payer.to_account_info())?;
+    }
+
+    let subscription = &ctx.accounts.subscription;
+
+    emit!(SubscriptionCancelledEvent {
+        mint: subscription.mint,
+        subscription: subscription.key(),
+        payer: subscription.payer,
+        payee: subscription.payee,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,640 @@
+// src/instructions/transfer_fee.rs
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::TransferFeeConfig;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    BaseStateWithExtensions, StateWithExtensions,
+};
+use anchor_spl::token_2022::spl_token_2022::state::Mint as SplMint;
+use anchor_spl::token_interface::{
+    self, harvest_withheld_tokens_to_mint, transfer_fee_set, withdraw_withheld_tokens_from_mint,
+    Burn, HarvestWithheldTokensToMint, Mint, MintTo, TokenAccount, TokenInterface,
+    TransferFeeSetTransferFee, WithdrawWithheldTokensFromMint,
+};
+
+// ============================================================================
+// Set Transfer Fee (with Multisig)
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct SetTransferFee<'info> {
+    #[account(
+        mut,
+        constraint = mint.key() == token_config.mint @ ErrorCode::MintMismatch,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        bump = token_config.bump,
+        constraint = token_config.is_setup_complete() @ ErrorCode::SetupIncomplete,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, mint.key().as_ref()],
+        bump = mint_authority.bump,
+    )]
+    pub mint_authority: Account<'info, MintAuthority>,
+
+    #[account(
+        mut,
+        seeds = [Multisig::MULTISIG_SEED, mint.key().as_ref()],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    /// CHECK: This is the instructions sysvar
+    #[account(address = solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Change the mint's transfer fee. Token-2022 applies a new fee two epochs
+/// after it is set.
+pub fn set_transfer_fee_handler(
+    ctx: Context<SetTransferFee>,
+    basis_points: u16,
+    maximum_fee: u64,
+) -> Result<()> {
+    let multisig = &mut ctx.accounts.multisig;
+
+    require_keys_eq!(
+        multisig.key(),
+        ctx.accounts.token_config.admin,
+        ErrorCode::Unauthorized
+    );
+    require!(
+        basis_points <= FeeConfig::MAX_FEE_BASIS_POINTS,
+        ErrorCode::InvalidTransferFee
+    );
+
+    let mint_key = ctx.accounts.mint.key();
+    let message = build_set_transfer_fee_message(&mint_key, basis_points, maximum_fee, multisig.nonce);
+    let readable_message =
+        build_readable_set_transfer_fee_message(&mint_key, basis_points, maximum_fee, multisig.nonce);
+
+    let nonce = multisig.nonce;
+    let approvers = validate_multisig_authorization(
+        multisig,
+        &ctx.accounts.instructions,
+        &message,
+        &readable_message,
+    )?;
+
+    let seeds = &[
+        MINT_AUTHORITY_SEED,
+        mint_key.as_ref(),
+        &[ctx.accounts.mint_authority.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_accounts = TransferFeeSetTransferFee {
+        token_program_id: ctx.accounts.token_program.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        authority: ctx.accounts.mint_authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    transfer_fee_set(cpi_ctx, basis_points, maximum_fee)?;
+
+    emit!(TransferFeeUpdatedEvent {
+        mint: mint_key,
+        basis_points,
+        maximum_fee,
+        nonce,
+        approvers,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// ============================================================================
+// Update Fee Config (with Multisig)
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct UpdateFeeConfig<'info> {
+    /// Funds the fee config the first time
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        constraint = mint.key() == token_config.mint @ ErrorCode::MintMismatch,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        bump = token_config.bump,
+        constraint = token_config.is_setup_complete() @ ErrorCode::SetupIncomplete,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = FeeConfig::LEN,
+        seeds = [FEE_CONFIG_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub fee_config: Account<'info, FeeConfig>,
+
+    #[account(token::mint = mint)]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [Multisig::MULTISIG_SEED, mint.key().as_ref()],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    /// CHECK: This is the instructions sysvar
+    #[account(address = solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Set the token account withdrawn fees go to, and whether fees withheld
+/// from internal-whitelisted owners may be refunded.
+pub fn update_fee_config_handler(
+    ctx: Context<UpdateFeeConfig>,
+    exempt_internal_whitelist: bool,
+) -> Result<()> {
+    let multisig = &mut ctx.accounts.multisig;
+
+    require_keys_eq!(
+        multisig.key(),
+        ctx.accounts.token_config.admin,
+        ErrorCode::Unauthorized
+    );
+
+    let treasury = ctx.accounts.treasury.key();
+    let message = build_update_fee_config_message(
+        &ctx.accounts.fee_config.key(),
+        &treasury,
+        exempt_internal_whitelist,
+        multisig.nonce,
+    );
+    let readable_message = build_readable_update_fee_config_message(
+        &ctx.accounts.fee_config.key(),
+        &treasury,
+        exempt_internal_whitelist,
+        multisig.nonce,
+    );
+
+    let nonce = multisig.nonce;
+    let approvers = validate_multisig_authorization(
+        multisig,
+        &ctx.accounts.instructions,
+        &message,
+        &readable_message,
+    )?;
+
+    let fee_config = &mut ctx.accounts.fee_config;
+    fee_config.mint = ctx.accounts.mint.key();
+    fee_config.treasury = treasury;
+    fee_config.exempt_internal_whitelist = exempt_internal_whitelist;
+    fee_config.bump = ctx.bumps.fee_config;
+
+    emit!(FeeConfigUpdatedEvent {
+        mint: fee_config.mint,
+        treasury,
+        exempt_internal_whitelist,
+        nonce,
+        approvers,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// ============================================================================
+// Harvest Transfer Fees
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct HarvestTransferFees<'info> {
+    #[account(
+        mut,
+        constraint = mint.key() == token_config.mint @ ErrorCode::MintMismatch,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        bump = token_config.bump,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    // Remaining accounts: token accounts to harvest, writable
+}
+
+/// Move fees withheld in the token accounts passed as remaining accounts
+/// into the mint. Anyone may call it.
+pub fn harvest_transfer_fees_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, HarvestTransferFees<'info>>,
+) -> Result<()> {
+    let cpi_accounts = HarvestWithheldTokensToMint {
+        token_program_id: ctx.accounts.token_program.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    harvest_withheld_tokens_to_mint(cpi_ctx, ctx.remaining_accounts.to_vec())
+}
+
+// ============================================================================
+// Withdraw Transfer Fees
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct WithdrawTransferFees<'info> {
+    #[account(
+        mut,
+        constraint = mint.key() == token_config.mint @ ErrorCode::MintMismatch,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        bump = token_config.bump,
+        constraint = token_config.is_setup_complete() @ ErrorCode::SetupIncomplete,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, mint.key().as_ref()],
+        bump = mint_authority.bump,
+    )]
+    pub mint_authority: Account<'info, MintAuthority>,
+
+    #[account(
+        mut,
+        seeds = [FEE_CONFIG_SEED, mint.key().as_ref()],
+        bump = fee_config.bump,
+    )]
+    pub fee_config: Account<'info, FeeConfig>,
+
+    #[account(
+        mut,
+        address = fee_config.treasury @ ErrorCode::InvalidFeeTreasury,
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Withdraw the fees harvested into the mint to the treasury. Anyone may
+/// call it since the destination is fixed by the multisig.
+///
+/// Refunds are minted before the fees they cover reach the mint, so the
+/// part of the withdrawal matching refunds already paid is burned again,
+/// through the permanent delegate, instead of being kept by the treasury.
+pub fn withdraw_transfer_fees_handler(ctx: Context<WithdrawTransferFees>) -> Result<()> {
+    let withheld = {
+        let mint_info = ctx.accounts.mint.to_account_info();
+        let data = mint_info.try_borrow_data()?;
+        let mint = StateWithExtensions::<SplMint>::unpack(&data)?;
+        u64::from(mint.get_extension::<TransferFeeConfig>()?.withheld_amount)
+    };
+
+    let mint_key = ctx.accounts.mint.key();
+    let seeds = &[
+        MINT_AUTHORITY_SEED,
+        mint_key.as_ref(),
+        &[ctx.accounts.mint_authority.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_accounts = WithdrawWithheldTokensFromMint {
+        token_program_id: ctx.accounts.token_program.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        destination: ctx.accounts.treasury.to_account_info(),
+        authority: ctx.accounts.mint_authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    withdraw_withheld_tokens_from_mint(cpi_ctx)?;
+
+    let fee_config = &mut ctx.accounts.fee_config;
+    let refunds_settled = withheld.min(fee_config.refunds_outstanding);
+    if refunds_settled > 0 {
+        let cpi_accounts = Burn {
+            mint: ctx.accounts.mint.to_account_info(),
+            from: ctx.accounts.treasury.to_account_info(),
+            authority: ctx.accounts.mint_authority.to_account_info(),
+        };
+        token_interface::burn(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds,
+            ),
+            refunds_settled,
+        )?;
+        fee_config.refunds_outstanding -= refunds_settled;
+    }
+
+    emit!(TransferFeesWithdrawnEvent {
+        mint: mint_key,
+        treasury: ctx.accounts.treasury.key(),
+        amount: withheld - refunds_settled,
+        refunds_settled,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// ============================================================================
+// Refund Exempt Fees
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct OpenFeeRefund<'info> {
+    /// Funds the refund account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        constraint = mint.key() == token_config.mint @ ErrorCode::MintMismatch,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        bump = token_config.bump,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        seeds = [FEE_CONFIG_SEED, mint.key().as_ref()],
+        bump = fee_config.bump,
+        constraint = fee_config.exempt_internal_whitelist @ ErrorCode::NotFeeExempt,
+    )]
+    pub fee_config: Account<'info, FeeConfig>,
+
+    #[account(
+        seeds = [INTERNAL_WHITELIST_SEED, mint.key().as_ref()],
+        bump = internal_whitelist.bump,
+        constraint = internal_whitelist.is_whitelisted(&token_account.owner) @ ErrorCode::NotFeeExempt,
+    )]
+    pub internal_whitelist: Account<'info, InternalWhiteList>,
+
+    #[account(token::mint = mint)]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = FeeRefund::LEN,
+        seeds = [FEE_REFUND_SEED, mint.key().as_ref(), token_account.key().as_ref()],
+        bump
+    )]
+    pub fee_refund: Account<'info, FeeRefund>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Open the account the transfer hook credits with fees withheld on an
+/// exempt owner's transfers. Fees withheld before it exists are not
+/// refunded. Anyone may call it.
+pub fn open_fee_refund_handler(ctx: Context<OpenFeeRefund>) -> Result<()> {
+    let fee_refund = &mut ctx.accounts.fee_refund;
+    fee_refund.mint = ctx.accounts.mint.key();
+    fee_refund.token_account = ctx.accounts.token_account.key();
+    fee_refund.owed = 0;
+    fee_refund.bump = ctx.bumps.fee_refund;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RefundExemptFees<'info> {
+    #[account(
+        mut,
+        constraint = mint.key() == token_config.mint @ ErrorCode::MintMismatch,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        bump = token_config.bump,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, mint.key().as_ref()],
+        bump = mint_authority.bump,
+    )]
+    pub mint_authority: Account<'info, MintAuthority>,
+
+    #[account(
+        mut,
+        seeds = [FEE_CONFIG_SEED, mint.key().as_ref()],
+        bump = fee_config.bump,
+    )]
+    pub fee_config: Account<'info, FeeConfig>,
+
+    #[account(
+        mut,
+        seeds = [FEE_REFUND_SEED, mint.key().as_ref(), token_account.key().as_ref()],
+        bump = fee_refund.bump,
+    )]
+    pub fee_refund: Account<'info, FeeRefund>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+    )]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Token-2022 cannot skip the fee for particular accounts, so the transfer
+/// hook credits the fee withheld on an exempt owner's transfers to their
+/// refund account, and this mints it back to them. The credit is taken when
+/// the transfer happens, so it does not matter whether the withheld fee has
+/// since been harvested. Anyone may call it.
+pub fn refund_exempt_fees_handler(ctx: Context<RefundExemptFees>) -> Result<()> {
+    let amount = ctx.accounts.fee_refund.owed;
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let mint_key = ctx.accounts.mint.key();
+    let seeds = &[
+        MINT_AUTHORITY_SEED,
+        mint_key.as_ref(),
+        &[ctx.accounts.mint_authority.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_accounts = MintTo {
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.token_account.to_account_info(),
+        authority: ctx.accounts.mint_authority.to_account_info(),
+    };
+    token_interface::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        ),
+        amount,
+    )?;
+
+    ctx.accounts.fee_refund.owed = 0;
+    let fee_config = &mut ctx.accounts.fee_config;
+    fee_config.refunds_outstanding = fee_config
+        .refunds_outstanding
+        .checked_add(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    emit!(TransferFeeRefundedEvent {
+        mint: mint_key,
+        owner: ctx.accounts.token_account.owner,
+        token_account: ctx.accounts.token_account.key(),
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Credit the fee Token-2022 withheld on a transfer of `amount` to the
+/// refund account of whichever side is exempt, the destination first.
+/// Called by the transfer hook; owners without a refund account are not
+/// credited.
+pub(crate) fn credit_exempt_fee(
+    mint: &AccountInfo,
+    fee_config: &AccountInfo,
+    internal_whitelist: &InternalWhiteList,
+    parties: [(&Pubkey, &AccountInfo); 2],
+    amount: u64,
+) -> Result<()> {
+    if amount == CONFIDENTIAL_TRANSFER_AMOUNT {
+        return Ok(());
+    }
+    let Some(fee_config) = load_fee_config(fee_config)? else {
+        return Ok(());
+    };
+    if !fee_config.exempt_internal_whitelist {
+        return Ok(());
+    }
+
+    let Some((_, fee_refund)) = parties.into_iter().find(|(owner, fee_refund)| {
+        internal_whitelist.is_whitelisted(owner) && fee_refund.owner == &crate::ID
+    }) else {
+        return Ok(());
+    };
+
+    let fee = epoch_fee(mint, amount)?;
+    if fee == 0 {
+        return Ok(());
+    }
+
+    let mut data = fee_refund.try_borrow_mut_data()?;
+    let mut refund = FeeRefund::try_deserialize(&mut &data[..])?;
+    refund.owed = refund
+        .owed
+        .checked_add(fee)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    refund.try_serialize(&mut &mut data[..])
+}
+
+// ============================================================================
+// Fees on Burn-and-Mint Transfers
+// ============================================================================
+
+/// The fee config, which does not exist until the multisig first sets a
+/// treasury.
+pub(crate) fn load_fee_config(fee_config: &AccountInfo) -> Result<Option<FeeConfig>> {
+    if fee_config.owner != &crate::ID {
+        return Ok(None);
+    }
+
+    let data = fee_config.try_borrow_data()?;
+    Ok(Some(FeeConfig::try_deserialize(&mut &data[..])?))
+}
+
+/// The fee Token-2022 would withhold this epoch on a transfer of `amount`
+/// from `from` to `to`. Paths that burn and mint instead of transferring
+/// charge it themselves. Transfers touching an exempt owner are charged
+/// nothing, since the fee withheld on them would be refunded.
+pub(crate) fn burn_and_mint_fee(
+    mint: &AccountInfo,
+    fee_config: Option<&FeeConfig>,
+    internal_whitelist: &InternalWhiteList,
+    from: &Pubkey,
+    to: &Pubkey,
+    amount: u64,
+) -> Result<u64> {
+    if fee_config.is_some_and(|config| config.exempt_internal_whitelist)
+        && (internal_whitelist.is_whitelisted(from) || internal_whitelist.is_whitelisted(to))
+    {
+        return Ok(0);
+    }
+
+    epoch_fee(mint, amount)
+}
+
+fn epoch_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    let data = mint.try_borrow_data()?;
+    let mint = StateWithExtensions::<SplMint>::unpack(&data)?;
+    let Ok(transfer_fee_config) = mint.get_extension::<TransferFeeConfig>() else {
+        return Ok(0);
+    };
+
+    transfer_fee_config
+        .calculate_epoch_fee(Clock::get()?.epoch, amount)
+        .ok_or_else(|| ErrorCode::MathOverflow.into())
+}
+
+/// Mint the fee charged on a burn-and-mint transfer to the fee treasury,
+/// where withdrawn fees go.
+pub(crate) fn collect_fee<'info>(
+    fee: u64,
+    fee_config: Option<&FeeConfig>,
+    treasury: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    mint_authority: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    if fee == 0 {
+        return Ok(());
+    }
+
+    let fee_config = fee_config.ok_or(ErrorCode::InvalidFeeTreasury)?;
+    require_keys_eq!(
+        treasury.key(),
+        fee_config.treasury,
+        ErrorCode::InvalidFeeTreasury
+    );
+
+    let cpi_accounts = MintTo {
+        mint: mint.clone(),
+        to: treasury.clone(),
+        authority: mint_authority.clone(),
+    };
+    token_interface::mint_to(
+        CpiContext::new_with_signer(token_program.clone(), cpi_accounts, signer_seeds),
+        fee,
+    )
+}
@@ -0,0 +1,268 @@
+// src/instructions/transfer_hook.rs
+use crate::errors::ErrorCode;
+use crate::instructions::transfer_fee::credit_exempt_fee;
+use crate::instructions::yield_distribution::record_yield_outflow;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::extension::permanent_delegate::PermanentDelegate;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_hook::TransferHookAccount;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    BaseStateWithExtensions, StateWithExtensions,
+};
+use anchor_spl::token_2022::spl_token_2022::state::{Account as SplAccount, Mint as SplMint};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+// Account order is fixed by the transfer hook interface: source, mint,
+// destination, owner, extra metas list, then the metas `initialize_third`
+// registered.
+#[derive(Accounts)]
+pub struct TransferHook<'info> {
+    #[account(token::mint = mint)]
+    pub source_token: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(token::mint = mint)]
+    pub destination_token: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: source owner or delegate, validated by Token-2022
+    pub owner: UncheckedAccount<'info>,
+
+    /// CHECK: extra metas account
+    #[account(
+        seeds = [META_LIST_ACCOUNT_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub extra_metas_account: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        bump = token_config.bump,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        seeds = [BLACK_LIST_SEED, mint.key().as_ref()],
+        bump = blacklist.bump,
+    )]
+    pub blacklist: Account<'info, BlackList>,
+
+    #[account(
+        seeds = [INTERNAL_WHITELIST_SEED, mint.key().as_ref()],
+        bump = internal_whitelist.bump,
+    )]
+    pub internal_whitelist: Account<'info, InternalWhiteList>,
+
+    #[account(
+        seeds = [TRANSFER_TIERS_SEED, mint.key().as_ref()],
+        bump = transfer_tiers.bump,
+    )]
+    pub transfer_tiers: Account<'info, TransferTiers>,
+
+    /// CHECK: the source owner's `TransferLimits`, which may not exist;
+    /// loaded in the handler
+    #[account(
+        mut,
+        seeds = [TRANSFER_LIMITS_SEED, mint.key().as_ref(), source_token.owner.as_ref()],
+        bump,
+    )]
+    pub transfer_limits: UncheckedAccount<'info>,
+
+    /// CHECK: the source account's `YieldPosition`, which may not exist;
+    /// loaded in the handler
+    #[account(
+        mut,
+        seeds = [YIELD_POSITION_SEED, mint.key().as_ref(), source_token.key().as_ref()],
+        bump,
+    )]
+    pub source_yield_position: UncheckedAccount<'info>,
+
+    /// CHECK: the `FeeConfig`, which may not exist; loaded in the handler
+    #[account(
+        seeds = [FEE_CONFIG_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub fee_config: UncheckedAccount<'info>,
+
+    /// CHECK: the source account's `FeeRefund`, which may not exist;
+    /// loaded in the handler
+    #[account(
+        mut,
+        seeds = [FEE_REFUND_SEED, mint.key().as_ref(), source_token.key().as_ref()],
+        bump,
+    )]
+    pub source_fee_refund: UncheckedAccount<'info>,
+
+    /// CHECK: the destination account's `FeeRefund`, which may not exist;
+    /// loaded in the handler
+    #[account(
+        mut,
+        seeds = [FEE_REFUND_SEED, mint.key().as_ref(), destination_token.key().as_ref()],
+        bump,
+    )]
+    pub destination_fee_refund: UncheckedAccount<'info>,
+
+    /// CHECK: the authority's `BlacklistEntry`, which may not exist; loaded
+    /// in the handler
+    #[account(
+        seeds = [BLACKLIST_ENTRY_SEED, mint.key().as_ref(), owner.key().as_ref()],
+        bump,
+    )]
+    pub authority_blacklist_entry: UncheckedAccount<'info>,
+
+    /// CHECK: the source owner's `BlacklistEntry`, which may not exist;
+    /// loaded in the handler
+    #[account(
+        seeds = [BLACKLIST_ENTRY_SEED, mint.key().as_ref(), source_token.owner.as_ref()],
+        bump,
+    )]
+    pub source_blacklist_entry: UncheckedAccount<'info>,
+
+    /// CHECK: the destination owner's `BlacklistEntry`, which may not exist;
+    /// loaded in the handler
+    #[account(
+        seeds = [BLACKLIST_ENTRY_SEED, mint.key().as_ref(), destination_token.owner.as_ref()],
+        bump,
+    )]
+    pub destination_blacklist_entry: UncheckedAccount<'info>,
+}
+
+/// Called by Token-2022 on every transfer of the mint. Rejects transfers
+/// before setup completes, while transfers are paused, transfers touching a
+/// blacklisted address and transfers over the source owner's tier limits,
+/// except those made by the permanent delegate.
+///
+/// Token-2022 calls it after moving the tokens, so the source balance is
+/// already reduced when it is recorded against the source's yield position.
+/// The fee withheld on a transfer touching an exempt owner is credited to
+/// their refund account here, before anyone can harvest it.
+pub fn transfer_hook_handler(ctx: Context<TransferHook>, amount: u64) -> Result<()> {
+    assert_is_transferring(&ctx.accounts.source_token.to_account_info())?;
+
+    require!(
+        ctx.accounts.token_config.is_setup_complete(),
+        ErrorCode::SetupIncomplete
+    );
+    require!(
+        !ctx.accounts.token_config.transfer_paused,
+        ErrorCode::TransfersPaused
+    );
+
+    record_yield_outflow(
+        &ctx.accounts.source_yield_position,
+        ctx.accounts.source_token.amount,
+    )?;
+
+    credit_exempt_fee(
+        &ctx.accounts.mint.to_account_info(),
+        &ctx.accounts.fee_config,
+        &ctx.accounts.internal_whitelist,
+        [
+            (
+                &ctx.accounts.destination_token.owner,
+                &ctx.accounts.destination_fee_refund,
+            ),
+            (
+                &ctx.accounts.source_token.owner,
+                &ctx.accounts.source_fee_refund,
+            ),
+        ],
+        amount,
+    )?;
+
+    let authority = ctx.accounts.owner.key();
+    if is_permanent_delegate(&ctx.accounts.mint.to_account_info(), &authority)? {
+        return Ok(());
+    }
+
+    let blacklist = &ctx.accounts.blacklist;
+    let clock = Clock::get()?;
+    let entries = [
+        ctx.accounts.authority_blacklist_entry.to_account_info(),
+        ctx.accounts.source_blacklist_entry.to_account_info(),
+        ctx.accounts.destination_blacklist_entry.to_account_info(),
+    ];
+    require!(
+        !blacklist.is_blocked(&authority, &entries, &clock)?
+            && !blacklist.is_blocked(&ctx.accounts.source_token.owner, &entries, &clock)?,
+        ErrorCode::SignerBlacklisted
+    );
+    require!(
+        !blacklist.is_blocked(&ctx.accounts.destination_token.owner, &entries, &clock)?,
+        ErrorCode::ReceiverBlacklisted
+    );
+
+    let internal_whitelist = &ctx.accounts.internal_whitelist;
+
+    // Confidential amounts are hidden from the hook, so both sides must still
+    // be internally whitelisted, as they were when their accounts were approved
+    if amount == CONFIDENTIAL_TRANSFER_AMOUNT {
+        require!(
+            internal_whitelist.is_whitelisted(&ctx.accounts.source_token.owner)
+                && internal_whitelist.is_whitelisted(&ctx.accounts.destination_token.owner),
+            ErrorCode::ConfidentialTransferNotAllowed
+        );
+        return Ok(());
+    }
+
+    // Treasury and exchange accounts are exempt from tier limits
+    if internal_whitelist.is_whitelisted(&ctx.accounts.source_token.owner) {
+        return Ok(());
+    }
+
+    record_transfer(
+        &ctx.accounts.transfer_tiers,
+        &ctx.accounts.transfer_limits,
+        amount,
+    )
+}
+
+/// The source owner's limits account exists once they are KYC approved.
+/// Without one, `amount` is checked against the default tier's
+/// per-transaction cap and no usage is recorded.
+pub(crate) fn record_transfer(
+    transfer_tiers: &TransferTiers,
+    transfer_limits: &AccountInfo,
+    amount: u64,
+) -> Result<()> {
+    if transfer_limits.owner != &crate::ID {
+        let tier_limits = transfer_tiers.limits(TransferLimits::DEFAULT_TIER)?;
+        require!(
+            amount <= tier_limits.per_transaction,
+            ErrorCode::PerTransactionLimitExceeded
+        );
+        return Ok(());
+    }
+
+    let mut data = transfer_limits.try_borrow_mut_data()?;
+    let mut limits = TransferLimits::try_deserialize(&mut &data[..])?;
+    let tier_limits = transfer_tiers.limits(limits.tier)?;
+    limits.record(amount, &tier_limits, Clock::get()?.unix_timestamp)?;
+
+    limits.try_serialize(&mut &mut data[..])
+}
+
+/// Token-2022 flags the source account while it calls the hook, so the hook
+/// cannot be invoked directly to fake a transfer.
+fn assert_is_transferring(source_token: &AccountInfo) -> Result<()> {
+    let data = source_token.try_borrow_data()?;
+    let account = StateWithExtensions::<SplAccount>::unpack(&data)?;
+    let extension = account.get_extension::<TransferHookAccount>()?;
+
+    require!(
+        bool::from(extension.transferring),
+        ErrorCode::NotTransferring
+    );
+
+    Ok(())
+}
+
+fn is_permanent_delegate(mint: &AccountInfo, authority: &Pubkey) -> Result<bool> {
+    let data = mint.try_borrow_data()?;
+    let mint = StateWithExtensions::<SplMint>::unpack(&data)?;
+
+    Ok(match mint.get_extension::<PermanentDelegate>() {
+        Ok(extension) => Option::<Pubkey>::from(extension.delegate) == Some(*authority),
+        Err(_) => false,
+    })
+}
@@ -0,0 +1,228 @@
+// src/instructions/transfer_limits.rs
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+// ============================================================================
+// Configure Transfer Tier (with Multisig)
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct ConfigureTransferTier<'info> {
+    #[account(
+        constraint = mint.key() == token_config.mint @ ErrorCode::MintMismatch,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        bump = token_config.bump,
+        constraint = token_config.is_setup_complete() @ ErrorCode::SetupIncomplete,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        mut,
+        seeds = [TRANSFER_TIERS_SEED, mint.key().as_ref()],
+        bump = transfer_tiers.bump,
+    )]
+    pub transfer_tiers: Account<'info, TransferTiers>,
+
+    #[account(
+        mut,
+        seeds = [Multisig::MULTISIG_SEED, mint.key().as_ref()],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    /// CHECK: This is the instructions sysvar
+    #[account(address = solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+}
+
+pub fn configure_transfer_tier_handler(
+    ctx: Context<ConfigureTransferTier>,
+    tier: u8,
+    per_transaction_limit: u64,
+    daily_limit: u64,
+) -> Result<()> {
+    let multisig = &mut ctx.accounts.multisig;
+
+    require_keys_eq!(
+        multisig.key(),
+        ctx.accounts.token_config.admin,
+        ErrorCode::Unauthorized
+    );
+
+    let limits = TierLimits {
+        per_transaction: per_transaction_limit,
+        daily: daily_limit,
+    };
+    let message = build_configure_transfer_tier_message(
+        &ctx.accounts.transfer_tiers.key(),
+        tier,
+        &limits,
+        multisig.nonce,
+    );
+    let readable_message = build_readable_configure_transfer_tier_message(
+        &ctx.accounts.transfer_tiers.key(),
+        tier,
+        &limits,
+        multisig.nonce,
+    );
+
+    let nonce = multisig.nonce;
+    let approvers = validate_multisig_authorization(
+        multisig,
+        &ctx.accounts.instructions,
+        &message,
+        &readable_message,
+    )?;
+
+    let transfer_tiers = &mut ctx.accounts.transfer_tiers;
+    transfer_tiers.configure(tier, limits)?;
+
+    emit!(TransferTierConfiguredEvent {
+        mint: transfer_tiers.mint,
+        tier,
+        per_transaction_limit,
+        daily_limit,
+        nonce,
+        approvers,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// ============================================================================
+// Set Transfer Tier (with Multisig)
+// ============================================================================
+
+#[derive(Accounts)]
+#[instruction(owner: Pubkey)]
+pub struct SetTransferTier<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        constraint = mint.key() == token_config.mint @ ErrorCode::MintMismatch,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        bump = token_config.bump,
+        constraint = token_config.is_setup_complete() @ ErrorCode::SetupIncomplete,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = TransferLimits::LEN,
+        seeds = [TRANSFER_LIMITS_SEED, mint.key().as_ref(), owner.as_ref()],
+        bump
+    )]
+    pub transfer_limits: Account<'info, TransferLimits>,
+
+    /// The owner's KYC record, kept at the same tier
+    #[account(
+        mut,
+        seeds = [KYC_RECORD_SEED, mint.key().as_ref(), owner.as_ref()],
+        bump = kyc_record.bump,
+    )]
+    pub kyc_record: Account<'info, KycRecord>,
+
+    #[account(
+        seeds = [INTERNAL_WHITELIST_SEED, mint.key().as_ref()],
+        bump = internal_whitelist.bump,
+    )]
+    pub internal_whitelist: Account<'info, InternalWhiteList>,
+
+    #[account(
+        seeds = [EXTERNAL_WHITELIST_SEED, mint.key().as_ref()],
+        bump = external_whitelist.bump,
+    )]
+    pub external_whitelist: Account<'info, ExternalWhiteList>,
+
+    #[account(
+        mut,
+        seeds = [Multisig::MULTISIG_SEED, mint.key().as_ref()],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    /// CHECK: This is the instructions sysvar
+    #[account(address = solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Move a KYC-approved `owner` to `tier`, in their KYC record and their
+/// limits account, creating the latter if they were approved before
+/// `kyc_approve` created it. Usage in the current window carries over to
+/// the new tier.
+pub fn set_transfer_tier_handler(
+    ctx: Context<SetTransferTier>,
+    owner: Pubkey,
+    tier: u8,
+) -> Result<()> {
+    let multisig = &mut ctx.accounts.multisig;
+
+    require_keys_eq!(
+        multisig.key(),
+        ctx.accounts.token_config.admin,
+        ErrorCode::Unauthorized
+    );
+
+    let message = build_set_transfer_tier_message(
+        &ctx.accounts.transfer_limits.key(),
+        &owner,
+        tier,
+        multisig.nonce,
+    );
+    let readable_message = build_readable_set_transfer_tier_message(
+        &ctx.accounts.transfer_limits.key(),
+        &owner,
+        tier,
+        multisig.nonce,
+    );
+
+    let nonce = multisig.nonce;
+    let approvers = validate_multisig_authorization(
+        multisig,
+        &ctx.accounts.instructions,
+        &message,
+        &readable_message,
+    )?;
+
+    // Tier 0 stays reserved for whitelisted owners, as in `kyc_approve`
+    let whitelisted = ctx.accounts.internal_whitelist.is_whitelisted(&owner)
+        || ctx.accounts.external_whitelist.is_whitelisted(&owner);
+    require!(
+        KycRecord::is_valid_tier(tier, whitelisted),
+        ErrorCode::InvalidKycTier
+    );
+
+    let transfer_limits = &mut ctx.accounts.transfer_limits;
+    transfer_limits.mint = ctx.accounts.mint.key();
+    transfer_limits.owner = owner;
+    transfer_limits.tier = tier;
+    transfer_limits.bump = ctx.bumps.transfer_limits;
+    ctx.accounts.kyc_record.tier = tier;
+
+    emit!(TransferTierAssignedEvent {
+        mint: transfer_limits.mint,
+        owner,
+        tier,
+        nonce,
+        approvers,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
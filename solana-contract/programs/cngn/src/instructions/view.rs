@@ -0,0 +1,174 @@
+// src/instructions/view.rs
+//! Read-only instructions for integrators. Call them through simulation
+//! (`.view()` in the Anchor client) and read the structured return data.
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+/// Membership of one address in every cNGN list
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AddressStatus {
+    pub address: Pubkey,
+    /// Listed and not expired, as far as the `BlacklistEntry` passed shows
+    pub blacklisted: bool,
+    pub internal_whitelisted: bool,
+    pub external_whitelisted: bool,
+    pub forwarder: bool,
+    pub trusted_contract: bool,
+    pub minter: bool,
+    /// Remaining mint allowance; zero when the address is not a minter
+    pub mint_allowance: u64,
+}
+
+/// Current multisig configuration
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MultisigInfo {
+    pub multisig: Pubkey,
+    pub owners: Vec<Pubkey>,
+    pub threshold: u8,
+    pub nonce: u64,
+}
+
+/// Current pause flags and admin of the token
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TokenState {
+    pub mint: Pubkey,
+    pub admin: Pubkey,
+    pub mint_paused: bool,
+    pub transfer_paused: bool,
+    pub init_phase: InitPhase,
+}
+
+// ============================================================================
+// Address Status
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct GetAddressStatus<'info> {
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        bump = token_config.bump,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        seeds = [BLACK_LIST_SEED, token_config.mint.as_ref()],
+        bump,
+    )]
+    pub blacklist: Account<'info, BlackList>,
+
+    #[account(
+        seeds = [INTERNAL_WHITELIST_SEED, token_config.mint.as_ref()],
+        bump,
+    )]
+    pub internal_whitelist: Account<'info, InternalWhiteList>,
+
+    #[account(
+        seeds = [EXTERNAL_WHITELIST_SEED, token_config.mint.as_ref()],
+        bump,
+    )]
+    pub external_whitelist: Account<'info, ExternalWhiteList>,
+
+    #[account(
+        seeds = [CAN_FORWARD_SEED, token_config.mint.as_ref()],
+        bump,
+    )]
+    pub can_forward: Account<'info, CanForward>,
+
+    #[account(
+        seeds = [TRUSTED_CONTRACTS_SEED, token_config.mint.as_ref()],
+        bump,
+    )]
+    pub trusted_contracts: Account<'info, TrustedContracts>,
+
+    #[account(
+        seeds = [CAN_MINT_SEED, token_config.mint.as_ref()],
+        bump,
+    )]
+    pub can_mint: Account<'info, CanMint>,
+    // Remaining accounts: the address's `BlacklistEntry`, without which a
+    // listing is reported as in force even after it has expired
+}
+
+pub fn get_address_status_handler(
+    ctx: Context<GetAddressStatus>,
+    address: Pubkey,
+) -> Result<AddressStatus> {
+    let can_mint = &ctx.accounts.can_mint;
+    let minter = can_mint.can_mint(&address);
+    let mint_allowance = if minter {
+        can_mint.get_mint_amount(&address)?
+    } else {
+        0
+    };
+
+    Ok(AddressStatus {
+        address,
+        blacklisted: ctx.accounts.blacklist.is_blocked(
+            &address,
+            ctx.remaining_accounts,
+            &Clock::get()?,
+        )?,
+        internal_whitelisted: ctx.accounts.internal_whitelist.is_whitelisted(&address),
+        external_whitelisted: ctx.accounts.external_whitelist.is_whitelisted(&address),
+        forwarder: ctx.accounts.can_forward.is_trusted_forwarder(&address),
+        trusted_contract: ctx.accounts.trusted_contracts.is_trusted_contract(&address),
+        minter,
+        mint_allowance,
+    })
+}
+
+// ============================================================================
+// Multisig Info
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct GetMultisigInfo<'info> {
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [Multisig::MULTISIG_SEED, mint.key().as_ref()],
+        bump = multisig.bump,
+    )]
+    pub multisig: Account<'info, Multisig>,
+}
+
+pub fn get_multisig_info_handler(ctx: Context<GetMultisigInfo>) -> Result<MultisigInfo> {
+    let multisig = &ctx.accounts.multisig;
+
+    Ok(MultisigInfo {
+        multisig: multisig.key(),
+        owners: multisig.owners.clone(),
+        threshold: multisig.threshold,
+        nonce: multisig.nonce,
+    })
+}
+
+// ============================================================================
+// Token State
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct GetTokenState<'info> {
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        bump = token_config.bump,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+}
+
+pub fn get_token_state_handler(ctx: Context<GetTokenState>) -> Result<TokenState> {
+    let token_config = &ctx.accounts.token_config;
+
+    Ok(TokenState {
+        mint: token_config.mint,
+        admin: token_config.admin,
+        mint_paused: token_config.mint_paused,
+        transfer_paused: token_config.transfer_paused,
+        init_phase: token_config.init_phase,
+    })
+}
@@ -0,0 +1,388 @@
+// src/instructions/yield_distribution.rs
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, MintTo, TokenAccount, TokenInterface};
+
+// ============================================================================
+// Update Yield Config (with Multisig)
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct UpdateYieldConfig<'info> {
+    /// Funds the yield config the first time
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        constraint = mint.key() == token_config.mint @ ErrorCode::MintMismatch,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        bump = token_config.bump,
+        constraint = token_config.is_setup_complete() @ ErrorCode::SetupIncomplete,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = YieldConfig::LEN,
+        seeds = [YIELD_CONFIG_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub yield_config: Account<'info, YieldConfig>,
+
+    #[account(
+        mut,
+        seeds = [Multisig::MULTISIG_SEED, mint.key().as_ref()],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    /// CHECK: This is the instructions sysvar
+    #[account(address = solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Set the annual yield rate, the accrual period and the bounds yield
+/// minting must stay within. The first call starts the first period; a new
+/// rate applies to periods closed after it.
+pub fn update_yield_config_handler(
+    ctx: Context<UpdateYieldConfig>,
+    annual_rate_basis_points: u16,
+    period_seconds: u32,
+    supply_cap: u64,
+    reserves: u64,
+) -> Result<()> {
+    let multisig = &mut ctx.accounts.multisig;
+
+    require_keys_eq!(
+        multisig.key(),
+        ctx.accounts.token_config.admin,
+        ErrorCode::Unauthorized
+    );
+    require!(
+        annual_rate_basis_points <= YieldConfig::MAX_RATE_BASIS_POINTS && period_seconds > 0,
+        ErrorCode::InvalidYieldConfig
+    );
+
+    let yield_config_key = ctx.accounts.yield_config.key();
+    let message = build_update_yield_config_message(
+        &yield_config_key,
+        annual_rate_basis_points,
+        period_seconds,
+        supply_cap,
+        reserves,
+        multisig.nonce,
+    );
+    let readable_message = build_readable_update_yield_config_message(
+        &yield_config_key,
+        annual_rate_basis_points,
+        period_seconds,
+        supply_cap,
+        reserves,
+        multisig.nonce,
+    );
+
+    let nonce = multisig.nonce;
+    let approvers = validate_multisig_authorization(
+        multisig,
+        &ctx.accounts.instructions,
+        &message,
+        &readable_message,
+    )?;
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    let yield_config = &mut ctx.accounts.yield_config;
+    if yield_config.mint == Pubkey::default() {
+        yield_config.mint = ctx.accounts.mint.key();
+        yield_config.period_start = timestamp;
+        yield_config.bump = ctx.bumps.yield_config;
+    }
+    yield_config.annual_rate_basis_points = annual_rate_basis_points;
+    yield_config.period_seconds = period_seconds;
+    yield_config.supply_cap = supply_cap;
+    yield_config.reserves = reserves;
+
+    emit!(YieldConfigUpdatedEvent {
+        mint: ctx.accounts.mint.key(),
+        annual_rate_basis_points,
+        period_seconds,
+        supply_cap,
+        reserves,
+        nonce,
+        approvers,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+// ============================================================================
+// Advance Yield Period
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct AdvanceYieldPeriod<'info> {
+    #[account(
+        mut,
+        seeds = [YIELD_CONFIG_SEED, yield_config.mint.as_ref()],
+        bump = yield_config.bump,
+    )]
+    pub yield_config: Account<'info, YieldConfig>,
+}
+
+/// Close the current accrual period once it has elapsed. Anyone can call it.
+pub fn advance_yield_period_handler(ctx: Context<AdvanceYieldPeriod>) -> Result<()> {
+    let yield_config = &mut ctx.accounts.yield_config;
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    yield_config.advance(timestamp)?;
+
+    emit!(YieldPeriodAccruedEvent {
+        mint: yield_config.mint,
+        period: yield_config.period,
+        annual_rate_basis_points: yield_config.annual_rate_basis_points,
+        yield_index: yield_config.yield_index,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+// ============================================================================
+// Opt In / Out of Yield
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct OptInYield<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        constraint = mint.key() == yield_config.mint @ ErrorCode::MintMismatch,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [YIELD_CONFIG_SEED, mint.key().as_ref()],
+        bump = yield_config.bump,
+    )]
+    pub yield_config: Account<'info, YieldConfig>,
+
+    #[account(
+        token::mint = mint,
+        token::authority = owner,
+    )]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [BLACK_LIST_SEED, mint.key().as_ref()],
+        bump = blacklist.bump,
+    )]
+    pub blacklist: Account<'info, BlackList>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = YieldPosition::LEN,
+        seeds = [YIELD_POSITION_SEED, mint.key().as_ref(), token_account.key().as_ref()],
+        bump
+    )]
+    pub yield_position: Account<'info, YieldPosition>,
+
+    pub system_program: Program<'info, System>,
+    // Remaining accounts: the owner's `BlacklistEntry` if they are
+    // blacklisted
+}
+
+/// Start earning yield on a token account from the current period.
+pub fn opt_in_yield_handler(ctx: Context<OptInYield>) -> Result<()> {
+    let owner = ctx.accounts.owner.key();
+    require!(
+        !ctx.accounts
+            .blacklist
+            .is_blocked(&owner, ctx.remaining_accounts, &Clock::get()?)?,
+        ErrorCode::UserBlacklisted
+    );
+
+    let yield_position = &mut ctx.accounts.yield_position;
+    yield_position.mint = ctx.accounts.mint.key();
+    yield_position.owner = owner;
+    yield_position.token_account = ctx.accounts.token_account.key();
+    yield_position.yield_index = ctx.accounts.yield_config.yield_index;
+    yield_position.balance = ctx.accounts.token_account.amount;
+    yield_position.bump = ctx.bumps.yield_position;
+
+    emit!(YieldOptedInEvent {
+        mint: yield_position.mint,
+        owner,
+        token_account: yield_position.token_account,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct OptOutYield<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [YIELD_POSITION_SEED, yield_position.mint.as_ref(), yield_position.token_account.as_ref()],
+        bump = yield_position.bump,
+        constraint = yield_position.owner == owner.key() @ ErrorCode::Unauthorized,
+    )]
+    pub yield_position: Account<'info, YieldPosition>,
+}
+
+/// Stop earning yield and reclaim the position's rent. Yield not yet
+/// distributed is forfeited.
+pub fn opt_out_yield_handler(ctx: Context<OptOutYield>) -> Result<()> {
+    let yield_position = &ctx.accounts.yield_position;
+
+    emit!(YieldOptedOutEvent {
+        mint: yield_position.mint,
+        owner: yield_position.owner,
+        token_account: yield_position.token_account,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// ============================================================================
+// Distribute Yield
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct DistributeYield<'info> {
+    #[account(
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        bump = token_config.bump,
+        constraint = !token_config.mint_paused @ ErrorCode::MintingPaused,
+        constraint = token_config.is_setup_complete() @ ErrorCode::SetupIncomplete,
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        mut,
+        constraint = mint.key() == token_config.mint @ ErrorCode::MintMismatch,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED, mint.key().as_ref()],
+        bump = mint_authority.bump,
+    )]
+    pub mint_authority: Account<'info, MintAuthority>,
+
+    #[account(
+        seeds = [YIELD_CONFIG_SEED, mint.key().as_ref()],
+        bump = yield_config.bump,
+    )]
+    pub yield_config: Account<'info, YieldConfig>,
+
+    #[account(
+        mut,
+        seeds = [YIELD_POSITION_SEED, mint.key().as_ref(), token_account.key().as_ref()],
+        bump = yield_position.bump,
+    )]
+    pub yield_position: Account<'info, YieldPosition>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+    )]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [BLACK_LIST_SEED, mint.key().as_ref()],
+        bump = blacklist.bump,
+    )]
+    pub blacklist: Account<'info, BlackList>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    // Remaining accounts: the owner's `BlacklistEntry` if they are
+    // blacklisted
+}
+
+/// Mint the yield a position has accrued over the closed periods. Anyone can
+/// call it.
+pub fn distribute_yield_handler(ctx: Context<DistributeYield>) -> Result<()> {
+    let owner = ctx.accounts.token_account.owner;
+    require!(
+        !ctx.accounts
+            .blacklist
+            .is_blocked(&owner, ctx.remaining_accounts, &Clock::get()?)?,
+        ErrorCode::ReceiverBlacklisted
+    );
+
+    let yield_config = &ctx.accounts.yield_config;
+    let amount = ctx
+        .accounts
+        .yield_position
+        .accrue(yield_config.yield_index, ctx.accounts.token_account.amount)?;
+
+    if amount > 0 {
+        yield_config.check_mintable(ctx.accounts.mint.supply, amount)?;
+
+        let mint_key = ctx.accounts.mint.key();
+        let seeds = &[
+            MINT_AUTHORITY_SEED,
+            mint_key.as_ref(),
+            &[ctx.accounts.mint_authority.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_accounts = MintTo {
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.token_account.to_account_info(),
+            authority: ctx.accounts.mint_authority.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token_interface::mint_to(cpi_ctx, amount)?;
+
+        ctx.accounts.token_account.reload()?;
+    }
+
+    ctx.accounts.yield_position.balance = ctx.accounts.token_account.amount;
+
+    emit!(YieldDistributedEvent {
+        mint: ctx.accounts.mint.key(),
+        owner,
+        token_account: ctx.accounts.token_account.key(),
+        amount,
+        period: yield_config.period,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Lower an account's tracked yield balance to `balance` after tokens leave
+/// it. Accounts that have not opted in have no position and nothing to track.
+pub(crate) fn record_yield_outflow(yield_position: &AccountInfo, balance: u64) -> Result<()> {
+    if yield_position.owner != &crate::ID {
+        return Ok(());
+    }
+
+    let mut data = yield_position.try_borrow_mut_data()?;
+    let mut position = YieldPosition::try_deserialize(&mut &data[..])?;
+    position.record_outflow(balance);
+
+    position.try_serialize(&mut &mut data[..])
+}
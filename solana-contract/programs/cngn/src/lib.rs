@@ -1,11 +1,14 @@
 // lib.rs
 use anchor_lang::prelude::*;
+use spl_discriminator::SplDiscriminate;
+use spl_transfer_hook_interface::instruction::ExecuteInstruction;
 
-mod errors;
-mod events;
-mod instructions;
-mod state;
-use instructions::*;
+pub mod errors;
+pub mod events;
+pub mod instructions;
+pub mod state;
+pub use instructions::*;
+pub use state::{BatchMode, BlacklistDetails, MetadataField};
 
 
 declare_id!("25hdB3aDv1sfkMCcXbNkPBPvtU7ZmEPoSdXcC1m1dMZN");
@@ -21,8 +24,16 @@ pub mod cngn {
         symbol: String,
         uri: String,
         decimals: u8,
+        fee_withdraw_elgamal_pubkey: [u8; 32],
     ) -> Result<()> {
-        instructions::initialize::initialize_handler(ctx, name, symbol, uri, decimals)
+        instructions::initialize::initialize_handler(
+            ctx,
+            name,
+            symbol,
+            uri,
+            decimals,
+            fee_withdraw_elgamal_pubkey,
+        )
     }
     pub fn initialize_secondary(ctx: Context<InitializeSecondary>) -> Result<()> {
         instructions::initialize::initialize_secondary_handler(ctx)
@@ -47,6 +58,15 @@ pub mod cngn {
     pub fn initialize_third(ctx: Context<InitializeThird>) -> Result<()> {
         instructions::initialize::initialize_third_handler(ctx)
     }
+
+    pub fn migrate_token_config(ctx: Context<MigrateTokenConfig>) -> Result<()> {
+        instructions::initialize::migrate_token_config_handler(ctx)
+    }
+
+    pub fn upgrade_transfer_hook(ctx: Context<UpgradeTransferHook>) -> Result<()> {
+        instructions::initialize::upgrade_transfer_hook_handler(ctx)
+    }
+
     pub fn mint(ctx: Context<MintTokens>, amount: u64) -> Result<()> {
         instructions::mint::handler(ctx, amount)
     }
@@ -86,14 +106,28 @@ pub mod cngn {
         instructions::admin::remove_trusted_contract_handler(ctx, contract)
     }
 
-    pub fn add_blacklist(ctx: Context<AddBlackList>, evil_user: Pubkey) -> Result<()> {
-        instructions::admin::add_blacklist_handler(ctx, evil_user)
+    pub fn add_blacklist<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AddBlackList<'info>>,
+        evil_user: Pubkey,
+        details: BlacklistDetails,
+    ) -> Result<()> {
+        instructions::admin::add_blacklist_handler(ctx, evil_user, details)
     }
 
-    pub fn remove_blacklist(ctx: Context<RemoveBlackList>, clear_user: Pubkey) -> Result<()> {
+    pub fn remove_blacklist<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RemoveBlackList<'info>>,
+        clear_user: Pubkey,
+    ) -> Result<()> {
         instructions::admin::remove_blacklist_handler(ctx, clear_user)
     }
 
+    pub fn prune_expired_blacklist(
+        ctx: Context<PruneExpiredBlacklist>,
+        user: Pubkey,
+    ) -> Result<()> {
+        instructions::admin::prune_expired_blacklist_handler(ctx, user)
+    }
+
     pub fn whitelist_internal_user(
         ctx: Context<WhitelistInternalUser>,
         user: Pubkey,
@@ -133,24 +167,313 @@ pub mod cngn {
         instructions::admin::change_admin_handler(ctx, new_admin)
     }
 
-    // fallback instruction handler as workaround to anchor instruction discriminator check
-    // pub fn fallback<'info>(
-    //     program_id: &Pubkey,
-    //     accounts: &'info [AccountInfo<'info>],
-    //     data: &[u8],
-    // ) -> Result<()> {
-    //     let instruction = TransferHookInstruction::unpack(data)?;
-
-    //     // match instruction discriminator to transfer hook interface execute instruction
-    //     // token2022 program CPIs this instruction on token transfer
-    //     match instruction {
-    //         TransferHookInstruction::Execute { amount } => {
-    //             let amount_bytes = amount.to_le_bytes();
-
-    //             // invoke custom transfer hook instruction on our program
-    //             __private::__global::transfer_hook(program_id, accounts, &amount_bytes)
-    //         }
-    //         _ => return Err(ProgramError::InvalidInstructionData.into()),
-    //     }
-    // }
+    pub fn initialize_bridge(ctx: Context<InitializeBridge>) -> Result<()> {
+        instructions::bridge::initialize_bridge_handler(ctx)
+    }
+
+    pub fn update_bridge(
+        ctx: Context<UpdateBridge>,
+        attesters: Vec<Pubkey>,
+        threshold: u8,
+        paused: bool,
+    ) -> Result<()> {
+        instructions::bridge::update_bridge_handler(ctx, attesters, threshold, paused)
+    }
+
+    pub fn bridge_in(
+        ctx: Context<BridgeIn>,
+        source_chain: u32,
+        nonce: u64,
+        sender: [u8; 32],
+        amount: u128,
+    ) -> Result<()> {
+        instructions::bridge::bridge_in_handler(ctx, source_chain, nonce, sender, amount)
+    }
+
+    pub fn bridge_out(
+        ctx: Context<BridgeOut>,
+        destination_chain: u32,
+        recipient: [u8; 32],
+        amount: u64,
+    ) -> Result<()> {
+        instructions::bridge::bridge_out_handler(ctx, destination_chain, recipient, amount)
+    }
+
+    pub fn initialize_metadata(ctx: Context<InitializeMetadata>, uri: String) -> Result<()> {
+        instructions::metadata::initialize_metadata_handler(ctx, uri)
+    }
+
+    pub fn update_metadata_field(
+        ctx: Context<UpdateMetadataField>,
+        field: MetadataField,
+        value: String,
+    ) -> Result<()> {
+        instructions::metadata::update_metadata_field_handler(ctx, field, value)
+    }
+
+    pub fn initialize_kyc(ctx: Context<InitializeKyc>) -> Result<()> {
+        instructions::kyc::initialize_kyc_handler(ctx)
+    }
+
+    pub fn add_kyc_operator(ctx: Context<AddKycOperator>, operator: Pubkey) -> Result<()> {
+        instructions::kyc::add_kyc_operator_handler(ctx, operator)
+    }
+
+    pub fn remove_kyc_operator(ctx: Context<RemoveKycOperator>, operator: Pubkey) -> Result<()> {
+        instructions::kyc::remove_kyc_operator_handler(ctx, operator)
+    }
+
+    pub fn kyc_approve(ctx: Context<KycApprove>, tier: u8) -> Result<()> {
+        instructions::kyc::kyc_approve_handler(ctx, tier)
+    }
+
+    pub fn kyc_approve_allowlisted(
+        ctx: Context<KycApproveAllowlisted>,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::kyc::kyc_approve_allowlisted_handler(ctx, proof)
+    }
+
+    pub fn update_external_allowlist(
+        ctx: Context<UpdateExternalAllowlist>,
+        merkle_root: [u8; 32],
+        grace_period_seconds: u32,
+    ) -> Result<()> {
+        instructions::external_allowlist::update_external_allowlist_handler(
+            ctx,
+            merkle_root,
+            grace_period_seconds,
+        )
+    }
+
+    pub fn revoke_allowlist_approval(ctx: Context<RevokeAllowlistApproval>) -> Result<()> {
+        instructions::external_allowlist::revoke_allowlist_approval_handler(ctx)
+    }
+
+    pub fn configure_transfer_tier(
+        ctx: Context<ConfigureTransferTier>,
+        tier: u8,
+        per_transaction_limit: u64,
+        daily_limit: u64,
+    ) -> Result<()> {
+        instructions::transfer_limits::configure_transfer_tier_handler(
+            ctx,
+            tier,
+            per_transaction_limit,
+            daily_limit,
+        )
+    }
+
+    pub fn set_transfer_tier(ctx: Context<SetTransferTier>, owner: Pubkey, tier: u8) -> Result<()> {
+        instructions::transfer_limits::set_transfer_tier_handler(ctx, owner, tier)
+    }
+
+    pub fn set_transfer_fee(
+        ctx: Context<SetTransferFee>,
+        basis_points: u16,
+        maximum_fee: u64,
+    ) -> Result<()> {
+        instructions::transfer_fee::set_transfer_fee_handler(ctx, basis_points, maximum_fee)
+    }
+
+    pub fn update_fee_config(
+        ctx: Context<UpdateFeeConfig>,
+        exempt_internal_whitelist: bool,
+    ) -> Result<()> {
+        instructions::transfer_fee::update_fee_config_handler(ctx, exempt_internal_whitelist)
+    }
+
+    pub fn harvest_transfer_fees<'info>(
+        ctx: Context<'_, '_, 'info, 'info, HarvestTransferFees<'info>>,
+    ) -> Result<()> {
+        instructions::transfer_fee::harvest_transfer_fees_handler(ctx)
+    }
+
+    pub fn withdraw_transfer_fees(ctx: Context<WithdrawTransferFees>) -> Result<()> {
+        instructions::transfer_fee::withdraw_transfer_fees_handler(ctx)
+    }
+
+    pub fn open_fee_refund(ctx: Context<OpenFeeRefund>) -> Result<()> {
+        instructions::transfer_fee::open_fee_refund_handler(ctx)
+    }
+
+    pub fn refund_exempt_fees(ctx: Context<RefundExemptFees>) -> Result<()> {
+        instructions::transfer_fee::refund_exempt_fees_handler(ctx)
+    }
+
+    pub fn update_confidential_auditor(
+        ctx: Context<UpdateConfidentialAuditor>,
+        auditor_elgamal_pubkey: Option<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::confidential::update_confidential_auditor_handler(
+            ctx,
+            auditor_elgamal_pubkey,
+        )
+    }
+
+    pub fn approve_confidential_account(ctx: Context<ApproveConfidentialAccount>) -> Result<()> {
+        instructions::confidential::approve_confidential_account_handler(ctx)
+    }
+
+    pub fn update_yield_config(
+        ctx: Context<UpdateYieldConfig>,
+        annual_rate_basis_points: u16,
+        period_seconds: u32,
+        supply_cap: u64,
+        reserves: u64,
+    ) -> Result<()> {
+        instructions::yield_distribution::update_yield_config_handler(
+            ctx,
+            annual_rate_basis_points,
+            period_seconds,
+            supply_cap,
+            reserves,
+        )
+    }
+
+    pub fn advance_yield_period(ctx: Context<AdvanceYieldPeriod>) -> Result<()> {
+        instructions::yield_distribution::advance_yield_period_handler(ctx)
+    }
+
+    pub fn opt_in_yield(ctx: Context<OptInYield>) -> Result<()> {
+        instructions::yield_distribution::opt_in_yield_handler(ctx)
+    }
+
+    pub fn opt_out_yield(ctx: Context<OptOutYield>) -> Result<()> {
+        instructions::yield_distribution::opt_out_yield_handler(ctx)
+    }
+
+    pub fn distribute_yield(ctx: Context<DistributeYield>) -> Result<()> {
+        instructions::yield_distribution::distribute_yield_handler(ctx)
+    }
+
+    pub fn create_escrow(
+        ctx: Context<CreateEscrow>,
+        id: u64,
+        payee: Pubkey,
+        arbiter: Pubkey,
+        amount: u64,
+        deadline: i64,
+    ) -> Result<()> {
+        instructions::escrow::create_escrow_handler(ctx, id, payee, arbiter, amount, deadline)
+    }
+
+    pub fn dispute_escrow(ctx: Context<DisputeEscrow>) -> Result<()> {
+        instructions::escrow::dispute_escrow_handler(ctx)
+    }
+
+    pub fn release_escrow(ctx: Context<SettleEscrow>) -> Result<()> {
+        instructions::escrow::release_escrow_handler(ctx)
+    }
+
+    pub fn refund_escrow(ctx: Context<SettleEscrow>) -> Result<()> {
+        instructions::escrow::refund_escrow_handler(ctx)
+    }
+
+    pub fn create_subscription(
+        ctx: Context<CreateSubscription>,
+        payee: Pubkey,
+        amount_per_period: u64,
+        period_seconds: u32,
+    ) -> Result<()> {
+        instructions::subscription::create_subscription_handler(
+            ctx,
+            payee,
+            amount_per_period,
+            period_seconds,
+        )
+    }
+
+    pub fn execute_pull(ctx: Context<ExecutePull>, amount: u64) -> Result<()> {
+        instructions::subscription::execute_pull_handler(ctx, amount)
+    }
+
+    pub fn cancel_subscription(ctx: Context<CancelSubscription>) -> Result<()> {
+        instructions::subscription::cancel_subscription_handler(ctx)
+    }
+
+    pub fn batch_transfer<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchTransfer<'info>>,
+        amounts: Vec<u64>,
+        mode: BatchMode,
+    ) -> Result<()> {
+        instructions::batch::batch_transfer_handler(ctx, amounts, mode)
+    }
+
+    pub fn batch_add_blacklist<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AddBlackList<'info>>,
+        users: Vec<Pubkey>,
+        details: BlacklistDetails,
+    ) -> Result<()> {
+        instructions::batch_admin::batch_add_blacklist_handler(ctx, users, details)
+    }
+
+    pub fn batch_remove_blacklist<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RemoveBlackList<'info>>,
+        users: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::batch_admin::batch_remove_blacklist_handler(ctx, users)
+    }
+
+    pub fn batch_whitelist_internal(
+        ctx: Context<WhitelistInternalUser>,
+        users: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::batch_admin::batch_whitelist_internal_handler(ctx, users)
+    }
+
+    pub fn batch_whitelist_external(
+        ctx: Context<WhitelistExternalUser>,
+        users: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::batch_admin::batch_whitelist_external_handler(ctx, users)
+    }
+
+    pub fn batch_add_can_forward(
+        ctx: Context<AddCanForward>,
+        forwarders: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::batch_admin::batch_add_can_forward_handler(ctx, forwarders)
+    }
+
+    pub fn batch_remove_can_forward(
+        ctx: Context<RemoveCanForward>,
+        forwarders: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::batch_admin::batch_remove_can_forward_handler(ctx, forwarders)
+    }
+
+    pub fn batch_add_trusted_contract(
+        ctx: Context<AddTrustedContract>,
+        contracts: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::batch_admin::batch_add_trusted_contract_handler(ctx, contracts)
+    }
+
+    pub fn batch_remove_trusted_contract(
+        ctx: Context<RemoveTrustedContract>,
+        contracts: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::batch_admin::batch_remove_trusted_contract_handler(ctx, contracts)
+    }
+
+    pub fn get_address_status(
+        ctx: Context<GetAddressStatus>,
+        address: Pubkey,
+    ) -> Result<AddressStatus> {
+        instructions::view::get_address_status_handler(ctx, address)
+    }
+
+    pub fn get_multisig_info(ctx: Context<GetMultisigInfo>) -> Result<MultisigInfo> {
+        instructions::view::get_multisig_info_handler(ctx)
+    }
+
+    pub fn get_token_state(ctx: Context<GetTokenState>) -> Result<TokenState> {
+        instructions::view::get_token_state_handler(ctx)
+    }
+
+    #[instruction(discriminator = ExecuteInstruction::SPL_DISCRIMINATOR_SLICE)]
+    pub fn transfer_hook(ctx: Context<TransferHook>, amount: u64) -> Result<()> {
+        instructions::transfer_hook::transfer_hook_handler(ctx, amount)
+    }
 }
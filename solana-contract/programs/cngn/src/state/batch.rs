@@ -0,0 +1,142 @@
+// state/batch.rs
+use crate::state::{build_readable_message, BlacklistDetails};
+use anchor_lang::prelude::*;
+use sha2::{Digest, Sha256};
+
+/// Recipients per `batch_transfer`; larger payrolls are split across
+/// transactions
+pub const MAX_BATCH_SIZE: usize = 32;
+
+/// Addresses per batch admin instruction. The transaction size limit is
+/// usually reached first: about 15 addresses fit next to two owner signatures
+/// in a legacy transaction.
+pub const MAX_ADMIN_BATCH_SIZE: usize = 20;
+
+/// What `batch_transfer` does with a recipient it cannot pay (blacklisted
+/// owner, frozen or foreign token account)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BatchMode {
+    Atomic,      // Fail the whole batch
+    SkipInvalid, // Pay everyone else
+}
+
+/// Leaf of the payout tree: `sha256(destination || amount)`
+pub fn payout_leaf(destination: &Pubkey, amount: u64) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(destination.as_ref());
+    hasher.update(amount.to_le_bytes());
+    hasher.finalize().into()
+}
+
+/// Root over the leaves in payout order. Each level hashes adjacent pairs
+/// as `sha256(left || right)` and carries an odd last node up unchanged; an
+/// empty batch has an all-zero root.
+pub fn merkle_root(mut level: Vec<[u8; 32]>) -> [u8; 32] {
+    if level.is_empty() {
+        return [0; 32];
+    }
+
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => {
+                    let mut hasher = Sha256::new();
+                    hasher.update(left);
+                    hasher.update(right);
+                    hasher.finalize().into()
+                }
+                [node] => *node,
+                _ => unreachable!(),
+            })
+            .collect();
+    }
+
+    level[0]
+}
+
+/// `sha256` over the concatenated addresses of a batch admin instruction
+pub fn address_list_hash(addresses: &[Pubkey]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    for address in addresses {
+        hasher.update(address.as_ref());
+    }
+    hasher.finalize().into()
+}
+
+/// Build message for a batch admin instruction: the action tag (e.g.
+/// `BATCH_ADD_BLACKLIST`), the list account, the hash of the address list and
+/// the nonce. One approval covers the whole list.
+pub fn build_batch_admin_message(
+    action: &str,
+    list_account: &Pubkey,
+    addresses: &[Pubkey],
+    nonce: u64,
+) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+
+    hasher.update(action.as_bytes());
+    hasher.update(list_account.as_ref());
+    hasher.update(address_list_hash(addresses));
+    hasher.update(nonce.to_le_bytes());
+
+    hasher.finalize().to_vec()
+}
+
+// The list itself is too long to display, so owners see its size and hash
+// and check them against the list they were given.
+pub fn build_readable_batch_admin_message(
+    action: &str,
+    list_account: &Pubkey,
+    addresses: &[Pubkey],
+    nonce: u64,
+) -> Vec<u8> {
+    build_readable_message(
+        action,
+        &format!(
+            "count:{} list:{}",
+            addresses.len(),
+            hex::encode(address_list_hash(addresses))
+        ),
+        list_account,
+        nonce,
+    )
+}
+
+/// Build message for `batch_add_blacklist`: the batch message with the
+/// blacklist details every entry is recorded with
+pub fn build_batch_add_blacklist_message(
+    blacklist_account: &Pubkey,
+    users: &[Pubkey],
+    details: &BlacklistDetails,
+    nonce: u64,
+) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+
+    hasher.update(b"BATCH_ADD_BLACKLIST");
+    hasher.update(blacklist_account.as_ref());
+    hasher.update(address_list_hash(users));
+    details.update_digest(&mut hasher);
+    hasher.update(nonce.to_le_bytes());
+
+    hasher.finalize().to_vec()
+}
+
+pub fn build_readable_batch_add_blacklist_message(
+    blacklist_account: &Pubkey,
+    users: &[Pubkey],
+    details: &BlacklistDetails,
+    nonce: u64,
+) -> Vec<u8> {
+    build_readable_message(
+        "BATCH_ADD_BLACKLIST",
+        &format!(
+            "count:{} list:{} {}",
+            users.len(),
+            hex::encode(address_list_hash(users)),
+            details.readable()
+        ),
+        blacklist_account,
+        nonce,
+    )
+}
@@ -1,6 +1,10 @@
 // state/blacklist.rs
 use anchor_lang::prelude::*;
 use crate::errors::ErrorCode;
+use crate::state::{build_readable_message, update_string};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::str::FromStr;
 
 #[account]
 pub struct BlackList {
@@ -15,6 +19,33 @@ impl BlackList {
     pub fn is_blacklisted(&self, address: &Pubkey) -> bool {
         self.blacklist.contains(address)
     }
+
+    /// Whether `address` is blocked at `clock`: it is listed and its
+    /// `BlacklistEntry` has not expired. The entry is looked up among
+    /// `entries`; a listing whose entry is not passed, or that predates
+    /// entries and has none, stays in force.
+    pub fn is_blocked(
+        &self,
+        address: &Pubkey,
+        entries: &[AccountInfo],
+        clock: &Clock,
+    ) -> Result<bool> {
+        if !self.is_blacklisted(address) {
+            return Ok(false);
+        }
+
+        let (key, _) = BlacklistEntry::address(&self.mint, address);
+        let Some(entry) = entries.iter().find(|entry| entry.key == &key) else {
+            return Ok(true);
+        };
+        if entry.owner != &crate::ID {
+            return Ok(true);
+        }
+
+        let data = entry.try_borrow_data()?;
+        let entry = BlacklistEntry::try_deserialize(&mut &data[..])?;
+        Ok(!entry.is_lapsed(clock.unix_timestamp))
+    }
     
     pub fn add(&mut self, address: &Pubkey) -> Result<()> {
         if self.blacklist.len() >= Self::MAX_BLACKLISTED {
@@ -47,4 +78,166 @@ impl BlackList {
         4 + (32 * max_blacklisted) + // vec length + blacklist
         1 // bump
     }
-}
\ No newline at end of file
+}
+
+pub const BLACKLIST_ENTRY_SEED: &[u8] = b"blacklist-entry";
+
+/// Why an address was blacklisted
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlacklistReason {
+    Sanctions,
+    CourtOrder,
+    FraudReport,
+    InternalInvestigation,
+    Other,
+}
+
+impl BlacklistReason {
+    pub const ALL: [Self; 5] = [
+        Self::Sanctions,
+        Self::CourtOrder,
+        Self::FraudReport,
+        Self::InternalInvestigation,
+        Self::Other,
+    ];
+
+    /// Label used in readable approval messages
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Sanctions => "sanctions",
+            Self::CourtOrder => "court_order",
+            Self::FraudReport => "fraud_report",
+            Self::InternalInvestigation => "internal_investigation",
+            Self::Other => "other",
+        }
+    }
+}
+
+impl fmt::Display for BlacklistReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+impl FromStr for BlacklistReason {
+    type Err = String;
+
+    /// Parse a reason from its label
+    fn from_str(label: &str) -> std::result::Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|reason| reason.label() == label)
+            .ok_or_else(|| {
+                let labels: Vec<&str> = Self::ALL.iter().map(Self::label).collect();
+                format!(
+                    "unknown blacklist reason {label:?}, expected one of {}",
+                    labels.join(", ")
+                )
+            })
+    }
+}
+
+/// The compliance record approved with a blacklisting
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BlacklistDetails {
+    pub reason: BlacklistReason,
+    pub case_reference: String,
+    pub review_at: i64,  // Unix time the listing is up for review, 0 for none
+    pub expires_at: i64, // Unix time the listing lapses on its own, 0 for never
+}
+
+impl BlacklistDetails {
+    pub const MAX_CASE_REFERENCE_LENGTH: usize = 64;
+
+    pub const LEN: usize = 1 + // reason
+        4 + Self::MAX_CASE_REFERENCE_LENGTH + // case_reference
+        8 + // review_at
+        8; // expires_at
+
+    pub fn validate(&self, now: i64) -> Result<()> {
+        require!(
+            !self.case_reference.is_empty()
+                && self.case_reference.len() <= Self::MAX_CASE_REFERENCE_LENGTH
+                && self
+                    .case_reference
+                    .bytes()
+                    .all(|byte| byte.is_ascii_graphic() || byte == b' ')
+                && (self.review_at == 0 || self.review_at > now)
+                && (self.expires_at == 0 || self.expires_at > now),
+            ErrorCode::InvalidBlacklistDetails
+        );
+
+        Ok(())
+    }
+
+    /// Commit the details to an approval digest
+    pub fn update_digest(&self, hasher: &mut Sha256) {
+        hasher.update([self.reason as u8]);
+        update_string(hasher, &self.case_reference);
+        hasher.update(self.review_at.to_le_bytes());
+        hasher.update(self.expires_at.to_le_bytes());
+    }
+
+    /// The details as they appear in readable approval messages; the case
+    /// reference is quoted like metadata values, and `validate` keeps it to
+    /// printable ASCII so the message stays in the restricted ASCII format
+    pub fn readable(&self) -> String {
+        format!(
+            "reason:{} case:{:?} review_at:{} expires_at:{}",
+            self.reason.label(),
+            self.case_reference,
+            self.review_at,
+            self.expires_at
+        )
+    }
+}
+
+/// Why and since when `user` is blacklisted, at
+/// `["blacklist-entry", mint, user]`. `BlackList` remains the list every
+/// check reads; the entry is created and closed alongside it, and is the
+/// only record of when the listing expires.
+#[account]
+pub struct BlacklistEntry {
+    pub mint: Pubkey,
+    pub user: Pubkey,
+    pub details: BlacklistDetails,
+    pub listed_at: i64,
+    pub payer: Pubkey, // Funded the entry and gets its rent back when it is closed
+    pub bump: u8,
+}
+
+impl BlacklistEntry {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // mint
+        32 + // user
+        BlacklistDetails::LEN + // details
+        8 + // listed_at
+        32 + // payer
+        1; // bump
+
+    /// Whether the listing's `expires_at` has passed at `now`
+    pub fn is_lapsed(&self, now: i64) -> bool {
+        self.details.expires_at != 0 && now >= self.details.expires_at
+    }
+
+    pub fn address(mint: &Pubkey, user: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[BLACKLIST_ENTRY_SEED, mint.as_ref(), user.as_ref()],
+            &crate::ID,
+        )
+    }
+}
+
+pub fn build_readable_add_blacklist_message(
+    blacklist_account: &Pubkey,
+    user: &Pubkey,
+    details: &BlacklistDetails,
+    nonce: u64,
+) -> Vec<u8> {
+    build_readable_message(
+        "ADD_BLACKLIST",
+        &format!("target:{user} {}", details.readable()),
+        blacklist_account,
+        nonce,
+    )
+}
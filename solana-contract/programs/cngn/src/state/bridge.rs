@@ -0,0 +1,134 @@
+// state/bridge.rs
+use crate::errors::ErrorCode;
+use crate::state::build_readable_message;
+use anchor_lang::prelude::*;
+use cngn_messages::TransferMessage;
+use sha2::{Digest, Sha256};
+
+pub const BRIDGE_CONFIG_SEED: &[u8] = b"bridge-config";
+pub const BRIDGE_MESSAGE_SEED: &[u8] = b"bridge-message";
+
+#[account]
+pub struct BridgeConfig {
+    pub mint: Pubkey,
+    pub attesters: Vec<Pubkey>, // Keys allowed to attest inbound transfers
+    pub threshold: u8,          // Attestations required per inbound transfer
+    pub paused: bool,           // Blocks both directions while set
+    pub outbound_nonce: u64,    // Sequence number of the next bridge_out
+    pub bump: u8,
+}
+
+impl BridgeConfig {
+    // Attestations are reported as a u16 bitmap over `attesters`
+    pub const MAX_ATTESTERS: usize = 10;
+
+    pub const LEN: usize = 8 +              // discriminator
+        32 +                                // mint
+        4 + 32 * Self::MAX_ATTESTERS +      // attesters vec
+        1 +                                 // threshold
+        1 +                                 // paused
+        8 +                                 // outbound_nonce
+        1; // bump
+
+    pub fn set_attesters(&mut self, attesters: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        require!(
+            attesters.len() <= Self::MAX_ATTESTERS,
+            ErrorCode::TooManyAttesters
+        );
+        for (index, attester) in attesters.iter().enumerate() {
+            require!(
+                !attesters[..index].contains(attester),
+                ErrorCode::DuplicateAttesters
+            );
+        }
+        // An empty attester set is allowed to disable inbound transfers
+        require!(
+            threshold as usize <= attesters.len() && (threshold > 0 || attesters.is_empty()),
+            ErrorCode::InvalidThreshold
+        );
+
+        self.attesters = attesters;
+        self.threshold = threshold;
+
+        Ok(())
+    }
+}
+
+/// Marks an inbound message as processed; its address is derived from the
+/// source chain and the source chain's nonce, so a message can only be
+/// minted once.
+#[account]
+pub struct ProcessedBridgeMessage {
+    pub source_chain: u32,
+    pub nonce: u64,
+    pub sender: [u8; 32],
+    pub recipient: Pubkey,
+    pub amount: u64, // In the token's own decimals
+    pub processed_at: i64,
+    pub bump: u8,
+}
+
+impl ProcessedBridgeMessage {
+    pub const LEN: usize = 8 + // discriminator
+        4 +  // source_chain
+        8 +  // nonce
+        32 + // sender
+        32 + // recipient
+        8 +  // amount
+        8 +  // processed_at
+        1; // bump
+}
+
+/// Build the digest attesters sign to release an inbound transfer: the
+/// `cngn-messages` encoding of the transfer, bound to this bridge config.
+pub fn build_bridge_in_message(bridge_config: &Pubkey, message: &TransferMessage) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+
+    hasher.update(b"BRIDGE_IN");
+    hasher.update(bridge_config.as_ref());
+    hasher.update(message.encode());
+
+    hasher.finalize().to_vec()
+}
+
+/// Build message for replacing the bridge attesters and pause flag
+pub fn build_update_bridge_message(
+    bridge_config: &Pubkey,
+    attesters: &[Pubkey],
+    threshold: u8,
+    paused: bool,
+    nonce: u64,
+) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+
+    hasher.update(b"UPDATE_BRIDGE");
+    hasher.update(bridge_config.as_ref());
+    for attester in attesters {
+        hasher.update(attester.as_ref());
+    }
+    hasher.update([threshold, paused as u8]);
+    hasher.update(nonce.to_le_bytes());
+
+    hasher.finalize().to_vec()
+}
+
+pub fn build_readable_update_bridge_message(
+    bridge_config: &Pubkey,
+    attesters: &[Pubkey],
+    threshold: u8,
+    paused: bool,
+    nonce: u64,
+) -> Vec<u8> {
+    let attesters = attesters
+        .iter()
+        .map(|attester| attester.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+
+    build_readable_message(
+        "UPDATE_BRIDGE",
+        &format!("attesters:{attesters} threshold:{threshold} paused:{paused}"),
+        bridge_config,
+        nonce,
+    )
+}
@@ -0,0 +1,50 @@
+// state/confidential.rs
+use crate::state::build_readable_message;
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::solana_zk_sdk::encryption::pod::elgamal::PodElGamalPubkey;
+use sha2::{Digest, Sha256};
+
+/// Token-2022 calls the transfer hook with this amount for confidential
+/// transfers, whose amount is encrypted.
+pub const CONFIDENTIAL_TRANSFER_AMOUNT: u64 = u64::MAX;
+
+/// Build message for setting or clearing the confidential transfer auditor
+pub fn build_update_confidential_auditor_message(
+    mint: &Pubkey,
+    auditor_elgamal_pubkey: Option<[u8; 32]>,
+    nonce: u64,
+) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+
+    hasher.update(b"UPDATE_CONFIDENTIAL_AUDITOR");
+    hasher.update(mint.as_ref());
+    match auditor_elgamal_pubkey {
+        Some(auditor) => {
+            hasher.update([1]);
+            hasher.update(auditor);
+        }
+        None => hasher.update([0]),
+    }
+    hasher.update(nonce.to_le_bytes());
+
+    hasher.finalize().to_vec()
+}
+
+/// The auditor appears base64-encoded, as Solana tooling prints ElGamal keys
+pub fn build_readable_update_confidential_auditor_message(
+    mint: &Pubkey,
+    auditor_elgamal_pubkey: Option<[u8; 32]>,
+    nonce: u64,
+) -> Vec<u8> {
+    let auditor = match auditor_elgamal_pubkey {
+        Some(auditor) => PodElGamalPubkey::from(auditor).to_string(),
+        None => "none".to_string(),
+    };
+
+    build_readable_message(
+        "UPDATE_CONFIDENTIAL_AUDITOR",
+        &format!("auditor:{auditor}"),
+        mint,
+        nonce,
+    )
+}
@@ -0,0 +1,64 @@
+// state/escrow.rs
+use anchor_lang::prelude::*;
+
+pub const ESCROW_SEED: &[u8] = b"escrow";
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EscrowStatus {
+    Locked,
+    Disputed, // Only the arbiter can settle it
+    Released,
+    Refunded,
+}
+
+/// Funds a payer has locked for a payee. The tokens sit in the escrow PDA's
+/// associated token account until they are released or refunded.
+#[account]
+pub struct Escrow {
+    pub mint: Pubkey,
+    pub payer: Pubkey,
+    pub payee: Pubkey,
+    pub arbiter: Pubkey,
+    pub id: u64, // Chosen by the payer; part of the seeds
+    pub amount: u64,
+    pub deadline: i64, // Anyone can refund a locked escrow after this
+    pub status: EscrowStatus,
+    pub bump: u8,
+}
+
+impl Escrow {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // mint
+        32 + // payer
+        32 + // payee
+        32 + // arbiter
+        8 +  // id
+        8 +  // amount
+        8 +  // deadline
+        1 +  // status
+        1; // bump
+
+    pub fn is_open(&self) -> bool {
+        matches!(self.status, EscrowStatus::Locked | EscrowStatus::Disputed)
+    }
+
+    /// The payer or the arbiter can release a locked escrow; once disputed
+    /// only the arbiter can.
+    pub fn can_release(&self, authority: &Pubkey) -> bool {
+        match self.status {
+            EscrowStatus::Locked => *authority == self.payer || *authority == self.arbiter,
+            EscrowStatus::Disputed => *authority == self.arbiter,
+            _ => false,
+        }
+    }
+
+    /// Anyone can refund a locked escrow after its deadline; the arbiter can
+    /// refund a disputed one at any time.
+    pub fn can_refund(&self, authority: &Pubkey, now: i64) -> bool {
+        match self.status {
+            EscrowStatus::Locked => now >= self.deadline,
+            EscrowStatus::Disputed => *authority == self.arbiter,
+            _ => false,
+        }
+    }
+}
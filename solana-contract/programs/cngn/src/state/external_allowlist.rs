@@ -0,0 +1,138 @@
+// state/external_allowlist.rs
+use crate::state::build_readable_message;
+use anchor_lang::prelude::*;
+use sha2::{Digest, Sha256};
+
+pub const EXTERNAL_ALLOWLIST_SEED: &[u8] = b"external-allowlist";
+pub const ALLOWLIST_APPROVAL_SEED: &[u8] = b"allowlist-approval";
+
+/// Merkle root of externally whitelisted owners too many to store in
+/// `ExternalWhiteList`. Owners prove membership when
+/// `kyc_approve_allowlisted` thaws their token accounts, and
+/// `revoke_allowlist_approval` freezes those accounts again once the root
+/// they proved against is retired. After a rotation the previous root stays
+/// valid until `previous_root_expires_at`, so proofs built against it keep
+/// working while clients pick up the new tree.
+#[account]
+pub struct ExternalAllowlist {
+    pub mint: Pubkey,
+    pub merkle_root: [u8; 32], // All zero while no root is published
+    pub previous_merkle_root: [u8; 32],
+    pub previous_root_expires_at: i64,
+    pub bump: u8,
+}
+
+impl ExternalAllowlist {
+    pub const MAX_GRACE_PERIOD_SECONDS: u32 = 30 * 24 * 60 * 60;
+    /// Enough for 2^32 owners
+    pub const MAX_PROOF_LENGTH: usize = 32;
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // mint
+        32 + // merkle_root
+        32 + // previous_merkle_root
+        8 +  // previous_root_expires_at
+        1; // bump
+
+    /// Publish `merkle_root`, keeping the current root valid for
+    /// `grace_period_seconds`
+    pub fn rotate(&mut self, merkle_root: [u8; 32], grace_period_seconds: u32, now: i64) {
+        self.previous_merkle_root = self.merkle_root;
+        self.previous_root_expires_at = now + grace_period_seconds as i64;
+        self.merkle_root = merkle_root;
+    }
+
+    /// Whether `proof` shows `owner` is in the current tree, or in the
+    /// previous one during its grace period
+    pub fn is_allowlisted(&self, owner: &Pubkey, proof: &[[u8; 32]], now: i64) -> bool {
+        proof.len() <= Self::MAX_PROOF_LENGTH
+            && self.accepts_root(&allowlist_proof_root(owner, proof), now)
+    }
+
+    /// Whether `root` is the current root, or the previous one during its
+    /// grace period
+    pub fn accepts_root(&self, root: &[u8; 32], now: i64) -> bool {
+        let current = self.merkle_root != [0; 32] && *root == self.merkle_root;
+        let previous = self.previous_merkle_root != [0; 32]
+            && now < self.previous_root_expires_at
+            && *root == self.previous_merkle_root;
+
+        current || previous
+    }
+}
+
+/// Root an owner's allowlist proof was checked against when
+/// `kyc_approve_allowlisted` last approved them
+#[account]
+pub struct AllowlistApproval {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub merkle_root: [u8; 32],
+    pub bump: u8,
+}
+
+impl AllowlistApproval {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // mint
+        32 + // owner
+        32 + // merkle_root
+        1; // bump
+}
+
+/// Leaf of the allowlist tree
+pub fn allowlist_leaf(owner: &Pubkey) -> [u8; 32] {
+    Sha256::digest(owner.as_ref()).into()
+}
+
+/// Parent of two allowlist tree nodes, hashed in ascending byte order so a
+/// proof needs no left/right flags
+pub fn allowlist_parent(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// Root implied by `proof`, the sibling hashes from `owner`'s leaf up
+pub fn allowlist_proof_root(owner: &Pubkey, proof: &[[u8; 32]]) -> [u8; 32] {
+    proof.iter().fold(allowlist_leaf(owner), |node, sibling| {
+        allowlist_parent(&node, sibling)
+    })
+}
+
+/// Build message for publishing a new allowlist root
+pub fn build_update_external_allowlist_message(
+    external_allowlist: &Pubkey,
+    merkle_root: &[u8; 32],
+    grace_period_seconds: u32,
+    nonce: u64,
+) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+
+    hasher.update(b"UPDATE_EXTERNAL_ALLOWLIST");
+    hasher.update(external_allowlist.as_ref());
+    hasher.update(merkle_root);
+    hasher.update(grace_period_seconds.to_le_bytes());
+    hasher.update(nonce.to_le_bytes());
+
+    hasher.finalize().to_vec()
+}
+
+pub fn build_readable_update_external_allowlist_message(
+    external_allowlist: &Pubkey,
+    merkle_root: &[u8; 32],
+    grace_period_seconds: u32,
+    nonce: u64,
+) -> Vec<u8> {
+    build_readable_message(
+        "UPDATE_EXTERNAL_ALLOWLIST",
+        &format!(
+            "root:{} grace_period_seconds:{grace_period_seconds}",
+            hex::encode(merkle_root)
+        ),
+        external_allowlist,
+        nonce,
+    )
+}
@@ -0,0 +1,108 @@
+// state/kyc.rs
+use crate::errors::ErrorCode;
+use anchor_lang::prelude::*;
+use sha2::{Digest, Sha256};
+
+pub const KYC_OPERATORS_SEED: &[u8] = b"kyc-operators";
+pub const KYC_RECORD_SEED: &[u8] = b"kyc";
+
+/// Keys allowed to approve token account owners after KYC
+#[account]
+pub struct KycOperators {
+    pub mint: Pubkey,
+    pub operators: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+impl KycOperators {
+    pub const MAX_OPERATORS: usize = 10;
+
+    pub const LEN: usize = 8 +              // discriminator
+        32 +                                // mint
+        4 + 32 * Self::MAX_OPERATORS +      // operators vec
+        1; // bump
+
+    pub fn is_operator(&self, operator: &Pubkey) -> bool {
+        self.operators.contains(operator)
+    }
+
+    pub fn add(&mut self, operator: &Pubkey) -> Result<()> {
+        require!(
+            self.operators.len() < Self::MAX_OPERATORS,
+            ErrorCode::TooManyKycOperators
+        );
+
+        if !self.is_operator(operator) {
+            self.operators.push(*operator);
+        }
+
+        Ok(())
+    }
+
+    pub fn remove(&mut self, operator: &Pubkey) {
+        self.operators.retain(|key| key != operator);
+    }
+}
+
+/// KYC outcome for a token account owner, written by `kyc_approve`
+#[account]
+pub struct KycRecord {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub tier: u8, // 0 for whitelisted institutions, otherwise 1..=MAX_TIER
+    pub approved_by: Pubkey,
+    pub approved_at: i64,
+    pub bump: u8,
+}
+
+impl KycRecord {
+    /// Tier reserved for owners on the internal or external whitelist
+    pub const WHITELISTED_TIER: u8 = 0;
+    pub const MAX_TIER: u8 = 3;
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // mint
+        32 + // owner
+        1 +  // tier
+        32 + // approved_by
+        8 +  // approved_at
+        1; // bump
+
+    /// Whitelisted owners may take any tier including `WHITELISTED_TIER`;
+    /// everyone else needs a regular tier.
+    pub fn is_valid_tier(tier: u8, whitelisted: bool) -> bool {
+        tier <= Self::MAX_TIER && (whitelisted || tier != Self::WHITELISTED_TIER)
+    }
+}
+
+/// Build message for adding a KYC operator
+pub fn build_add_kyc_operator_message(
+    kyc_operators_account: &Pubkey,
+    operator: &Pubkey,
+    nonce: u64,
+) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+
+    hasher.update(b"ADD_KYC_OPERATOR");
+    hasher.update(kyc_operators_account.as_ref());
+    hasher.update(operator.as_ref());
+    hasher.update(nonce.to_le_bytes());
+
+    hasher.finalize().to_vec()
+}
+
+/// Build message for removing a KYC operator
+pub fn build_remove_kyc_operator_message(
+    kyc_operators_account: &Pubkey,
+    operator: &Pubkey,
+    nonce: u64,
+) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+
+    hasher.update(b"REMOVE_KYC_OPERATOR");
+    hasher.update(kyc_operators_account.as_ref());
+    hasher.update(operator.as_ref());
+    hasher.update(nonce.to_le_bytes());
+
+    hasher.finalize().to_vec()
+}
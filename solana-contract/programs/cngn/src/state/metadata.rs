@@ -0,0 +1,123 @@
+// state/metadata.rs
+use crate::errors::ErrorCode;
+use crate::state::{build_readable_message, TokenConfig};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::spl_token_metadata_interface::state::Field;
+use sha2::{Digest, Sha256};
+
+/// A Token-2022 metadata field; `Key` is an additional field such as
+/// `issuer` or `licence`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum MetadataField {
+    Name,
+    Symbol,
+    Uri,
+    Key(String),
+}
+
+impl MetadataField {
+    pub const MAX_KEY_LENGTH: usize = 32;
+    pub const MAX_VALUE_LENGTH: usize = 200;
+
+    /// Check `value` fits the field; name and symbol are also kept in
+    /// `TokenConfig` and share its limits.
+    pub fn validate(&self, value: &str) -> Result<()> {
+        let valid = match self {
+            Self::Name => !value.is_empty() && value.len() <= TokenConfig::MAX_NAME_LENGTH,
+            Self::Symbol => !value.is_empty() && value.len() <= TokenConfig::MAX_SYMBOL_LENGTH,
+            Self::Uri => value.len() <= Self::MAX_VALUE_LENGTH,
+            Self::Key(key) => {
+                !key.is_empty()
+                    && key.len() <= Self::MAX_KEY_LENGTH
+                    && value.len() <= Self::MAX_VALUE_LENGTH
+            }
+        };
+        require!(valid, ErrorCode::InvalidMetadataField);
+
+        Ok(())
+    }
+
+    /// Label used in readable approval messages
+    pub fn label(&self) -> String {
+        match self {
+            Self::Name => "name".to_string(),
+            Self::Symbol => "symbol".to_string(),
+            Self::Uri => "uri".to_string(),
+            Self::Key(key) => format!("key:{key:?}"),
+        }
+    }
+}
+
+impl From<MetadataField> for Field {
+    fn from(field: MetadataField) -> Self {
+        match field {
+            MetadataField::Name => Field::Name,
+            MetadataField::Symbol => Field::Symbol,
+            MetadataField::Uri => Field::Uri,
+            MetadataField::Key(key) => Field::Key(key),
+        }
+    }
+}
+
+pub(crate) fn update_string(hasher: &mut Sha256, value: &str) {
+    hasher.update((value.len() as u32).to_le_bytes());
+    hasher.update(value.as_bytes());
+}
+
+/// Build message for initializing the on-mint metadata with `uri`
+pub fn build_initialize_metadata_message(mint: &Pubkey, uri: &str, nonce: u64) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+
+    hasher.update(b"INITIALIZE_METADATA");
+    hasher.update(mint.as_ref());
+    update_string(&mut hasher, uri);
+    hasher.update(nonce.to_le_bytes());
+
+    hasher.finalize().to_vec()
+}
+
+/// Build message for setting one metadata field
+pub fn build_update_metadata_field_message(
+    mint: &Pubkey,
+    field: &MetadataField,
+    value: &str,
+    nonce: u64,
+) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+
+    hasher.update(b"UPDATE_METADATA_FIELD");
+    hasher.update(mint.as_ref());
+    match field {
+        MetadataField::Name => hasher.update([0]),
+        MetadataField::Symbol => hasher.update([1]),
+        MetadataField::Uri => hasher.update([2]),
+        MetadataField::Key(key) => {
+            hasher.update([3]);
+            update_string(&mut hasher, key);
+        }
+    }
+    update_string(&mut hasher, value);
+    hasher.update(nonce.to_le_bytes());
+
+    hasher.finalize().to_vec()
+}
+
+// Values are quoted so spaces cannot blur field boundaries; values outside
+// printable ASCII can only be approved through the digest form.
+pub fn build_readable_initialize_metadata_message(mint: &Pubkey, uri: &str, nonce: u64) -> Vec<u8> {
+    build_readable_message("INITIALIZE_METADATA", &format!("uri:{uri:?}"), mint, nonce)
+}
+
+pub fn build_readable_update_metadata_field_message(
+    mint: &Pubkey,
+    field: &MetadataField,
+    value: &str,
+    nonce: u64,
+) -> Vec<u8> {
+    build_readable_message(
+        "UPDATE_METADATA_FIELD",
+        &format!("{} value:{value:?}", field.label()),
+        mint,
+        nonce,
+    )
+}
@@ -8,6 +8,17 @@ pub mod internal_whitelist;
 pub mod external_whitelist;
 pub mod trusted_contracts;
 pub mod multisig;
+pub mod bridge;
+pub mod metadata;
+pub mod kyc;
+pub mod transfer_limits;
+pub mod transfer_fee;
+pub mod confidential;
+pub mod yield_distribution;
+pub mod escrow;
+pub mod subscription;
+pub mod batch;
+pub mod external_allowlist;
 
 
 
@@ -20,4 +31,15 @@ pub use internal_whitelist::*;
 pub use external_whitelist::*;
 pub use trusted_contracts::*;
 pub use multisig::*;
+pub use bridge::*;
+pub use metadata::*;
+pub use kyc::*;
+pub use transfer_limits::*;
+pub use transfer_fee::*;
+pub use confidential::*;
+pub use yield_distribution::*;
+pub use escrow::*;
+pub use subscription::*;
+pub use batch::*;
+pub use external_allowlist::*;
 
@@ -1,10 +1,10 @@
 // src/state/multisig.rs
 use crate::errors::ErrorCode;
+use crate::state::BlacklistDetails;
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::sysvar::instructions::load_instruction_at_checked;
 use sha2::{Digest, Sha256};
 use solana_program::ed25519_program;
-use std::collections::BTreeSet;
 
 #[account]
 pub struct Multisig {
@@ -14,6 +14,9 @@ pub struct Multisig {
     pub bump: u8,
 }
 
+// Approvals are reported as a u16 bitmap over `owners`
+const _: () = assert!(Multisig::MAX_OWNERS <= u16::BITS as usize);
+
 #[derive(AnchorSerialize, AnchorDeserialize)]
 struct Ed25519SignatureOffsets {
     signature_offset: u16,
@@ -41,6 +44,16 @@ impl Multisig {
         self.owners.contains(key)
     }
 
+    /// Owners selected by an approval bitmap, bit `i` standing for `owners[i]`.
+    pub fn approvers_from_bitmap(owners: &[Pubkey], bitmap: u16) -> Vec<Pubkey> {
+        owners
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| bitmap & (1 << index) != 0)
+            .map(|(_, owner)| *owner)
+            .collect()
+    }
+
     pub fn assert_valid_threshold(owners_len: usize, threshold: u8) -> Result<()> {
         require!(threshold > 0, ErrorCode::InvalidThreshold);
         require!(
@@ -69,72 +82,91 @@ impl Multisig {
 // Standalone helper functions (NOT methods on Multisig)
 // ============================================================================
 
-/// Parse an Ed25519 instruction and extract the signer pubkey and message
-fn parse_ed25519_ix(ix: &solana_program::instruction::Instruction) -> Result<(Pubkey, Vec<u8>)> {
+/// Parse an Ed25519 instruction and extract the signer pubkey and message of
+/// every signature it verifies. Several owners may share one instruction (and
+/// one copy of the message), which keeps readable approvals within the
+/// transaction size limit.
+fn parse_ed25519_ix(
+    ix: &solana_program::instruction::Instruction,
+) -> Result<Vec<(Pubkey, Vec<u8>)>> {
+    const OFFSETS_START: usize = 2;
+    const OFFSETS_SIZE: usize = 14;
+
     let data = &ix.data;
 
     // Verify minimum length
     require!(data.len() >= 16, ErrorCode::InvalidEd25519Instruction);
 
     // Check number of signatures
-    let num_signatures = data[0];
-    require!(num_signatures == 1, ErrorCode::InvalidEd25519Instruction);
-
-    // Parse offsets structure (bytes 2..16)
-    let offsets = Ed25519SignatureOffsets::try_from_slice(&data[2..16])
-        .map_err(|_| ErrorCode::InvalidEd25519Instruction)?;
-
-    // Validate that all data is in the current instruction
+    let num_signatures = data[0] as usize;
+    require!(num_signatures >= 1, ErrorCode::InvalidEd25519Instruction);
     require!(
-        offsets.signature_instruction_index == u16::MAX
-            && offsets.public_key_instruction_index == u16::MAX
-            && offsets.message_instruction_index == u16::MAX,
+        data.len() >= OFFSETS_START + num_signatures * OFFSETS_SIZE,
         ErrorCode::InvalidEd25519Instruction
     );
 
     let data_len = data.len();
+    let mut signatures = Vec::with_capacity(num_signatures);
 
-    // Bounds checks
-    require!(
-        (offsets.signature_offset as usize + 64) <= data_len,
-        ErrorCode::InvalidEd25519Instruction
-    );
-    require!(
-        (offsets.public_key_offset as usize + 32) <= data_len,
-        ErrorCode::InvalidEd25519Instruction
-    );
-    require!(
-        (offsets.message_data_offset as usize + offsets.message_data_size as usize) <= data_len,
-        ErrorCode::InvalidEd25519Instruction
-    );
+    for i in 0..num_signatures {
+        // Parse offsets structure
+        let start = OFFSETS_START + i * OFFSETS_SIZE;
+        let offsets = Ed25519SignatureOffsets::try_from_slice(&data[start..start + OFFSETS_SIZE])
+            .map_err(|_| ErrorCode::InvalidEd25519Instruction)?;
 
-    // Extract public key (32 bytes)
-    let pubkey_start = offsets.public_key_offset as usize;
-    let pubkey_bytes = &data[pubkey_start..pubkey_start + 32];
-    let signer = Pubkey::new_from_array(
-        pubkey_bytes
-            .try_into()
-            .map_err(|_| ErrorCode::InvalidPublicKey)?,
-    );
+        // Validate that all data is in the current instruction
+        require!(
+            offsets.signature_instruction_index == u16::MAX
+                && offsets.public_key_instruction_index == u16::MAX
+                && offsets.message_instruction_index == u16::MAX,
+            ErrorCode::InvalidEd25519Instruction
+        );
 
-    // Extract message
-    let msg_start = offsets.message_data_offset as usize;
-    let msg_end = msg_start + offsets.message_data_size as usize;
-    let message = data[msg_start..msg_end].to_vec();
+        // Bounds checks
+        require!(
+            (offsets.signature_offset as usize + 64) <= data_len,
+            ErrorCode::InvalidEd25519Instruction
+        );
+        require!(
+            (offsets.public_key_offset as usize + 32) <= data_len,
+            ErrorCode::InvalidEd25519Instruction
+        );
+        require!(
+            (offsets.message_data_offset as usize + offsets.message_data_size as usize)
+                <= data_len,
+            ErrorCode::InvalidEd25519Instruction
+        );
+
+        // Extract public key (32 bytes)
+        let pubkey_start = offsets.public_key_offset as usize;
+        let pubkey_bytes = &data[pubkey_start..pubkey_start + 32];
+        let signer = Pubkey::new_from_array(
+            pubkey_bytes
+                .try_into()
+                .map_err(|_| ErrorCode::InvalidPublicKey)?,
+        );
 
-    Ok((signer, message))
+        // Extract message
+        let msg_start = offsets.message_data_offset as usize;
+        let msg_end = msg_start + offsets.message_data_size as usize;
+        signatures.push((signer, data[msg_start..msg_end].to_vec()));
+    }
+
+    Ok(signatures)
 }
 
-/// Validate that enough multisig owners have signed the expected message
-pub fn validate_multisig_authorization(
-    multisig: &mut Multisig,
+/// Collect approvals of any of `messages` by `signers` from the Ed25519
+/// instructions in the transaction, stopping once `threshold` is reached.
+/// Returns a bitmap over `signers`, bit `i` set when `signers[i]` signed.
+pub fn collect_ed25519_approvals(
+    signers: &[Pubkey],
+    threshold: u8,
     instructions: &AccountInfo,
-    expected_message: &[u8],
-) -> Result<()> {
+    messages: &[&[u8]],
+) -> Result<u16> {
     const MAX_INSTRUCTIONS_TO_CHECK: usize = 20;
 
-    let mut approvals = 0u8;
-    let mut seen = BTreeSet::<Pubkey>::new();
+    let mut approvers = 0u16;
 
     let mut idx = 0;
     while idx < MAX_INSTRUCTIONS_TO_CHECK {
@@ -149,39 +181,149 @@ pub fn validate_multisig_authorization(
             continue;
         }
 
-        // Parse the Ed25519 instruction to extract signer and message
-        let (signer, message) = parse_ed25519_ix(&ix)?;
+        // Parse the Ed25519 instruction to extract signers and messages
+        for (signer, message) in parse_ed25519_ix(&ix)? {
+            // Check if message matches one of the forms we expect
+            if !messages.contains(&message.as_slice()) {
+                continue;
+            }
 
-        // Check if message matches what we expect
-        if message != expected_message {
-            continue;
+            // Check if signer is expected; setting its bit deduplicates
+            if let Some(index) = signers.iter().position(|key| *key == signer) {
+                approvers |= 1 << index;
+            }
         }
 
-        // Check if signer is a valid owner
-        if !multisig.is_owner(&signer) {
-            continue;
+        // Early exit if we've reached threshold
+        if approvers.count_ones() >= threshold as u32 {
+            break;
         }
+    }
 
-        // Count unique approvals (deduplicate)
-        if seen.insert(signer) {
-            approvals += 1;
+    Ok(approvers)
+}
 
-            // Early exit if we've reached threshold
-            if approvals >= multisig.threshold {
-                break;
-            }
-        }
-    }
+/// Validate that enough multisig owners have signed the expected message.
+/// Each owner may sign either the digest form or the human-readable form
+/// built by `build_readable_message`; both count towards the threshold.
+/// Returns the approving owners as a bitmap over `multisig.owners`, bit `i`
+/// set when `owners[i]` approved (see `Multisig::approvers_from_bitmap`).
+pub fn validate_multisig_authorization(
+    multisig: &mut Multisig,
+    instructions: &AccountInfo,
+    expected_message: &[u8],
+    readable_message: &[u8],
+) -> Result<u16> {
+    let approvers = collect_ed25519_approvals(
+        &multisig.owners,
+        multisig.threshold,
+        instructions,
+        &[expected_message, readable_message],
+    )?;
 
     // Verify we have enough approvals
     require!(
-        approvals >= multisig.threshold,
+        approvers.count_ones() >= multisig.threshold as u32,
         ErrorCode::NotEnoughMultisigSigners
     );
     // This prevents replay attacks
     multisig.nonce += 1;
 
-    Ok(())
+    Ok(approvers)
+}
+
+// ============================================================================
+// Human-readable approval messages
+// ============================================================================
+
+/// Signing domain of a Solana off-chain message
+pub const OFFCHAIN_SIGNING_DOMAIN: &[u8; 16] = b"\xffsolana offchain";
+/// Off-chain message header version
+pub const OFFCHAIN_HEADER_VERSION: u8 = 0;
+/// Off-chain message format: printable ASCII, which hardware wallets display in full
+pub const OFFCHAIN_FORMAT_RESTRICTED_ASCII: u8 = 0;
+
+/// Build the human-readable alternative to a `build_*_message` digest.
+///
+/// The text reads `cNGN <ACTION> <details> nonce:<nonce> account:<account>`
+/// and is wrapped in a Solana off-chain message envelope, so owners can sign
+/// it with `solana sign-offchain-message` or a Ledger and see what they are
+/// approving. `account` is the same account the digest form commits to.
+pub fn build_readable_message(action: &str, details: &str, account: &Pubkey, nonce: u64) -> Vec<u8> {
+    let text = if details.is_empty() {
+        format!("cNGN {action} nonce:{nonce} account:{account}")
+    } else {
+        format!("cNGN {action} {details} nonce:{nonce} account:{account}")
+    };
+
+    let mut message = Vec::with_capacity(OFFCHAIN_SIGNING_DOMAIN.len() + 4 + text.len());
+    message.extend_from_slice(OFFCHAIN_SIGNING_DOMAIN);
+    message.push(OFFCHAIN_HEADER_VERSION);
+    message.push(OFFCHAIN_FORMAT_RESTRICTED_ASCII);
+    message.extend_from_slice(&(text.len() as u16).to_le_bytes());
+    message.extend_from_slice(text.as_bytes());
+
+    message
+}
+
+/// Build the readable message for an action on a single target key
+pub fn build_readable_target_message(
+    action: &str,
+    account: &Pubkey,
+    target: &Pubkey,
+    nonce: u64,
+) -> Vec<u8> {
+    build_readable_message(action, &format!("target:{target}"), account, nonce)
+}
+
+/// Build the readable message for updating multisig configuration
+pub fn build_readable_update_multisig_message(
+    multisig: &Pubkey,
+    new_owners: &[Pubkey],
+    new_threshold: u8,
+    nonce: u64,
+) -> Vec<u8> {
+    let owners = new_owners
+        .iter()
+        .map(|owner| owner.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+
+    build_readable_message(
+        "UPDATE_MULTISIG",
+        &format!("owners:{owners} threshold:{new_threshold}"),
+        multisig,
+        nonce,
+    )
+}
+
+/// Build the readable message for setting a minter's allowance
+pub fn build_readable_set_mint_amount_message(
+    can_mint_account: &Pubkey,
+    user: &Pubkey,
+    amount: u64,
+    nonce: u64,
+) -> Vec<u8> {
+    build_readable_message(
+        "SET_MINT_AMOUNT",
+        &format!("target:{user} amount:{amount}"),
+        can_mint_account,
+        nonce,
+    )
+}
+
+/// Build the readable message for pausing or resuming minting
+pub fn build_readable_pause_mint_message(
+    token_config: &Pubkey,
+    pause_mint: bool,
+    nonce: u64,
+) -> Vec<u8> {
+    build_readable_message(
+        "PAUSE_MINTING",
+        &format!("paused:{pause_mint}"),
+        token_config,
+        nonce,
+    )
 }
 
 /// Build message for updating multisig configuration
@@ -200,8 +342,8 @@ pub fn build_update_multisig_message(
         hasher.update(owner.as_ref());
     }
 
-    hasher.update(&[new_threshold]);
-    hasher.update(&nonce.to_le_bytes());
+    hasher.update([new_threshold]);
+    hasher.update(nonce.to_le_bytes());
 
     hasher.finalize().to_vec()
 }
@@ -213,7 +355,7 @@ pub fn build_add_can_mint_message(can_mint_account: &Pubkey, user: &Pubkey, nonc
     hasher.update(b"ADD_CAN_MINT");
     hasher.update(can_mint_account.as_ref());
     hasher.update(user.as_ref());
-    hasher.update(&nonce.to_le_bytes());
+    hasher.update(nonce.to_le_bytes());
 
     hasher.finalize().to_vec()
 }
@@ -229,7 +371,7 @@ pub fn build_remove_can_mint_message(
     hasher.update(b"REMOVE_CAN_MINT");
     hasher.update(can_mint_account.as_ref());
     hasher.update(user.as_ref());
-    hasher.update(&nonce.to_le_bytes());
+    hasher.update(nonce.to_le_bytes());
 
     hasher.finalize().to_vec()
 }
@@ -244,7 +386,7 @@ pub fn build_set_mint_amount_message(
     hasher.update(b"SET_MINT_AMOUNT");
     hasher.update(can_mint_account.as_ref());
     hasher.update(user.as_ref());
-    hasher.update(&nonce.to_le_bytes());
+    hasher.update(nonce.to_le_bytes());
 
     hasher.finalize().to_vec()
 }
@@ -259,7 +401,7 @@ pub fn build_remove_mint_amount_message(
     hasher.update(b"REMOVE_MINT_AMOUNT");
     hasher.update(can_mint_account.as_ref());
     hasher.update(user.as_ref());
-    hasher.update(&nonce.to_le_bytes());
+    hasher.update(nonce.to_le_bytes());
 
     hasher.finalize().to_vec()
 }
@@ -274,7 +416,7 @@ pub fn build_add_can_forward_message(
     hasher.update(b"ADD_CAN_FORWARD");
     hasher.update(can_forward_account.as_ref());
     hasher.update(forwarder.as_ref());
-    hasher.update(&nonce.to_le_bytes());
+    hasher.update(nonce.to_le_bytes());
 
     hasher.finalize().to_vec()
 }
@@ -290,7 +432,7 @@ pub fn build_remove_can_forward_message(
     hasher.update(b"REMOVE_CAN_FORWARD");
     hasher.update(can_forward_account.as_ref());
     hasher.update(forwarder.as_ref());
-    hasher.update(&nonce.to_le_bytes());
+    hasher.update(nonce.to_le_bytes());
 
     hasher.finalize().to_vec()
 }
@@ -299,6 +441,7 @@ pub fn build_remove_can_forward_message(
 pub fn build_add_blacklist_message(
     blacklist_account: &Pubkey,
     user: &Pubkey,
+    details: &BlacklistDetails,
     nonce: u64,
 ) -> Vec<u8> {
     let mut hasher = Sha256::new();
@@ -306,7 +449,8 @@ pub fn build_add_blacklist_message(
     hasher.update(b"ADD_BLACKLIST");
     hasher.update(blacklist_account.as_ref());
     hasher.update(user.as_ref());
-    hasher.update(&nonce.to_le_bytes());
+    details.update_digest(&mut hasher);
+    hasher.update(nonce.to_le_bytes());
 
     hasher.finalize().to_vec()
 }
@@ -322,7 +466,7 @@ pub fn build_remove_blacklist_message(
     hasher.update(b"REMOVE_BLACKLIST");
     hasher.update(blacklist_account.as_ref());
     hasher.update(user.as_ref());
-    hasher.update(&nonce.to_le_bytes());
+    hasher.update(nonce.to_le_bytes());
 
     hasher.finalize().to_vec()
 }
@@ -338,7 +482,7 @@ pub fn build_add_trusted_contract_message(
     hasher.update(b"ADD_TRUSTED_CONTRACT");
     hasher.update(trusted_contracts_account.as_ref());
     hasher.update(contract.as_ref());
-    hasher.update(&nonce.to_le_bytes());
+    hasher.update(nonce.to_le_bytes());
 
     hasher.finalize().to_vec()
 }
@@ -354,7 +498,7 @@ pub fn build_remove_trusted_contract_message(
     hasher.update(b"REMOVE_TRUSTED_CONTRACT");
     hasher.update(trusted_contracts_account.as_ref());
     hasher.update(contract.as_ref());
-    hasher.update(&nonce.to_le_bytes());
+    hasher.update(nonce.to_le_bytes());
 
     hasher.finalize().to_vec()
 }
@@ -370,7 +514,7 @@ pub fn build_whitelist_internal_message(
     hasher.update(b"WHITELIST_INTERNAL");
     hasher.update(internal_whitelist_account.as_ref());
     hasher.update(user.as_ref());
-    hasher.update(&nonce.to_le_bytes());
+    hasher.update(nonce.to_le_bytes());
 
     hasher.finalize().to_vec()
 }
@@ -386,7 +530,7 @@ pub fn build_whitelist_external_message(
     hasher.update(b"WHITELIST_EXTERNAL");
     hasher.update(external_whitelist_account.as_ref());
     hasher.update(user.as_ref());
-    hasher.update(&nonce.to_le_bytes());
+    hasher.update(nonce.to_le_bytes());
 
     hasher.finalize().to_vec()
 }
@@ -402,7 +546,7 @@ pub fn build_blacklist_internal_message(
     hasher.update(b"BLACKLIST_INTERNAL");
     hasher.update(internal_whitelist_account.as_ref());
     hasher.update(user.as_ref());
-    hasher.update(&nonce.to_le_bytes());
+    hasher.update(nonce.to_le_bytes());
 
     hasher.finalize().to_vec()
 }
@@ -418,7 +562,7 @@ pub fn build_blacklist_external_message(
     hasher.update(b"BLACKLIST_EXTERNAL");
     hasher.update(external_whitelist_account.as_ref());
     hasher.update(user.as_ref());
-    hasher.update(&nonce.to_le_bytes());
+    hasher.update(nonce.to_le_bytes());
 
     hasher.finalize().to_vec()
 }
@@ -428,7 +572,7 @@ pub fn build_pause_mint_message(token_config_account: &Pubkey, nonce: u64) -> Ve
 
     hasher.update(b"PAUSE_MINTING");
     hasher.update(token_config_account.as_ref());
-    hasher.update(&nonce.to_le_bytes());
+    hasher.update(nonce.to_le_bytes());
 
     hasher.finalize().to_vec()
 }
@@ -438,7 +582,7 @@ pub fn build_pause_transfer_message(token_config_account: &Pubkey, nonce: u64) -
 
     hasher.update(b"PAUSE_TRANSFER");
     hasher.update(token_config_account.as_ref());
-    hasher.update(&nonce.to_le_bytes());
+    hasher.update(nonce.to_le_bytes());
 
     hasher.finalize().to_vec()
 }
@@ -0,0 +1,77 @@
+// state/subscription.rs
+use crate::errors::ErrorCode;
+use anchor_lang::prelude::*;
+
+pub const SUBSCRIPTION_SEED: &[u8] = b"subscription";
+/// Program-owned delegate payers approve on their token account; pulls are
+/// capped per subscription, the approval caps them all together
+pub const PULL_DELEGATE_SEED: &[u8] = b"pull-delegate";
+pub const PULL_ALLOWANCE_SEED: &[u8] = b"pull-allowance";
+
+/// A payer's standing authorization for a payee to pull up to
+/// `amount_per_period` every `period_seconds`.
+#[account]
+pub struct Subscription {
+    pub mint: Pubkey,
+    pub payer: Pubkey,
+    pub payer_token_account: Pubkey,
+    pub payee: Pubkey,
+    pub amount_per_period: u64,
+    pub period_seconds: u32,
+    pub period_start: i64,
+    pub pulled_in_period: u64,
+    pub bump: u8,
+}
+
+impl Subscription {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // mint
+        32 + // payer
+        32 + // payer_token_account
+        32 + // payee
+        8 +  // amount_per_period
+        4 +  // period_seconds
+        8 +  // period_start
+        8 +  // pulled_in_period
+        1; // bump
+
+    /// Count a pull against the current period, starting a new period when
+    /// the last one has ended. Periods stay aligned to the subscription's
+    /// start.
+    pub fn pull(&mut self, amount: u64, now: i64) -> Result<()> {
+        let period_seconds = self.period_seconds as i64;
+        if now >= self.period_start + period_seconds {
+            let elapsed = (now - self.period_start) / period_seconds;
+            self.period_start += elapsed * period_seconds;
+            self.pulled_in_period = 0;
+        }
+
+        let pulled = self
+            .pulled_in_period
+            .checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(
+            pulled <= self.amount_per_period,
+            ErrorCode::SubscriptionLimitExceeded
+        );
+        self.pulled_in_period = pulled;
+
+        Ok(())
+    }
+}
+
+/// Live subscriptions drawing on a token account's approval of the pull
+/// delegate. Cancelling the last one revokes the approval.
+#[account]
+pub struct PullAllowance {
+    pub token_account: Pubkey,
+    pub subscriptions: u32,
+    pub bump: u8,
+}
+
+impl PullAllowance {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // token_account
+        4 +  // subscriptions
+        1; // bump
+}
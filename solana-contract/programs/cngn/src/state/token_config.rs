@@ -16,6 +16,34 @@ pub struct TokenConfig {
     pub mint_paused: bool,     // Flag to track if minting is paused
     pub transfer_paused: bool, // Flag to track if transfers are paused
     pub bump: u8,              // PDA bump
+    pub init_phase: InitPhase, // Next initialization step
+    pub initializer: Pubkey,   // Signer of `initialize`; runs the later phases
+}
+
+/// Initialization runs in three instructions to fit the stack; each phase can
+/// only follow the previous one and only the original initializer runs them.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InitPhase {
+    AwaitingSecondary,
+    AwaitingThird,
+    Complete,
+    /// A migrated mint whose `initialize_third` ran before transfer tiers
+    /// and blacklist entries existed; `upgrade_transfer_hook` completes it
+    AwaitingHookUpgrade,
+}
+
+/// `TokenConfig` as deployed before `init_phase` and `initializer` were
+/// added. Only `migrate_token_config` reads it.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyTokenConfig {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+    pub mint: Pubkey,
+    pub admin: Pubkey,
+    pub mint_paused: bool,
+    pub transfer_paused: bool,
+    pub bump: u8,
 }
 
 // state/token_config.rs
@@ -34,7 +62,14 @@ impl TokenConfig {
         32 + // admin
         1 +  // mint_paused
         1 +  // transfer_paused
-        1; // bump
+        1 +  // bump
+        1 +  // init_phase
+        32; // initializer
+
+    /// Space of a `TokenConfig` created before setup was tracked
+    pub const LEGACY_LEN: usize = Self::LEN - 1 - 32;
 
-    
+    pub fn is_setup_complete(&self) -> bool {
+        self.init_phase == InitPhase::Complete
+    }
 }
@@ -0,0 +1,111 @@
+// state/transfer_fee.rs
+use crate::state::build_readable_message;
+use anchor_lang::prelude::*;
+use sha2::{Digest, Sha256};
+
+pub const FEE_CONFIG_SEED: &[u8] = b"fee-config";
+pub const FEE_REFUND_SEED: &[u8] = b"fee-refund";
+
+/// Where withheld transfer fees go and who is spared from them. The fee
+/// rate itself lives in the mint's TransferFeeConfig extension.
+#[account]
+pub struct FeeConfig {
+    pub mint: Pubkey,
+    pub treasury: Pubkey, // Token account receiving withdrawn fees
+    pub exempt_internal_whitelist: bool, // Refund fees withheld from internal-whitelisted owners
+    pub refunds_outstanding: u64, // Refunds minted but not yet netted out of withdrawn fees
+    pub bump: u8,
+}
+
+impl FeeConfig {
+    pub const MAX_FEE_BASIS_POINTS: u16 = 10_000;
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // mint
+        32 + // treasury
+        1 +  // exempt_internal_whitelist
+        8 +  // refunds_outstanding
+        1; // bump
+}
+
+/// Fees withheld on an exempt token account's transfers, in or out, that
+/// have not been refunded yet. The transfer hook adds to it.
+#[account]
+pub struct FeeRefund {
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    pub owed: u64,
+    pub bump: u8,
+}
+
+impl FeeRefund {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // mint
+        32 + // token_account
+        8 +  // owed
+        1; // bump
+}
+
+/// Build message for changing the transfer fee
+pub fn build_set_transfer_fee_message(
+    mint: &Pubkey,
+    basis_points: u16,
+    maximum_fee: u64,
+    nonce: u64,
+) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+
+    hasher.update(b"SET_TRANSFER_FEE");
+    hasher.update(mint.as_ref());
+    hasher.update(basis_points.to_le_bytes());
+    hasher.update(maximum_fee.to_le_bytes());
+    hasher.update(nonce.to_le_bytes());
+
+    hasher.finalize().to_vec()
+}
+
+pub fn build_readable_set_transfer_fee_message(
+    mint: &Pubkey,
+    basis_points: u16,
+    maximum_fee: u64,
+    nonce: u64,
+) -> Vec<u8> {
+    build_readable_message(
+        "SET_TRANSFER_FEE",
+        &format!("basis_points:{basis_points} maximum_fee:{maximum_fee}"),
+        mint,
+        nonce,
+    )
+}
+
+/// Build message for setting the fee treasury and exemptions
+pub fn build_update_fee_config_message(
+    fee_config: &Pubkey,
+    treasury: &Pubkey,
+    exempt_internal_whitelist: bool,
+    nonce: u64,
+) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+
+    hasher.update(b"UPDATE_FEE_CONFIG");
+    hasher.update(fee_config.as_ref());
+    hasher.update(treasury.as_ref());
+    hasher.update([exempt_internal_whitelist as u8]);
+    hasher.update(nonce.to_le_bytes());
+
+    hasher.finalize().to_vec()
+}
+
+pub fn build_readable_update_fee_config_message(
+    fee_config: &Pubkey,
+    treasury: &Pubkey,
+    exempt_internal_whitelist: bool,
+    nonce: u64,
+) -> Vec<u8> {
+    build_readable_message(
+        "UPDATE_FEE_CONFIG",
+        &format!("treasury:{treasury} exempt_internal_whitelist:{exempt_internal_whitelist}"),
+        fee_config,
+        nonce,
+    )
+}
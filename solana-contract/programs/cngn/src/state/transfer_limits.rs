@@ -0,0 +1,181 @@
+// state/transfer_limits.rs
+use crate::errors::ErrorCode;
+use crate::state::{build_readable_message, KycRecord};
+use anchor_lang::prelude::*;
+use sha2::{Digest, Sha256};
+
+pub const TRANSFER_TIERS_SEED: &[u8] = b"transfer-tiers";
+pub const TRANSFER_LIMITS_SEED: &[u8] = b"transfer-limits";
+
+/// Caps applied to every owner assigned to a tier
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TierLimits {
+    pub per_transaction: u64,
+    pub daily: u64, // Per rolling window of `TransferLimits::WINDOW_SECONDS`
+}
+
+impl TierLimits {
+    pub const UNLIMITED: Self = Self {
+        per_transaction: u64::MAX,
+        daily: u64::MAX,
+    };
+
+    pub const LEN: usize = 8 + 8;
+}
+
+/// Limits for each KYC tier, indexed by tier
+#[account]
+pub struct TransferTiers {
+    pub mint: Pubkey,
+    pub tiers: [TierLimits; TransferTiers::TIER_COUNT],
+    pub bump: u8,
+}
+
+impl TransferTiers {
+    pub const TIER_COUNT: usize = KycRecord::MAX_TIER as usize + 1;
+
+    pub const LEN: usize = 8 +                      // discriminator
+        32 +                                        // mint
+        TierLimits::LEN * Self::TIER_COUNT +        // tiers
+        1; // bump
+
+    pub fn limits(&self, tier: u8) -> Result<TierLimits> {
+        self.tiers
+            .get(tier as usize)
+            .copied()
+            .ok_or_else(|| ErrorCode::InvalidKycTier.into())
+    }
+
+    pub fn configure(&mut self, tier: u8, limits: TierLimits) -> Result<()> {
+        require!(
+            limits.per_transaction <= limits.daily,
+            ErrorCode::InvalidTransferLimits
+        );
+
+        let entry = self
+            .tiers
+            .get_mut(tier as usize)
+            .ok_or(ErrorCode::InvalidKycTier)?;
+        *entry = limits;
+
+        Ok(())
+    }
+}
+
+/// An owner's tier and usage in the current window. `kyc_approve` creates it
+/// with the owner's KYC tier; the transfer hook holds owners without one to
+/// the per-transaction cap of `DEFAULT_TIER`.
+#[account]
+pub struct TransferLimits {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub tier: u8,
+    pub window_start: i64, // Time of the first transfer in the window
+    pub window_spent: u64,
+    pub bump: u8,
+}
+
+impl TransferLimits {
+    pub const WINDOW_SECONDS: i64 = 24 * 60 * 60;
+    /// Tier applied to owners without a limits account
+    pub const DEFAULT_TIER: u8 = 1;
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // mint
+        32 + // owner
+        1 +  // tier
+        8 +  // window_start
+        8 +  // window_spent
+        1; // bump
+
+    /// Check `amount` against the tier's caps and add it to the window. A
+    /// window opens with the first transfer after the previous one expired.
+    pub fn record(&mut self, amount: u64, limits: &TierLimits, now: i64) -> Result<()> {
+        require!(
+            amount <= limits.per_transaction,
+            ErrorCode::PerTransactionLimitExceeded
+        );
+
+        if now.saturating_sub(self.window_start) >= Self::WINDOW_SECONDS {
+            self.window_start = now;
+            self.window_spent = 0;
+        }
+
+        let spent = self
+            .window_spent
+            .checked_add(amount)
+            .filter(|spent| *spent <= limits.daily)
+            .ok_or(ErrorCode::DailyLimitExceeded)?;
+        self.window_spent = spent;
+
+        Ok(())
+    }
+}
+
+/// Build message for setting a tier's caps
+pub fn build_configure_transfer_tier_message(
+    transfer_tiers: &Pubkey,
+    tier: u8,
+    limits: &TierLimits,
+    nonce: u64,
+) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+
+    hasher.update(b"CONFIGURE_TRANSFER_TIER");
+    hasher.update(transfer_tiers.as_ref());
+    hasher.update([tier]);
+    hasher.update(limits.per_transaction.to_le_bytes());
+    hasher.update(limits.daily.to_le_bytes());
+    hasher.update(nonce.to_le_bytes());
+
+    hasher.finalize().to_vec()
+}
+
+pub fn build_readable_configure_transfer_tier_message(
+    transfer_tiers: &Pubkey,
+    tier: u8,
+    limits: &TierLimits,
+    nonce: u64,
+) -> Vec<u8> {
+    build_readable_message(
+        "CONFIGURE_TRANSFER_TIER",
+        &format!(
+            "tier:{tier} per_transaction:{} daily:{}",
+            limits.per_transaction, limits.daily
+        ),
+        transfer_tiers,
+        nonce,
+    )
+}
+
+/// Build message for assigning an owner to a tier
+pub fn build_set_transfer_tier_message(
+    transfer_limits: &Pubkey,
+    owner: &Pubkey,
+    tier: u8,
+    nonce: u64,
+) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+
+    hasher.update(b"SET_TRANSFER_TIER");
+    hasher.update(transfer_limits.as_ref());
+    hasher.update(owner.as_ref());
+    hasher.update([tier]);
+    hasher.update(nonce.to_le_bytes());
+
+    hasher.finalize().to_vec()
+}
+
+pub fn build_readable_set_transfer_tier_message(
+    transfer_limits: &Pubkey,
+    owner: &Pubkey,
+    tier: u8,
+    nonce: u64,
+) -> Vec<u8> {
+    build_readable_message(
+        "SET_TRANSFER_TIER",
+        &format!("owner:{owner} tier:{tier}"),
+        transfer_limits,
+        nonce,
+    )
+}
@@ -0,0 +1,157 @@
+// state/yield_distribution.rs
+use crate::errors::ErrorCode;
+use crate::state::build_readable_message;
+use anchor_lang::prelude::*;
+use sha2::{Digest, Sha256};
+
+pub const YIELD_CONFIG_SEED: &[u8] = b"yield-config";
+pub const YIELD_POSITION_SEED: &[u8] = b"yield-position";
+
+/// Savings yield settings. Yield accrues into a cumulative per-token index
+/// once per period and is minted to opted-in token accounts, within the
+/// supply cap and the reserves backing the token.
+#[account]
+pub struct YieldConfig {
+    pub mint: Pubkey,
+    pub annual_rate_basis_points: u16,
+    pub period_seconds: u32,
+    pub supply_cap: u64, // Total supply yield may not push the mint beyond
+    pub reserves: u64,   // Attested reserves, in base units
+    pub period: u64,     // Number of accrued periods
+    pub period_start: i64,
+    pub yield_index: u128, // Cumulative yield per token, scaled by `INDEX_SCALE`
+    pub bump: u8,
+}
+
+impl YieldConfig {
+    pub const MAX_RATE_BASIS_POINTS: u16 = 10_000;
+    pub const SECONDS_PER_YEAR: u128 = 365 * 24 * 60 * 60;
+    pub const INDEX_SCALE: u128 = 1_000_000_000_000;
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // mint
+        2 +  // annual_rate_basis_points
+        4 +  // period_seconds
+        8 +  // supply_cap
+        8 +  // reserves
+        8 +  // period
+        8 +  // period_start
+        16 + // yield_index
+        1; // bump
+
+    /// Close the current period at the current rate. Periods that were
+    /// missed are caught up one call at a time.
+    pub fn advance(&mut self, now: i64) -> Result<()> {
+        let period_end = self
+            .period_start
+            .checked_add(self.period_seconds as i64)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(now >= period_end, ErrorCode::YieldPeriodNotElapsed);
+
+        let period_yield =
+            self.annual_rate_basis_points as u128 * self.period_seconds as u128 * Self::INDEX_SCALE
+                / (Self::MAX_RATE_BASIS_POINTS as u128 * Self::SECONDS_PER_YEAR);
+
+        self.yield_index = self
+            .yield_index
+            .checked_add(period_yield)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.period += 1;
+        self.period_start = period_end;
+
+        Ok(())
+    }
+
+    /// Reject yield that would take the supply past the cap or the reserves.
+    pub fn check_mintable(&self, supply: u64, amount: u64) -> Result<()> {
+        let new_supply = supply.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+        require!(
+            new_supply <= self.supply_cap,
+            ErrorCode::YieldExceedsSupplyCap
+        );
+        require!(new_supply <= self.reserves, ErrorCode::YieldExceedsReserves);
+
+        Ok(())
+    }
+}
+
+/// An opted-in token account and the yield index it was last paid up to
+#[account]
+pub struct YieldPosition {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub token_account: Pubkey,
+    pub yield_index: u128,
+    pub balance: u64, // Lowest balance held since the last payout
+    pub bump: u8,
+}
+
+impl YieldPosition {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // mint
+        32 + // owner
+        32 + // token_account
+        16 + // yield_index
+        8 +  // balance
+        1; // bump
+
+    /// Yield owed since the last payout. It is paid on the lowest balance
+    /// held since then, so tokens moved in after a payout, or moved on to
+    /// another position before one, earn nothing for periods they were not
+    /// held throughout.
+    pub fn accrue(&mut self, yield_index: u128, balance: u64) -> Result<u64> {
+        let eligible = self.balance.min(balance) as u128;
+        let owed = eligible
+            .checked_mul(yield_index.saturating_sub(self.yield_index))
+            .ok_or(ErrorCode::MathOverflow)?
+            / YieldConfig::INDEX_SCALE;
+
+        self.yield_index = yield_index;
+        u64::try_from(owed).map_err(|_| ErrorCode::MathOverflow.into())
+    }
+
+    /// Lower the tracked balance after tokens leave the account.
+    pub fn record_outflow(&mut self, balance: u64) {
+        self.balance = self.balance.min(balance);
+    }
+}
+
+/// Build message for updating the yield rate, period and minting bounds
+pub fn build_update_yield_config_message(
+    yield_config: &Pubkey,
+    annual_rate_basis_points: u16,
+    period_seconds: u32,
+    supply_cap: u64,
+    reserves: u64,
+    nonce: u64,
+) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+
+    hasher.update(b"UPDATE_YIELD_CONFIG");
+    hasher.update(yield_config.as_ref());
+    hasher.update(annual_rate_basis_points.to_le_bytes());
+    hasher.update(period_seconds.to_le_bytes());
+    hasher.update(supply_cap.to_le_bytes());
+    hasher.update(reserves.to_le_bytes());
+    hasher.update(nonce.to_le_bytes());
+
+    hasher.finalize().to_vec()
+}
+
+pub fn build_readable_update_yield_config_message(
+    yield_config: &Pubkey,
+    annual_rate_basis_points: u16,
+    period_seconds: u32,
+    supply_cap: u64,
+    reserves: u64,
+    nonce: u64,
+) -> Vec<u8> {
+    build_readable_message(
+        "UPDATE_YIELD_CONFIG",
+        &format!(
+            "annual_rate_basis_points:{annual_rate_basis_points} period_seconds:{period_seconds} supply_cap:{supply_cap} reserves:{reserves}"
+        ),
+        yield_config,
+        nonce,
+    )
+}
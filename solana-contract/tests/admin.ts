@@ -3,12 +3,12 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { Cngn } from "../target/types/cngn";
 import { assert, expect } from 'chai';
-import { PublicKey, Keypair, SYSVAR_INSTRUCTIONS_PUBKEY, TransactionInstruction } from '@solana/web3.js';
-import { calculatePDAs } from '../utils/helpers';
+import { PublicKey, Keypair, SystemProgram, SYSVAR_INSTRUCTIONS_PUBKEY, TransactionInstruction } from '@solana/web3.js';
+import { calculatePDAs, blacklistDetails, blacklistEntry } from '../utils/helpers';
 import { initializeMultisig, initializeToken } from "../utils/token_initializer";
 import * as crypto from 'crypto';
 import nacl from 'tweetnacl';
-import { createMint, TOKEN_2022_PROGRAM_ID } from "@solana/spl-token";
+import { TOKEN_2022_PROGRAM_ID } from "@solana/spl-token";
 
 
 
@@ -159,8 +159,6 @@ describe("cngn admin functionality tests with multisig", () => {
     await new Promise(resolve => setTimeout(resolve, 2000));
 
     console.log("Initializing token...");
-    await createMint(provider.connection, payer, payer.publicKey, payer.publicKey, 6, mint, null, TOKEN_2022_PROGRAM_ID)
-    
     await initializeToken(program, provider, mint, pdas, payer.publicKey);
     console.log("Initializing multisig...");
 
@@ -384,11 +382,14 @@ describe("cngn admin functionality tests with multisig", () => {
 
       const accountToBlacklist = Keypair.generate();
       const multisigAccount = await program.account.multisig.fetch(multisigPda);
+      const { details, bytes } = blacklistDetails("courtOrder", "FHC/L/CS/1234/2024", Math.floor(Date.now() / 1000) + 86400);
+      const entry = blacklistEntry(mint.publicKey, program.programId, accountToBlacklist.publicKey);
 
       const message = buildMessageHash(
         "ADD_BLACKLIST",
         pdas.blacklist,
         accountToBlacklist.publicKey,
+        bytes,
         multisigAccount.nonce.toNumber()
       );
 
@@ -396,8 +397,9 @@ describe("cngn admin functionality tests with multisig", () => {
       const ed25519Ix2 = createEd25519Ix(owner2, message);
 
       const tx = await program.methods
-        .addBlacklist(accountToBlacklist.publicKey)
+        .addBlacklist(accountToBlacklist.publicKey, details)
         .accounts({
+          payer: payer.publicKey,
           mint: mint.publicKey,
           tokenConfig: pdas.tokenConfig,
           canMint: pdas.canMint,
@@ -407,8 +409,10 @@ describe("cngn admin functionality tests with multisig", () => {
           canForward: pdas.canForward,
           blacklist: pdas.blacklist,
           multisig: multisigPda,
-          instructions: SYSVAR_INSTRUCTIONS_PUBKEY
+          instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+          systemProgram: SystemProgram.programId
         })
+        .remainingAccounts([entry])
         .preInstructions([ed25519Ix1, ed25519Ix2])
         .rpc();
 
@@ -419,12 +423,20 @@ describe("cngn admin functionality tests with multisig", () => {
         account.equals(accountToBlacklist.publicKey)
       );
       assert.isTrue(accountFound, "Account was not added to blacklist");
+
+      const record = await program.account.blacklistEntry.fetch(entry.pubkey);
+      assert.deepEqual(record.details.reason, { courtOrder: {} });
+      assert.equal(record.details.caseReference, "FHC/L/CS/1234/2024");
+      assert.isTrue(record.details.reviewAt.eq(details.reviewAt));
+      assert.isTrue(record.payer.equals(payer.publicKey));
     });
 
     it("Admin can remove account from blacklist with multisig", async () => {
       console.log("Testing removing from blacklist with multisig...");
 
       const accountToUnblacklist = Keypair.generate();
+      const { details, bytes } = blacklistDetails("fraudReport", "FR-0042");
+      const entry = blacklistEntry(mint.publicKey, program.programId, accountToUnblacklist.publicKey);
 
       // First add to blacklist
       let multisigAccount = await program.account.multisig.fetch(multisigPda);
@@ -432,6 +444,7 @@ describe("cngn admin functionality tests with multisig", () => {
         "ADD_BLACKLIST",
         pdas.blacklist,
         accountToUnblacklist.publicKey,
+        bytes,
         multisigAccount.nonce.toNumber()
       );
 
@@ -439,8 +452,9 @@ describe("cngn admin functionality tests with multisig", () => {
       let ed25519Ix2 = createEd25519Ix(owner2, message);
 
       await program.methods
-        .addBlacklist(accountToUnblacklist.publicKey)
+        .addBlacklist(accountToUnblacklist.publicKey, details)
         .accounts({
+          payer: payer.publicKey,
           mint: mint.publicKey,
           tokenConfig: pdas.tokenConfig,
           canMint: pdas.canMint,
@@ -450,8 +464,10 @@ describe("cngn admin functionality tests with multisig", () => {
           canForward: pdas.canForward,
           blacklist: pdas.blacklist,
           multisig: multisigPda,
-          instructions: SYSVAR_INSTRUCTIONS_PUBKEY
+          instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+          systemProgram: SystemProgram.programId
         })
+        .remainingAccounts([entry])
         .preInstructions([ed25519Ix1, ed25519Ix2])
         .rpc();
 
@@ -474,8 +490,10 @@ describe("cngn admin functionality tests with multisig", () => {
           tokenConfig: pdas.tokenConfig,
           blacklist: pdas.blacklist,
           multisig: multisigPda,
+          rentRecipient: payer.publicKey,
           instructions: SYSVAR_INSTRUCTIONS_PUBKEY
         })
+        .remainingAccounts([entry])
         .preInstructions([ed25519Ix1, ed25519Ix2])
         .rpc();
 
@@ -486,6 +504,134 @@ describe("cngn admin functionality tests with multisig", () => {
         account.equals(accountToUnblacklist.publicKey)
       );
       assert.isFalse(accountFoundAfter, "Account was not removed from blacklist");
+      assert.isNull(
+        await provider.connection.getAccountInfo(entry.pubkey),
+        "Blacklist entry was not closed"
+      );
+    });
+
+    it("Anyone can prune a blacklisting once it expires", async () => {
+      const accountToBlacklist = Keypair.generate();
+      const now = await provider.connection.getBlockTime(await provider.connection.getSlot());
+      const { details, bytes } = blacklistDetails("courtOrder", "FHC/L/CS/77/2024", 0, now + 3);
+      const entry = blacklistEntry(mint.publicKey, program.programId, accountToBlacklist.publicKey);
+
+      const multisigAccount = await program.account.multisig.fetch(multisigPda);
+      const message = buildMessageHash(
+        "ADD_BLACKLIST",
+        pdas.blacklist,
+        accountToBlacklist.publicKey,
+        bytes,
+        multisigAccount.nonce.toNumber()
+      );
+
+      await program.methods
+        .addBlacklist(accountToBlacklist.publicKey, details)
+        .accounts({
+          payer: payer.publicKey,
+          mint: mint.publicKey,
+          tokenConfig: pdas.tokenConfig,
+          canMint: pdas.canMint,
+          internalWhitelist: pdas.internalWhitelist,
+          externalWhitelist: pdas.externalWhitelist,
+          trustedContracts: pdas.trustedContracts,
+          canForward: pdas.canForward,
+          blacklist: pdas.blacklist,
+          multisig: multisigPda,
+          instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+          systemProgram: SystemProgram.programId
+        })
+        .remainingAccounts([entry])
+        .preInstructions([createEd25519Ix(owner1, message), createEd25519Ix(owner2, message)])
+        .rpc();
+
+      const prune = () =>
+        program.methods
+          .pruneExpiredBlacklist(accountToBlacklist.publicKey)
+          .accounts({
+            tokenConfig: pdas.tokenConfig,
+            blacklist: pdas.blacklist,
+            blacklistEntry: entry.pubkey,
+            rentRecipient: payer.publicKey
+          })
+          .rpc();
+
+      try {
+        await prune();
+        assert.fail("Should not prune a listing before it expires");
+      } catch (error) {
+        assert.include(error.toString(), "BlacklistNotExpired");
+      }
+
+      await new Promise(resolve => setTimeout(resolve, 5000));
+
+      const status = await program.methods
+        .getAddressStatus(accountToBlacklist.publicKey)
+        .accounts({
+          mint: mint.publicKey,
+          tokenConfig: pdas.tokenConfig,
+          blacklist: pdas.blacklist,
+          internalWhitelist: pdas.internalWhitelist,
+          externalWhitelist: pdas.externalWhitelist,
+          canForward: pdas.canForward,
+          trustedContracts: pdas.trustedContracts,
+          canMint: pdas.canMint
+        })
+        .view();
+      assert.isTrue(status.blacklisted, "An expired listing should stay listed until it is pruned");
+
+      await prune();
+
+      const blacklistAccount = await program.account.blackList.fetch(pdas.blacklist);
+      assert.isFalse(
+        blacklistAccount.blacklist.some(account => account.equals(accountToBlacklist.publicKey)),
+        "Expired listing was not pruned"
+      );
+      assert.isNull(
+        await provider.connection.getAccountInfo(entry.pubkey),
+        "Blacklist entry was not closed"
+      );
+    });
+
+    it("Approvals do not carry over to other blacklist details", async () => {
+      const accountToBlacklist = Keypair.generate();
+      const multisigAccount = await program.account.multisig.fetch(multisigPda);
+      const approved = blacklistDetails("internalInvestigation", "INT-7");
+      const submitted = blacklistDetails("sanctions", "INT-7");
+
+      const message = buildMessageHash(
+        "ADD_BLACKLIST",
+        pdas.blacklist,
+        accountToBlacklist.publicKey,
+        approved.bytes,
+        multisigAccount.nonce.toNumber()
+      );
+
+      try {
+        await program.methods
+          .addBlacklist(accountToBlacklist.publicKey, submitted.details)
+          .accounts({
+            payer: payer.publicKey,
+            mint: mint.publicKey,
+            tokenConfig: pdas.tokenConfig,
+            canMint: pdas.canMint,
+            internalWhitelist: pdas.internalWhitelist,
+            externalWhitelist: pdas.externalWhitelist,
+            trustedContracts: pdas.trustedContracts,
+            canForward: pdas.canForward,
+            blacklist: pdas.blacklist,
+            multisig: multisigPda,
+            instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+            systemProgram: SystemProgram.programId
+          })
+          .remainingAccounts([blacklistEntry(mint.publicKey, program.programId, accountToBlacklist.publicKey)])
+          .preInstructions([createEd25519Ix(owner1, message), createEd25519Ix(owner2, message)])
+          .rpc();
+
+        assert.fail("Should have rejected details the owners did not approve");
+      } catch (error) {
+        assert.include(error.toString(), "NotEnoughMultisigSigners");
+      }
     });
   });
 
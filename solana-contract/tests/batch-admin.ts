@@ -0,0 +1,242 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Cngn } from "../target/types/cngn";
+import { assert, expect } from 'chai';
+import { PublicKey, Keypair, SystemProgram, SYSVAR_INSTRUCTIONS_PUBKEY, TransactionInstruction } from '@solana/web3.js';
+import { calculatePDAs, TokenPDAs, blacklistDetails, blacklistEntry } from '../utils/helpers';
+import { initializeToken, initializeMultisig } from '../utils/token_initializer';
+import * as crypto from 'crypto';
+import nacl from 'tweetnacl';
+
+describe("cngn batch admin operations", () => {
+  const connection = new anchor.web3.Connection('http://127.0.0.1:8899', 'confirmed');
+  const wallet = anchor.Wallet.local();
+  const provider = new anchor.AnchorProvider(connection, wallet, {
+    commitment: 'confirmed',
+  });
+  anchor.setProvider(provider);
+  const payer = (provider.wallet as anchor.Wallet).payer;
+  const program = anchor.workspace.Cngn as Program<Cngn>;
+
+  const mint = Keypair.generate();
+
+  // Multisig owners
+  const owner1 = Keypair.generate();
+  const owner2 = Keypair.generate();
+  const owner3 = Keypair.generate();
+  const threshold = 2;
+
+  const exchanges = [1, 2, 3].map(() => Keypair.generate().publicKey);
+  const offenders = [1, 2].map(() => Keypair.generate().publicKey);
+
+  let pdas: TokenPDAs;
+
+  // Ed25519 instruction with one signature over `message`
+  function createEd25519Ix(signer: Keypair, message: Buffer): TransactionInstruction {
+    const signature = nacl.sign.detached(message, signer.secretKey);
+    const publicKey = signer.publicKey.toBytes();
+
+    const offsetsStruct = Buffer.alloc(14);
+    offsetsStruct.writeUInt16LE(16, 0);
+    offsetsStruct.writeUInt16LE(0xFFFF, 2);
+    offsetsStruct.writeUInt16LE(80, 4);
+    offsetsStruct.writeUInt16LE(0xFFFF, 6);
+    offsetsStruct.writeUInt16LE(112, 8);
+    offsetsStruct.writeUInt16LE(message.length, 10);
+    offsetsStruct.writeUInt16LE(0xFFFF, 12);
+
+    const data = Buffer.concat([
+      Buffer.from([1, 0]),
+      offsetsStruct,
+      Buffer.from(signature),
+      publicKey,
+      message
+    ]);
+
+    return new TransactionInstruction({
+      keys: [],
+      programId: new PublicKey('Ed25519SigVerify111111111111111111111111111'),
+      data,
+    });
+  }
+
+  function u64(value: anchor.BN | number): Buffer {
+    const buf = Buffer.alloc(8);
+    buf.writeBigUInt64LE(BigInt(value.toString()));
+    return buf;
+  }
+
+  function listHash(addresses: PublicKey[]): Buffer {
+    return crypto.createHash('sha256').update(Buffer.concat(addresses.map(address => address.toBuffer()))).digest();
+  }
+
+  function buildMessage(action: string, ...parts: Buffer[]): Buffer {
+    const hash = crypto.createHash('sha256');
+    hash.update(Buffer.from(action));
+    for (const part of parts) {
+      hash.update(part);
+    }
+    return hash.digest();
+  }
+
+  async function currentNonce(): Promise<number> {
+    const multisigAccount = await program.account.multisig.fetch(pdas.multisig);
+    return multisigAccount.nonce.toNumber();
+  }
+
+  const sanctions = blacklistDetails("sanctions", "SANCTIONS-LIST-2024-07");
+  const entries = (users: PublicKey[]) =>
+    users.map(user => blacklistEntry(mint.publicKey, program.programId, user));
+
+  function blacklistAccounts() {
+    return {
+      payer: payer.publicKey,
+      mint: mint.publicKey,
+      tokenConfig: pdas.tokenConfig,
+      canMint: pdas.canMint,
+      internalWhitelist: pdas.internalWhitelist,
+      externalWhitelist: pdas.externalWhitelist,
+      trustedContracts: pdas.trustedContracts,
+      canForward: pdas.canForward,
+      blacklist: pdas.blacklist,
+      multisig: pdas.multisig,
+      instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      systemProgram: SystemProgram.programId,
+    };
+  }
+
+  before(async () => {
+    pdas = calculatePDAs(mint.publicKey, program.programId);
+
+    await initializeToken(program, provider, mint, pdas, payer.publicKey);
+    await initializeMultisig(program, provider, mint, pdas, [owner1.publicKey, owner2.publicKey, owner3.publicKey], threshold);
+  });
+
+  it("Whitelists several exchanges under one approval", async () => {
+    const nonce = await currentNonce();
+    const message = buildMessage("BATCH_WHITELIST_EXTERNAL", pdas.externalWhitelist.toBuffer(), listHash(exchanges), u64(nonce));
+
+    await program.methods
+      .batchWhitelistExternal(exchanges)
+      .accounts({
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        externalWhitelist: pdas.externalWhitelist,
+        blacklist: pdas.blacklist,
+        multisig: pdas.multisig,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .preInstructions([createEd25519Ix(owner1, message), createEd25519Ix(owner2, message)])
+      .rpc();
+
+    const whitelist = await program.account.externalWhiteList.fetch(pdas.externalWhitelist);
+    for (const exchange of exchanges) {
+      expect(whitelist.whitelist.map(key => key.toBase58())).to.include(exchange.toBase58());
+    }
+    expect(await currentNonce()).to.equal(nonce + 1);
+  });
+
+  it("Rejects an approval of a different address list", async () => {
+    const nonce = await currentNonce();
+    const message = buildMessage("BATCH_ADD_BLACKLIST", pdas.blacklist.toBuffer(), listHash(offenders.slice(0, 1)), sanctions.bytes, u64(nonce));
+
+    try {
+      await program.methods
+        .batchAddBlacklist(offenders, sanctions.details)
+        .accounts(blacklistAccounts())
+        .remainingAccounts(entries(offenders))
+        .preInstructions([createEd25519Ix(owner1, message), createEd25519Ix(owner2, message)])
+        .rpc();
+      assert.fail("Expected the batch to be rejected");
+    } catch (error: any) {
+      expect(error.toString()).to.include("NotEnoughMultisigSigners");
+    }
+  });
+
+  it("Rejects a case reference outside printable ASCII", async () => {
+    const details = blacklistDetails("sanctions", "SANCTIONS-LIST-é");
+    const message = buildMessage("BATCH_ADD_BLACKLIST", pdas.blacklist.toBuffer(), listHash(offenders), details.bytes, u64(await currentNonce()));
+
+    try {
+      await program.methods
+        .batchAddBlacklist(offenders, details.details)
+        .accounts(blacklistAccounts())
+        .remainingAccounts(entries(offenders))
+        .preInstructions([createEd25519Ix(owner1, message), createEd25519Ix(owner2, message)])
+        .rpc();
+      assert.fail("Expected the details to be rejected");
+    } catch (error: any) {
+      expect(error.toString()).to.include("InvalidBlacklistDetails");
+    }
+  });
+
+  it("Blacklists an exchange and strips it from the external whitelist", async () => {
+    const users = [...offenders, exchanges[0]];
+    const message = buildMessage("BATCH_ADD_BLACKLIST", pdas.blacklist.toBuffer(), listHash(users), sanctions.bytes, u64(await currentNonce()));
+
+    await program.methods
+      .batchAddBlacklist(users, sanctions.details)
+      .accounts(blacklistAccounts())
+      .remainingAccounts(entries(users))
+      .preInstructions([createEd25519Ix(owner1, message), createEd25519Ix(owner2, message)])
+      .rpc();
+
+    const blacklist = await program.account.blackList.fetch(pdas.blacklist);
+    expect(blacklist.blacklist.length).to.equal(users.length);
+    const whitelist = await program.account.externalWhiteList.fetch(pdas.externalWhitelist);
+    expect(whitelist.whitelist.map(key => key.toBase58())).to.not.include(exchanges[0].toBase58());
+    for (const entry of entries(users)) {
+      const record = await program.account.blacklistEntry.fetch(entry.pubkey);
+      expect(record.details.caseReference).to.equal("SANCTIONS-LIST-2024-07");
+    }
+  });
+
+  it("Lifts several blacklistings under one approval", async () => {
+    const message = buildMessage("BATCH_REMOVE_BLACKLIST", pdas.blacklist.toBuffer(), listHash(offenders), u64(await currentNonce()));
+
+    await program.methods
+      .batchRemoveBlacklist(offenders)
+      .accounts({
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        blacklist: pdas.blacklist,
+        multisig: pdas.multisig,
+        rentRecipient: payer.publicKey,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      // Each entry is followed by the payer recorded in it, which gets its rent back
+      .remainingAccounts(entries(offenders).flatMap(entry => [
+        entry,
+        { pubkey: payer.publicKey, isSigner: false, isWritable: true },
+      ]))
+      .preInstructions([createEd25519Ix(owner1, message), createEd25519Ix(owner2, message)])
+      .rpc();
+
+    const blacklist = await program.account.blackList.fetch(pdas.blacklist);
+    expect(blacklist.blacklist.map(key => key.toBase58())).to.deep.equal([exchanges[0].toBase58()]);
+    for (const entry of entries(offenders)) {
+      expect(await provider.connection.getAccountInfo(entry.pubkey)).to.be.null;
+    }
+  });
+
+  it("Rejects an empty batch", async () => {
+    const message = buildMessage("BATCH_ADD_TRUSTED_CONTRACT", pdas.trustedContracts.toBuffer(), listHash([]), u64(await currentNonce()));
+
+    try {
+      await program.methods
+        .batchAddTrustedContract([])
+        .accounts({
+          mint: mint.publicKey,
+          tokenConfig: pdas.tokenConfig,
+          trustedContracts: pdas.trustedContracts,
+          multisig: pdas.multisig,
+          instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .preInstructions([createEd25519Ix(owner1, message), createEd25519Ix(owner2, message)])
+        .rpc();
+      assert.fail("Expected the batch to be rejected");
+    } catch (error: any) {
+      expect(error.toString()).to.include("InvalidBatch");
+    }
+  });
+});
@@ -0,0 +1,283 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Cngn } from "../target/types/cngn";
+import { assert, expect } from 'chai';
+import { PublicKey, Keypair, SystemProgram, SYSVAR_INSTRUCTIONS_PUBKEY, TransactionInstruction } from '@solana/web3.js';
+import { TOKEN_2022_PROGRAM_ID, getAccount } from '@solana/spl-token';
+import { calculatePDAs, TokenPDAs, blacklistDetails, blacklistEntry } from '../utils/helpers';
+import { initializeToken, initializeMultisig, setupUserAccounts, kycApproveAccounts, kycPdas, yieldPositionPda, feeConfigPda } from '../utils/token_initializer';
+import * as crypto from 'crypto';
+import nacl from 'tweetnacl';
+
+describe("cngn batch transfers", () => {
+  const connection = new anchor.web3.Connection('http://127.0.0.1:8899', 'confirmed');
+  const wallet = anchor.Wallet.local();
+  const provider = new anchor.AnchorProvider(connection, wallet, {
+    commitment: 'confirmed',
+  });
+  anchor.setProvider(provider);
+  const payer = (provider.wallet as anchor.Wallet).payer;
+  const program = anchor.workspace.Cngn as Program<Cngn>;
+
+  const mint = Keypair.generate();
+
+  // Multisig owners
+  const owner1 = Keypair.generate();
+  const owner2 = Keypair.generate();
+  const owner3 = Keypair.generate();
+  const threshold = 2;
+
+  const employer = Keypair.generate();
+  const alice = Keypair.generate();
+  const bob = Keypair.generate();
+  const blocked = Keypair.generate();
+
+  let pdas: TokenPDAs;
+  let employerTokenAccount: PublicKey;
+  let aliceTokenAccount: PublicKey;
+  let bobTokenAccount: PublicKey;
+  let blockedTokenAccount: PublicKey;
+  let employerLimits: PublicKey;
+
+  // Ed25519 instruction with one signature over `message`
+  function createEd25519Ix(signer: Keypair, message: Buffer): TransactionInstruction {
+    const signature = nacl.sign.detached(message, signer.secretKey);
+    const publicKey = signer.publicKey.toBytes();
+
+    const offsetsStruct = Buffer.alloc(14);
+    offsetsStruct.writeUInt16LE(16, 0);
+    offsetsStruct.writeUInt16LE(0xFFFF, 2);
+    offsetsStruct.writeUInt16LE(80, 4);
+    offsetsStruct.writeUInt16LE(0xFFFF, 6);
+    offsetsStruct.writeUInt16LE(112, 8);
+    offsetsStruct.writeUInt16LE(message.length, 10);
+    offsetsStruct.writeUInt16LE(0xFFFF, 12);
+
+    const data = Buffer.concat([
+      Buffer.from([1, 0]),
+      offsetsStruct,
+      Buffer.from(signature),
+      publicKey,
+      message
+    ]);
+
+    return new TransactionInstruction({
+      keys: [],
+      programId: new PublicKey('Ed25519SigVerify111111111111111111111111111'),
+      data,
+    });
+  }
+
+  function u64(value: anchor.BN | number): Buffer {
+    const buf = Buffer.alloc(8);
+    buf.writeBigUInt64LE(BigInt(value.toString()));
+    return buf;
+  }
+
+  function buildMessage(action: string, ...parts: Buffer[]): Buffer {
+    const hash = crypto.createHash('sha256');
+    hash.update(Buffer.from(action));
+    for (const part of parts) {
+      hash.update(part);
+    }
+    return hash.digest();
+  }
+
+  async function currentNonce(): Promise<number> {
+    const multisigAccount = await program.account.multisig.fetch(pdas.multisig);
+    return multisigAccount.nonce.toNumber();
+  }
+
+  // Minting consumes the minter's allowance, so re-authorise every time
+  async function mintTo(tokenAccount: PublicKey, amount: anchor.BN) {
+    let message = buildMessage("ADD_CAN_MINT", pdas.canMint.toBuffer(), payer.publicKey.toBuffer(), u64(await currentNonce()));
+    await program.methods
+      .addCanMint(payer.publicKey)
+      .accounts({
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        blacklist: pdas.blacklist,
+        canMint: pdas.canMint,
+        trustedContracts: pdas.trustedContracts,
+        multisig: pdas.multisig,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .preInstructions([createEd25519Ix(owner1, message), createEd25519Ix(owner2, message)])
+      .rpc();
+
+    message = buildMessage("SET_MINT_AMOUNT", pdas.canMint.toBuffer(), payer.publicKey.toBuffer(), u64(await currentNonce()));
+    await program.methods
+      .setMintAmount(payer.publicKey, amount)
+      .accounts({
+        multisig: pdas.multisig,
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        canMint: pdas.canMint,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .preInstructions([createEd25519Ix(owner1, message), createEd25519Ix(owner2, message)])
+      .rpc();
+
+    await program.methods
+      .mint(amount)
+      .accounts({
+        authority: payer.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        mintAuthority: pdas.mintAuthority,
+        mint: mint.publicKey,
+        tokenAccount,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        blacklist: pdas.blacklist,
+        canMint: pdas.canMint,
+        trustedContracts: pdas.trustedContracts,
+      })
+      .rpc();
+  }
+
+  async function balance(tokenAccount: PublicKey): Promise<string> {
+    const account = await getAccount(provider.connection, tokenAccount, 'confirmed', TOKEN_2022_PROGRAM_ID);
+    return account.amount.toString();
+  }
+
+  function leaf(tokenAccount: PublicKey, amount: number): Buffer {
+    return crypto.createHash('sha256').update(tokenAccount.toBuffer()).update(u64(amount)).digest();
+  }
+
+  function pair(left: Buffer, right: Buffer): Buffer {
+    return crypto.createHash('sha256').update(left).update(right).digest();
+  }
+
+  function batchTransfer(recipients: PublicKey[], amounts: number[], mode: any) {
+    return program.methods
+      .batchTransfer(amounts.map(amount => new anchor.BN(amount)), mode)
+      .accounts({
+        sender: employer.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        mint: mint.publicKey,
+        mintAuthority: pdas.mintAuthority,
+        senderTokenAccount: employerTokenAccount,
+        blacklist: pdas.blacklist,
+        internalWhitelist: pdas.internalWhitelist,
+        transferTiers: pdas.transferTiers,
+        transferLimits: employerLimits,
+        yieldPosition: yieldPositionPda(mint.publicKey, program.programId, employerTokenAccount),
+        feeConfig: feeConfigPda(mint.publicKey, program.programId),
+        // No fee treasury is set in this suite and the fee is zero, so it is not read
+        feeTreasury: employerTokenAccount,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .remainingAccounts(recipients.map(pubkey => ({ pubkey, isSigner: false, isWritable: true })))
+      .signers([employer]);
+  }
+
+  before(async () => {
+    pdas = calculatePDAs(mint.publicKey, program.programId);
+    [employerLimits] = PublicKey.findProgramAddressSync(
+      [Buffer.from("transfer-limits"), mint.publicKey.toBuffer(), employer.publicKey.toBuffer()],
+      program.programId
+    );
+
+    for (const account of [employer.publicKey, alice.publicKey, bob.publicKey, blocked.publicKey]) {
+      await provider.connection.requestAirdrop(account, 1 * anchor.web3.LAMPORTS_PER_SOL);
+    }
+    await new Promise(resolve => setTimeout(resolve, 2000));
+
+    await initializeToken(program, provider, mint, pdas, payer.publicKey);
+    await initializeMultisig(program, provider, mint, pdas, [owner1.publicKey, owner2.publicKey, owner3.publicKey], threshold);
+    const accounts = await setupUserAccounts(provider, [employer, alice, bob, blocked], mint.publicKey);
+    [employerTokenAccount, aliceTokenAccount, bobTokenAccount, blockedTokenAccount] = accounts;
+    await kycApproveAccounts(program, provider, mint, pdas, [owner1, owner2], accounts);
+
+    let message = buildMessage(
+      "SET_TRANSFER_TIER",
+      employerLimits.toBuffer(),
+      employer.publicKey.toBuffer(),
+      Buffer.from([1]),
+      u64(await currentNonce())
+    );
+    await program.methods
+      .setTransferTier(employer.publicKey, 1)
+      .accounts({
+        payer: payer.publicKey,
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        transferLimits: employerLimits,
+        kycRecord: kycPdas(mint.publicKey, program.programId, employer.publicKey).kycRecord,
+        internalWhitelist: pdas.internalWhitelist,
+        externalWhitelist: pdas.externalWhitelist,
+        multisig: pdas.multisig,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        systemProgram: SystemProgram.programId,
+      })
+      .preInstructions([createEd25519Ix(owner1, message), createEd25519Ix(owner2, message)])
+      .rpc();
+
+    const { details, bytes } = blacklistDetails("fraudReport", "FR-1");
+    message = buildMessage("ADD_BLACKLIST", pdas.blacklist.toBuffer(), blocked.publicKey.toBuffer(), bytes, u64(await currentNonce()));
+    await program.methods
+      .addBlacklist(blocked.publicKey, details)
+      .accounts({
+        payer: payer.publicKey,
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        canMint: pdas.canMint,
+        internalWhitelist: pdas.internalWhitelist,
+        externalWhitelist: pdas.externalWhitelist,
+        trustedContracts: pdas.trustedContracts,
+        canForward: pdas.canForward,
+        blacklist: pdas.blacklist,
+        multisig: pdas.multisig,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts([blacklistEntry(mint.publicKey, program.programId, blocked.publicKey)])
+      .preInstructions([createEd25519Ix(owner1, message), createEd25519Ix(owner2, message)])
+      .rpc();
+
+    await mintTo(employerTokenAccount, new anchor.BN(10_000_000));
+  });
+
+  it("Pays every recipient and emits the payout root", async () => {
+    const listener = program.addEventListener("batchTransferEvent", (event) => {
+      expect(event.paid).to.equal(2);
+      expect(event.skipped).to.equal(0);
+      expect(event.totalAmount.toString()).to.equal("500000");
+      const root = pair(leaf(aliceTokenAccount, 200_000), leaf(bobTokenAccount, 300_000));
+      expect(Buffer.from(event.merkleRoot).equals(root)).to.be.true;
+    });
+
+    await batchTransfer([aliceTokenAccount, bobTokenAccount], [200_000, 300_000], { atomic: {} }).rpc();
+    await program.removeEventListener(listener);
+
+    expect(await balance(aliceTokenAccount)).to.equal("200000");
+    expect(await balance(bobTokenAccount)).to.equal("300000");
+    expect(await balance(employerTokenAccount)).to.equal("9500000");
+  });
+
+  it("Fails an atomic batch with a blacklisted recipient", async () => {
+    try {
+      await batchTransfer([aliceTokenAccount, blockedTokenAccount], [100_000, 100_000], { atomic: {} }).rpc();
+      assert.fail("Expected the batch to be rejected");
+    } catch (error: any) {
+      expect(error.toString()).to.include("ReceiverBlacklisted");
+    }
+    expect(await balance(aliceTokenAccount)).to.equal("200000");
+  });
+
+  it("Skips a blacklisted recipient when asked to", async () => {
+    await batchTransfer([aliceTokenAccount, blockedTokenAccount], [100_000, 100_000], { skipInvalid: {} }).rpc();
+
+    expect(await balance(aliceTokenAccount)).to.equal("300000");
+    expect(await balance(blockedTokenAccount)).to.equal("0");
+    expect(await balance(employerTokenAccount)).to.equal("9400000");
+  });
+
+  it("Rejects a batch whose amounts do not match its recipients", async () => {
+    try {
+      await batchTransfer([aliceTokenAccount, bobTokenAccount], [100_000], { atomic: {} }).rpc();
+      assert.fail("Expected the batch to be rejected");
+    } catch (error: any) {
+      expect(error.toString()).to.include("InvalidBatch");
+    }
+  });
+});
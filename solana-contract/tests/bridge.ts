@@ -0,0 +1,266 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Cngn } from "../target/types/cngn";
+import { assert, expect } from 'chai';
+import { PublicKey, Keypair, SYSVAR_INSTRUCTIONS_PUBKEY, TransactionInstruction } from '@solana/web3.js';
+import { TOKEN_2022_PROGRAM_ID, getAccount } from '@solana/spl-token';
+import { calculatePDAs, TokenPDAs } from '../utils/helpers';
+import { initializeToken, initializeMultisig, setupUserAccounts, kycApproveAccounts, yieldPositionPda } from '../utils/token_initializer';
+import * as crypto from 'crypto';
+import nacl from 'tweetnacl';
+
+describe("cngn bridge", () => {
+  const connection = new anchor.web3.Connection('http://127.0.0.1:8899', 'confirmed');
+  const wallet = anchor.Wallet.local();
+  const provider = new anchor.AnchorProvider(connection, wallet, {
+    commitment: 'confirmed',
+  });
+  anchor.setProvider(provider);
+  const payer = (provider.wallet as anchor.Wallet).payer;
+  const program = anchor.workspace.Cngn as Program<Cngn>;
+
+  const mint = Keypair.generate();
+  const user = Keypair.generate();
+
+  // Multisig owners
+  const owner1 = Keypair.generate();
+  const owner2 = Keypair.generate();
+  const owner3 = Keypair.generate();
+  const threshold = 2;
+
+  // Bridge attesters
+  const attester1 = Keypair.generate();
+  const attester2 = Keypair.generate();
+
+  // cngn-messages chain ids
+  const SOLANA_CHAIN_ID = 1;
+  const BASE_CHAIN_ID = 5;
+  // 1 cNGN: 6 decimals on Solana, 18 on the wire
+  const amount = new anchor.BN(1_000_000);
+  const canonicalAmount = BigInt(1_000_000) * BigInt(10) ** BigInt(12);
+  const evmSender = Buffer.concat([Buffer.alloc(12), Buffer.from("71c7656ec7ab88b098defb751b7401b5f6d8976f", "hex")]);
+
+  let pdas: TokenPDAs;
+  let multisigPda: PublicKey;
+  let bridgeConfig: PublicKey;
+  let userTokenAccount: PublicKey;
+
+  // Ed25519 instruction with one signature over `message`
+  function createEd25519Ix(signer: Keypair, message: Buffer): TransactionInstruction {
+    const signature = nacl.sign.detached(message, signer.secretKey);
+    const publicKey = signer.publicKey.toBytes();
+
+    const offsetsStruct = Buffer.alloc(14);
+    offsetsStruct.writeUInt16LE(16, 0);
+    offsetsStruct.writeUInt16LE(0xFFFF, 2);
+    offsetsStruct.writeUInt16LE(80, 4);
+    offsetsStruct.writeUInt16LE(0xFFFF, 6);
+    offsetsStruct.writeUInt16LE(112, 8);
+    offsetsStruct.writeUInt16LE(message.length, 10);
+    offsetsStruct.writeUInt16LE(0xFFFF, 12);
+
+    const data = Buffer.concat([
+      Buffer.from([1, 0]),
+      offsetsStruct,
+      Buffer.from(signature),
+      publicKey,
+      message
+    ]);
+
+    return new TransactionInstruction({
+      keys: [],
+      programId: new PublicKey('Ed25519SigVerify111111111111111111111111111'),
+      data,
+    });
+  }
+
+  function u32(value: number): Buffer {
+    const buf = Buffer.alloc(4);
+    buf.writeUInt32LE(value);
+    return buf;
+  }
+
+  function u64(value: anchor.BN | number): Buffer {
+    const buf = Buffer.alloc(8);
+    buf.writeBigUInt64LE(BigInt(value.toString()));
+    return buf;
+  }
+
+  function buildUpdateBridgeMessage(attesters: PublicKey[], bridgeThreshold: number, paused: boolean, nonce: number): Buffer {
+    const hash = crypto.createHash('sha256');
+    hash.update(Buffer.from("UPDATE_BRIDGE"));
+    hash.update(bridgeConfig.toBuffer());
+    for (const attester of attesters) {
+      hash.update(attester.toBuffer());
+    }
+    hash.update(Buffer.from([bridgeThreshold, paused ? 1 : 0]));
+    hash.update(u64(nonce));
+    return hash.digest();
+  }
+
+  // cngn-messages v1 encoding (big-endian), see crates/cngn-messages
+  function encodeTransfer(sourceChain: number, destinationChain: number, nonce: anchor.BN, sender: Buffer, recipient: Buffer, value: bigint): Buffer {
+    const header = Buffer.alloc(17);
+    header.writeUInt8(1, 0);
+    header.writeUInt32BE(sourceChain, 1);
+    header.writeUInt32BE(destinationChain, 5);
+    header.writeBigUInt64BE(BigInt(nonce.toString()), 9);
+    const amountBuf = Buffer.from(value.toString(16).padStart(32, "0"), "hex");
+    return Buffer.concat([header, sender, recipient, amountBuf]);
+  }
+
+  function buildBridgeInMessage(nonce: anchor.BN, sender: Buffer, recipient: PublicKey): Buffer {
+    const hash = crypto.createHash('sha256');
+    hash.update(Buffer.from("BRIDGE_IN"));
+    hash.update(bridgeConfig.toBuffer());
+    hash.update(encodeTransfer(BASE_CHAIN_ID, SOLANA_CHAIN_ID, nonce, sender, recipient.toBuffer(), canonicalAmount));
+    return hash.digest();
+  }
+
+  function processedMessagePda(sourceChain: number, nonce: anchor.BN): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("bridge-message"), mint.publicKey.toBuffer(), u32(sourceChain), u64(nonce)],
+      program.programId
+    )[0];
+  }
+
+  async function bridgeIn(nonce: anchor.BN, attesters: Keypair[]) {
+    const message = buildBridgeInMessage(nonce, evmSender, user.publicKey);
+
+    return program.methods
+      .bridgeIn(BASE_CHAIN_ID, nonce, Array.from(evmSender), new anchor.BN(canonicalAmount.toString()))
+      .accounts({
+        payer: payer.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        bridgeConfig,
+        mint: mint.publicKey,
+        mintAuthority: pdas.mintAuthority,
+        tokenAccount: userTokenAccount,
+        blacklist: pdas.blacklist,
+        processedMessage: processedMessagePda(BASE_CHAIN_ID, nonce),
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .preInstructions(attesters.map((attester) => createEd25519Ix(attester, message)))
+      .rpc();
+  }
+
+  before(async () => {
+    pdas = calculatePDAs(mint.publicKey, program.programId);
+    [multisigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("multisig"), mint.publicKey.toBuffer()],
+      program.programId
+    );
+    [bridgeConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from("bridge-config"), mint.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await provider.connection.requestAirdrop(user.publicKey, 2 * anchor.web3.LAMPORTS_PER_SOL);
+    await new Promise(resolve => setTimeout(resolve, 2000));
+
+    await initializeToken(program, provider, mint, pdas, payer.publicKey);
+    await initializeMultisig(program, provider, mint, pdas, [owner1.publicKey, owner2.publicKey, owner3.publicKey], threshold);
+    [userTokenAccount] = await setupUserAccounts(provider, [user], mint.publicKey);
+    await kycApproveAccounts(program, provider, mint, pdas, [owner1, owner2], [userTokenAccount]);
+
+    await program.methods
+      .initializeBridge()
+      .accounts({
+        initializer: payer.publicKey,
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        bridgeConfig,
+      })
+      .rpc();
+  });
+
+  it("Starts paused and without attesters", async () => {
+    const config = await program.account.bridgeConfig.fetch(bridgeConfig);
+    expect(config.paused).to.be.true;
+    expect(config.attesters).to.be.empty;
+    expect(config.threshold).to.equal(0);
+  });
+
+  it("Multisig sets the attesters and resumes the bridge", async () => {
+    const attesters = [attester1.publicKey, attester2.publicKey];
+    const multisigAccount = await program.account.multisig.fetch(multisigPda);
+    const message = buildUpdateBridgeMessage(attesters, 2, false, multisigAccount.nonce.toNumber());
+
+    await program.methods
+      .updateBridge(attesters, 2, false)
+      .accounts({
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        bridgeConfig,
+        multisig: multisigPda,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .preInstructions([createEd25519Ix(owner1, message), createEd25519Ix(owner2, message)])
+      .rpc();
+
+    const config = await program.account.bridgeConfig.fetch(bridgeConfig);
+    expect(config.paused).to.be.false;
+    expect(config.threshold).to.equal(2);
+    expect(config.attesters.map((key: PublicKey) => key.toBase58())).to.deep.equal(
+      attesters.map((key) => key.toBase58())
+    );
+  });
+
+  it("Rejects an inbound transfer without enough attestations", async () => {
+    try {
+      await bridgeIn(new anchor.BN(0), [attester1]);
+      assert.fail("Expected the transfer to be rejected");
+    } catch (error: any) {
+      expect(error.toString()).to.include("NotEnoughAttestations");
+    }
+  });
+
+  it("Mints an attested inbound transfer exactly once", async () => {
+    const nonce = new anchor.BN(7);
+    await bridgeIn(nonce, [attester1, attester2]);
+
+    const account = await getAccount(provider.connection, userTokenAccount, undefined, TOKEN_2022_PROGRAM_ID);
+    expect(account.amount.toString()).to.equal(amount.toString());
+
+    const processed = await program.account.processedBridgeMessage.fetch(processedMessagePda(BASE_CHAIN_ID, nonce));
+    assert.ok(processed.recipient.equals(user.publicKey));
+
+    try {
+      await bridgeIn(nonce, [attester1, attester2]);
+      assert.fail("Expected the replay to be rejected");
+    } catch (error: any) {
+      expect(error.toString()).to.include("already in use");
+    }
+  });
+
+  function bridgeOut() {
+    const recipient = Buffer.alloc(32);
+    Buffer.from("00000000000000000000000000000000000000aa", "hex").copy(recipient, 12);
+
+    return program.methods
+      .bridgeOut(BASE_CHAIN_ID, Array.from(recipient), amount)
+      .accounts({
+        sender: user.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        bridgeConfig,
+        mint: mint.publicKey,
+        tokenAccount: userTokenAccount,
+        blacklist: pdas.blacklist,
+        yieldPosition: yieldPositionPda(mint.publicKey, program.programId, userTokenAccount),
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+  }
+
+  it("Burns an outbound transfer and advances the outbound nonce", async () => {
+    await bridgeOut();
+
+    const account = await getAccount(provider.connection, userTokenAccount, undefined, TOKEN_2022_PROGRAM_ID);
+    expect(account.amount.toString()).to.equal("0");
+
+    const config = await program.account.bridgeConfig.fetch(bridgeConfig);
+    expect(config.outboundNonce.toString()).to.equal("1");
+  });
+});
@@ -7,11 +7,11 @@ import {
     TOKEN_2022_PROGRAM_ID,
     getAccount,
     burn,
-    createBurnInstruction
+    createBurnInstruction,
+    getMint
 } from "@solana/spl-token";
 import { calculatePDAs } from "../utils/helpers";
 import { initializeToken, setupUserAccounts, TOKEN_PARAMS } from "../utils/token_initializer";
-import { transferAuthorityToPDA } from "./transfer-authority-to-pda";
 
 describe("cngn burn test", () => {
     const provider = anchor.AnchorProvider.env();
@@ -54,11 +54,10 @@ describe("cngn burn test", () => {
 
         console.log("Initializing token...");
         await initializeToken(program, provider, mint, pdas);
-
-     let afterMintInfo=await transferAuthorityToPDA(pdas, mint, payer, provider)
-      // Assertions to verify transfer
-      assert(afterMintInfo.mintAuthority?.equals(pdas.mintAuthority), "Mint authority should be the PDA");
-      assert(afterMintInfo.freezeAuthority?.equals(payer.publicKey), "Freeze authority should be the PDA");
+    // initialize creates the mint with the PDA as mint and freeze authority
+    const mintInfo = await getMint(provider.connection, mint.publicKey, null, TOKEN_2022_PROGRAM_ID);
+    assert(mintInfo.mintAuthority?.equals(pdas.mintAuthority), "Mint authority should be the PDA");
+    assert(mintInfo.freezeAuthority?.equals(pdas.mintAuthority), "Freeze authority should be the PDA");
         // Create token accounts
         const userAccounts = await setupUserAccounts(
             provider,
@@ -0,0 +1,161 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Cngn } from "../target/types/cngn";
+import { assert, expect } from 'chai';
+import { PublicKey, Keypair, SYSVAR_INSTRUCTIONS_PUBKEY, TransactionInstruction } from '@solana/web3.js';
+import { TOKEN_2022_PROGRAM_ID, ExtensionType, getMint, getExtensionTypes } from '@solana/spl-token';
+import { calculatePDAs, TokenPDAs } from '../utils/helpers';
+import { initializeToken, initializeMultisig, setupUserAccounts, kycApproveAccounts, kycPdas } from '../utils/token_initializer';
+import * as crypto from 'crypto';
+import nacl from 'tweetnacl';
+
+describe("cngn confidential transfers", () => {
+  const connection = new anchor.web3.Connection('http://127.0.0.1:8899', 'confirmed');
+  const wallet = anchor.Wallet.local();
+  const provider = new anchor.AnchorProvider(connection, wallet, {
+    commitment: 'confirmed',
+  });
+  anchor.setProvider(provider);
+  const payer = (provider.wallet as anchor.Wallet).payer;
+  const program = anchor.workspace.Cngn as Program<Cngn>;
+
+  const mint = Keypair.generate();
+
+  // Multisig owners
+  const owner1 = Keypair.generate();
+  const owner2 = Keypair.generate();
+  const owner3 = Keypair.generate();
+  const threshold = 2;
+
+  const user = Keypair.generate();
+
+  let pdas: TokenPDAs;
+  let userTokenAccount: PublicKey;
+
+  // Ed25519 instruction with one signature over `message`
+  function createEd25519Ix(signer: Keypair, message: Buffer): TransactionInstruction {
+    const signature = nacl.sign.detached(message, signer.secretKey);
+    const publicKey = signer.publicKey.toBytes();
+
+    const offsetsStruct = Buffer.alloc(14);
+    offsetsStruct.writeUInt16LE(16, 0);
+    offsetsStruct.writeUInt16LE(0xFFFF, 2);
+    offsetsStruct.writeUInt16LE(80, 4);
+    offsetsStruct.writeUInt16LE(0xFFFF, 6);
+    offsetsStruct.writeUInt16LE(112, 8);
+    offsetsStruct.writeUInt16LE(message.length, 10);
+    offsetsStruct.writeUInt16LE(0xFFFF, 12);
+
+    const data = Buffer.concat([
+      Buffer.from([1, 0]),
+      offsetsStruct,
+      Buffer.from(signature),
+      publicKey,
+      message
+    ]);
+
+    return new TransactionInstruction({
+      keys: [],
+      programId: new PublicKey('Ed25519SigVerify111111111111111111111111111'),
+      data,
+    });
+  }
+
+  async function currentNonce(): Promise<number> {
+    const multisigAccount = await program.account.multisig.fetch(pdas.multisig);
+    return multisigAccount.nonce.toNumber();
+  }
+
+  function buildUpdateAuditorMessage(auditor: number[] | null, nonce: number): Buffer {
+    const nonceBuf = Buffer.alloc(8);
+    nonceBuf.writeBigUInt64LE(BigInt(nonce));
+    const hash = crypto.createHash('sha256');
+    hash.update(Buffer.from("UPDATE_CONFIDENTIAL_AUDITOR"));
+    hash.update(mint.publicKey.toBuffer());
+    hash.update(auditor ? Buffer.concat([Buffer.from([1]), Buffer.from(auditor)]) : Buffer.from([0]));
+    hash.update(nonceBuf);
+    return hash.digest();
+  }
+
+  async function updateAuditor(auditor: number[] | null) {
+    const message = buildUpdateAuditorMessage(auditor, await currentNonce());
+    return program.methods
+      .updateConfidentialAuditor(auditor)
+      .accounts({
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        mintAuthority: pdas.mintAuthority,
+        multisig: pdas.multisig,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .preInstructions([createEd25519Ix(owner1, message), createEd25519Ix(owner2, message)])
+      .rpc();
+  }
+
+  before(async () => {
+    pdas = calculatePDAs(mint.publicKey, program.programId);
+
+    await initializeToken(program, provider, mint, pdas, payer.publicKey);
+    await initializeMultisig(program, provider, mint, pdas, [owner1.publicKey, owner2.publicKey, owner3.publicKey], threshold);
+    [userTokenAccount] = await setupUserAccounts(provider, [user], mint.publicKey);
+    await kycApproveAccounts(program, provider, mint, pdas, [owner1, owner2], [userTokenAccount]);
+  });
+
+  it("Creates the mint with the confidential transfer extensions", async () => {
+    const mintInfo = await getMint(provider.connection, mint.publicKey, 'confirmed', TOKEN_2022_PROGRAM_ID);
+    const extensions = getExtensionTypes(mintInfo.tlvData);
+    expect(extensions).to.include(ExtensionType.ConfidentialTransferMint);
+    expect(extensions).to.include(ExtensionType.ConfidentialTransferFeeConfig);
+  });
+
+  it("Multisig sets and removes the auditor", async () => {
+    const auditor = new Array(32).fill(0);
+    await updateAuditor(auditor);
+    await updateAuditor(null);
+  });
+
+  it("Rejects an auditor change without enough approvals", async () => {
+    const message = buildUpdateAuditorMessage(null, await currentNonce());
+    try {
+      await program.methods
+        .updateConfidentialAuditor(null)
+        .accounts({
+          mint: mint.publicKey,
+          tokenConfig: pdas.tokenConfig,
+          mintAuthority: pdas.mintAuthority,
+          multisig: pdas.multisig,
+          instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .preInstructions([createEd25519Ix(owner1, message)])
+        .rpc();
+      assert.fail("Expected the auditor change to be rejected");
+    } catch (error: any) {
+      expect(error.toString()).to.include("NotEnoughMultisigSigners");
+    }
+  });
+
+  it("Refuses confidential approval for owners off the internal whitelist", async () => {
+    const { kycOperators } = kycPdas(mint.publicKey, program.programId);
+    try {
+      await program.methods
+        .approveConfidentialAccount()
+        .accounts({
+          operator: payer.publicKey,
+          tokenConfig: pdas.tokenConfig,
+          mint: mint.publicKey,
+          mintAuthority: pdas.mintAuthority,
+          kycOperators,
+          tokenAccount: userTokenAccount,
+          blacklist: pdas.blacklist,
+          internalWhitelist: pdas.internalWhitelist,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .rpc();
+      assert.fail("Expected the approval to be rejected");
+    } catch (error: any) {
+      expect(error.toString()).to.include("ConfidentialTransferNotAllowed");
+    }
+  });
+});
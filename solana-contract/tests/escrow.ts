@@ -0,0 +1,304 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Cngn } from "../target/types/cngn";
+import { assert, expect } from 'chai';
+import { PublicKey, Keypair, SystemProgram, SYSVAR_INSTRUCTIONS_PUBKEY, TransactionInstruction } from '@solana/web3.js';
+import { TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, getAccount, getAssociatedTokenAddressSync } from '@solana/spl-token';
+import { calculatePDAs, TokenPDAs } from '../utils/helpers';
+import { initializeToken, initializeMultisig, setupUserAccounts, kycApproveAccounts, kycPdas, yieldPositionPda, feeConfigPda } from '../utils/token_initializer';
+import * as crypto from 'crypto';
+import nacl from 'tweetnacl';
+
+describe("cngn escrow", () => {
+  const connection = new anchor.web3.Connection('http://127.0.0.1:8899', 'confirmed');
+  const wallet = anchor.Wallet.local();
+  const provider = new anchor.AnchorProvider(connection, wallet, {
+    commitment: 'confirmed',
+  });
+  anchor.setProvider(provider);
+  const payer = (provider.wallet as anchor.Wallet).payer;
+  const program = anchor.workspace.Cngn as Program<Cngn>;
+
+  const mint = Keypair.generate();
+
+  // Multisig owners
+  const owner1 = Keypair.generate();
+  const owner2 = Keypair.generate();
+  const owner3 = Keypair.generate();
+  const threshold = 2;
+
+  const buyer = Keypair.generate();
+  const seller = Keypair.generate();
+  const arbiter = Keypair.generate();
+
+  const amount = new anchor.BN(1_000_000);
+
+  let pdas: TokenPDAs;
+  let buyerTokenAccount: PublicKey;
+  let sellerTokenAccount: PublicKey;
+  let buyerLimits: PublicKey;
+
+  // Ed25519 instruction with one signature over `message`
+  function createEd25519Ix(signer: Keypair, message: Buffer): TransactionInstruction {
+    const signature = nacl.sign.detached(message, signer.secretKey);
+    const publicKey = signer.publicKey.toBytes();
+
+    const offsetsStruct = Buffer.alloc(14);
+    offsetsStruct.writeUInt16LE(16, 0);
+    offsetsStruct.writeUInt16LE(0xFFFF, 2);
+    offsetsStruct.writeUInt16LE(80, 4);
+    offsetsStruct.writeUInt16LE(0xFFFF, 6);
+    offsetsStruct.writeUInt16LE(112, 8);
+    offsetsStruct.writeUInt16LE(message.length, 10);
+    offsetsStruct.writeUInt16LE(0xFFFF, 12);
+
+    const data = Buffer.concat([
+      Buffer.from([1, 0]),
+      offsetsStruct,
+      Buffer.from(signature),
+      publicKey,
+      message
+    ]);
+
+    return new TransactionInstruction({
+      keys: [],
+      programId: new PublicKey('Ed25519SigVerify111111111111111111111111111'),
+      data,
+    });
+  }
+
+  function u64(value: anchor.BN | number): Buffer {
+    const buf = Buffer.alloc(8);
+    buf.writeBigUInt64LE(BigInt(value.toString()));
+    return buf;
+  }
+
+  function buildMessage(action: string, ...parts: Buffer[]): Buffer {
+    const hash = crypto.createHash('sha256');
+    hash.update(Buffer.from(action));
+    for (const part of parts) {
+      hash.update(part);
+    }
+    return hash.digest();
+  }
+
+  async function currentNonce(): Promise<number> {
+    const multisigAccount = await program.account.multisig.fetch(pdas.multisig);
+    return multisigAccount.nonce.toNumber();
+  }
+
+  // Minting consumes the minter's allowance, so re-authorise every time
+  async function mintTo(tokenAccount: PublicKey, amount: anchor.BN) {
+    let message = buildMessage("ADD_CAN_MINT", pdas.canMint.toBuffer(), payer.publicKey.toBuffer(), u64(await currentNonce()));
+    await program.methods
+      .addCanMint(payer.publicKey)
+      .accounts({
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        blacklist: pdas.blacklist,
+        canMint: pdas.canMint,
+        trustedContracts: pdas.trustedContracts,
+        multisig: pdas.multisig,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .preInstructions([createEd25519Ix(owner1, message), createEd25519Ix(owner2, message)])
+      .rpc();
+
+    message = buildMessage("SET_MINT_AMOUNT", pdas.canMint.toBuffer(), payer.publicKey.toBuffer(), u64(await currentNonce()));
+    await program.methods
+      .setMintAmount(payer.publicKey, amount)
+      .accounts({
+        multisig: pdas.multisig,
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        canMint: pdas.canMint,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .preInstructions([createEd25519Ix(owner1, message), createEd25519Ix(owner2, message)])
+      .rpc();
+
+    await program.methods
+      .mint(amount)
+      .accounts({
+        authority: payer.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        mintAuthority: pdas.mintAuthority,
+        mint: mint.publicKey,
+        tokenAccount,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        blacklist: pdas.blacklist,
+        canMint: pdas.canMint,
+        trustedContracts: pdas.trustedContracts,
+      })
+      .rpc();
+  }
+
+  function escrowPdas(id: number) {
+    const idBuf = u64(id);
+    const [escrow] = PublicKey.findProgramAddressSync(
+      [Buffer.from("escrow"), mint.publicKey.toBuffer(), buyer.publicKey.toBuffer(), idBuf],
+      program.programId
+    );
+    const vault = getAssociatedTokenAddressSync(mint.publicKey, escrow, true, TOKEN_2022_PROGRAM_ID);
+    return { escrow, vault };
+  }
+
+  async function createEscrow(id: number, deadline: number) {
+    const { escrow, vault } = escrowPdas(id);
+    await program.methods
+      .createEscrow(new anchor.BN(id), seller.publicKey, arbiter.publicKey, amount, new anchor.BN(deadline))
+      .accounts({
+        payer: buyer.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        mint: mint.publicKey,
+        mintAuthority: pdas.mintAuthority,
+        payerTokenAccount: buyerTokenAccount,
+        escrow,
+        vault,
+        blacklist: pdas.blacklist,
+        internalWhitelist: pdas.internalWhitelist,
+        transferTiers: pdas.transferTiers,
+        transferLimits: buyerLimits,
+        yieldPosition: yieldPositionPda(mint.publicKey, program.programId, buyerTokenAccount),
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([buyer])
+      .rpc();
+    return { escrow, vault };
+  }
+
+  function settleAccounts(id: number, authority: PublicKey, recipientTokenAccount: PublicKey) {
+    const { escrow, vault } = escrowPdas(id);
+    return {
+      authority,
+      payer: buyer.publicKey,
+      tokenConfig: pdas.tokenConfig,
+      mint: mint.publicKey,
+      mintAuthority: pdas.mintAuthority,
+      escrow,
+      vault,
+      recipientTokenAccount,
+      blacklist: pdas.blacklist,
+      internalWhitelist: pdas.internalWhitelist,
+      feeConfig: feeConfigPda(mint.publicKey, program.programId),
+      // No fee treasury is set in this suite and the fee is zero, so it is not read
+      feeTreasury: recipientTokenAccount,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+    };
+  }
+
+  const now = () => Math.floor(Date.now() / 1000);
+
+  before(async () => {
+    pdas = calculatePDAs(mint.publicKey, program.programId);
+    [buyerLimits] = PublicKey.findProgramAddressSync(
+      [Buffer.from("transfer-limits"), mint.publicKey.toBuffer(), buyer.publicKey.toBuffer()],
+      program.programId
+    );
+
+    for (const account of [buyer.publicKey, seller.publicKey, arbiter.publicKey]) {
+      await provider.connection.requestAirdrop(account, 1 * anchor.web3.LAMPORTS_PER_SOL);
+    }
+    await new Promise(resolve => setTimeout(resolve, 2000));
+
+    await initializeToken(program, provider, mint, pdas, payer.publicKey);
+    await initializeMultisig(program, provider, mint, pdas, [owner1.publicKey, owner2.publicKey, owner3.publicKey], threshold);
+    const accounts = await setupUserAccounts(provider, [buyer, seller], mint.publicKey);
+    [buyerTokenAccount, sellerTokenAccount] = accounts;
+    await kycApproveAccounts(program, provider, mint, pdas, [owner1, owner2], accounts);
+
+    const message = buildMessage(
+      "SET_TRANSFER_TIER",
+      buyerLimits.toBuffer(),
+      buyer.publicKey.toBuffer(),
+      Buffer.from([1]),
+      u64(await currentNonce())
+    );
+    await program.methods
+      .setTransferTier(buyer.publicKey, 1)
+      .accounts({
+        payer: payer.publicKey,
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        transferLimits: buyerLimits,
+        kycRecord: kycPdas(mint.publicKey, program.programId, buyer.publicKey).kycRecord,
+        internalWhitelist: pdas.internalWhitelist,
+        externalWhitelist: pdas.externalWhitelist,
+        multisig: pdas.multisig,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        systemProgram: SystemProgram.programId,
+      })
+      .preInstructions([createEd25519Ix(owner1, message), createEd25519Ix(owner2, message)])
+      .rpc();
+
+    await mintTo(buyerTokenAccount, new anchor.BN(10_000_000));
+  });
+
+  it("Rejects an escrow with a past deadline", async () => {
+    try {
+      await createEscrow(0, now() - 60);
+      assert.fail("Expected the escrow to be rejected");
+    } catch (error: any) {
+      expect(error.toString()).to.include("InvalidEscrow");
+    }
+  });
+
+  it("Locks funds and releases them to the payee", async () => {
+    const { escrow, vault } = await createEscrow(1, now() + 3600);
+    expect((await getAccount(provider.connection, vault, 'confirmed', TOKEN_2022_PROGRAM_ID)).amount.toString()).to.equal(amount.toString());
+
+    await program.methods
+      .releaseEscrow()
+      .accounts(settleAccounts(1, buyer.publicKey, sellerTokenAccount))
+      .signers([buyer])
+      .rpc();
+
+    expect((await getAccount(provider.connection, sellerTokenAccount, 'confirmed', TOKEN_2022_PROGRAM_ID)).amount.toString()).to.equal(amount.toString());
+    assert.isNull(await provider.connection.getAccountInfo(escrow));
+    assert.isNull(await provider.connection.getAccountInfo(vault));
+  });
+
+  it("Refuses a refund before the deadline", async () => {
+    await createEscrow(2, now() + 3600);
+    try {
+      await program.methods
+        .refundEscrow()
+        .accounts(settleAccounts(2, payer.publicKey, buyerTokenAccount))
+        .rpc();
+      assert.fail("Expected the refund to be rejected");
+    } catch (error: any) {
+      expect(error.toString()).to.include("EscrowNotAuthorized");
+    }
+  });
+
+  it("Leaves a disputed escrow to the arbiter", async () => {
+    const { escrow, vault } = escrowPdas(2);
+    await program.methods
+      .disputeEscrow()
+      .accounts({ authority: seller.publicKey, escrow })
+      .signers([seller])
+      .rpc();
+
+    try {
+      await program.methods
+        .releaseEscrow()
+        .accounts(settleAccounts(2, buyer.publicKey, sellerTokenAccount))
+        .signers([buyer])
+        .rpc();
+      assert.fail("Expected the payer to be refused");
+    } catch (error: any) {
+      expect(error.toString()).to.include("EscrowNotAuthorized");
+    }
+
+    await program.methods
+      .refundEscrow()
+      .accounts(settleAccounts(2, arbiter.publicKey, buyerTokenAccount))
+      .signers([arbiter])
+      .rpc();
+
+    assert.isNull(await provider.connection.getAccountInfo(escrow));
+    assert.isNull(await provider.connection.getAccountInfo(vault));
+  });
+});
@@ -0,0 +1,314 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Cngn } from "../target/types/cngn";
+import { assert, expect } from 'chai';
+import { PublicKey, Keypair, SystemProgram, SYSVAR_INSTRUCTIONS_PUBKEY, TransactionInstruction } from '@solana/web3.js';
+import { TOKEN_2022_PROGRAM_ID, getAccount } from '@solana/spl-token';
+import { calculatePDAs, TokenPDAs } from '../utils/helpers';
+import { initializeToken, initializeMultisig, setupUserAccounts, kycPdas } from '../utils/token_initializer';
+import * as crypto from 'crypto';
+import nacl from 'tweetnacl';
+
+describe("cngn external allowlist", () => {
+  const connection = new anchor.web3.Connection('http://127.0.0.1:8899', 'confirmed');
+  const wallet = anchor.Wallet.local();
+  const provider = new anchor.AnchorProvider(connection, wallet, {
+    commitment: 'confirmed',
+  });
+  anchor.setProvider(provider);
+  const payer = (provider.wallet as anchor.Wallet).payer;
+  const program = anchor.workspace.Cngn as Program<Cngn>;
+
+  const mint = Keypair.generate();
+
+  // Multisig owners
+  const owner1 = Keypair.generate();
+  const owner2 = Keypair.generate();
+  const owner3 = Keypair.generate();
+  const threshold = 2;
+
+  const operator = Keypair.generate();
+  const alice = Keypair.generate();
+  const bob = Keypair.generate();
+  const carol = Keypair.generate();
+  const mallory = Keypair.generate();
+
+  let pdas: TokenPDAs;
+  let kycOperators: PublicKey;
+  let externalAllowlist: PublicKey;
+  let aliceTokenAccount: PublicKey;
+  let bobTokenAccount: PublicKey;
+  let malloryTokenAccount: PublicKey;
+
+  // Ed25519 instruction with one signature over `message`
+  function createEd25519Ix(signer: Keypair, message: Buffer): TransactionInstruction {
+    const signature = nacl.sign.detached(message, signer.secretKey);
+    const publicKey = signer.publicKey.toBytes();
+
+    const offsetsStruct = Buffer.alloc(14);
+    offsetsStruct.writeUInt16LE(16, 0);
+    offsetsStruct.writeUInt16LE(0xFFFF, 2);
+    offsetsStruct.writeUInt16LE(80, 4);
+    offsetsStruct.writeUInt16LE(0xFFFF, 6);
+    offsetsStruct.writeUInt16LE(112, 8);
+    offsetsStruct.writeUInt16LE(message.length, 10);
+    offsetsStruct.writeUInt16LE(0xFFFF, 12);
+
+    const data = Buffer.concat([
+      Buffer.from([1, 0]),
+      offsetsStruct,
+      Buffer.from(signature),
+      publicKey,
+      message
+    ]);
+
+    return new TransactionInstruction({
+      keys: [],
+      programId: new PublicKey('Ed25519SigVerify111111111111111111111111111'),
+      data,
+    });
+  }
+
+  function buildTargetMessage(action: string, account: PublicKey, target: PublicKey, nonce: number): Buffer {
+    const nonceBuf = Buffer.alloc(8);
+    nonceBuf.writeBigUInt64LE(BigInt(nonce));
+    const hash = crypto.createHash('sha256');
+    hash.update(Buffer.from(action));
+    hash.update(account.toBuffer());
+    hash.update(target.toBuffer());
+    hash.update(nonceBuf);
+    return hash.digest();
+  }
+
+  async function currentNonce(): Promise<number> {
+    const multisigAccount = await program.account.multisig.fetch(pdas.multisig);
+    return multisigAccount.nonce.toNumber();
+  }
+
+  // Allowlist tree as `cngn-admin allowlist` builds it: sorted sha256(owner)
+  // leaves, parents hashed in ascending order, odd nodes moved up
+  function sha256(...parts: Buffer[]): Buffer {
+    const hash = crypto.createHash('sha256');
+    parts.forEach(part => hash.update(part));
+    return hash.digest();
+  }
+
+  function parent(a: Buffer, b: Buffer): Buffer {
+    return Buffer.compare(a, b) <= 0 ? sha256(a, b) : sha256(b, a);
+  }
+
+  function allowlistTree(owners: PublicKey[]): Buffer[][] {
+    const levels = [owners.map(owner => sha256(owner.toBuffer())).sort(Buffer.compare)];
+    while (levels[levels.length - 1].length > 1) {
+      const level = levels[levels.length - 1];
+      const next: Buffer[] = [];
+      for (let i = 0; i < level.length; i += 2) {
+        next.push(i + 1 < level.length ? parent(level[i], level[i + 1]) : level[i]);
+      }
+      levels.push(next);
+    }
+    return levels;
+  }
+
+  function allowlistProof(levels: Buffer[][], owner: PublicKey): number[][] {
+    let index = levels[0].findIndex(leaf => leaf.equals(sha256(owner.toBuffer())));
+    const proof: number[][] = [];
+    for (const level of levels.slice(0, -1)) {
+      const sibling = level[index ^ 1];
+      if (sibling) {
+        proof.push([...sibling]);
+      }
+      index = Math.floor(index / 2);
+    }
+    return proof;
+  }
+
+  async function publishRoot(root: Buffer, gracePeriodSeconds: number) {
+    const grace = Buffer.alloc(4);
+    grace.writeUInt32LE(gracePeriodSeconds);
+    const nonce = Buffer.alloc(8);
+    nonce.writeBigUInt64LE(BigInt(await currentNonce()));
+    const message = sha256(Buffer.from("UPDATE_EXTERNAL_ALLOWLIST"), externalAllowlist.toBuffer(), root, grace, nonce);
+
+    await program.methods
+      .updateExternalAllowlist([...root], gracePeriodSeconds)
+      .accounts({
+        payer: payer.publicKey,
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        externalAllowlist,
+        multisig: pdas.multisig,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        systemProgram: SystemProgram.programId,
+      })
+      .preInstructions([createEd25519Ix(owner1, message), createEd25519Ix(owner2, message)])
+      .rpc();
+  }
+
+  function allowlistApproval(owner: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("allowlist-approval"), mint.publicKey.toBuffer(), owner.toBuffer()],
+      program.programId
+    )[0];
+  }
+
+  async function revokeAllowlistApproval(tokenAccount: PublicKey, owner: PublicKey) {
+    return program.methods
+      .revokeAllowlistApproval()
+      .accounts({
+        tokenConfig: pdas.tokenConfig,
+        mint: mint.publicKey,
+        mintAuthority: pdas.mintAuthority,
+        tokenAccount,
+        allowlistApproval: allowlistApproval(owner),
+        externalAllowlist,
+        internalWhitelist: pdas.internalWhitelist,
+        externalWhitelist: pdas.externalWhitelist,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .rpc();
+  }
+
+  async function kycApproveAllowlisted(tokenAccount: PublicKey, owner: PublicKey, proof: number[][]) {
+    return program.methods
+      .kycApproveAllowlisted(proof)
+      .accounts({
+        kycApprove: {
+          operator: operator.publicKey,
+          tokenConfig: pdas.tokenConfig,
+          mint: mint.publicKey,
+          mintAuthority: pdas.mintAuthority,
+          kycOperators,
+          tokenAccount,
+          blacklist: pdas.blacklist,
+          internalWhitelist: pdas.internalWhitelist,
+          externalWhitelist: pdas.externalWhitelist,
+          kycRecord: kycPdas(mint.publicKey, program.programId, owner).kycRecord,
+          transferLimits: kycPdas(mint.publicKey, program.programId, owner).transferLimits,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        },
+        externalAllowlist,
+        allowlistApproval: allowlistApproval(owner),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([operator])
+      .rpc();
+  }
+
+  const firstTree = () => allowlistTree([alice.publicKey, bob.publicKey, carol.publicKey]);
+
+  before(async () => {
+    pdas = calculatePDAs(mint.publicKey, program.programId);
+    ({ kycOperators } = kycPdas(mint.publicKey, program.programId));
+    [externalAllowlist] = PublicKey.findProgramAddressSync(
+      [Buffer.from("external-allowlist"), mint.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await provider.connection.requestAirdrop(operator.publicKey, 2 * anchor.web3.LAMPORTS_PER_SOL);
+    await new Promise(resolve => setTimeout(resolve, 2000));
+
+    await initializeToken(program, provider, mint, pdas, payer.publicKey);
+    await initializeMultisig(program, provider, mint, pdas, [owner1.publicKey, owner2.publicKey, owner3.publicKey], threshold);
+    [aliceTokenAccount, bobTokenAccount, malloryTokenAccount] = await setupUserAccounts(
+      provider,
+      [alice, bob, mallory],
+      mint.publicKey
+    );
+
+    await program.methods
+      .initializeKyc()
+      .accounts({
+        initializer: payer.publicKey,
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        kycOperators,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const message = buildTargetMessage("ADD_KYC_OPERATOR", kycOperators, operator.publicKey, await currentNonce());
+    await program.methods
+      .addKycOperator(operator.publicKey)
+      .accounts({
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        blacklist: pdas.blacklist,
+        kycOperators,
+        multisig: pdas.multisig,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .preInstructions([createEd25519Ix(owner1, message), createEd25519Ix(owner2, message)])
+      .rpc();
+  });
+
+  it("Multisig publishes the allowlist root", async () => {
+    const levels = firstTree();
+    const root = levels[levels.length - 1][0];
+    await publishRoot(root, 0);
+
+    const allowlist = await program.account.externalAllowlist.fetch(externalAllowlist);
+    assert.isTrue(Buffer.from(allowlist.merkleRoot).equals(root));
+  });
+
+  it("Approves an allowlisted owner at tier 0 with a proof", async () => {
+    await kycApproveAllowlisted(aliceTokenAccount, alice.publicKey, allowlistProof(firstTree(), alice.publicKey));
+
+    const account = await getAccount(provider.connection, aliceTokenAccount, 'confirmed', TOKEN_2022_PROGRAM_ID);
+    assert.isFalse(account.isFrozen);
+    const record = await program.account.kycRecord.fetch(
+      kycPdas(mint.publicKey, program.programId, alice.publicKey).kycRecord
+    );
+    expect(record.tier).to.equal(0);
+  });
+
+  it("Keeps an owner whose proof's root is still accepted", async () => {
+    try {
+      await revokeAllowlistApproval(aliceTokenAccount, alice.publicKey);
+      assert.fail("Expected the revocation to be refused");
+    } catch (error: any) {
+      expect(error.toString()).to.include("AllowlistApprovalStillValid");
+    }
+  });
+
+  it("Rejects an owner whose proof does not lead to the root", async () => {
+    try {
+      await kycApproveAllowlisted(malloryTokenAccount, mallory.publicKey, allowlistProof(firstTree(), alice.publicKey));
+      assert.fail("Expected the proof to be rejected");
+    } catch (error: any) {
+      expect(error.toString()).to.include("NotAllowlisted");
+    }
+  });
+
+  it("Accepts proofs against the previous root during the grace period", async () => {
+    const rotated = allowlistTree([alice.publicKey, carol.publicKey]);
+    await publishRoot(rotated[rotated.length - 1][0], 3600);
+
+    await kycApproveAllowlisted(bobTokenAccount, bob.publicKey, allowlistProof(firstTree(), bob.publicKey));
+    const record = await program.account.kycRecord.fetch(
+      kycPdas(mint.publicKey, program.programId, bob.publicKey).kycRecord
+    );
+    expect(record.tier).to.equal(0);
+  });
+
+  it("Rejects proofs against a root whose grace period has ended", async () => {
+    const rotated = allowlistTree([alice.publicKey, carol.publicKey]);
+    await publishRoot(rotated[rotated.length - 1][0], 0);
+    await publishRoot(rotated[rotated.length - 1][0], 0);
+
+    try {
+      await kycApproveAllowlisted(bobTokenAccount, bob.publicKey, allowlistProof(firstTree(), bob.publicKey));
+      assert.fail("Expected the stale proof to be rejected");
+    } catch (error: any) {
+      expect(error.toString()).to.include("NotAllowlisted");
+    }
+  });
+
+  it("Freezes an owner approved against a retired root", async () => {
+    await revokeAllowlistApproval(bobTokenAccount, bob.publicKey);
+
+    const account = await getAccount(provider.connection, bobTokenAccount, 'confirmed', TOKEN_2022_PROGRAM_ID);
+    assert.isTrue(account.isFrozen);
+  });
+});
@@ -8,12 +8,12 @@ import {
   getAccount,
   TOKEN_2022_PROGRAM_ID,
   AuthorityType,
-  createTransferCheckedWithTransferHookInstruction
+  createTransferCheckedWithTransferHookInstruction,
+  getMint
 } from '@solana/spl-token';
 import * as nacl from 'tweetnacl';
 import { calculatePDAs, stringToUint8Array, TokenPDAs } from '../utils/helpers';
 import { initializeToken, setupUserAccounts, TOKEN_PARAMS } from "../utils/token_initializer";
-import { transferAuthorityToPDA } from "./transfer-authority-to-pda";
 
 describe('Transaction Forwarding Tests', () => {
   const provider = anchor.AnchorProvider.env();
@@ -44,11 +44,10 @@ describe('Transaction Forwarding Tests', () => {
 
     // Initialize token
     await initializeToken(program, provider, mint, pdas);
-
-     let afterMintInfo=await transferAuthorityToPDA(pdas, mint, payer, provider)
-      // Assertions to verify transfer
-      assert(afterMintInfo.mintAuthority?.equals(pdas.mintAuthority), "Mint authority should be the PDA");
-      assert(afterMintInfo.freezeAuthority?.equals(payer.publicKey), "Freeze authority should be the PDA");
+    // initialize creates the mint with the PDA as mint and freeze authority
+    const mintInfo = await getMint(provider.connection, mint.publicKey, null, TOKEN_2022_PROGRAM_ID);
+    assert(mintInfo.mintAuthority?.equals(pdas.mintAuthority), "Mint authority should be the PDA");
+    assert(mintInfo.freezeAuthority?.equals(pdas.mintAuthority), "Freeze authority should be the PDA");
     // Setup user accounts
     const userAccounts = await setupUserAccounts(
       provider,
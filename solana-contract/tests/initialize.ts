@@ -0,0 +1,278 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Cngn } from "../target/types/cngn";
+import { assert, expect } from 'chai';
+import { Keypair, SystemProgram, SYSVAR_RENT_PUBKEY } from '@solana/web3.js';
+import { TOKEN_2022_PROGRAM_ID, createTransferCheckedWithTransferHookInstruction } from '@solana/spl-token';
+import { calculatePDAs, TokenPDAs, blacklistEntry } from '../utils/helpers';
+import { TOKEN_PARAMS, setupUserAccounts, kycPdas, yieldPositionPda, feeConfigPda, feeRefundPda } from '../utils/token_initializer';
+
+describe("cngn initialization phases", () => {
+  const connection = new anchor.web3.Connection('http://127.0.0.1:8899', 'confirmed');
+  const wallet = anchor.Wallet.local();
+  const provider = new anchor.AnchorProvider(connection, wallet, {
+    commitment: 'confirmed',
+  });
+  anchor.setProvider(provider);
+  const payer = (provider.wallet as anchor.Wallet).payer;
+  const program = anchor.workspace.Cngn as Program<Cngn>;
+
+  const mint = Keypair.generate();
+  const intruder = Keypair.generate();
+  const owner = Keypair.generate();
+
+  let pdas: TokenPDAs;
+
+  function initializeSecondary(initializer: Keypair) {
+    return program.methods
+      .initializeSecondary()
+      .accounts({
+        initializer: initializer.publicKey,
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        blacklist: pdas.blacklist,
+        canForward: pdas.canForward,
+        trustedContracts: pdas.trustedContracts,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([initializer])
+      .rpc();
+  }
+
+  function initializeThird(initializer: Keypair) {
+    return program.methods
+      .initializeThird()
+      .accounts({
+        initializer: initializer.publicKey,
+        mint: mint.publicKey,
+        extraMetasAccount: pdas.extraMetasAccount,
+        tokenConfig: pdas.tokenConfig,
+        internalWhitelist: pdas.internalWhitelist,
+        externalWhitelist: pdas.externalWhitelist,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([initializer])
+      .rpc();
+  }
+
+  function initializeMultisig(initializer: Keypair) {
+    return program.methods
+      .initializeMultisig([owner.publicKey], 1)
+      .accounts({
+        multisig: pdas.multisig,
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        payer: initializer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([initializer])
+      .rpc();
+  }
+
+  before(async () => {
+    pdas = calculatePDAs(mint.publicKey, program.programId);
+
+    const airdrop = await provider.connection.requestAirdrop(intruder.publicKey, 2 * anchor.web3.LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction(airdrop);
+
+    await program.methods
+      .initialize(TOKEN_PARAMS.name, TOKEN_PARAMS.symbol, TOKEN_PARAMS.uri, TOKEN_PARAMS.decimals, TOKEN_PARAMS.feeWithdrawElGamalPubkey)
+      .accounts({
+        initializer: payer.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        admin: payer.publicKey,
+        mintAuthority: pdas.mintAuthority,
+        mint: mint.publicKey,
+        extraMetasAccount: pdas.extraMetasAccount,
+        canMint: pdas.canMint,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([mint])
+      .rpc();
+  });
+
+  it("Rejects an admin that is neither the initializer nor the mint's multisig", async () => {
+    const otherMint = Keypair.generate();
+    const otherPdas = calculatePDAs(otherMint.publicKey, program.programId);
+
+    try {
+      await program.methods
+        .initialize(TOKEN_PARAMS.name, TOKEN_PARAMS.symbol, TOKEN_PARAMS.uri, TOKEN_PARAMS.decimals, TOKEN_PARAMS.feeWithdrawElGamalPubkey)
+        .accounts({
+          initializer: payer.publicKey,
+          tokenConfig: otherPdas.tokenConfig,
+          admin: intruder.publicKey,
+          mintAuthority: otherPdas.mintAuthority,
+          mint: otherMint.publicKey,
+          extraMetasAccount: otherPdas.extraMetasAccount,
+          canMint: otherPdas.canMint,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([otherMint])
+        .rpc();
+      assert.fail("Expected the admin to be rejected");
+    } catch (error: any) {
+      expect(error.toString()).to.include("InvalidAdmin");
+    }
+  });
+
+  it("Records the initializer and the next phase", async () => {
+    const tokenConfig = await program.account.tokenConfig.fetch(pdas.tokenConfig);
+    assert.ok(tokenConfig.initializer.equals(payer.publicKey));
+    expect(tokenConfig.initPhase).to.deep.equal({ awaitingSecondary: {} });
+  });
+
+  it("Rejects later phases from another signer", async () => {
+    try {
+      await initializeSecondary(intruder);
+      assert.fail("Expected the intruder to be rejected");
+    } catch (error: any) {
+      expect(error.toString()).to.include("Unauthorized");
+    }
+  });
+
+  it("Rejects phases out of order", async () => {
+    try {
+      await initializeThird(payer);
+      assert.fail("Expected the third phase to be rejected");
+    } catch (error: any) {
+      expect(error.toString()).to.include("InvalidInitPhase");
+    }
+  });
+
+  it("Refuses the multisig until setup completes", async () => {
+    await initializeSecondary(payer);
+
+    try {
+      await initializeMultisig(payer);
+      assert.fail("Expected the multisig to be refused");
+    } catch (error: any) {
+      expect(error.toString()).to.include("SetupIncomplete");
+    }
+  });
+
+  it("Refuses minting until setup completes", async () => {
+    const [tokenAccount] = await setupUserAccounts(provider, [owner], mint.publicKey);
+
+    try {
+      await program.methods
+        .mint(new anchor.BN(1))
+        .accounts({
+          authority: payer.publicKey,
+          tokenConfig: pdas.tokenConfig,
+          mint: mint.publicKey,
+          mintAuthority: pdas.mintAuthority,
+          tokenAccount,
+          blacklist: pdas.blacklist,
+          canMint: pdas.canMint,
+          trustedContracts: pdas.trustedContracts,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .rpc();
+      assert.fail("Expected minting to be refused");
+    } catch (error: any) {
+      expect(error.toString()).to.include("SetupIncomplete");
+    }
+  });
+
+  it("Completes setup and lets only the initializer create the multisig", async () => {
+    await initializeThird(payer);
+
+    const tokenConfig = await program.account.tokenConfig.fetch(pdas.tokenConfig);
+    expect(tokenConfig.initPhase).to.deep.equal({ complete: {} });
+
+    try {
+      await initializeMultisig(intruder);
+      assert.fail("Expected the intruder to be rejected");
+    } catch (error: any) {
+      expect(error.toString()).to.include("Unauthorized");
+    }
+
+    await initializeMultisig(payer);
+    const updated = await program.account.tokenConfig.fetch(pdas.tokenConfig);
+    assert.ok(updated.admin.equals(pdas.multisig));
+  });
+
+  it("Refuses to migrate a token config already in the current layout", async () => {
+    try {
+      await program.methods
+        .migrateTokenConfig()
+        .accounts({
+          payer: payer.publicKey,
+          mint: mint.publicKey,
+          tokenConfig: pdas.tokenConfig,
+          mintAuthority: pdas.mintAuthority,
+          blacklist: pdas.blacklist,
+          internalWhitelist: pdas.internalWhitelist,
+          externalWhitelist: pdas.externalWhitelist,
+          extraMetasAccount: pdas.extraMetasAccount,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+      assert.fail("Expected the migration to be refused");
+    } catch (error: any) {
+      expect(error.toString()).to.include("TokenConfigAlreadyMigrated");
+    }
+  });
+
+  it("Refuses to upgrade the hook of a mint that was not migrated", async () => {
+    try {
+      await program.methods
+        .upgradeTransferHook()
+        .accounts({
+          payer: payer.publicKey,
+          mint: mint.publicKey,
+          tokenConfig: pdas.tokenConfig,
+          extraMetasAccount: pdas.extraMetasAccount,
+          transferTiers: pdas.transferTiers,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+      assert.fail("Expected the upgrade to be refused");
+    } catch (error: any) {
+      expect(error.toString()).to.include("InvalidInitPhase");
+    }
+  });
+
+  it("Resolves the hook's extra accounts in the order the hook reads them", async () => {
+    const sender = Keypair.generate();
+    const receiver = Keypair.generate();
+    const [source, destination] = await setupUserAccounts(provider, [sender, receiver], mint.publicKey);
+
+    const transferIx = await createTransferCheckedWithTransferHookInstruction(
+      provider.connection,
+      source,
+      mint.publicKey,
+      destination,
+      sender.publicKey,
+      BigInt(1),
+      TOKEN_PARAMS.decimals,
+      undefined,
+      'confirmed',
+      TOKEN_2022_PROGRAM_ID,
+    );
+
+    // source, mint, destination and owner come first
+    const extras = transferIx.keys.slice(4, 16);
+    const expected = [
+      pdas.tokenConfig,
+      pdas.blacklist,
+      pdas.internalWhitelist,
+      pdas.transferTiers,
+      kycPdas(mint.publicKey, program.programId, sender.publicKey).transferLimits,
+      yieldPositionPda(mint.publicKey, program.programId, source),
+      feeConfigPda(mint.publicKey, program.programId),
+      feeRefundPda(mint.publicKey, program.programId, source),
+      feeRefundPda(mint.publicKey, program.programId, destination),
+      blacklistEntry(mint.publicKey, program.programId, sender.publicKey).pubkey,
+      blacklistEntry(mint.publicKey, program.programId, sender.publicKey).pubkey,
+      blacklistEntry(mint.publicKey, program.programId, receiver.publicKey).pubkey,
+    ];
+    expect(extras.map(key => key.pubkey.toBase58())).to.deep.equal(expected.map(key => key.toBase58()));
+    expect(extras.map(key => key.isWritable)).to.deep.equal([false, false, false, false, true, true, false, true, true, false, false, false]);
+  });
+});
@@ -0,0 +1,233 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Cngn } from "../target/types/cngn";
+import { assert, expect } from 'chai';
+import { PublicKey, Keypair, SystemProgram, SYSVAR_INSTRUCTIONS_PUBKEY, TransactionInstruction } from '@solana/web3.js';
+import { TOKEN_2022_PROGRAM_ID, getAccount } from '@solana/spl-token';
+import { calculatePDAs, TokenPDAs } from '../utils/helpers';
+import { initializeToken, initializeMultisig, setupUserAccounts, kycPdas } from '../utils/token_initializer';
+import * as crypto from 'crypto';
+import nacl from 'tweetnacl';
+
+describe("cngn kyc", () => {
+  const connection = new anchor.web3.Connection('http://127.0.0.1:8899', 'confirmed');
+  const wallet = anchor.Wallet.local();
+  const provider = new anchor.AnchorProvider(connection, wallet, {
+    commitment: 'confirmed',
+  });
+  anchor.setProvider(provider);
+  const payer = (provider.wallet as anchor.Wallet).payer;
+  const program = anchor.workspace.Cngn as Program<Cngn>;
+
+  const mint = Keypair.generate();
+
+  // Multisig owners
+  const owner1 = Keypair.generate();
+  const owner2 = Keypair.generate();
+  const owner3 = Keypair.generate();
+  const threshold = 2;
+
+  const operator = Keypair.generate();
+  const retailUser = Keypair.generate();
+  const treasury = Keypair.generate();
+
+  let pdas: TokenPDAs;
+  let kycOperators: PublicKey;
+  let retailTokenAccount: PublicKey;
+  let treasuryTokenAccount: PublicKey;
+
+  // Ed25519 instruction with one signature over `message`
+  function createEd25519Ix(signer: Keypair, message: Buffer): TransactionInstruction {
+    const signature = nacl.sign.detached(message, signer.secretKey);
+    const publicKey = signer.publicKey.toBytes();
+
+    const offsetsStruct = Buffer.alloc(14);
+    offsetsStruct.writeUInt16LE(16, 0);
+    offsetsStruct.writeUInt16LE(0xFFFF, 2);
+    offsetsStruct.writeUInt16LE(80, 4);
+    offsetsStruct.writeUInt16LE(0xFFFF, 6);
+    offsetsStruct.writeUInt16LE(112, 8);
+    offsetsStruct.writeUInt16LE(message.length, 10);
+    offsetsStruct.writeUInt16LE(0xFFFF, 12);
+
+    const data = Buffer.concat([
+      Buffer.from([1, 0]),
+      offsetsStruct,
+      Buffer.from(signature),
+      publicKey,
+      message
+    ]);
+
+    return new TransactionInstruction({
+      keys: [],
+      programId: new PublicKey('Ed25519SigVerify111111111111111111111111111'),
+      data,
+    });
+  }
+
+  function buildTargetMessage(action: string, account: PublicKey, target: PublicKey, nonce: number): Buffer {
+    const nonceBuf = Buffer.alloc(8);
+    nonceBuf.writeBigUInt64LE(BigInt(nonce));
+    const hash = crypto.createHash('sha256');
+    hash.update(Buffer.from(action));
+    hash.update(account.toBuffer());
+    hash.update(target.toBuffer());
+    hash.update(nonceBuf);
+    return hash.digest();
+  }
+
+  async function currentNonce(): Promise<number> {
+    const multisigAccount = await program.account.multisig.fetch(pdas.multisig);
+    return multisigAccount.nonce.toNumber();
+  }
+
+  async function kycApprove(signer: Keypair, tokenAccount: PublicKey, owner: PublicKey, tier: number) {
+    return program.methods
+      .kycApprove(tier)
+      .accounts({
+        operator: signer.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        mint: mint.publicKey,
+        mintAuthority: pdas.mintAuthority,
+        kycOperators,
+        tokenAccount,
+        blacklist: pdas.blacklist,
+        internalWhitelist: pdas.internalWhitelist,
+        externalWhitelist: pdas.externalWhitelist,
+        kycRecord: kycPdas(mint.publicKey, program.programId, owner).kycRecord,
+        transferLimits: kycPdas(mint.publicKey, program.programId, owner).transferLimits,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  before(async () => {
+    pdas = calculatePDAs(mint.publicKey, program.programId);
+    ({ kycOperators } = kycPdas(mint.publicKey, program.programId));
+
+    await provider.connection.requestAirdrop(operator.publicKey, 2 * anchor.web3.LAMPORTS_PER_SOL);
+    await new Promise(resolve => setTimeout(resolve, 2000));
+
+    await initializeToken(program, provider, mint, pdas, payer.publicKey);
+    await initializeMultisig(program, provider, mint, pdas, [owner1.publicKey, owner2.publicKey, owner3.publicKey], threshold);
+    [retailTokenAccount, treasuryTokenAccount] = await setupUserAccounts(provider, [retailUser, treasury], mint.publicKey);
+
+    await program.methods
+      .initializeKyc()
+      .accounts({
+        initializer: payer.publicKey,
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        kycOperators,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  });
+
+  it("Creates token accounts frozen", async () => {
+    const account = await getAccount(provider.connection, retailTokenAccount, 'confirmed', TOKEN_2022_PROGRAM_ID);
+    assert.isTrue(account.isFrozen);
+  });
+
+  it("Rejects approval from a key that is not a KYC operator", async () => {
+    try {
+      await kycApprove(operator, retailTokenAccount, retailUser.publicKey, 1);
+      assert.fail("Expected approval to be rejected");
+    } catch (error: any) {
+      expect(error.toString()).to.include("KycOperatorNotAuthorized");
+    }
+  });
+
+  it("Multisig adds a KYC operator", async () => {
+    const message = buildTargetMessage("ADD_KYC_OPERATOR", kycOperators, operator.publicKey, await currentNonce());
+
+    await program.methods
+      .addKycOperator(operator.publicKey)
+      .accounts({
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        blacklist: pdas.blacklist,
+        kycOperators,
+        multisig: pdas.multisig,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .preInstructions([createEd25519Ix(owner1, message), createEd25519Ix(owner2, message)])
+      .rpc();
+
+    const operators = await program.account.kycOperators.fetch(kycOperators);
+    assert.isTrue(operators.operators.some((key) => key.equals(operator.publicKey)));
+  });
+
+  it("Reserves tier 0 for whitelisted owners", async () => {
+    try {
+      await kycApprove(operator, retailTokenAccount, retailUser.publicKey, 0);
+      assert.fail("Expected tier 0 to be rejected");
+    } catch (error: any) {
+      expect(error.toString()).to.include("InvalidKycTier");
+    }
+  });
+
+  it("Thaws the account and records the tier", async () => {
+    await kycApprove(operator, retailTokenAccount, retailUser.publicKey, 2);
+
+    const account = await getAccount(provider.connection, retailTokenAccount, 'confirmed', TOKEN_2022_PROGRAM_ID);
+    assert.isFalse(account.isFrozen);
+
+    const record = await program.account.kycRecord.fetch(
+      kycPdas(mint.publicKey, program.programId, retailUser.publicKey).kycRecord
+    );
+    expect(record.tier).to.equal(2);
+    assert.ok(record.approvedBy.equals(operator.publicKey));
+
+    const limits = await program.account.transferLimits.fetch(
+      kycPdas(mint.publicKey, program.programId, retailUser.publicKey).transferLimits
+    );
+    expect(limits.tier).to.equal(2);
+  });
+
+  it("Approves an internally whitelisted owner at tier 0", async () => {
+    const message = buildTargetMessage("WHITELIST_INTERNAL", pdas.internalWhitelist, treasury.publicKey, await currentNonce());
+    await program.methods
+      .whitelistInternalUser(treasury.publicKey)
+      .accounts({
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        internalWhitelist: pdas.internalWhitelist,
+        blacklist: pdas.blacklist,
+        multisig: pdas.multisig,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .preInstructions([createEd25519Ix(owner1, message), createEd25519Ix(owner3, message)])
+      .rpc();
+
+    await kycApprove(operator, treasuryTokenAccount, treasury.publicKey, 0);
+
+    const account = await getAccount(provider.connection, treasuryTokenAccount, 'confirmed', TOKEN_2022_PROGRAM_ID);
+    assert.isFalse(account.isFrozen);
+  });
+
+  it("Multisig removes the KYC operator", async () => {
+    const message = buildTargetMessage("REMOVE_KYC_OPERATOR", kycOperators, operator.publicKey, await currentNonce());
+
+    await program.methods
+      .removeKycOperator(operator.publicKey)
+      .accounts({
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        kycOperators,
+        multisig: pdas.multisig,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .preInstructions([createEd25519Ix(owner2, message), createEd25519Ix(owner3, message)])
+      .rpc();
+
+    try {
+      await kycApprove(operator, retailTokenAccount, retailUser.publicKey, 3);
+      assert.fail("Expected approval to be rejected");
+    } catch (error: any) {
+      expect(error.toString()).to.include("KycOperatorNotAuthorized");
+    }
+  });
+});
@@ -0,0 +1,213 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Cngn } from "../target/types/cngn";
+import { assert, expect } from 'chai';
+import { PublicKey, Keypair, SYSVAR_INSTRUCTIONS_PUBKEY, TransactionInstruction } from '@solana/web3.js';
+import { TOKEN_2022_PROGRAM_ID, getTokenMetadata } from '@solana/spl-token';
+import { calculatePDAs, TokenPDAs } from '../utils/helpers';
+import { initializeToken, initializeMultisig, TOKEN_PARAMS } from '../utils/token_initializer';
+import * as crypto from 'crypto';
+import nacl from 'tweetnacl';
+
+describe("cngn token metadata", () => {
+  const connection = new anchor.web3.Connection('http://127.0.0.1:8899', 'confirmed');
+  const wallet = anchor.Wallet.local();
+  const provider = new anchor.AnchorProvider(connection, wallet, {
+    commitment: 'confirmed',
+  });
+  anchor.setProvider(provider);
+  const payer = (provider.wallet as anchor.Wallet).payer;
+  const program = anchor.workspace.Cngn as Program<Cngn>;
+
+  const mint = Keypair.generate();
+
+  // Multisig owners
+  const owner1 = Keypair.generate();
+  const owner2 = Keypair.generate();
+  const owner3 = Keypair.generate();
+  const threshold = 2;
+
+  const uri = "https://cngn.co/metadata.json";
+
+  let pdas: TokenPDAs;
+  let multisigPda: PublicKey;
+
+  // Ed25519 instruction with one signature over `message`
+  function createEd25519Ix(signer: Keypair, message: Buffer): TransactionInstruction {
+    const signature = nacl.sign.detached(message, signer.secretKey);
+    const publicKey = signer.publicKey.toBytes();
+
+    const offsetsStruct = Buffer.alloc(14);
+    offsetsStruct.writeUInt16LE(16, 0);
+    offsetsStruct.writeUInt16LE(0xFFFF, 2);
+    offsetsStruct.writeUInt16LE(80, 4);
+    offsetsStruct.writeUInt16LE(0xFFFF, 6);
+    offsetsStruct.writeUInt16LE(112, 8);
+    offsetsStruct.writeUInt16LE(message.length, 10);
+    offsetsStruct.writeUInt16LE(0xFFFF, 12);
+
+    const data = Buffer.concat([
+      Buffer.from([1, 0]),
+      offsetsStruct,
+      Buffer.from(signature),
+      publicKey,
+      message
+    ]);
+
+    return new TransactionInstruction({
+      keys: [],
+      programId: new PublicKey('Ed25519SigVerify111111111111111111111111111'),
+      data,
+    });
+  }
+
+  function u64(value: anchor.BN | number): Buffer {
+    const buf = Buffer.alloc(8);
+    buf.writeBigUInt64LE(BigInt(value.toString()));
+    return buf;
+  }
+
+  // u32 length prefix followed by the UTF-8 bytes
+  function str(value: string): Buffer {
+    const bytes = Buffer.from(value, 'utf8');
+    const len = Buffer.alloc(4);
+    len.writeUInt32LE(bytes.length);
+    return Buffer.concat([len, bytes]);
+  }
+
+  function buildInitializeMetadataMessage(nonce: number): Buffer {
+    const hash = crypto.createHash('sha256');
+    hash.update(Buffer.from("INITIALIZE_METADATA"));
+    hash.update(mint.publicKey.toBuffer());
+    hash.update(str(uri));
+    hash.update(u64(nonce));
+    return hash.digest();
+  }
+
+  function buildUpdateFieldMessage(key: string | null, tag: number, value: string, nonce: number): Buffer {
+    const hash = crypto.createHash('sha256');
+    hash.update(Buffer.from("UPDATE_METADATA_FIELD"));
+    hash.update(mint.publicKey.toBuffer());
+    hash.update(Buffer.from([tag]));
+    if (key !== null) {
+      hash.update(str(key));
+    }
+    hash.update(str(value));
+    hash.update(u64(nonce));
+    return hash.digest();
+  }
+
+  async function currentNonce(): Promise<number> {
+    const multisigAccount = await program.account.multisig.fetch(multisigPda);
+    return multisigAccount.nonce.toNumber();
+  }
+
+  async function updateField(field: any, message: Buffer, value: string, signers: Keypair[]) {
+    return program.methods
+      .updateMetadataField(field, value)
+      .accounts({
+        payer: payer.publicKey,
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        mintAuthority: pdas.mintAuthority,
+        multisig: multisigPda,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .preInstructions(signers.map((signer) => createEd25519Ix(signer, message)))
+      .rpc();
+  }
+
+  before(async () => {
+    pdas = calculatePDAs(mint.publicKey, program.programId);
+    [multisigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("multisig"), mint.publicKey.toBuffer()],
+      program.programId
+    );
+
+    // initialize creates the mint with a metadata pointer to itself
+    await initializeToken(program, provider, mint, pdas, payer.publicKey);
+    await initializeMultisig(program, provider, mint, pdas, [owner1.publicKey, owner2.publicKey, owner3.publicKey], threshold);
+  });
+
+  it("Rejects metadata initialization below the threshold", async () => {
+    const message = buildInitializeMetadataMessage(await currentNonce());
+
+    try {
+      await program.methods
+        .initializeMetadata(uri)
+        .accounts({
+          payer: payer.publicKey,
+          mint: mint.publicKey,
+          tokenConfig: pdas.tokenConfig,
+          mintAuthority: pdas.mintAuthority,
+          multisig: multisigPda,
+          instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .preInstructions([createEd25519Ix(owner1, message)])
+        .rpc();
+      assert.fail("Expected initialization to be rejected");
+    } catch (error: any) {
+      expect(error.toString()).to.include("NotEnoughMultisigSigners");
+    }
+  });
+
+  it("Multisig writes the metadata onto the mint", async () => {
+    const message = buildInitializeMetadataMessage(await currentNonce());
+
+    await program.methods
+      .initializeMetadata(uri)
+      .accounts({
+        payer: payer.publicKey,
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        mintAuthority: pdas.mintAuthority,
+        multisig: multisigPda,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .preInstructions([createEd25519Ix(owner1, message), createEd25519Ix(owner2, message)])
+      .rpc();
+
+    const metadata = await getTokenMetadata(provider.connection, mint.publicKey, 'confirmed', TOKEN_2022_PROGRAM_ID);
+    expect(metadata.name).to.equal(TOKEN_PARAMS.name);
+    expect(metadata.symbol).to.equal(TOKEN_PARAMS.symbol);
+    expect(metadata.uri).to.equal(uri);
+    assert.ok(metadata.updateAuthority.equals(pdas.mintAuthority));
+  });
+
+  it("Adds an issuer field", async () => {
+    const value = "WrappedCBDC Ltd";
+    const message = buildUpdateFieldMessage("issuer", 3, value, await currentNonce());
+
+    await updateField({ key: { 0: "issuer" } }, message, value, [owner1, owner3]);
+
+    const metadata = await getTokenMetadata(provider.connection, mint.publicKey, 'confirmed', TOKEN_2022_PROGRAM_ID);
+    expect(metadata.additionalMetadata).to.deep.include(["issuer", value]);
+  });
+
+  it("Mirrors a name change into the token config", async () => {
+    const value = "Nigerian Naira Stablecoin";
+    const message = buildUpdateFieldMessage(null, 0, value, await currentNonce());
+
+    await updateField({ name: {} }, message, value, [owner2, owner3]);
+
+    const metadata = await getTokenMetadata(provider.connection, mint.publicKey, 'confirmed', TOKEN_2022_PROGRAM_ID);
+    expect(metadata.name).to.equal(value);
+    const tokenConfig = await program.account.tokenConfig.fetch(pdas.tokenConfig);
+    expect(tokenConfig.name).to.equal(value);
+  });
+
+  it("Rejects a symbol longer than the token config allows", async () => {
+    const value = "CNGNCNGNCNGN";
+    const message = buildUpdateFieldMessage(null, 1, value, await currentNonce());
+
+    try {
+      await updateField({ symbol: {} }, message, value, [owner1, owner2]);
+      assert.fail("Expected the update to be rejected");
+    } catch (error: any) {
+      expect(error.toString()).to.include("InvalidMetadataField");
+    }
+  });
+});
@@ -2,10 +2,10 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { Cngn } from "../target/types/cngn";
 import { assert, expect } from 'chai';
-import { PublicKey, Keypair, SYSVAR_INSTRUCTIONS_PUBKEY, TransactionInstruction } from '@solana/web3.js';
-import { TOKEN_2022_PROGRAM_ID, createMintToInstruction, getAccount, getAssociatedTokenAddressSync, createMint } from '@solana/spl-token';
-import { calculatePDAs, TokenPDAs } from '../utils/helpers';
-import { TOKEN_PARAMS, initializeToken, initializeMultisig, setupUserAccounts } from '../utils/token_initializer';
+import { PublicKey, Keypair, SystemProgram, SYSVAR_INSTRUCTIONS_PUBKEY, TransactionInstruction } from '@solana/web3.js';
+import { TOKEN_2022_PROGRAM_ID, createMintToInstruction, getAccount, getAssociatedTokenAddressSync } from '@solana/spl-token';
+import { calculatePDAs, TokenPDAs, blacklistDetails, blacklistEntry } from '../utils/helpers';
+import { TOKEN_PARAMS, initializeToken, initializeMultisig, setupUserAccounts, kycApproveAccounts } from '../utils/token_initializer';
 import * as crypto from 'crypto';
 import nacl from 'tweetnacl';
 
@@ -122,7 +122,6 @@ describe("cngn mint test with multisig", () => {
     await new Promise(resolve => setTimeout(resolve, 2000));
 
     console.log("Initializing token...");
-    await createMint(provider.connection, payer, pdas.mintAuthority, pdas.mintAuthority, 6, mint, null, TOKEN_2022_PROGRAM_ID);
     await initializeToken(program, provider, mint, pdas, payer.publicKey);
 
     console.log("Initializing multisig...");
@@ -139,6 +138,9 @@ describe("cngn mint test with multisig", () => {
       blacklistedReceiverTokenAccount
     ] = userAccounts;
 
+    // New token accounts are frozen until KYC-approved
+    await kycApproveAccounts(program, provider, mint, pdas, [owner1, owner2], userAccounts);
+
     // Setup authorized user with multisig
     let multisigAccount = await program.account.multisig.fetch(multisigPda);
     let message = buildMessageHash(
@@ -237,18 +239,21 @@ describe("cngn mint test with multisig", () => {
 
     // Add blacklisted users with multisig
     multisigAccount = await program.account.multisig.fetch(multisigPda);
+    const blacklistedUserDetails = blacklistDetails("fraudReport", "FR-MINT-1");
     message = buildMessageHash(
       "ADD_BLACKLIST",
       pdas.blacklist,
       blacklistedUser.publicKey,
+      blacklistedUserDetails.bytes,
       multisigAccount.nonce.toNumber()
     );
     ed25519Ix1 = createEd25519Ix(owner1, message);
     ed25519Ix2 = createEd25519Ix(owner2, message);
 
     await program.methods
-      .addBlacklist(blacklistedUser.publicKey)
+      .addBlacklist(blacklistedUser.publicKey, blacklistedUserDetails.details)
       .accounts({
+        payer: payer.publicKey,
         mint: mint.publicKey,
         tokenConfig: pdas.tokenConfig,
         canMint: pdas.canMint,
@@ -258,25 +263,30 @@ describe("cngn mint test with multisig", () => {
         canForward: pdas.canForward,
         blacklist: pdas.blacklist,
         multisig: multisigPda,
-        instructions: SYSVAR_INSTRUCTIONS_PUBKEY
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        systemProgram: SystemProgram.programId
       })
+      .remainingAccounts([blacklistEntry(mint.publicKey, program.programId, blacklistedUser.publicKey)])
       .preInstructions([ed25519Ix1, ed25519Ix2])
       .rpc();
 
     // Add blacklisted receiver
     multisigAccount = await program.account.multisig.fetch(multisigPda);
+    const blacklistedReceiverDetails = blacklistDetails("fraudReport", "FR-MINT-2");
     message = buildMessageHash(
       "ADD_BLACKLIST",
       pdas.blacklist,
       blacklistedReceiver.publicKey,
+      blacklistedReceiverDetails.bytes,
       multisigAccount.nonce.toNumber()
     );
     ed25519Ix1 = createEd25519Ix(owner1, message);
     ed25519Ix2 = createEd25519Ix(owner2, message);
 
     await program.methods
-      .addBlacklist(blacklistedReceiver.publicKey)
+      .addBlacklist(blacklistedReceiver.publicKey, blacklistedReceiverDetails.details)
       .accounts({
+        payer: payer.publicKey,
         mint: mint.publicKey,
         tokenConfig: pdas.tokenConfig,
         canMint: pdas.canMint,
@@ -286,8 +296,10 @@ describe("cngn mint test with multisig", () => {
         canForward: pdas.canForward,
         blacklist: pdas.blacklist,
         multisig: multisigPda,
-        instructions: SYSVAR_INSTRUCTIONS_PUBKEY
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        systemProgram: SystemProgram.programId
       })
+      .remainingAccounts([blacklistEntry(mint.publicKey, program.programId, blacklistedReceiver.publicKey)])
       .preInstructions([ed25519Ix1, ed25519Ix2])
       .rpc();
 
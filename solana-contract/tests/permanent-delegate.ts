@@ -1,10 +1,9 @@
 import type { Program } from '@coral-xyz/anchor';
 import * as anchor from '@coral-xyz/anchor';
-import { burnChecked, createAccount, getAccount, mintTo, TOKEN_2022_PROGRAM_ID } from '@solana/spl-token';
+import { burnChecked, createAccount, getAccount, getMint, mintTo, TOKEN_2022_PROGRAM_ID } from '@solana/spl-token';
 import type { Cngn } from '../target/types/cngn';
 import { Keypair, PublicKey } from '@solana/web3.js';
 import { calculatePDAs, TokenPDAs } from '../utils/helpers';
-import { transferAuthorityToPDA } from './transfer-authority-to-pda';
 import { initializeToken, setupUserAccounts, TOKEN_PARAMS } from '../utils/token_initializer';
 import { assert, expect } from 'chai';
 describe('permanent-delegate', () => {
@@ -40,11 +39,10 @@ describe('permanent-delegate', () => {
 
     // Initialize the token
     await initializeToken(program, provider, mint, pdas);
-
-     let afterMintInfo=await transferAuthorityToPDA(pdas, mint, payer, provider)
-      // Assertions to verify transfer
-      assert(afterMintInfo.mintAuthority?.equals(pdas.mintAuthority), "Mint authority should be the PDA");
-      assert(afterMintInfo.freezeAuthority?.equals(payer.publicKey), "Freeze authority should be the PDA");
+    // initialize creates the mint with the PDA as mint and freeze authority
+    const mintInfo = await getMint(provider.connection, mint.publicKey, null, TOKEN_2022_PROGRAM_ID);
+    assert(mintInfo.mintAuthority?.equals(pdas.mintAuthority), "Mint authority should be the PDA");
+    assert(mintInfo.freezeAuthority?.equals(pdas.mintAuthority), "Freeze authority should be the PDA");
     // Fund the test accounts
     // Airdrop SOL to the test accounts
     const users = [unauthorizedUser, blacklistedUser, authorizedUser, blacklistedReceiver];
@@ -0,0 +1,292 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Cngn } from "../target/types/cngn";
+import { assert, expect } from 'chai';
+import { PublicKey, Keypair, SystemProgram, SYSVAR_INSTRUCTIONS_PUBKEY, TransactionInstruction } from '@solana/web3.js';
+import { TOKEN_2022_PROGRAM_ID, createApproveCheckedInstruction, getAccount } from '@solana/spl-token';
+import { calculatePDAs, TokenPDAs } from '../utils/helpers';
+import { TOKEN_PARAMS, initializeToken, initializeMultisig, setupUserAccounts, kycApproveAccounts, kycPdas, yieldPositionPda, feeConfigPda } from '../utils/token_initializer';
+import * as crypto from 'crypto';
+import nacl from 'tweetnacl';
+
+describe("cngn subscriptions", () => {
+  const connection = new anchor.web3.Connection('http://127.0.0.1:8899', 'confirmed');
+  const wallet = anchor.Wallet.local();
+  const provider = new anchor.AnchorProvider(connection, wallet, {
+    commitment: 'confirmed',
+  });
+  anchor.setProvider(provider);
+  const payer = (provider.wallet as anchor.Wallet).payer;
+  const program = anchor.workspace.Cngn as Program<Cngn>;
+
+  const mint = Keypair.generate();
+
+  // Multisig owners
+  const owner1 = Keypair.generate();
+  const owner2 = Keypair.generate();
+  const owner3 = Keypair.generate();
+  const threshold = 2;
+
+  const customer = Keypair.generate();
+  const merchant = Keypair.generate();
+
+  const amountPerPeriod = new anchor.BN(1_000_000);
+
+  let pdas: TokenPDAs;
+  let customerTokenAccount: PublicKey;
+  let merchantTokenAccount: PublicKey;
+  let customerLimits: PublicKey;
+  let pullDelegate: PublicKey;
+  let pullAllowance: PublicKey;
+  let subscription: PublicKey;
+
+  // Ed25519 instruction with one signature over `message`
+  function createEd25519Ix(signer: Keypair, message: Buffer): TransactionInstruction {
+    const signature = nacl.sign.detached(message, signer.secretKey);
+    const publicKey = signer.publicKey.toBytes();
+
+    const offsetsStruct = Buffer.alloc(14);
+    offsetsStruct.writeUInt16LE(16, 0);
+    offsetsStruct.writeUInt16LE(0xFFFF, 2);
+    offsetsStruct.writeUInt16LE(80, 4);
+    offsetsStruct.writeUInt16LE(0xFFFF, 6);
+    offsetsStruct.writeUInt16LE(112, 8);
+    offsetsStruct.writeUInt16LE(message.length, 10);
+    offsetsStruct.writeUInt16LE(0xFFFF, 12);
+
+    const data = Buffer.concat([
+      Buffer.from([1, 0]),
+      offsetsStruct,
+      Buffer.from(signature),
+      publicKey,
+      message
+    ]);
+
+    return new TransactionInstruction({
+      keys: [],
+      programId: new PublicKey('Ed25519SigVerify111111111111111111111111111'),
+      data,
+    });
+  }
+
+  function u64(value: anchor.BN | number): Buffer {
+    const buf = Buffer.alloc(8);
+    buf.writeBigUInt64LE(BigInt(value.toString()));
+    return buf;
+  }
+
+  function buildMessage(action: string, ...parts: Buffer[]): Buffer {
+    const hash = crypto.createHash('sha256');
+    hash.update(Buffer.from(action));
+    for (const part of parts) {
+      hash.update(part);
+    }
+    return hash.digest();
+  }
+
+  async function currentNonce(): Promise<number> {
+    const multisigAccount = await program.account.multisig.fetch(pdas.multisig);
+    return multisigAccount.nonce.toNumber();
+  }
+
+  // Minting consumes the minter's allowance, so re-authorise every time
+  async function mintTo(tokenAccount: PublicKey, amount: anchor.BN) {
+    let message = buildMessage("ADD_CAN_MINT", pdas.canMint.toBuffer(), payer.publicKey.toBuffer(), u64(await currentNonce()));
+    await program.methods
+      .addCanMint(payer.publicKey)
+      .accounts({
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        blacklist: pdas.blacklist,
+        canMint: pdas.canMint,
+        trustedContracts: pdas.trustedContracts,
+        multisig: pdas.multisig,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .preInstructions([createEd25519Ix(owner1, message), createEd25519Ix(owner2, message)])
+      .rpc();
+
+    message = buildMessage("SET_MINT_AMOUNT", pdas.canMint.toBuffer(), payer.publicKey.toBuffer(), u64(await currentNonce()));
+    await program.methods
+      .setMintAmount(payer.publicKey, amount)
+      .accounts({
+        multisig: pdas.multisig,
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        canMint: pdas.canMint,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .preInstructions([createEd25519Ix(owner1, message), createEd25519Ix(owner2, message)])
+      .rpc();
+
+    await program.methods
+      .mint(amount)
+      .accounts({
+        authority: payer.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        mintAuthority: pdas.mintAuthority,
+        mint: mint.publicKey,
+        tokenAccount,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        blacklist: pdas.blacklist,
+        canMint: pdas.canMint,
+        trustedContracts: pdas.trustedContracts,
+      })
+      .rpc();
+  }
+
+  async function pull(amount: anchor.BN) {
+    return program.methods
+      .executePull(amount)
+      .accounts({
+        payee: merchant.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        mint: mint.publicKey,
+        mintAuthority: pdas.mintAuthority,
+        pullDelegate,
+        subscription,
+        payerTokenAccount: customerTokenAccount,
+        payeeTokenAccount: merchantTokenAccount,
+        blacklist: pdas.blacklist,
+        internalWhitelist: pdas.internalWhitelist,
+        transferTiers: pdas.transferTiers,
+        transferLimits: customerLimits,
+        yieldPosition: yieldPositionPda(mint.publicKey, program.programId, customerTokenAccount),
+        feeConfig: feeConfigPda(mint.publicKey, program.programId),
+        // No fee treasury is set in this suite and the fee is zero, so it is not read
+        feeTreasury: merchantTokenAccount,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([merchant])
+      .rpc();
+  }
+
+  before(async () => {
+    pdas = calculatePDAs(mint.publicKey, program.programId);
+    [customerLimits] = PublicKey.findProgramAddressSync(
+      [Buffer.from("transfer-limits"), mint.publicKey.toBuffer(), customer.publicKey.toBuffer()],
+      program.programId
+    );
+    [pullDelegate] = PublicKey.findProgramAddressSync(
+      [Buffer.from("pull-delegate"), mint.publicKey.toBuffer()],
+      program.programId
+    );
+    [subscription] = PublicKey.findProgramAddressSync(
+      [Buffer.from("subscription"), mint.publicKey.toBuffer(), customer.publicKey.toBuffer(), merchant.publicKey.toBuffer()],
+      program.programId
+    );
+
+    for (const account of [customer.publicKey, merchant.publicKey]) {
+      await provider.connection.requestAirdrop(account, 1 * anchor.web3.LAMPORTS_PER_SOL);
+    }
+    await new Promise(resolve => setTimeout(resolve, 2000));
+
+    await initializeToken(program, provider, mint, pdas, payer.publicKey);
+    await initializeMultisig(program, provider, mint, pdas, [owner1.publicKey, owner2.publicKey, owner3.publicKey], threshold);
+    const accounts = await setupUserAccounts(provider, [customer, merchant], mint.publicKey);
+    [customerTokenAccount, merchantTokenAccount] = accounts;
+    [pullAllowance] = PublicKey.findProgramAddressSync(
+      [Buffer.from("pull-allowance"), mint.publicKey.toBuffer(), customerTokenAccount.toBuffer()],
+      program.programId
+    );
+    await kycApproveAccounts(program, provider, mint, pdas, [owner1, owner2], accounts);
+
+    const message = buildMessage(
+      "SET_TRANSFER_TIER",
+      customerLimits.toBuffer(),
+      customer.publicKey.toBuffer(),
+      Buffer.from([1]),
+      u64(await currentNonce())
+    );
+    await program.methods
+      .setTransferTier(customer.publicKey, 1)
+      .accounts({
+        payer: payer.publicKey,
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        transferLimits: customerLimits,
+        kycRecord: kycPdas(mint.publicKey, program.programId, customer.publicKey).kycRecord,
+        internalWhitelist: pdas.internalWhitelist,
+        externalWhitelist: pdas.externalWhitelist,
+        multisig: pdas.multisig,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        systemProgram: SystemProgram.programId,
+      })
+      .preInstructions([createEd25519Ix(owner1, message), createEd25519Ix(owner2, message)])
+      .rpc();
+
+    await mintTo(customerTokenAccount, new anchor.BN(10_000_000));
+  });
+
+  it("Customer subscribes and approves the pull delegate", async () => {
+    await program.methods
+      .createSubscription(merchant.publicKey, amountPerPeriod, 3600)
+      .accounts({
+        payer: customer.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        mint: mint.publicKey,
+        payerTokenAccount: customerTokenAccount,
+        subscription,
+        pullAllowance,
+        blacklist: pdas.blacklist,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([customer])
+      .rpc();
+
+    const approveIx = createApproveCheckedInstruction(
+      customerTokenAccount,
+      mint.publicKey,
+      pullDelegate,
+      customer.publicKey,
+      BigInt(5_000_000),
+      TOKEN_PARAMS.decimals,
+      [],
+      TOKEN_2022_PROGRAM_ID,
+    );
+    await provider.sendAndConfirm(new anchor.web3.Transaction().add(approveIx), [customer]);
+
+    const account = await program.account.subscription.fetch(subscription);
+    expect(account.amountPerPeriod.toString()).to.equal(amountPerPeriod.toString());
+    const allowance = await program.account.pullAllowance.fetch(pullAllowance);
+    expect(allowance.subscriptions).to.equal(1);
+  });
+
+  it("Merchant pulls within the period allowance", async () => {
+    await pull(new anchor.BN(600_000));
+
+    const account = await program.account.subscription.fetch(subscription);
+    expect(account.pulledInPeriod.toString()).to.equal("600000");
+    const merchantAccount = await getAccount(provider.connection, merchantTokenAccount, 'confirmed', TOKEN_2022_PROGRAM_ID);
+    expect(merchantAccount.amount.toString()).to.equal("600000");
+  });
+
+  it("Rejects a pull over the period allowance", async () => {
+    try {
+      await pull(new anchor.BN(500_000));
+      assert.fail("Expected the pull to be rejected");
+    } catch (error: any) {
+      expect(error.toString()).to.include("SubscriptionLimitExceeded");
+    }
+  });
+
+  it("Cancelling the last subscription revokes the pull delegate", async () => {
+    await program.methods
+      .cancelSubscription()
+      .accounts({
+        payer: customer.publicKey,
+        subscription,
+        payerTokenAccount: customerTokenAccount,
+        pullAllowance,
+        pullDelegate,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([customer])
+      .rpc();
+
+    assert.isNull(await provider.connection.getAccountInfo(subscription));
+    assert.isNull(await provider.connection.getAccountInfo(pullAllowance));
+    const account = await getAccount(provider.connection, customerTokenAccount, 'confirmed', TOKEN_2022_PROGRAM_ID);
+    assert.isNull(account.delegate);
+  });
+});
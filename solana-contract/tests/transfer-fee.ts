@@ -0,0 +1,413 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Cngn } from "../target/types/cngn";
+import { assert, expect } from 'chai';
+import { PublicKey, Keypair, SystemProgram, SYSVAR_INSTRUCTIONS_PUBKEY, TransactionInstruction } from '@solana/web3.js';
+import {
+  TOKEN_2022_PROGRAM_ID,
+  createTransferCheckedWithTransferHookInstruction,
+  getAccount,
+  getMint,
+  getTransferFeeConfig,
+} from '@solana/spl-token';
+import { calculatePDAs, TokenPDAs } from '../utils/helpers';
+import { initializeToken, initializeMultisig, setupUserAccounts, kycApproveAccounts, feeRefundPda, TOKEN_PARAMS } from '../utils/token_initializer';
+import * as crypto from 'crypto';
+import nacl from 'tweetnacl';
+
+describe("cngn transfer fees", () => {
+  const connection = new anchor.web3.Connection('http://127.0.0.1:8899', 'confirmed');
+  const wallet = anchor.Wallet.local();
+  const provider = new anchor.AnchorProvider(connection, wallet, {
+    commitment: 'confirmed',
+  });
+  anchor.setProvider(provider);
+  const payer = (provider.wallet as anchor.Wallet).payer;
+  const program = anchor.workspace.Cngn as Program<Cngn>;
+
+  const mint = Keypair.generate();
+
+  // Multisig owners
+  const owner1 = Keypair.generate();
+  const owner2 = Keypair.generate();
+  const owner3 = Keypair.generate();
+  const threshold = 2;
+
+  const treasuryOwner = Keypair.generate();
+  const user = Keypair.generate();
+  // Internally whitelisted, so exempt from fees
+  const exchange = Keypair.generate();
+
+  let pdas: TokenPDAs;
+  let feeConfig: PublicKey;
+  let treasuryTokenAccount: PublicKey;
+  let userTokenAccount: PublicKey;
+  let exchangeTokenAccount: PublicKey;
+
+  // Ed25519 instruction with one signature over `message`
+  function createEd25519Ix(signer: Keypair, message: Buffer): TransactionInstruction {
+    const signature = nacl.sign.detached(message, signer.secretKey);
+    const publicKey = signer.publicKey.toBytes();
+
+    const offsetsStruct = Buffer.alloc(14);
+    offsetsStruct.writeUInt16LE(16, 0);
+    offsetsStruct.writeUInt16LE(0xFFFF, 2);
+    offsetsStruct.writeUInt16LE(80, 4);
+    offsetsStruct.writeUInt16LE(0xFFFF, 6);
+    offsetsStruct.writeUInt16LE(112, 8);
+    offsetsStruct.writeUInt16LE(message.length, 10);
+    offsetsStruct.writeUInt16LE(0xFFFF, 12);
+
+    const data = Buffer.concat([
+      Buffer.from([1, 0]),
+      offsetsStruct,
+      Buffer.from(signature),
+      publicKey,
+      message
+    ]);
+
+    return new TransactionInstruction({
+      keys: [],
+      programId: new PublicKey('Ed25519SigVerify111111111111111111111111111'),
+      data,
+    });
+  }
+
+  function u16(value: number): Buffer {
+    const buf = Buffer.alloc(2);
+    buf.writeUInt16LE(value);
+    return buf;
+  }
+
+  function u64(value: anchor.BN | number): Buffer {
+    const buf = Buffer.alloc(8);
+    buf.writeBigUInt64LE(BigInt(value.toString()));
+    return buf;
+  }
+
+  function buildMessage(action: string, ...parts: Buffer[]): Buffer {
+    const hash = crypto.createHash('sha256');
+    hash.update(Buffer.from(action));
+    for (const part of parts) {
+      hash.update(part);
+    }
+    return hash.digest();
+  }
+
+  async function currentNonce(): Promise<number> {
+    const multisigAccount = await program.account.multisig.fetch(pdas.multisig);
+    return multisigAccount.nonce.toNumber();
+  }
+
+  function buildSetTransferFeeMessage(basisPoints: number, maximumFee: anchor.BN, nonce: number): Buffer {
+    const hash = crypto.createHash('sha256');
+    hash.update(Buffer.from("SET_TRANSFER_FEE"));
+    hash.update(mint.publicKey.toBuffer());
+    hash.update(u16(basisPoints));
+    hash.update(u64(maximumFee));
+    hash.update(u64(nonce));
+    return hash.digest();
+  }
+
+  async function setTransferFee(basisPoints: number, maximumFee: anchor.BN) {
+    const message = buildSetTransferFeeMessage(basisPoints, maximumFee, await currentNonce());
+    return program.methods
+      .setTransferFee(basisPoints, maximumFee)
+      .accounts({
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        mintAuthority: pdas.mintAuthority,
+        multisig: pdas.multisig,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .preInstructions([createEd25519Ix(owner1, message), createEd25519Ix(owner2, message)])
+      .rpc();
+  }
+
+  async function balanceOf(tokenAccount: PublicKey): Promise<bigint> {
+    const account = await getAccount(provider.connection, tokenAccount, 'confirmed', TOKEN_2022_PROGRAM_ID);
+    return account.amount;
+  }
+
+  async function mintTo(tokenAccount: PublicKey, amount: anchor.BN) {
+    let message = buildMessage("ADD_CAN_MINT", pdas.canMint.toBuffer(), payer.publicKey.toBuffer(), u64(await currentNonce()));
+    await program.methods
+      .addCanMint(payer.publicKey)
+      .accounts({
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        blacklist: pdas.blacklist,
+        canMint: pdas.canMint,
+        trustedContracts: pdas.trustedContracts,
+        multisig: pdas.multisig,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .preInstructions([createEd25519Ix(owner1, message), createEd25519Ix(owner2, message)])
+      .rpc();
+
+    message = buildMessage("SET_MINT_AMOUNT", pdas.canMint.toBuffer(), payer.publicKey.toBuffer(), u64(await currentNonce()));
+    await program.methods
+      .setMintAmount(payer.publicKey, amount)
+      .accounts({
+        multisig: pdas.multisig,
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        canMint: pdas.canMint,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .preInstructions([createEd25519Ix(owner1, message), createEd25519Ix(owner2, message)])
+      .rpc();
+
+    await program.methods
+      .mint(amount)
+      .accounts({
+        authority: payer.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        mintAuthority: pdas.mintAuthority,
+        mint: mint.publicKey,
+        tokenAccount,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        blacklist: pdas.blacklist,
+        canMint: pdas.canMint,
+        trustedContracts: pdas.trustedContracts,
+      })
+      .rpc();
+  }
+
+  async function transfer(owner: Keypair, source: PublicKey, destination: PublicKey, amount: bigint) {
+    const transferIx = await createTransferCheckedWithTransferHookInstruction(
+      provider.connection,
+      source,
+      mint.publicKey,
+      destination,
+      owner.publicKey,
+      amount,
+      TOKEN_PARAMS.decimals,
+      undefined,
+      'confirmed',
+      TOKEN_2022_PROGRAM_ID,
+    );
+    await provider.sendAndConfirm(new anchor.web3.Transaction().add(transferIx), [owner]);
+  }
+
+  async function harvest(tokenAccounts: PublicKey[]) {
+    await program.methods
+      .harvestTransferFees()
+      .accounts({
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .remainingAccounts(tokenAccounts.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true })))
+      .rpc();
+  }
+
+  // A new fee applies two epochs after it is set
+  async function waitForFee() {
+    const mintInfo = await getMint(provider.connection, mint.publicKey, 'confirmed', TOKEN_2022_PROGRAM_ID);
+    const feeEpoch = BigInt(getTransferFeeConfig(mintInfo).newerTransferFee.epoch.toString());
+    while (BigInt((await provider.connection.getEpochInfo()).epoch) < feeEpoch) {
+      await new Promise((resolve) => setTimeout(resolve, 2_000));
+    }
+  }
+
+  before(async () => {
+    pdas = calculatePDAs(mint.publicKey, program.programId);
+    [feeConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from("fee-config"), mint.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await initializeToken(program, provider, mint, pdas, payer.publicKey);
+    await initializeMultisig(program, provider, mint, pdas, [owner1.publicKey, owner2.publicKey, owner3.publicKey], threshold);
+    const accounts = await setupUserAccounts(provider, [treasuryOwner, user, exchange], mint.publicKey);
+    [treasuryTokenAccount, userTokenAccount, exchangeTokenAccount] = accounts;
+    await kycApproveAccounts(program, provider, mint, pdas, [owner1, owner2], accounts);
+
+    const message = buildMessage(
+      "WHITELIST_INTERNAL",
+      pdas.internalWhitelist.toBuffer(),
+      exchange.publicKey.toBuffer(),
+      u64(await currentNonce())
+    );
+    await program.methods
+      .whitelistInternalUser(exchange.publicKey)
+      .accounts({
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        internalWhitelist: pdas.internalWhitelist,
+        blacklist: pdas.blacklist,
+        multisig: pdas.multisig,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .preInstructions([createEd25519Ix(owner1, message), createEd25519Ix(owner2, message)])
+      .rpc();
+  });
+
+  it("Creates the mint with a zero fee controlled by the PDA", async () => {
+    const mintInfo = await getMint(provider.connection, mint.publicKey, 'confirmed', TOKEN_2022_PROGRAM_ID);
+    const config = getTransferFeeConfig(mintInfo);
+    assert.ok(config.transferFeeConfigAuthority.equals(pdas.mintAuthority));
+    assert.ok(config.withdrawWithheldAuthority.equals(pdas.mintAuthority));
+    expect(config.newerTransferFee.transferFeeBasisPoints).to.equal(0);
+  });
+
+  it("Multisig sets a new transfer fee", async () => {
+    await setTransferFee(25, new anchor.BN(1_000_000));
+
+    const mintInfo = await getMint(provider.connection, mint.publicKey, 'confirmed', TOKEN_2022_PROGRAM_ID);
+    const config = getTransferFeeConfig(mintInfo);
+    expect(config.newerTransferFee.transferFeeBasisPoints).to.equal(25);
+    expect(config.newerTransferFee.maximumFee.toString()).to.equal("1000000");
+  });
+
+  it("Rejects a fee above 100%", async () => {
+    try {
+      await setTransferFee(10_001, new anchor.BN(0));
+      assert.fail("Expected the fee to be rejected");
+    } catch (error: any) {
+      expect(error.toString()).to.include("InvalidTransferFee");
+    }
+  });
+
+  it("Multisig sets the fee treasury", async () => {
+    const nonce = Buffer.alloc(8);
+    nonce.writeBigUInt64LE(BigInt(await currentNonce()));
+    const message = crypto.createHash('sha256')
+      .update(Buffer.from("UPDATE_FEE_CONFIG"))
+      .update(feeConfig.toBuffer())
+      .update(treasuryTokenAccount.toBuffer())
+      .update(Buffer.from([1]))
+      .update(nonce)
+      .digest();
+
+    await program.methods
+      .updateFeeConfig(true)
+      .accounts({
+        payer: payer.publicKey,
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        feeConfig,
+        treasury: treasuryTokenAccount,
+        multisig: pdas.multisig,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        systemProgram: SystemProgram.programId,
+      })
+      .preInstructions([createEd25519Ix(owner2, message), createEd25519Ix(owner3, message)])
+      .rpc();
+
+    const config = await program.account.feeConfig.fetch(feeConfig);
+    assert.ok(config.treasury.equals(treasuryTokenAccount));
+    assert.isTrue(config.exemptInternalWhitelist);
+  });
+
+  it("Refunds an exempt owner's fee after it was harvested", async () => {
+    const feeRefund = feeRefundPda(mint.publicKey, program.programId, exchangeTokenAccount);
+    await program.methods
+      .openFeeRefund()
+      .accounts({
+        payer: payer.publicKey,
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        feeConfig,
+        internalWhitelist: pdas.internalWhitelist,
+        tokenAccount: exchangeTokenAccount,
+        feeRefund,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    await mintTo(userTokenAccount, new anchor.BN(100_000_000));
+    await waitForFee();
+    await transfer(user, userTokenAccount, exchangeTokenAccount, 100_000_000n);
+
+    // 25 basis points of the transfer
+    const refund = await program.account.feeRefund.fetch(feeRefund);
+    expect(refund.owed.toString()).to.equal("250000");
+    expect(await balanceOf(exchangeTokenAccount)).to.equal(99_750_000n);
+
+    // Harvesting first moves the withheld fee out of the exempt account
+    await harvest([exchangeTokenAccount]);
+
+    await program.methods
+      .refundExemptFees()
+      .accounts({
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        mintAuthority: pdas.mintAuthority,
+        feeConfig,
+        feeRefund,
+        tokenAccount: exchangeTokenAccount,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .rpc();
+
+    expect(await balanceOf(exchangeTokenAccount)).to.equal(100_000_000n);
+    expect((await program.account.feeRefund.fetch(feeRefund)).owed.toString()).to.equal("0");
+    const config = await program.account.feeConfig.fetch(feeConfig);
+    expect(config.refundsOutstanding.toString()).to.equal("250000");
+  });
+
+  it("Harvests and withdraws withheld fees to the treasury, less refunds paid", async () => {
+    await harvest([userTokenAccount]);
+
+    const before = await balanceOf(treasuryTokenAccount);
+    await program.methods
+      .withdrawTransferFees()
+      .accounts({
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        mintAuthority: pdas.mintAuthority,
+        feeConfig,
+        treasury: treasuryTokenAccount,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .rpc();
+
+    // The only fee withheld was refunded, so it is burned instead of kept
+    expect(await balanceOf(treasuryTokenAccount)).to.equal(before);
+    const config = await program.account.feeConfig.fetch(feeConfig);
+    expect(config.refundsOutstanding.toString()).to.equal("0");
+  });
+
+  it("Rejects withdrawing to another account", async () => {
+    try {
+      await program.methods
+        .withdrawTransferFees()
+        .accounts({
+          mint: mint.publicKey,
+          tokenConfig: pdas.tokenConfig,
+          mintAuthority: pdas.mintAuthority,
+          feeConfig,
+          treasury: userTokenAccount,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .rpc();
+      assert.fail("Expected the withdrawal to be rejected");
+    } catch (error: any) {
+      expect(error.toString()).to.include("InvalidFeeTreasury");
+    }
+  });
+
+  it("Opens refund accounts only for internally whitelisted owners", async () => {
+    try {
+      await program.methods
+        .openFeeRefund()
+        .accounts({
+          payer: payer.publicKey,
+          mint: mint.publicKey,
+          tokenConfig: pdas.tokenConfig,
+          feeConfig,
+          internalWhitelist: pdas.internalWhitelist,
+          tokenAccount: userTokenAccount,
+          feeRefund: feeRefundPda(mint.publicKey, program.programId, userTokenAccount),
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+      assert.fail("Expected the refund account to be rejected");
+    } catch (error: any) {
+      expect(error.toString()).to.include("NotFeeExempt");
+    }
+  });
+});
@@ -0,0 +1,284 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Cngn } from "../target/types/cngn";
+import { assert, expect } from 'chai';
+import { PublicKey, Keypair, SystemProgram, SYSVAR_INSTRUCTIONS_PUBKEY, TransactionInstruction } from '@solana/web3.js';
+import { TOKEN_2022_PROGRAM_ID, createTransferCheckedWithTransferHookInstruction } from '@solana/spl-token';
+import { calculatePDAs, TokenPDAs } from '../utils/helpers';
+import { initializeToken, initializeMultisig, setupUserAccounts, kycApproveAccounts, TOKEN_PARAMS, kycPdas } from '../utils/token_initializer';
+import * as crypto from 'crypto';
+import nacl from 'tweetnacl';
+
+describe("cngn transfer limits", () => {
+  const connection = new anchor.web3.Connection('http://127.0.0.1:8899', 'confirmed');
+  const wallet = anchor.Wallet.local();
+  const provider = new anchor.AnchorProvider(connection, wallet, {
+    commitment: 'confirmed',
+  });
+  anchor.setProvider(provider);
+  const payer = (provider.wallet as anchor.Wallet).payer;
+  const program = anchor.workspace.Cngn as Program<Cngn>;
+
+  const mint = Keypair.generate();
+
+  // Multisig owners
+  const owner1 = Keypair.generate();
+  const owner2 = Keypair.generate();
+  const owner3 = Keypair.generate();
+  const threshold = 2;
+
+  const sender = Keypair.generate();
+  const receiver = Keypair.generate();
+  const treasury = Keypair.generate();
+
+  const perTransactionLimit = new anchor.BN(1_000_000);
+  const dailyLimit = new anchor.BN(2_500_000);
+
+  let pdas: TokenPDAs;
+  let senderTokenAccount: PublicKey;
+  let receiverTokenAccount: PublicKey;
+  let treasuryTokenAccount: PublicKey;
+  let senderLimits: PublicKey;
+
+  // Ed25519 instruction with one signature over `message`
+  function createEd25519Ix(signer: Keypair, message: Buffer): TransactionInstruction {
+    const signature = nacl.sign.detached(message, signer.secretKey);
+    const publicKey = signer.publicKey.toBytes();
+
+    const offsetsStruct = Buffer.alloc(14);
+    offsetsStruct.writeUInt16LE(16, 0);
+    offsetsStruct.writeUInt16LE(0xFFFF, 2);
+    offsetsStruct.writeUInt16LE(80, 4);
+    offsetsStruct.writeUInt16LE(0xFFFF, 6);
+    offsetsStruct.writeUInt16LE(112, 8);
+    offsetsStruct.writeUInt16LE(message.length, 10);
+    offsetsStruct.writeUInt16LE(0xFFFF, 12);
+
+    const data = Buffer.concat([
+      Buffer.from([1, 0]),
+      offsetsStruct,
+      Buffer.from(signature),
+      publicKey,
+      message
+    ]);
+
+    return new TransactionInstruction({
+      keys: [],
+      programId: new PublicKey('Ed25519SigVerify111111111111111111111111111'),
+      data,
+    });
+  }
+
+  function u64(value: anchor.BN | number): Buffer {
+    const buf = Buffer.alloc(8);
+    buf.writeBigUInt64LE(BigInt(value.toString()));
+    return buf;
+  }
+
+  function buildMessage(action: string, ...parts: Buffer[]): Buffer {
+    const hash = crypto.createHash('sha256');
+    hash.update(Buffer.from(action));
+    for (const part of parts) {
+      hash.update(part);
+    }
+    return hash.digest();
+  }
+
+  async function currentNonce(): Promise<number> {
+    const multisigAccount = await program.account.multisig.fetch(pdas.multisig);
+    return multisigAccount.nonce.toNumber();
+  }
+
+  // Minting consumes the minter's allowance, so re-authorise every time
+  async function mintTo(tokenAccount: PublicKey, amount: anchor.BN) {
+    let message = buildMessage("ADD_CAN_MINT", pdas.canMint.toBuffer(), payer.publicKey.toBuffer(), u64(await currentNonce()));
+    await program.methods
+      .addCanMint(payer.publicKey)
+      .accounts({
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        blacklist: pdas.blacklist,
+        canMint: pdas.canMint,
+        trustedContracts: pdas.trustedContracts,
+        multisig: pdas.multisig,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .preInstructions([createEd25519Ix(owner1, message), createEd25519Ix(owner2, message)])
+      .rpc();
+
+    message = buildMessage("SET_MINT_AMOUNT", pdas.canMint.toBuffer(), payer.publicKey.toBuffer(), u64(await currentNonce()));
+    await program.methods
+      .setMintAmount(payer.publicKey, amount)
+      .accounts({
+        multisig: pdas.multisig,
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        canMint: pdas.canMint,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .preInstructions([createEd25519Ix(owner1, message), createEd25519Ix(owner2, message)])
+      .rpc();
+
+    await program.methods
+      .mint(amount)
+      .accounts({
+        authority: payer.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        mintAuthority: pdas.mintAuthority,
+        mint: mint.publicKey,
+        tokenAccount,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        blacklist: pdas.blacklist,
+        canMint: pdas.canMint,
+        trustedContracts: pdas.trustedContracts,
+      })
+      .rpc();
+  }
+
+  async function transfer(owner: Keypair, source: PublicKey, amount: number) {
+    const transferIx = await createTransferCheckedWithTransferHookInstruction(
+      provider.connection,
+      source,
+      mint.publicKey,
+      receiverTokenAccount,
+      owner.publicKey,
+      BigInt(amount),
+      TOKEN_PARAMS.decimals,
+      undefined,
+      'confirmed',
+      TOKEN_2022_PROGRAM_ID,
+    );
+    await provider.sendAndConfirm(new anchor.web3.Transaction().add(transferIx), [owner]);
+  }
+
+  async function expectTransferError(owner: Keypair, source: PublicKey, amount: number, error: string) {
+    try {
+      await transfer(owner, source, amount);
+      assert.fail("Expected the transfer to be rejected");
+    } catch (err: any) {
+      expect(err.toString() + JSON.stringify(err.logs ?? [])).to.include(error);
+    }
+  }
+
+  before(async () => {
+    pdas = calculatePDAs(mint.publicKey, program.programId);
+    [senderLimits] = PublicKey.findProgramAddressSync(
+      [Buffer.from("transfer-limits"), mint.publicKey.toBuffer(), sender.publicKey.toBuffer()],
+      program.programId
+    );
+
+    for (const account of [sender.publicKey, treasury.publicKey]) {
+      await provider.connection.requestAirdrop(account, 1 * anchor.web3.LAMPORTS_PER_SOL);
+    }
+    await new Promise(resolve => setTimeout(resolve, 2000));
+
+    await initializeToken(program, provider, mint, pdas, payer.publicKey);
+    await initializeMultisig(program, provider, mint, pdas, [owner1.publicKey, owner2.publicKey, owner3.publicKey], threshold);
+    const accounts = await setupUserAccounts(provider, [sender, receiver, treasury], mint.publicKey);
+    [senderTokenAccount, receiverTokenAccount, treasuryTokenAccount] = accounts;
+    await kycApproveAccounts(program, provider, mint, pdas, [owner1, owner2], accounts);
+
+    await mintTo(senderTokenAccount, new anchor.BN(10_000_000));
+    await mintTo(treasuryTokenAccount, new anchor.BN(10_000_000));
+  });
+
+  it("KYC approval creates the sender's limits at the KYC tier", async () => {
+    const limits = await program.account.transferLimits.fetch(senderLimits);
+    const record = await program.account.kycRecord.fetch(
+      kycPdas(mint.publicKey, program.programId, sender.publicKey).kycRecord
+    );
+    expect(limits.owner.toBase58()).to.equal(sender.publicKey.toBase58());
+    expect(limits.tier).to.equal(record.tier);
+  });
+
+  it("Multisig configures tier 1 and assigns the sender to it", async () => {
+    let message = buildMessage(
+      "CONFIGURE_TRANSFER_TIER",
+      pdas.transferTiers.toBuffer(),
+      Buffer.from([1]),
+      u64(perTransactionLimit),
+      u64(dailyLimit),
+      u64(await currentNonce())
+    );
+    await program.methods
+      .configureTransferTier(1, perTransactionLimit, dailyLimit)
+      .accounts({
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        transferTiers: pdas.transferTiers,
+        multisig: pdas.multisig,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .preInstructions([createEd25519Ix(owner1, message), createEd25519Ix(owner3, message)])
+      .rpc();
+
+    message = buildMessage(
+      "SET_TRANSFER_TIER",
+      senderLimits.toBuffer(),
+      sender.publicKey.toBuffer(),
+      Buffer.from([1]),
+      u64(await currentNonce())
+    );
+    await program.methods
+      .setTransferTier(sender.publicKey, 1)
+      .accounts({
+        payer: payer.publicKey,
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        transferLimits: senderLimits,
+        kycRecord: kycPdas(mint.publicKey, program.programId, sender.publicKey).kycRecord,
+        internalWhitelist: pdas.internalWhitelist,
+        externalWhitelist: pdas.externalWhitelist,
+        multisig: pdas.multisig,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        systemProgram: SystemProgram.programId,
+      })
+      .preInstructions([createEd25519Ix(owner2, message), createEd25519Ix(owner3, message)])
+      .rpc();
+
+    const tiers = await program.account.transferTiers.fetch(pdas.transferTiers);
+    expect(tiers.tiers[1].daily.toString()).to.equal(dailyLimit.toString());
+    const limits = await program.account.transferLimits.fetch(senderLimits);
+    expect(limits.tier).to.equal(1);
+  });
+
+  it("Rejects a transfer over the per-transaction cap", async () => {
+    await expectTransferError(sender, senderTokenAccount, 1_000_001, "PerTransactionLimitExceeded");
+  });
+
+  it("Tracks usage within the daily window", async () => {
+    await transfer(sender, senderTokenAccount, 1_000_000);
+    await transfer(sender, senderTokenAccount, 1_000_000);
+
+    const limits = await program.account.transferLimits.fetch(senderLimits);
+    expect(limits.windowSpent.toString()).to.equal("2000000");
+  });
+
+  it("Rejects a transfer over the daily cap", async () => {
+    await expectTransferError(sender, senderTokenAccount, 600_000, "DailyLimitExceeded");
+    await transfer(sender, senderTokenAccount, 500_000);
+  });
+
+  it("Exempts internally whitelisted owners", async () => {
+    const message = buildMessage(
+      "WHITELIST_INTERNAL",
+      pdas.internalWhitelist.toBuffer(),
+      treasury.publicKey.toBuffer(),
+      u64(await currentNonce())
+    );
+    await program.methods
+      .whitelistInternalUser(treasury.publicKey)
+      .accounts({
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        internalWhitelist: pdas.internalWhitelist,
+        blacklist: pdas.blacklist,
+        multisig: pdas.multisig,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .preInstructions([createEd25519Ix(owner1, message), createEd25519Ix(owner2, message)])
+      .rpc();
+
+    await transfer(treasury, treasuryTokenAccount, 5_000_000);
+  });
+});
@@ -0,0 +1,98 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Cngn } from "../target/types/cngn";
+import { assert, expect } from 'chai';
+import { PublicKey, Keypair } from '@solana/web3.js';
+import { calculatePDAs } from '../utils/helpers';
+import { initializeMultisig, initializeToken } from "../utils/token_initializer";
+import { TOKEN_2022_PROGRAM_ID } from "@solana/spl-token";
+
+describe("cngn view instructions", () => {
+  const connection = new anchor.web3.Connection('http://127.0.0.1:8899', 'confirmed');
+  const wallet = anchor.Wallet.local();
+  const provider = new anchor.AnchorProvider(connection, wallet, {
+    commitment: 'confirmed',
+  });
+  anchor.setProvider(provider);
+  const payer = (provider.wallet as anchor.Wallet).payer;
+  const program = anchor.workspace.Cngn as Program<Cngn>;
+
+  const mint = Keypair.generate();
+  const regularUser = Keypair.generate();
+
+  const owner1 = Keypair.generate();
+  const owner2 = Keypair.generate();
+  const owner3 = Keypair.generate();
+  const threshold = 2;
+
+  let pdas: any;
+
+  before(async () => {
+    pdas = calculatePDAs(mint.publicKey, program.programId);
+
+    await initializeToken(program, provider, mint, pdas, payer.publicKey);
+    await initializeMultisig(program, provider, mint, pdas, [owner1.publicKey, owner2.publicKey, owner3.publicKey], threshold)
+  });
+
+  it("Reports list membership of an address", async () => {
+    const status = await program.methods
+      .getAddressStatus(regularUser.publicKey)
+      .accounts({
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        blacklist: pdas.blacklist,
+        internalWhitelist: pdas.internalWhitelist,
+        externalWhitelist: pdas.externalWhitelist,
+        canForward: pdas.canForward,
+        trustedContracts: pdas.trustedContracts,
+        canMint: pdas.canMint,
+      })
+      .view();
+
+    assert.ok(status.address.equals(regularUser.publicKey));
+    expect(status.blacklisted).to.be.false;
+    expect(status.internalWhitelisted).to.be.false;
+    expect(status.externalWhitelisted).to.be.false;
+    expect(status.forwarder).to.be.false;
+    expect(status.trustedContract).to.be.false;
+    expect(status.minter).to.be.false;
+    expect(status.mintAllowance.toString()).to.equal("0");
+  });
+
+  it("Reports the multisig owners, threshold and nonce", async () => {
+    const info = await program.methods
+      .getMultisigInfo()
+      .accounts({
+        mint: mint.publicKey,
+        multisig: pdas.multisig,
+      })
+      .view();
+
+    const multisigAccount = await program.account.multisig.fetch(pdas.multisig);
+
+    assert.ok(info.multisig.equals(pdas.multisig));
+    expect(info.owners.map((owner: PublicKey) => owner.toBase58())).to.deep.equal(
+      [owner1, owner2, owner3].map((owner) => owner.publicKey.toBase58())
+    );
+    expect(info.threshold).to.equal(threshold);
+    expect(info.nonce.toString()).to.equal(multisigAccount.nonce.toString());
+  });
+
+  it("Reports the pause flags", async () => {
+    const state = await program.methods
+      .getTokenState()
+      .accounts({
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+      })
+      .view();
+
+    const tokenConfig = await program.account.tokenConfig.fetch(pdas.tokenConfig);
+
+    assert.ok(state.mint.equals(mint.publicKey));
+    assert.ok(state.admin.equals(tokenConfig.admin));
+    expect(state.mintPaused).to.equal(tokenConfig.mintPaused);
+    expect(state.transferPaused).to.equal(tokenConfig.transferPaused);
+    expect(state.initPhase).to.deep.equal({ complete: {} });
+  });
+});
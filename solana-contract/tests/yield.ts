@@ -0,0 +1,356 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Cngn } from "../target/types/cngn";
+import { assert, expect } from 'chai';
+import { PublicKey, Keypair, SystemProgram, SYSVAR_INSTRUCTIONS_PUBKEY, TransactionInstruction } from '@solana/web3.js';
+import { TOKEN_2022_PROGRAM_ID, getAccount, createTransferCheckedWithTransferHookInstruction } from '@solana/spl-token';
+import { calculatePDAs, TokenPDAs } from '../utils/helpers';
+import { initializeToken, initializeMultisig, setupUserAccounts, kycApproveAccounts, TOKEN_PARAMS, yieldPositionPda } from '../utils/token_initializer';
+import * as crypto from 'crypto';
+import nacl from 'tweetnacl';
+
+describe("cngn savings yield", () => {
+  const connection = new anchor.web3.Connection('http://127.0.0.1:8899', 'confirmed');
+  const wallet = anchor.Wallet.local();
+  const provider = new anchor.AnchorProvider(connection, wallet, {
+    commitment: 'confirmed',
+  });
+  anchor.setProvider(provider);
+  const payer = (provider.wallet as anchor.Wallet).payer;
+  const program = anchor.workspace.Cngn as Program<Cngn>;
+
+  const mint = Keypair.generate();
+
+  // Multisig owners
+  const owner1 = Keypair.generate();
+  const owner2 = Keypair.generate();
+  const owner3 = Keypair.generate();
+  const threshold = 2;
+
+  const saver = Keypair.generate();
+  const cycler = Keypair.generate(); // Moves one balance between two positions
+
+  let pdas: TokenPDAs;
+  let yieldConfig: PublicKey;
+  let yieldPosition: PublicKey;
+  let saverTokenAccount: PublicKey;
+  let cyclerPosition: PublicKey;
+  let cyclerTokenAccount: PublicKey;
+
+  // Ed25519 instruction with one signature over `message`
+  function createEd25519Ix(signer: Keypair, message: Buffer): TransactionInstruction {
+    const signature = nacl.sign.detached(message, signer.secretKey);
+    const publicKey = signer.publicKey.toBytes();
+
+    const offsetsStruct = Buffer.alloc(14);
+    offsetsStruct.writeUInt16LE(16, 0);
+    offsetsStruct.writeUInt16LE(0xFFFF, 2);
+    offsetsStruct.writeUInt16LE(80, 4);
+    offsetsStruct.writeUInt16LE(0xFFFF, 6);
+    offsetsStruct.writeUInt16LE(112, 8);
+    offsetsStruct.writeUInt16LE(message.length, 10);
+    offsetsStruct.writeUInt16LE(0xFFFF, 12);
+
+    const data = Buffer.concat([
+      Buffer.from([1, 0]),
+      offsetsStruct,
+      Buffer.from(signature),
+      publicKey,
+      message
+    ]);
+
+    return new TransactionInstruction({
+      keys: [],
+      programId: new PublicKey('Ed25519SigVerify111111111111111111111111111'),
+      data,
+    });
+  }
+
+  function u16(value: number): Buffer {
+    const buf = Buffer.alloc(2);
+    buf.writeUInt16LE(value);
+    return buf;
+  }
+
+  function u32(value: number): Buffer {
+    const buf = Buffer.alloc(4);
+    buf.writeUInt32LE(value);
+    return buf;
+  }
+
+  function u64(value: anchor.BN | number): Buffer {
+    const buf = Buffer.alloc(8);
+    buf.writeBigUInt64LE(BigInt(value.toString()));
+    return buf;
+  }
+
+  function buildMessage(action: string, ...parts: Buffer[]): Buffer {
+    const hash = crypto.createHash('sha256');
+    hash.update(Buffer.from(action));
+    for (const part of parts) {
+      hash.update(part);
+    }
+    return hash.digest();
+  }
+
+  async function currentNonce(): Promise<number> {
+    const multisigAccount = await program.account.multisig.fetch(pdas.multisig);
+    return multisigAccount.nonce.toNumber();
+  }
+
+  async function updateYieldConfig(rate: number, periodSeconds: number, supplyCap: anchor.BN, reserves: anchor.BN) {
+    const message = crypto.createHash('sha256')
+      .update(Buffer.from("UPDATE_YIELD_CONFIG"))
+      .update(yieldConfig.toBuffer())
+      .update(u16(rate))
+      .update(u32(periodSeconds))
+      .update(u64(supplyCap))
+      .update(u64(reserves))
+      .update(u64(await currentNonce()))
+      .digest();
+
+    return program.methods
+      .updateYieldConfig(rate, periodSeconds, supplyCap, reserves)
+      .accounts({
+        payer: payer.publicKey,
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        yieldConfig,
+        multisig: pdas.multisig,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        systemProgram: SystemProgram.programId,
+      })
+      .preInstructions([createEd25519Ix(owner1, message), createEd25519Ix(owner2, message)])
+      .rpc();
+  }
+
+  // Minting consumes the minter's allowance, so re-authorise every time
+  async function mintTo(tokenAccount: PublicKey, amount: anchor.BN) {
+    let message = buildMessage("ADD_CAN_MINT", pdas.canMint.toBuffer(), payer.publicKey.toBuffer(), u64(await currentNonce()));
+    await program.methods
+      .addCanMint(payer.publicKey)
+      .accounts({
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        blacklist: pdas.blacklist,
+        canMint: pdas.canMint,
+        trustedContracts: pdas.trustedContracts,
+        multisig: pdas.multisig,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .preInstructions([createEd25519Ix(owner1, message), createEd25519Ix(owner2, message)])
+      .rpc();
+
+    message = buildMessage("SET_MINT_AMOUNT", pdas.canMint.toBuffer(), payer.publicKey.toBuffer(), u64(await currentNonce()));
+    await program.methods
+      .setMintAmount(payer.publicKey, amount)
+      .accounts({
+        multisig: pdas.multisig,
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        canMint: pdas.canMint,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .preInstructions([createEd25519Ix(owner1, message), createEd25519Ix(owner2, message)])
+      .rpc();
+
+    await program.methods
+      .mint(amount)
+      .accounts({
+        authority: payer.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        mintAuthority: pdas.mintAuthority,
+        mint: mint.publicKey,
+        tokenAccount,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        blacklist: pdas.blacklist,
+        canMint: pdas.canMint,
+        trustedContracts: pdas.trustedContracts,
+      })
+      .rpc();
+  }
+
+  async function balanceOf(tokenAccount: PublicKey): Promise<bigint> {
+    return (await getAccount(provider.connection, tokenAccount, 'confirmed', TOKEN_2022_PROGRAM_ID)).amount;
+  }
+
+  // Move the whole balance of `source`; the hook resolves the source's position
+  async function transferAll(owner: Keypair, source: PublicKey, destination: PublicKey) {
+    const transferIx = await createTransferCheckedWithTransferHookInstruction(
+      provider.connection,
+      source,
+      mint.publicKey,
+      destination,
+      owner.publicKey,
+      await balanceOf(source),
+      TOKEN_PARAMS.decimals,
+      undefined,
+      'confirmed',
+      TOKEN_2022_PROGRAM_ID,
+    );
+    await provider.sendAndConfirm(new anchor.web3.Transaction().add(transferIx), [owner]);
+  }
+
+  async function distribute(tokenAccount: PublicKey, position: PublicKey) {
+    await program.methods
+      .distributeYield()
+      .accounts({
+        tokenConfig: pdas.tokenConfig,
+        mint: mint.publicKey,
+        mintAuthority: pdas.mintAuthority,
+        yieldConfig,
+        yieldPosition: position,
+        tokenAccount,
+        blacklist: pdas.blacklist,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .rpc();
+  }
+
+  async function advance() {
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+    await program.methods.advanceYieldPeriod().accounts({ yieldConfig }).rpc();
+  }
+
+  before(async () => {
+    pdas = calculatePDAs(mint.publicKey, program.programId);
+    [yieldConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from("yield-config"), mint.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await initializeToken(program, provider, mint, pdas, payer.publicKey);
+    await initializeMultisig(program, provider, mint, pdas, [owner1.publicKey, owner2.publicKey, owner3.publicKey], threshold);
+    [saverTokenAccount, cyclerTokenAccount] = await setupUserAccounts(provider, [saver, cycler], mint.publicKey);
+    await kycApproveAccounts(program, provider, mint, pdas, [owner1, owner2], [saverTokenAccount, cyclerTokenAccount]);
+
+    yieldPosition = yieldPositionPda(mint.publicKey, program.programId, saverTokenAccount);
+    cyclerPosition = yieldPositionPda(mint.publicKey, program.programId, cyclerTokenAccount);
+  });
+
+  it("Multisig enables yield with bounds", async () => {
+    await updateYieldConfig(500, 1, new anchor.BN(1_000_000_000), new anchor.BN(1_000_000_000));
+
+    const config = await program.account.yieldConfig.fetch(yieldConfig);
+    expect(config.annualRateBasisPoints).to.equal(500);
+    expect(config.periodSeconds).to.equal(1);
+    expect(config.period.toNumber()).to.equal(0);
+  });
+
+  it("Rejects a rate above 100%", async () => {
+    try {
+      await updateYieldConfig(10_001, 1, new anchor.BN(0), new anchor.BN(0));
+      assert.fail("Expected the rate to be rejected");
+    } catch (error: any) {
+      expect(error.toString()).to.include("InvalidYieldConfig");
+    }
+  });
+
+  it("Opts a token account in", async () => {
+    await program.methods
+      .optInYield()
+      .accounts({
+        owner: saver.publicKey,
+        mint: mint.publicKey,
+        yieldConfig,
+        tokenAccount: saverTokenAccount,
+        blacklist: pdas.blacklist,
+        yieldPosition,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([saver])
+      .rpc();
+
+    const position = await program.account.yieldPosition.fetch(yieldPosition);
+    assert.ok(position.tokenAccount.equals(saverTokenAccount));
+  });
+
+  it("Advances a period and distributes", async () => {
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+    await program.methods.advanceYieldPeriod().accounts({ yieldConfig }).rpc();
+
+    const config = await program.account.yieldConfig.fetch(yieldConfig);
+    expect(config.period.toNumber()).to.equal(1);
+
+    await program.methods
+      .distributeYield()
+      .accounts({
+        tokenConfig: pdas.tokenConfig,
+        mint: mint.publicKey,
+        mintAuthority: pdas.mintAuthority,
+        yieldConfig,
+        yieldPosition,
+        tokenAccount: saverTokenAccount,
+        blacklist: pdas.blacklist,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .rpc();
+  });
+
+  it("Pays nothing on a balance cycled through two positions", async () => {
+    // 100% a year, so one period on this balance is worth a few base units
+    await updateYieldConfig(10_000, 1, new anchor.BN(10_000_000_000), new anchor.BN(10_000_000_000));
+    await mintTo(saverTokenAccount, new anchor.BN(1_000_000_000));
+    await distribute(saverTokenAccount, yieldPosition);
+
+    await program.methods
+      .optInYield()
+      .accounts({
+        owner: cycler.publicKey,
+        mint: mint.publicKey,
+        yieldConfig,
+        tokenAccount: cyclerTokenAccount,
+        blacklist: pdas.blacklist,
+        yieldPosition: cyclerPosition,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([cycler])
+      .rpc();
+
+    // Both positions record the balance at a payout, then it moves back
+    await transferAll(saver, saverTokenAccount, cyclerTokenAccount);
+    await distribute(cyclerTokenAccount, cyclerPosition);
+    await transferAll(cycler, cyclerTokenAccount, saverTokenAccount);
+
+    let position = await program.account.yieldPosition.fetch(yieldPosition);
+    expect(position.balance.toNumber()).to.equal(0);
+    position = await program.account.yieldPosition.fetch(cyclerPosition);
+    expect(position.balance.toNumber()).to.equal(0);
+
+    // Neither position held it for the whole period, so neither is paid
+    await advance();
+    const held = await balanceOf(saverTokenAccount);
+    await distribute(saverTokenAccount, yieldPosition);
+    expect((await balanceOf(saverTokenAccount)).toString()).to.equal(held.toString());
+
+    await transferAll(saver, saverTokenAccount, cyclerTokenAccount);
+    const cycled = await balanceOf(cyclerTokenAccount);
+    await distribute(cyclerTokenAccount, cyclerPosition);
+    expect((await balanceOf(cyclerTokenAccount)).toString()).to.equal(cycled.toString());
+
+    // Held through the next period, it earns once
+    await advance();
+    await distribute(cyclerTokenAccount, cyclerPosition);
+    expect(await balanceOf(cyclerTokenAccount) > cycled).to.be.true;
+  });
+
+  it("Rejects advancing before the period has elapsed", async () => {
+    await updateYieldConfig(500, 86_400, new anchor.BN(1_000_000_000), new anchor.BN(1_000_000_000));
+    try {
+      await program.methods.advanceYieldPeriod().accounts({ yieldConfig }).rpc();
+      assert.fail("Expected the period to still be open");
+    } catch (error: any) {
+      expect(error.toString()).to.include("YieldPeriodNotElapsed");
+    }
+  });
+
+  it("Opts the token account out", async () => {
+    await program.methods
+      .optOutYield()
+      .accounts({ owner: saver.publicKey, yieldPosition })
+      .signers([saver])
+      .rpc();
+
+    assert.isNull(await provider.connection.getAccountInfo(yieldPosition));
+  });
+});
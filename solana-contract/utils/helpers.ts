@@ -59,6 +59,7 @@ export interface TokenPDAs {
   externalWhitelist: PublicKey;
   extraMetasAccount: PublicKey;
   multisig:PublicKey;
+  transferTiers: PublicKey;
 }
 
 /**
@@ -123,6 +124,11 @@ export function calculatePDAs(mint: PublicKey, programId: PublicKey): TokenPDAs
     programId
   );
 
+  const [transferTiers] = PublicKey.findProgramAddressSync(
+    [Buffer.from("transfer-tiers"), mint.toBuffer()],
+    programId
+  );
+
   return {
     tokenConfig,
     mintAuthority,
@@ -134,11 +140,52 @@ export function calculatePDAs(mint: PublicKey, programId: PublicKey): TokenPDAs
     internalWhitelist,
     externalWhitelist,
     extraMetasAccount,
-    multisig
+    multisig,
+    transferTiers
   };
 }
 
 export function stringToUint8Array(input: string): Uint8Array {
   const encoder = new TextEncoder();
   return encoder.encode(input);
-}
\ No newline at end of file
+}
+
+export const BLACKLIST_REASONS = ["sanctions", "courtOrder", "fraudReport", "internalInvestigation", "other"] as const;
+export type BlacklistReason = typeof BLACKLIST_REASONS[number];
+
+/**
+ * Blacklist details as `add_blacklist` takes them, with the bytes the
+ * program hashes into the approval message after the target
+ */
+export function blacklistDetails(reason: BlacklistReason, caseReference: string, reviewAt = 0, expiresAt = 0) {
+  const caseBytes = Buffer.from(caseReference);
+  const bytes = Buffer.alloc(1 + 4 + caseBytes.length + 8 + 8);
+  bytes.writeUInt8(BLACKLIST_REASONS.indexOf(reason), 0);
+  bytes.writeUInt32LE(caseBytes.length, 1);
+  caseBytes.copy(bytes, 5);
+  bytes.writeBigInt64LE(BigInt(reviewAt), 5 + caseBytes.length);
+  bytes.writeBigInt64LE(BigInt(expiresAt), 13 + caseBytes.length);
+
+  return {
+    details: {
+      reason: { [reason]: {} } as any,
+      caseReference,
+      reviewAt: new anchor.BN(reviewAt),
+      expiresAt: new anchor.BN(expiresAt),
+    },
+    bytes,
+  };
+}
+
+/**
+ * The entry recording why `user` is blacklisted, passed to the blacklist
+ * instructions as a remaining account
+ */
+export function blacklistEntry(mint: PublicKey, programId: PublicKey, user: PublicKey) {
+  const [entry] = PublicKey.findProgramAddressSync(
+    [Buffer.from("blacklist-entry"), mint.toBuffer(), user.toBuffer()],
+    programId
+  );
+
+  return { pubkey: entry, isWritable: true, isSigner: false };
+}
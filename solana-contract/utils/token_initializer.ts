@@ -1,9 +1,10 @@
-import { PublicKey, SystemProgram, SYSVAR_RENT_PUBKEY, Keypair } from '@solana/web3.js';
-import { createMint, TOKEN_2022_PROGRAM_ID } from '@solana/spl-token';
+import { PublicKey, SystemProgram, SYSVAR_RENT_PUBKEY, SYSVAR_INSTRUCTIONS_PUBKEY, Keypair, TransactionInstruction } from '@solana/web3.js';
+import { TOKEN_2022_PROGRAM_ID, getAccount } from '@solana/spl-token';
 import * as anchor from "@coral-xyz/anchor";
+import * as crypto from 'crypto';
+import nacl from 'tweetnacl';
 
 import { createTokenAccountIfNeeded, TokenPDAs } from './helpers';
-import { createMintAccountWithExtensions } from '../app/utils/metadata2022';
 
 
 /**
@@ -16,7 +17,10 @@ export const TOKEN_PARAMS = {
   uri: "helper",
   mintAmount: new anchor.BN(100_000_000), // 10,000 tokens with 6 decimals
   transferAmount: new anchor.BN(5_000_000_000), // 5,000 tokens
-  partialAmount: new anchor.BN(2_500_000_000) // 2,500 tokens
+  partialAmount: new anchor.BN(2_500_000_000), // 2,500 tokens
+  // ElGamal key withheld confidential fees are encrypted to; the identity
+  // point is fine for tests
+  feeWithdrawElGamalPubkey: new Array(32).fill(0),
 };
 
 /**
@@ -25,6 +29,7 @@ export const TOKEN_PARAMS = {
  * @param provider - The Anchor provider
  * @param mint - The mint keypair
  * @param pdas - PDAs for the token
+ * @param feeWithdrawElGamalPubkey - 32-byte ElGamal key for withheld confidential fees
  */
 export async function initializeToken(
   program: any,
@@ -32,15 +37,13 @@ export async function initializeToken(
   mint: Keypair,
   pdas: TokenPDAs,
   admin: PublicKey,
-
+  feeWithdrawElGamalPubkey: number[] = TOKEN_PARAMS.feeWithdrawElGamalPubkey,
 ): Promise<void> {
   console.log("=========== Initializing token ========");
-  //await createMintAccountWithExtensions(provider, mint, TOKEN_PARAMS, program.programId);
-  //await createMint(provider.connection,provider.wallet.payer,provider.wallet.payer.publicKey,
-  //provider.wallet.payer.publicKey, TOKEN_PARAMS.decimals,mint,null,TOKEN_2022_PROGRAM_ID);
-  // Execute the initialization transaction
+  // `initialize` creates the mint with all of its extensions; the mint
+  // keypair only signs for the account creation
   const tx = await program.methods
-    .initialize(TOKEN_PARAMS.name, TOKEN_PARAMS.symbol, TOKEN_PARAMS.uri, TOKEN_PARAMS.decimals)
+    .initialize(TOKEN_PARAMS.name, TOKEN_PARAMS.symbol, TOKEN_PARAMS.uri, TOKEN_PARAMS.decimals, feeWithdrawElGamalPubkey)
     .accounts({
       initializer: provider.wallet.publicKey,
       tokenConfig: pdas.tokenConfig,
@@ -53,6 +56,7 @@ export async function initializeToken(
       systemProgram: SystemProgram.programId,
       rent: SYSVAR_RENT_PUBKEY,
     })
+    .signers([mint])
     .rpc();
 
   console.log("Initialization transaction signature", tx);
@@ -65,6 +69,7 @@ export async function initializeToken(
       .accounts({
         initializer: provider.wallet.publicKey,
         mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
         blacklist: pdas.blacklist,
         canForward: pdas.canForward,
         trustedContracts: pdas.trustedContracts,
@@ -74,7 +79,7 @@ export async function initializeToken(
 
     console.log("Secondary initialization transaction signature", tx2);
 
-    // Initialize third accounts (whitelists and extra metas)
+    // Initialize third accounts (whitelists, transfer tiers and extra metas)
     const tx3 = await program.methods
       .initializeThird()
       .accounts({
@@ -84,6 +89,7 @@ export async function initializeToken(
         tokenConfig: pdas.tokenConfig,
         internalWhitelist: pdas.internalWhitelist,
         externalWhitelist: pdas.externalWhitelist,
+        transferTiers: pdas.transferTiers,
         systemProgram: SystemProgram.programId,
       })
       .rpc();
@@ -148,4 +154,143 @@ export async function setupUserAccounts(
   }
 
   return tokenAccounts;
-}
\ No newline at end of file
+}
+// Ed25519 instruction with one signature over `message`
+function createEd25519Ix(signer: Keypair, message: Buffer): TransactionInstruction {
+  const signature = nacl.sign.detached(message, signer.secretKey);
+
+  const offsetsStruct = Buffer.alloc(14);
+  offsetsStruct.writeUInt16LE(16, 0);
+  offsetsStruct.writeUInt16LE(0xFFFF, 2);
+  offsetsStruct.writeUInt16LE(80, 4);
+  offsetsStruct.writeUInt16LE(0xFFFF, 6);
+  offsetsStruct.writeUInt16LE(112, 8);
+  offsetsStruct.writeUInt16LE(message.length, 10);
+  offsetsStruct.writeUInt16LE(0xFFFF, 12);
+
+  return new TransactionInstruction({
+    keys: [],
+    programId: new PublicKey('Ed25519SigVerify111111111111111111111111111'),
+    data: Buffer.concat([
+      Buffer.from([1, 0]),
+      offsetsStruct,
+      Buffer.from(signature),
+      signer.publicKey.toBytes(),
+      message,
+    ]),
+  });
+}
+
+export function kycPdas(mint: PublicKey, programId: PublicKey, owner?: PublicKey) {
+  const [kycOperators] = PublicKey.findProgramAddressSync(
+    [Buffer.from("kyc-operators"), mint.toBuffer()],
+    programId
+  );
+  const kycRecord = owner
+    ? PublicKey.findProgramAddressSync([Buffer.from("kyc"), mint.toBuffer(), owner.toBuffer()], programId)[0]
+    : undefined;
+  const transferLimits = owner
+    ? PublicKey.findProgramAddressSync([Buffer.from("transfer-limits"), mint.toBuffer(), owner.toBuffer()], programId)[0]
+    : undefined;
+  return { kycOperators, kycRecord, transferLimits };
+}
+
+/** The `FeeConfig`, which exists once the multisig sets a fee treasury. */
+export function feeConfigPda(mint: PublicKey, programId: PublicKey) {
+  return PublicKey.findProgramAddressSync([Buffer.from("fee-config"), mint.toBuffer()], programId)[0];
+}
+
+/** The `YieldPosition` of a token account, which exists once it opts in. */
+export function yieldPositionPda(mint: PublicKey, programId: PublicKey, tokenAccount: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("yield-position"), mint.toBuffer(), tokenAccount.toBuffer()],
+    programId
+  )[0];
+}
+
+/** The `FeeRefund` of a token account, which exists once an exempt owner opens it. */
+export function feeRefundPda(mint: PublicKey, programId: PublicKey, tokenAccount: PublicKey) {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("fee-refund"), mint.toBuffer(), tokenAccount.toBuffer()],
+    programId
+  )[0];
+}
+
+/**
+ * Token accounts start frozen. Makes the wallet a KYC operator (creating the
+ * operator list if needed) and approves every account at `tier`.
+ * @param approvers - multisig owners meeting the threshold
+ */
+export async function kycApproveAccounts(
+  program: any,
+  provider: anchor.AnchorProvider,
+  mint: Keypair,
+  pdas: TokenPDAs,
+  approvers: Keypair[],
+  tokenAccounts: PublicKey[],
+  tier: number = 1
+): Promise<void> {
+  const operator = provider.wallet.publicKey;
+  const { kycOperators } = kycPdas(mint.publicKey, program.programId);
+
+  if (!(await provider.connection.getAccountInfo(kycOperators))) {
+    await program.methods
+      .initializeKyc()
+      .accounts({
+        initializer: operator,
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        kycOperators,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  }
+
+  const operators = await program.account.kycOperators.fetch(kycOperators);
+  if (!operators.operators.some((key: PublicKey) => key.equals(operator))) {
+    const multisig = await program.account.multisig.fetch(pdas.multisig);
+    const nonce = Buffer.alloc(8);
+    nonce.writeBigUInt64LE(BigInt(multisig.nonce.toString()));
+    const message = crypto.createHash('sha256')
+      .update(Buffer.from("ADD_KYC_OPERATOR"))
+      .update(kycOperators.toBuffer())
+      .update(operator.toBuffer())
+      .update(nonce)
+      .digest();
+
+    await program.methods
+      .addKycOperator(operator)
+      .accounts({
+        mint: mint.publicKey,
+        tokenConfig: pdas.tokenConfig,
+        blacklist: pdas.blacklist,
+        kycOperators,
+        multisig: pdas.multisig,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .preInstructions(approvers.map((approver) => createEd25519Ix(approver, message)))
+      .rpc();
+  }
+
+  for (const tokenAccount of tokenAccounts) {
+    const { owner } = await getAccount(provider.connection, tokenAccount, 'confirmed', TOKEN_2022_PROGRAM_ID);
+    await program.methods
+      .kycApprove(tier)
+      .accounts({
+        operator,
+        tokenConfig: pdas.tokenConfig,
+        mint: mint.publicKey,
+        mintAuthority: pdas.mintAuthority,
+        kycOperators,
+        tokenAccount,
+        blacklist: pdas.blacklist,
+        internalWhitelist: pdas.internalWhitelist,
+        externalWhitelist: pdas.externalWhitelist,
+        kycRecord: kycPdas(mint.publicKey, program.programId, owner).kycRecord,
+        transferLimits: kycPdas(mint.publicKey, program.programId, owner).transferLimits,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  }
+}